@@ -2,7 +2,7 @@
 extern crate criterion;
 
 use base64::{
-    display,
+    alphabet, display,
     engine::{general_purpose::STANDARD, Engine},
     write,
 };
@@ -63,6 +63,28 @@ fn do_decode_bench_stream(b: &mut Bencher, &size: &usize) {
     });
 }
 
+fn do_find_invalid_bench(b: &mut Bencher, &size: &usize) {
+    let mut v: Vec<u8> = Vec::with_capacity(size * 3 / 4);
+    fill(&mut v);
+    let encoded = STANDARD.encode(&v);
+
+    b.iter(|| {
+        let invalid = STANDARD.find_invalid(&encoded);
+        black_box(&invalid);
+    });
+}
+
+fn do_translate_bench(b: &mut Bencher, &size: &usize) {
+    let mut v: Vec<u8> = Vec::with_capacity(size * 3 / 4);
+    fill(&mut v);
+    let encoded = STANDARD.encode(&v);
+
+    b.iter(|| {
+        let translated = base64::translate(&encoded, &alphabet::STANDARD, &alphabet::URL_SAFE);
+        black_box(&translated);
+    });
+}
+
 fn do_encode_bench(b: &mut Bencher, &size: &usize) {
     let mut v: Vec<u8> = Vec::with_capacity(size);
     fill(&mut v);
@@ -149,6 +171,8 @@ fn fill(v: &mut Vec<u8>) {
 
 const BYTE_SIZES: [usize; 5] = [3, 50, 100, 500, 3 * 1024];
 
+const SMALL_PAYLOAD_SIZES: [usize; 3] = [16, 32, 64];
+
 // Benchmarks over these byte sizes take longer so we will run fewer samples to
 // keep the benchmark runtime reasonable.
 const LARGE_BYTE_SIZES: [usize; 3] = [3 * 1024 * 1024, 10 * 1024 * 1024, 30 * 1024 * 1024];
@@ -221,6 +245,58 @@ fn decode_benchmarks(c: &mut Criterion, label: &str, byte_sizes: &[usize]) {
                 BenchmarkId::new("decode_stream", size),
                 size,
                 do_decode_bench_stream,
+            )
+            .bench_with_input(
+                BenchmarkId::new("find_invalid", size),
+                size,
+                do_find_invalid_bench,
+            )
+            .bench_with_input(
+                BenchmarkId::new("translate", size),
+                size,
+                do_translate_bench,
+            );
+    }
+
+    group.finish();
+}
+
+/// Encoding and decoding payloads the size of typical IDs, hashes, and tokens, where per-call
+/// overhead matters more than throughput. Sizes are of the decoded payload.
+fn small_payload_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("small_payload");
+    group
+        .warm_up_time(std::time::Duration::from_millis(500))
+        .measurement_time(std::time::Duration::from_secs(3));
+
+    for size in &SMALL_PAYLOAD_SIZES {
+        let mut payload: Vec<u8> = Vec::with_capacity(*size);
+        fill(&mut payload);
+        let encoded = STANDARD.encode(&payload);
+
+        group
+            .throughput(Throughput::Bytes(*size as u64))
+            .bench_with_input(BenchmarkId::new("encode", size), &payload, |b, payload| {
+                b.iter(|| black_box(STANDARD.encode(payload)))
+            })
+            .bench_with_input(
+                BenchmarkId::new("encode_slice", size),
+                &payload,
+                |b, payload| {
+                    let mut buf = [0_u8; 128];
+                    b.iter(|| black_box(STANDARD.encode_slice(payload, &mut buf).unwrap()))
+                },
+            )
+            .bench_with_input(BenchmarkId::new("decode", size), &encoded, |b, encoded| {
+                b.iter(|| black_box(STANDARD.decode(encoded).unwrap()))
+            })
+            .bench_with_input(
+                BenchmarkId::new("decode_slice", size),
+                &encoded,
+                |b, encoded| {
+                    let mut buf = [0_u8; 128];
+                    b.iter(|| black_box(STANDARD.decode_slice(encoded, &mut buf).unwrap()))
+                },
             );
     }
 
@@ -228,6 +304,7 @@ fn decode_benchmarks(c: &mut Criterion, label: &str, byte_sizes: &[usize]) {
 }
 
 fn bench(c: &mut Criterion) {
+    small_payload_benchmarks(c);
     encode_benchmarks(c, "encode_small_input", &BYTE_SIZES[..]);
     encode_benchmarks(c, "encode_large_input", &LARGE_BYTE_SIZES[..]);
     decode_benchmarks(c, "decode_small_input", &BYTE_SIZES[..]);
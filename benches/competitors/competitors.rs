@@ -0,0 +1,148 @@
+//! Compares the engines in this crate with other base64 implementations, then prints a table of
+//! their throughput.
+//!
+//! Run with `cargo bench --manifest-path benches/competitors/Cargo.toml`. Every implementation
+//! encodes to a new `String` and decodes to a new `Vec` with the standard alphabet and padding, so
+//! they all do the same work. Criterion's own results are in
+//! `benches/competitors/target/criterion/<encode|decode>/<implementation>/<size>`, as usual.
+//!
+//! New engines should be added to `IMPLEMENTATIONS` as they land, so claims about their speed can be
+//! checked against the other crates on the same inputs.
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use criterion::{black_box, BenchmarkId, Criterion, Throughput};
+use rand::{Rng, SeedableRng};
+use std::{env, fs, path::PathBuf};
+
+const BYTE_SIZES: [usize; 5] = [32, 256, 1024, 16 * 1024, 1024 * 1024];
+
+/// A base64 implementation to benchmark: its name and how it encodes and decodes.
+struct Implementation {
+    name: &'static str,
+    encode: fn(&[u8]) -> String,
+    decode: fn(&[u8]) -> Vec<u8>,
+}
+
+const IMPLEMENTATIONS: [Implementation; 3] = [
+    Implementation {
+        name: "base64",
+        encode: |input| STANDARD.encode(input),
+        decode: |input| STANDARD.decode(input).unwrap(),
+    },
+    Implementation {
+        name: "base64-simd",
+        encode: |input| base64_simd::STANDARD.encode_to_string(input),
+        decode: |input| base64_simd::STANDARD.decode_to_vec(input).unwrap(),
+    },
+    Implementation {
+        name: "data-encoding",
+        encode: |input| data_encoding::BASE64.encode(input),
+        decode: |input| data_encoding::BASE64.decode(input).unwrap(),
+    },
+];
+
+fn random_bytes(size: usize) -> Vec<u8> {
+    let mut r = rand::rngs::SmallRng::from_entropy();
+    (0..size).map(|_| r.gen()).collect()
+}
+
+fn competitor_benchmarks(c: &mut Criterion) {
+    let inputs: Vec<(Vec<u8>, String)> = BYTE_SIZES
+        .iter()
+        .map(|&size| {
+            let input = random_bytes(size);
+            let encoded = STANDARD.encode(&input);
+            (input, encoded)
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("encode");
+    for (input, _) in &inputs {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        for imp in &IMPLEMENTATIONS {
+            assert_eq!(STANDARD.encode(input), (imp.encode)(input), "{}", imp.name);
+            group.bench_with_input(
+                BenchmarkId::new(imp.name, input.len()),
+                input,
+                |b, input| b.iter(|| black_box((imp.encode)(input))),
+            );
+        }
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("decode");
+    for (input, encoded) in &inputs {
+        // throughput of the decoded data, so encode and decode are in the same units
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        for imp in &IMPLEMENTATIONS {
+            assert_eq!(input, &(imp.decode)(encoded.as_bytes()), "{}", imp.name);
+            group.bench_with_input(
+                BenchmarkId::new(imp.name, input.len()),
+                encoded,
+                |b, encoded| b.iter(|| black_box((imp.decode)(encoded.as_bytes()))),
+            );
+        }
+    }
+    group.finish();
+}
+
+/// Where Criterion writes its results, found the same way Criterion does.
+fn criterion_dir() -> PathBuf {
+    if let Some(dir) = env::var_os("CRITERION_HOME") {
+        return dir.into();
+    }
+    env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target"))
+        .join("criterion")
+}
+
+/// The mean time in nanoseconds of the latest run of a benchmark, if it ran.
+fn mean_ns(group: &str, implementation: &str, size: usize) -> Option<f64> {
+    let path = criterion_dir()
+        .join(group)
+        .join(implementation)
+        .join(size.to_string())
+        .join("new")
+        .join("estimates.json");
+    let estimates: serde_json::Value = serde_json::from_slice(&fs::read(path).ok()?).ok()?;
+    estimates["mean"]["point_estimate"].as_f64()
+}
+
+/// Print the throughput of every implementation in MiB/s of decoded data as a Markdown table, with
+/// a column per implementation. Benchmarks left out by a filter are shown as `-`.
+fn print_table() {
+    print!("\n| operation | size |");
+    for imp in &IMPLEMENTATIONS {
+        print!(" {} |", imp.name);
+    }
+    print!("\n|---|---:|");
+    for _ in &IMPLEMENTATIONS {
+        print!("---:|");
+    }
+    println!();
+
+    for &group in &["encode", "decode"] {
+        for &size in &BYTE_SIZES {
+            print!("| {} | {} |", group, size);
+            for imp in &IMPLEMENTATIONS {
+                match mean_ns(group, imp.name, size) {
+                    Some(ns) => {
+                        let mib_per_sec = size as f64 / (ns / 1e9) / (1024.0 * 1024.0);
+                        print!(" {:.0} |", mib_per_sec);
+                    }
+                    None => print!(" - |"),
+                }
+            }
+            println!();
+        }
+    }
+}
+
+fn main() {
+    let mut c = Criterion::default().configure_from_args();
+    competitor_benchmarks(&mut c);
+    c.final_summary();
+
+    print_table();
+}
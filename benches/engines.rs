@@ -0,0 +1,99 @@
+//! Compares engines across input sizes, alphabets, and padding modes.
+//!
+//! Run with `cargo bench --bench engines`. Criterion writes machine-readable results for each
+//! benchmark to `target/criterion/<group>/<function>/<size>/new/estimates.json`, which is the
+//! baseline to compare against when working on engine performance (see `--save-baseline` and
+//! `--baseline`).
+//!
+//! `GeneralPurpose` is currently the only engine. New engines should be added to
+//! `engine_benchmarks` alongside it so they are measured on the same inputs.
+
+#[macro_use]
+extern crate criterion;
+
+use base64::{
+    alphabet::{self, Alphabet},
+    engine::{
+        general_purpose::{GeneralPurpose, NO_PAD, PAD},
+        Engine, GeneralPurposeConfig,
+    },
+};
+use criterion::{black_box, BenchmarkGroup, BenchmarkId, Criterion, Throughput};
+use rand::{Rng, SeedableRng};
+
+const BYTE_SIZES: [usize; 6] = [3, 32, 256, 1024, 16 * 1024, 1024 * 1024];
+
+fn alphabets() -> Vec<(&'static str, Alphabet)> {
+    vec![
+        ("standard", alphabet::STANDARD),
+        ("url_safe", alphabet::URL_SAFE),
+        ("crypt", alphabet::CRYPT),
+    ]
+}
+
+fn configs() -> Vec<(&'static str, GeneralPurposeConfig)> {
+    vec![("pad", PAD), ("no_pad", NO_PAD)]
+}
+
+fn random_bytes(size: usize) -> Vec<u8> {
+    let mut r = rand::rngs::SmallRng::from_entropy();
+    (0..size).map(|_| r.gen()).collect()
+}
+
+fn bench_engine<E: Engine, M: criterion::measurement::Measurement>(
+    group: &mut BenchmarkGroup<M>,
+    name: &str,
+    engine: &E,
+) {
+    for &size in BYTE_SIZES.iter() {
+        let input = random_bytes(size);
+        let encoded = engine.encode(&input);
+        group.throughput(Throughput::Bytes(size as u64));
+
+        let mut encode_buf = vec![0; encoded.len()];
+        group.bench_with_input(
+            BenchmarkId::new(format!("{}/encode_slice", name), size),
+            &input,
+            |b, input| {
+                b.iter(|| {
+                    engine.encode_slice(input, &mut encode_buf).unwrap();
+                    black_box(&encode_buf);
+                })
+            },
+        );
+
+        let mut decode_buf = vec![0; size];
+        group.bench_with_input(
+            BenchmarkId::new(format!("{}/decode_slice", name), size),
+            &encoded,
+            |b, encoded| {
+                b.iter(|| {
+                    engine.decode_slice(encoded, &mut decode_buf).unwrap();
+                    black_box(&decode_buf);
+                })
+            },
+        );
+    }
+}
+
+fn engine_benchmarks(c: &mut Criterion) {
+    for (alphabet_name, alphabet) in alphabets() {
+        for (config_name, config) in configs() {
+            let mut group = c.benchmark_group(format!("{}_{}", alphabet_name, config_name));
+            group
+                .warm_up_time(std::time::Duration::from_millis(500))
+                .measurement_time(std::time::Duration::from_secs(2));
+
+            bench_engine(
+                &mut group,
+                "general_purpose",
+                &GeneralPurpose::new(&alphabet, config),
+            );
+
+            group.finish();
+        }
+    }
+}
+
+criterion_group!(benches, engine_benchmarks);
+criterion_main!(benches);
@@ -0,0 +1,28 @@
+//! Detects compiler features newer than the MSRV, so they can be used when available.
+
+use std::env;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // `panic!` in const fn, for a descriptive message when a const check fails
+    if rustc_minor_version().map_or(false, |minor| minor >= 57) {
+        println!("cargo:rustc-cfg=const_panic");
+    }
+}
+
+/// The minor version of the compiler, e.g. 48 for 1.48.0.
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    // e.g. "rustc 1.48.0 (7eac88abb 2020-11-16)"
+    let version = String::from_utf8(output.stdout).ok()?;
+    version
+        .split_whitespace()
+        .nth(1)?
+        .split('.')
+        .nth(1)?
+        .parse()
+        .ok()
+}
@@ -3,7 +3,7 @@ use std::io::{self, Read};
 use std::path::PathBuf;
 use std::process;
 
-use base64::{alphabet, engine, read, write};
+use base64::{alphabet, engine};
 use clap::Parser;
 
 #[derive(Clone, Debug, Parser, strum::EnumString, Default)]
@@ -64,11 +64,9 @@ fn main() {
     let stdout = io::stdout();
     let mut stdout = stdout.lock();
     let r = if opt.decode {
-        let mut decoder = read::DecoderReader::new(&mut input, &engine);
-        io::copy(&mut decoder, &mut stdout)
+        base64::io::copy_decode(&mut input, &mut stdout, &engine)
     } else {
-        let mut encoder = write::EncoderWriter::new(&mut stdout, &engine);
-        io::copy(&mut input, &mut encoder)
+        base64::io::copy_encode(&mut input, &mut stdout, &engine)
     };
     if let Err(e) = r {
         eprintln!(
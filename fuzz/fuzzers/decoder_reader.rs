@@ -0,0 +1,48 @@
+#![no_main]
+#[macro_use] extern crate libfuzzer_sys;
+extern crate base64;
+
+use base64::{read::DecoderReader, *};
+use std::io::{ErrorKind, Read};
+
+mod utils;
+
+// Drives `DecoderReader` with reads into buffers of sizes taken from the fuzzer input, from a
+// reader that hands out short reads, and checks it agrees with one-shot decoding. Both the
+// encoding of the payload and the payload itself are decoded, so invalid input is covered too.
+fuzz_target!(|data: &[u8]| {
+    let engine = utils::random_engine(data);
+    let (mut ops, payload) = utils::split_ops(data);
+    let encoded = engine.encode(payload);
+
+    for input in &[encoded.as_bytes(), payload] {
+        let expected = engine.decode(input);
+
+        let mut reader = DecoderReader::new(utils::ShortReader::new(input, ops.clone()), &engine);
+        let mut decoded = Vec::new();
+        let result = loop {
+            let mut buf = vec![0; ops.next_len(64)];
+            match reader.read(&mut buf) {
+                Ok(0) => break Ok(decoded),
+                Ok(len) => decoded.extend_from_slice(&buf[..len]),
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => break Err(e),
+            }
+        };
+        match (&expected, result) {
+            (Ok(expected), Ok(decoded)) => assert_eq!(expected, &decoded),
+            (Err(_), Err(e)) => assert_eq!(ErrorKind::InvalidData, e.kind()),
+            (expected, result) => panic!(
+                "decode returned {:?} but DecoderReader returned {:?}",
+                expected, result
+            ),
+        }
+
+        let mut chunked = Vec::new();
+        let chunked_result = engine.decode_chunked(input, |chunk| chunked.extend_from_slice(chunk));
+        assert_eq!(expected.is_ok(), chunked_result.is_ok());
+        if let Ok(expected) = expected {
+            assert_eq!(expected, chunked);
+        }
+    }
+});
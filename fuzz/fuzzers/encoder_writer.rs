@@ -0,0 +1,36 @@
+#![no_main]
+#[macro_use] extern crate libfuzzer_sys;
+extern crate base64;
+
+use base64::{display::Base64Display, write::EncoderWriter, *};
+use std::io::Write;
+
+mod utils;
+
+// Drives `EncoderWriter` with writes of sizes taken from the fuzzer input, interleaved with
+// flushes, into a writer that takes short writes, and checks it agrees with one-shot encoding.
+fuzz_target!(|data: &[u8]| {
+    let engine = utils::random_engine(data);
+    let (mut ops, payload) = utils::split_ops(data);
+    let expected = engine.encode(payload);
+
+    let mut writer = EncoderWriter::new(utils::ShortWriter::new(ops.clone()), &engine);
+    let mut remaining = payload;
+    while !remaining.is_empty() {
+        let op = ops.next_byte();
+        if op % 4 == 0 {
+            writer.flush().unwrap();
+        }
+        let len = std::cmp::min(remaining.len(), 1 + op as usize / 4);
+        // not `write_all`: `Ok(0)` just means buffered output went to the delegate first
+        match writer.write(&remaining[..len]) {
+            Ok(consumed) => remaining = &remaining[consumed..],
+            Err(e) => assert_eq!(std::io::ErrorKind::Interrupted, e.kind()),
+        }
+    }
+    let written = writer.finish().unwrap().written;
+    assert_eq!(expected.as_bytes(), &written[..]);
+
+    // `Base64Display` goes through the chunked encoder
+    assert_eq!(expected, Base64Display::new(payload, &engine).to_string());
+});
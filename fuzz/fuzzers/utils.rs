@@ -1,8 +1,12 @@
+// each fuzzer uses only some of these helpers
+#![allow(dead_code)]
+
 extern crate rand;
 extern crate rand_pcg;
 extern crate sha2;
 
 use base64::{alphabet, engine::{self, general_purpose}};
+use std::{cmp, io};
 use self::rand::{Rng, SeedableRng};
 use self::rand_pcg::Pcg32;
 use self::sha2::Digest as _;
@@ -30,10 +34,131 @@ pub fn random_engine(data: &[u8]) -> general_purpose::GeneralPurpose {
     } else {
         engine::DecodePaddingMode::RequireNone
     };
+    // short lines are the hardest case for the streaming types, which wrap across chunks
+    let line_wrap = match rng.gen_range(0, 4) {
+        0 => rng.gen_range(1, 8),
+        1 => 76,
+        _ => 0,
+    };
+    let line_ending = if rng.gen() {
+        engine::LineEnding::CRLF
+    } else {
+        engine::LineEnding::LF
+    };
     let config = general_purpose::GeneralPurposeConfig::new()
         .with_encode_padding(encode_padding)
         .with_decode_allow_trailing_bits(rng.gen())
-        .with_decode_padding_mode(decode_padding);
+        .with_decode_padding_mode(decode_padding)
+        .with_encode_line_wrap(line_wrap)
+        .with_encode_line_ending(line_ending)
+        .with_encode_trailing_newline(rng.gen());
 
     general_purpose::GeneralPurpose::new(&alphabet, config)
 }
+
+/// Split fuzzer input into control bytes, which size the operations on a streaming type, and the
+/// payload. The first byte is the number of control bytes.
+pub fn split_ops(data: &[u8]) -> (Ops<'_>, &[u8]) {
+    let (control_len, rest) = match data.split_first() {
+        Some((&len, rest)) => (len as usize, rest),
+        None => (0, data),
+    };
+    let (control, payload) = rest.split_at(cmp::min(control_len, rest.len()));
+
+    (Ops { control, index: 0 }, payload)
+}
+
+/// An endless sequence of control bytes, repeating the ones from the fuzzer input.
+#[derive(Clone)]
+pub struct Ops<'a> {
+    control: &'a [u8],
+    index: usize,
+}
+
+impl<'a> Ops<'a> {
+    /// The next control byte, or `u8::MAX` if there are none
+    pub fn next_byte(&mut self) -> u8 {
+        if self.control.is_empty() {
+            return u8::MAX;
+        }
+        let byte = self.control[self.index % self.control.len()];
+        self.index += 1;
+        byte
+    }
+
+    /// A length from 1 to `max`
+    pub fn next_len(&mut self, max: usize) -> usize {
+        1 + self.next_byte() as usize % max
+    }
+
+    /// Whether the next I/O call should be interrupted
+    fn interrupt(&mut self) -> bool {
+        self.next_byte() % 8 == 0
+    }
+}
+
+/// A writer that accepts a few bytes at a time and is sometimes interrupted, as sized by control
+/// bytes.
+pub struct ShortWriter<'a> {
+    pub written: Vec<u8>,
+    ops: Ops<'a>,
+    interrupted: bool,
+}
+
+impl<'a> ShortWriter<'a> {
+    pub fn new(ops: Ops<'a>) -> Self {
+        Self {
+            written: Vec::new(),
+            ops,
+            interrupted: false,
+        }
+    }
+}
+
+impl<'a> io::Write for ShortWriter<'a> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // never interrupted twice in a row, so writes always make progress
+        self.interrupted = !self.interrupted && self.ops.interrupt();
+        if self.interrupted {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "interrupted"));
+        }
+        let len = cmp::min(buf.len(), self.ops.next_len(16));
+        self.written.extend_from_slice(&buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A reader that hands out a few bytes at a time and is sometimes interrupted, as sized by
+/// control bytes.
+pub struct ShortReader<'a> {
+    data: &'a [u8],
+    ops: Ops<'a>,
+    interrupted: bool,
+}
+
+impl<'a> ShortReader<'a> {
+    pub fn new(data: &'a [u8], ops: Ops<'a>) -> Self {
+        Self {
+            data,
+            ops,
+            interrupted: false,
+        }
+    }
+}
+
+impl<'a> io::Read for ShortReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.interrupted = !self.interrupted && self.ops.interrupt();
+        if self.interrupted {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "interrupted"));
+        }
+        let len = cmp::min(cmp::min(buf.len(), self.ops.next_len(16)), self.data.len());
+        buf[..len].copy_from_slice(&self.data[..len]);
+        self.data = &self.data[len..];
+        Ok(len)
+    }
+}
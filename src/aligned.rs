@@ -0,0 +1,315 @@
+//! Incremental encoding that only ever writes whole 4-byte words of output.
+//!
+//! [`AlignedEncoder`] is for destinations that can only take output a word at a time, like
+//! word-aligned peripherals and fixed-size mailbox registers. Input can be pushed in pieces of any
+//! length: it's only encoded 3 bytes at a time, with the rest kept until more input arrives, and
+//! output is only written to the sink 4 bytes at a time, with the rest kept until there's a whole
+//! word of it. Nothing is allocated, so it works without `alloc`.
+//!
+//! # Examples
+//!
+//! ```
+//! use base64::{engine::general_purpose::STANDARD, sink::EncodeSink, Engine as _};
+//!
+//! /// Records the length of every write, like a peripheral that only takes whole words would see.
+//! #[derive(Default)]
+//! struct Mailbox {
+//!     writes: Vec<usize>,
+//!     received: Vec<u8>,
+//! }
+//!
+//! impl EncodeSink for Mailbox {
+//!     type Error = core::convert::Infallible;
+//!
+//!     fn write_encoded(&mut self, encoded: &[u8]) -> Result<(), Self::Error> {
+//!         self.writes.push(encoded.len());
+//!         self.received.extend_from_slice(encoded);
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let mut encoder = STANDARD.encode_aligned(Mailbox::default());
+//! for piece in [&b"he"[..], b"llo", b" world", b"!"] {
+//!     encoder.write(piece).unwrap();
+//! }
+//! let mailbox = encoder.finish().unwrap();
+//!
+//! assert_eq!(b"aGVsbG8gd29ybGQh", &mailbox.received[..]);
+//! assert!(mailbox.writes.iter().all(|len| len % 4 == 0));
+//! ```
+
+use crate::{
+    chunked_encoder::write_lines,
+    encode::add_padding,
+    engine::{Config, Engine, Padding},
+    sink::EncodeSink,
+};
+use core::cmp;
+
+// Room for the output of encoding a whole buffer of input, which is a multiple of 3 bytes
+const BUF_SIZE: usize = 1024;
+const CHUNK_SIZE: usize = BUF_SIZE / 4 * 3;
+
+/// Encodes input pushed to it in pieces of any length, writing the output to an [`EncodeSink`]
+/// only in multiples of 4 bytes.
+///
+/// The engine only ever encodes multiples of 3 bytes of input until [`finish`](Self::finish),
+/// which encodes the last 1 or 2 bytes, if any, with padding if the engine's config asks for it.
+/// Every write to the sink is a multiple of 4 bytes, except for the last one, which `finish` makes
+/// for whatever is left over when the whole output isn't a multiple of 4 bytes long. That can only
+/// happen when the engine doesn't pad, or wraps lines.
+///
+/// Line wrapping and trailing newlines from the engine's config are applied before the output is
+/// split into words, so the output is the same as [`Engine::encode`](crate::Engine::encode).
+///
+/// The output is incomplete unless `finish` is called. If the sink returns an error, the output
+/// written so far is incomplete and the encoder should be dropped.
+pub struct AlignedEncoder<'e, E: Engine + ?Sized, S: EncodeSink> {
+    engine: &'e E,
+    output: AlignedSink<S>,
+    /// Input that isn't yet a whole 3 bytes
+    extra_input: [u8; 3],
+    extra_input_len: usize,
+    /// Bytes already written on the current line, when wrapping lines
+    column: usize,
+    /// Whether any input has been written, so an empty input doesn't get a trailing newline
+    wrote_input: bool,
+}
+
+impl<'e, E: Engine + ?Sized, S: EncodeSink> AlignedEncoder<'e, E, S> {
+    /// Create a new encoder that will write to the provided sink.
+    pub fn new(engine: &'e E, sink: S) -> AlignedEncoder<'e, E, S> {
+        AlignedEncoder {
+            engine,
+            output: AlignedSink {
+                sink,
+                extra_output: [0; 4],
+                extra_output_len: 0,
+            },
+            extra_input: [0; 3],
+            extra_input_len: 0,
+            column: 0,
+            wrote_input: false,
+        }
+    }
+
+    /// Encode all of `input`, writing as much of the output as makes whole words to the sink.
+    ///
+    /// Up to 2 bytes of input and 3 bytes of output are kept for later calls or `finish`.
+    pub fn write(&mut self, mut input: &[u8]) -> Result<(), S::Error> {
+        self.wrote_input |= !input.is_empty();
+
+        if self.extra_input_len > 0 {
+            let take = cmp::min(3 - self.extra_input_len, input.len());
+            self.extra_input[self.extra_input_len..self.extra_input_len + take]
+                .copy_from_slice(&input[..take]);
+            self.extra_input_len += take;
+            input = &input[take..];
+            if self.extra_input_len < 3 {
+                return Ok(());
+            }
+
+            let mut buf = [0; 4];
+            let len = self.engine.internal_encode(&self.extra_input, &mut buf);
+            self.extra_input_len = 0;
+            self.write_output(&buf[..len])?;
+        }
+
+        let (whole, rest) = input.split_at(input.len() / 3 * 3);
+        let mut buf = [0; BUF_SIZE];
+        for chunk in whole.chunks(CHUNK_SIZE) {
+            let len = self.engine.internal_encode(chunk, &mut buf);
+            self.write_output(&buf[..len])?;
+        }
+
+        self.extra_input[..rest.len()].copy_from_slice(rest);
+        self.extra_input_len = rest.len();
+
+        Ok(())
+    }
+
+    /// Encode the leftover input, write all the remaining output, and return the sink.
+    pub fn finish(mut self) -> Result<S, S::Error> {
+        if self.extra_input_len > 0 {
+            let mut buf = [0; 4];
+            let mut len = self
+                .engine
+                .internal_encode(&self.extra_input[..self.extra_input_len], &mut buf);
+            if let Padding::Padded(padding_byte) = self.engine.config().padding() {
+                len += add_padding(len, &mut buf[len..], padding_byte);
+            }
+            self.write_output(&buf[..len])?;
+        }
+
+        let config = self.engine.config();
+        if self.wrote_input && config.encode_trailing_newline() {
+            self.output
+                .write_encoded(config.encode_line_ending().as_bytes())?;
+        }
+
+        self.output.flush()?;
+        Ok(self.output.sink)
+    }
+
+    fn write_output(&mut self, encoded: &[u8]) -> Result<(), S::Error> {
+        let config = self.engine.config();
+        let line_len = config.encode_line_wrap();
+        if line_len == 0 {
+            self.output.write_encoded(encoded)
+        } else {
+            let line_ending = config.encode_line_ending().as_bytes();
+            self.column = write_lines(
+                &mut self.output,
+                encoded,
+                line_len,
+                self.column,
+                line_ending,
+            )?;
+            Ok(())
+        }
+    }
+}
+
+/// Passes output on to `sink` in multiples of 4 bytes, keeping the rest until there's more.
+struct AlignedSink<S> {
+    sink: S,
+    extra_output: [u8; 4],
+    extra_output_len: usize,
+}
+
+impl<S: EncodeSink> AlignedSink<S> {
+    /// Write whatever output is left, even though it's not a whole word.
+    fn flush(&mut self) -> Result<(), S::Error> {
+        if self.extra_output_len > 0 {
+            self.sink
+                .write_encoded(&self.extra_output[..self.extra_output_len])?;
+            self.extra_output_len = 0;
+        }
+
+        Ok(())
+    }
+}
+
+impl<S: EncodeSink> EncodeSink for AlignedSink<S> {
+    type Error = S::Error;
+
+    fn write_encoded(&mut self, mut encoded: &[u8]) -> Result<(), Self::Error> {
+        if self.extra_output_len > 0 {
+            let take = cmp::min(4 - self.extra_output_len, encoded.len());
+            self.extra_output[self.extra_output_len..self.extra_output_len + take]
+                .copy_from_slice(&encoded[..take]);
+            self.extra_output_len += take;
+            encoded = &encoded[take..];
+            if self.extra_output_len < 4 {
+                return Ok(());
+            }
+
+            self.sink.write_encoded(&self.extra_output)?;
+            self.extra_output_len = 0;
+        }
+
+        let (words, rest) = encoded.split_at(encoded.len() / 4 * 4);
+        if !words.is_empty() {
+            self.sink.write_encoded(words)?;
+        }
+        self.extra_output[..rest.len()].copy_from_slice(rest);
+        self.extra_output_len = rest.len();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alphabet,
+        engine::{general_purpose::STANDARD, GeneralPurpose},
+        tests::random_config,
+        EncodeSliceError,
+    };
+    use core::convert::Infallible;
+    use rand::{Rng, RngCore, SeedableRng};
+
+    /// Keeps every write separately, to check their lengths
+    #[derive(Default)]
+    struct Writes(Vec<Vec<u8>>);
+
+    impl EncodeSink for Writes {
+        type Error = Infallible;
+
+        fn write_encoded(&mut self, encoded: &[u8]) -> Result<(), Self::Error> {
+            self.0.push(encoded.to_vec());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn aligned_writes_match_normal_encode() {
+        let mut rng = rand::rngs::SmallRng::from_entropy();
+
+        for _ in 0..10_000 {
+            let mut input = vec![0; rng.gen_range(0..3_000)];
+            rng.fill_bytes(&mut input);
+            let engine = GeneralPurpose::new(
+                &alphabet::STANDARD,
+                random_config(&mut rng)
+                    .with_encode_line_wrap(if rng.gen() { 0 } else { rng.gen_range(1..80) })
+                    .with_encode_trailing_newline(rng.gen())
+                    .with_encode_line_ending(rng.gen()),
+            );
+
+            let mut encoder = engine.encode_aligned(Writes::default());
+            let mut rest = &input[..];
+            while !rest.is_empty() {
+                let (piece, after) = rest.split_at(rng.gen_range(0..=rest.len().min(10)));
+                encoder.write(piece).unwrap();
+                rest = after;
+            }
+            let writes = encoder.finish().unwrap().0;
+
+            let expected = engine.encode(&input);
+            assert_eq!(expected.as_bytes(), &writes.concat()[..]);
+            assert!(writes.iter().all(|w| !w.is_empty()));
+            if let Some((_, words)) = writes.split_last() {
+                assert!(words.iter().all(|w| w.len() % 4 == 0));
+            }
+            if expected.len() % 4 == 0 {
+                assert!(writes.iter().all(|w| w.len() % 4 == 0));
+            }
+        }
+    }
+
+    #[test]
+    fn aligned_keeps_leftovers_until_finish() {
+        let mut encoder = STANDARD.encode_aligned(Writes::default());
+        encoder.write(b"he").unwrap();
+        assert!(encoder.output.sink.0.is_empty());
+        encoder.write(b"llo").unwrap();
+        assert_eq!(vec![b"aGVs".to_vec()], encoder.output.sink.0);
+        assert_eq!(b"lo", &encoder.extra_input[..encoder.extra_input_len]);
+
+        let writes = encoder.finish().unwrap().0;
+        assert_eq!(vec![b"aGVs".to_vec(), b"bG8=".to_vec()], writes);
+    }
+
+    #[test]
+    fn aligned_reports_sink_errors() {
+        let mut buf = [0_u8; 6];
+        let mut encoder = STANDARD.encode_aligned(&mut buf[..]);
+        assert_eq!(
+            Err(EncodeSliceError::OutputSliceTooSmall),
+            encoder.write(b"abcdef")
+        );
+
+        let mut buf = [0_u8; 8];
+        let mut encoder = STANDARD.encode_aligned(&mut buf[..]);
+        encoder.write(b"abcdef").unwrap();
+        encoder.write(b"g").unwrap();
+        assert_eq!(
+            EncodeSliceError::OutputSliceTooSmall,
+            encoder.finish().unwrap_err()
+        );
+        assert_eq!(b"YWJjZGVm", &buf);
+    }
+}
@@ -4,8 +4,10 @@ use crate::PAD_BYTE;
 use core::{convert, fmt};
 #[cfg(any(feature = "std", test))]
 use std::error;
+#[cfg(feature = "alphabet-registry")]
+use std::{string::String, sync::RwLock, vec::Vec};
 
-const ALPHABET_SIZE: usize = 64;
+pub(crate) const ALPHABET_SIZE: usize = 64;
 
 /// An alphabet defines the 64 ASCII characters (symbols) used for base64.
 ///
@@ -76,7 +78,7 @@ impl Alphabet {
     pub const fn new(alphabet: &str) -> Result<Self, ParseAlphabetError> {
         let bytes = alphabet.as_bytes();
         if bytes.len() != ALPHABET_SIZE {
-            return Err(ParseAlphabetError::InvalidLength);
+            return Err(ParseAlphabetError::InvalidLength { len: bytes.len() });
         }
 
         {
@@ -87,28 +89,26 @@ impl Alphabet {
                 // must be ascii printable. 127 (DEL) is commonly considered printable
                 // for some reason but clearly unsuitable for base64.
                 if !(byte >= 32_u8 && byte <= 126_u8) {
-                    return Err(ParseAlphabetError::UnprintableByte(byte));
+                    return Err(ParseAlphabetError::UnprintableByte { byte, index });
                 }
                 // = is assumed to be padding, so cannot be used as a symbol
                 if byte == PAD_BYTE {
-                    return Err(ParseAlphabetError::ReservedByte(byte));
+                    return Err(ParseAlphabetError::ReservedByte { byte, index });
                 }
 
                 // Check for duplicates while staying within what const allows.
                 // It's n^2, but only over 64 hot bytes, and only once, so it's likely in the single digit
                 // microsecond range.
+                // Only earlier bytes are checked, so a duplicate is reported where it repeats.
 
                 let mut probe_index = 0;
-                while probe_index < ALPHABET_SIZE {
-                    if probe_index == index {
-                        probe_index += 1;
-                        continue;
-                    }
-
-                    let probe_byte = bytes[probe_index];
-
-                    if byte == probe_byte {
-                        return Err(ParseAlphabetError::DuplicatedByte(byte));
+                while probe_index < index {
+                    if byte == bytes[probe_index] {
+                        return Err(ParseAlphabetError::DuplicatedByte {
+                            byte,
+                            index,
+                            first_index: probe_index,
+                        });
                     }
 
                     probe_index += 1;
@@ -126,6 +126,104 @@ impl Alphabet {
     pub fn as_str(&self) -> &str {
         core::str::from_utf8(&self.symbols).unwrap()
     }
+
+    /// Returns the fewest changes that turn `other` into this alphabet, where a change either swaps
+    /// two symbols or replaces one with a byte `other` doesn't use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64::alphabet::{self, Alphabet};
+    ///
+    /// let swapped =
+    ///     Alphabet::new("BACDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/").unwrap();
+    /// assert_eq!(1, swapped.transposition_distance(&alphabet::STANDARD));
+    /// // `-` and `_` replace `+` and `/`
+    /// assert_eq!(2, alphabet::URL_SAFE.transposition_distance(&alphabet::STANDARD));
+    /// ```
+    pub fn transposition_distance(&self, other: &Alphabet) -> usize {
+        // where each byte is in `other`, if it's there
+        let mut position = [None; 256];
+        for (index, &byte) in other.symbols.iter().enumerate() {
+            position[usize::from(byte)] = Some(index);
+        }
+
+        // Following each symbol of `self` to where it is in `other` splits the symbols that moved
+        // into cycles, which take one swap fewer than their length to undo, and chains ending in a
+        // byte `other` doesn't use, which take one replacement and a swap for each other symbol.
+        let mut visited = [false; ALPHABET_SIZE];
+        let mut changed = 0;
+        let mut cycles = 0;
+        for start in 0..ALPHABET_SIZE {
+            if visited[start] || self.symbols[start] == other.symbols[start] {
+                continue;
+            }
+
+            let mut index = start;
+            loop {
+                visited[index] = true;
+                changed += 1;
+                match position[usize::from(self.symbols[index])] {
+                    Some(next) if next == start => {
+                        cycles += 1;
+                        break;
+                    }
+                    Some(next) if !visited[next] => index = next,
+                    _ => break,
+                }
+            }
+        }
+
+        changed - cycles
+    }
+
+    /// Check that this alphabet isn't a slight variation of one of the alphabets in this module.
+    ///
+    /// Swapping a few symbols of [`STANDARD`] doesn't make encoded data secret: the result still
+    /// looks like base64, and the changes are easy to work out from a few samples. This is for
+    /// linting configuration, to catch custom alphabets made to obfuscate data rather than to
+    /// interoperate with something.
+    ///
+    /// An alphabet that is one of the alphabets in this module is fine.
+    ///
+    /// # Errors
+    ///
+    /// [`IndistinctAlphabetError`] for the nearest alphabet in this module, if this alphabet is at
+    /// most `max_distance` changes from it, as counted by
+    /// [`transposition_distance`](Self::transposition_distance).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64::alphabet::{self, Alphabet};
+    ///
+    /// let swapped =
+    ///     Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789/+").unwrap();
+    /// let err = swapped.validate_distinctiveness(4).unwrap_err();
+    /// assert_eq!("standard", err.near());
+    /// assert_eq!(1, err.distance());
+    ///
+    /// assert_eq!(Ok(()), alphabet::URL_SAFE.validate_distinctiveness(4));
+    /// ```
+    pub fn validate_distinctiveness(
+        &self,
+        max_distance: usize,
+    ) -> Result<(), IndistinctAlphabetError> {
+        if NAMED.iter().any(|&(_, alphabet)| alphabet == self) {
+            return Ok(());
+        }
+
+        let (near, distance) = NAMED
+            .iter()
+            .map(|&(name, alphabet)| (name, self.transposition_distance(alphabet)))
+            .min_by_key(|&(_, distance)| distance)
+            .expect("there are named alphabets");
+        if distance > max_distance {
+            return Ok(());
+        }
+
+        Err(IndistinctAlphabetError { near, distance })
+    }
 }
 
 impl convert::TryFrom<&str> for Alphabet {
@@ -137,25 +235,101 @@ impl convert::TryFrom<&str> for Alphabet {
 }
 
 /// Possible errors when constructing an [Alphabet] from a `str`.
-#[derive(Debug, Eq, PartialEq)]
+///
+/// Indexes are byte offsets into the `str`, and the first error from the start is reported.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
 pub enum ParseAlphabetError {
     /// Alphabets must be 64 ASCII bytes
-    InvalidLength,
+    InvalidLength {
+        /// The length of the `str` in bytes
+        len: usize,
+    },
     /// All bytes must be unique
-    DuplicatedByte(u8),
+    DuplicatedByte {
+        /// The repeated byte
+        byte: u8,
+        /// Where it's repeated
+        index: usize,
+        /// Where it first appears
+        first_index: usize,
+    },
     /// All bytes must be printable (in the range `[32, 126]`).
-    UnprintableByte(u8),
+    UnprintableByte {
+        /// The unprintable byte, which may be part of a non-ASCII character
+        byte: u8,
+        /// Where it is
+        index: usize,
+    },
     /// `=` cannot be used
-    ReservedByte(u8),
+    ReservedByte {
+        /// The reserved byte
+        byte: u8,
+        /// Where it is
+        index: usize,
+    },
+}
+
+impl ParseAlphabetError {
+    /// Returns the index of the offending byte, or `None` if the length is wrong.
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            Self::InvalidLength { .. } => None,
+            Self::DuplicatedByte { index, .. }
+            | Self::UnprintableByte { index, .. }
+            | Self::ReservedByte { index, .. } => Some(*index),
+        }
+    }
+
+    /// Returns `true` if the alphabet is wrong in a single byte at [`index`](Self::index), so that
+    /// replacing that byte could fix it, as with a typo in a config file.
+    ///
+    /// An alphabet of the wrong length has symbols missing or extra ones, which can't be told
+    /// apart from the rest, so that isn't recoverable. Other errors may still come after the one
+    /// reported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64::alphabet::{Alphabet, ParseAlphabetError};
+    ///
+    /// let err = Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+A")
+    ///     .unwrap_err();
+    /// assert_eq!(
+    ///     ParseAlphabetError::DuplicatedByte { byte: b'A', index: 63, first_index: 0 },
+    ///     err
+    /// );
+    /// assert!(err.is_recoverable());
+    ///
+    /// assert!(!Alphabet::new("ABC").unwrap_err().is_recoverable());
+    /// ```
+    pub fn is_recoverable(&self) -> bool {
+        self.index().is_some()
+    }
 }
 
 impl fmt::Display for ParseAlphabetError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::InvalidLength => write!(f, "Invalid length - must be 64 bytes"),
-            Self::DuplicatedByte(b) => write!(f, "Duplicated byte: {:#04x}", b),
-            Self::UnprintableByte(b) => write!(f, "Unprintable byte: {:#04x}", b),
-            Self::ReservedByte(b) => write!(f, "Reserved byte: {:#04x}", b),
+            Self::InvalidLength { len } => {
+                write!(f, "Invalid length {} - must be 64 bytes", len)
+            }
+            Self::DuplicatedByte {
+                byte,
+                index,
+                first_index,
+            } => write!(
+                f,
+                "Duplicated byte: {:#04x} at index {}, first at index {}",
+                byte, index, first_index
+            ),
+            Self::UnprintableByte { byte, index } => {
+                write!(f, "Unprintable byte: {:#04x} at index {}", byte, index)
+            }
+            Self::ReservedByte { byte, index } => {
+                write!(f, "Reserved byte: {:#04x} at index {}", byte, index)
+            }
         }
     }
 }
@@ -163,6 +337,40 @@ impl fmt::Display for ParseAlphabetError {
 #[cfg(any(feature = "std", test))]
 impl error::Error for ParseAlphabetError {}
 
+/// An error from [`Alphabet::validate_distinctiveness`]: the alphabet is a few changes away from
+/// one of the alphabets in this module.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct IndistinctAlphabetError {
+    near: &'static str,
+    distance: usize,
+}
+
+impl IndistinctAlphabetError {
+    /// The name of the nearest alphabet, as accepted by [`by_name`].
+    pub fn near(&self) -> &'static str {
+        self.near
+    }
+
+    /// How many changes away from it the alphabet is.
+    pub fn distance(&self) -> usize {
+        self.distance
+    }
+}
+
+impl fmt::Display for IndistinctAlphabetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Alphabet is only {} swaps or replacements away from the {} alphabet",
+            self.distance, self.near
+        )
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl error::Error for IndistinctAlphabetError {}
+
 /// The standard alphabet (with `+` and `/`) specified in [RFC 4648][].
 ///
 /// [RFC 4648]: https://datatracker.ietf.org/doc/html/rfc4648#section-4
@@ -203,6 +411,117 @@ pub const BIN_HEX: Alphabet = Alphabet::from_str_unchecked(
     "!\"#$%&'()*+,-012345689@ABCDEFGHIJKLMNPQRSTUVXYZ[`abcdefhijklmpqr",
 );
 
+/// The alphabets in this module, by the names [`by_name`] accepts.
+const NAMED: [(&str, &Alphabet); 6] = [
+    ("standard", &STANDARD),
+    ("url-safe", &URL_SAFE),
+    ("crypt", &CRYPT),
+    ("bcrypt", &BCRYPT),
+    ("imap-mutf7", &IMAP_MUTF7),
+    ("bin-hex", &BIN_HEX),
+];
+
+#[cfg(feature = "alphabet-registry")]
+lazy_static::lazy_static! {
+    /// Alphabets added with [`register`], shared by every thread
+    static ref REGISTERED: RwLock<Vec<(String, &'static Alphabet)>> = RwLock::new(Vec::new());
+}
+
+/// Look up an alphabet by name.
+///
+/// The alphabets in this module are called `standard`, `url-safe`, `crypt`, `bcrypt`,
+/// `imap-mutf7`, and `bin-hex`. With the `alphabet-registry` feature, others can be added with
+/// `register()`. Case and the use of `-` or `_` don't matter.
+///
+/// # Examples
+///
+/// ```
+/// use base64::alphabet;
+///
+/// assert_eq!(Some(&alphabet::URL_SAFE), alphabet::by_name("URL_SAFE"));
+/// assert_eq!(None, alphabet::by_name("base32"));
+/// ```
+pub fn by_name(name: &str) -> Option<&'static Alphabet> {
+    NAMED
+        .iter()
+        .find(|(known, _)| names_match(known, name))
+        .map(|&(_, alphabet)| alphabet)
+        .or_else(|| registered(name))
+}
+
+/// Make `alphabet` available from [`by_name`] as `name`.
+///
+/// Returns `false`, without registering anything, if `name` is already taken.
+///
+/// The registry is shared by the whole process, so an alphabet registered at startup can be looked
+/// up from any thread, like a thread pool's workers.
+///
+/// Requires the `alphabet-registry` feature.
+///
+/// # Examples
+///
+/// ```
+/// use base64::alphabet::{self, Alphabet};
+///
+/// static CUSTOM: Alphabet = match Alphabet::new(
+///     "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz+/",
+/// ) {
+///     Ok(x) => x,
+///     Err(_) => panic!("creation of alphabet failed"),
+/// };
+///
+/// assert!(alphabet::register("digits-first", &CUSTOM));
+/// assert_eq!(Some(&CUSTOM), alphabet::by_name("Digits_First"));
+/// assert!(!alphabet::register("standard", &CUSTOM));
+/// ```
+#[cfg(feature = "alphabet-registry")]
+pub fn register(name: &str, alphabet: &'static Alphabet) -> bool {
+    if NAMED.iter().any(|(known, _)| names_match(known, name)) {
+        return false;
+    }
+
+    // hold the lock while checking, so two threads can't register the same name
+    let mut registered = REGISTERED
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if registered.iter().any(|(known, _)| names_match(known, name)) {
+        return false;
+    }
+
+    registered.push((name.into(), alphabet));
+    true
+}
+
+#[cfg(feature = "alphabet-registry")]
+fn registered(name: &str) -> Option<&'static Alphabet> {
+    REGISTERED
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .iter()
+        .find(|(known, _)| names_match(known, name))
+        .map(|&(_, alphabet)| alphabet)
+}
+
+#[cfg(not(feature = "alphabet-registry"))]
+fn registered(_name: &str) -> Option<&'static Alphabet> {
+    None
+}
+
+/// Returns `true` if the names are equal, ignoring case and treating `_` like `-`.
+fn names_match(a: &str, b: &str) -> bool {
+    let normalize = |b: u8| {
+        if b == b'_' {
+            b'-'
+        } else {
+            b.to_ascii_lowercase()
+        }
+    };
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .all(|(a, b)| normalize(a) == normalize(b))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::alphabet::*;
@@ -211,7 +530,11 @@ mod tests {
     #[test]
     fn detects_duplicate_start() {
         assert_eq!(
-            ParseAlphabetError::DuplicatedByte(b'A'),
+            ParseAlphabetError::DuplicatedByte {
+                byte: b'A',
+                index: 1,
+                first_index: 0
+            },
             Alphabet::new("AACDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/")
                 .unwrap_err()
         );
@@ -220,7 +543,11 @@ mod tests {
     #[test]
     fn detects_duplicate_end() {
         assert_eq!(
-            ParseAlphabetError::DuplicatedByte(b'/'),
+            ParseAlphabetError::DuplicatedByte {
+                byte: b'/',
+                index: 63,
+                first_index: 62
+            },
             Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789//")
                 .unwrap_err()
         );
@@ -229,7 +556,11 @@ mod tests {
     #[test]
     fn detects_duplicate_middle() {
         assert_eq!(
-            ParseAlphabetError::DuplicatedByte(b'Z'),
+            ParseAlphabetError::DuplicatedByte {
+                byte: b'Z',
+                index: 26,
+                first_index: 25
+            },
             Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZZbcdefghijklmnopqrstuvwxyz0123456789+/")
                 .unwrap_err()
         );
@@ -238,7 +569,7 @@ mod tests {
     #[test]
     fn detects_length() {
         assert_eq!(
-            ParseAlphabetError::InvalidLength,
+            ParseAlphabetError::InvalidLength { len: 73 },
             Alphabet::new(
                 "xxxxxxxxxABCDEFGHIJKLMNOPQRSTUVWXYZZbcdefghijklmnopqrstuvwxyz0123456789+/",
             )
@@ -249,7 +580,10 @@ mod tests {
     #[test]
     fn detects_padding() {
         assert_eq!(
-            ParseAlphabetError::ReservedByte(b'='),
+            ParseAlphabetError::ReservedByte {
+                byte: b'=',
+                index: 63
+            },
             Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+=")
                 .unwrap_err()
         );
@@ -259,12 +593,39 @@ mod tests {
     fn detects_unprintable() {
         // form feed
         assert_eq!(
-            ParseAlphabetError::UnprintableByte(0xc),
+            ParseAlphabetError::UnprintableByte {
+                byte: 0xc,
+                index: 0
+            },
             Alphabet::new("\x0cBCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/")
                 .unwrap_err()
         );
     }
 
+    #[test]
+    fn detects_non_ascii_at_its_byte_index() {
+        let err = Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789é")
+            .unwrap_err();
+        assert_eq!(
+            ParseAlphabetError::UnprintableByte {
+                byte: 0xc3,
+                index: 62
+            },
+            err
+        );
+        assert_eq!(Some(62), err.index());
+        assert!(err.is_recoverable());
+        assert_eq!("Unprintable byte: 0xc3 at index 62", err.to_string());
+    }
+
+    #[test]
+    fn length_errors_are_not_recoverable() {
+        let err = Alphabet::new("").unwrap_err();
+        assert_eq!(None, err.index());
+        assert!(!err.is_recoverable());
+        assert_eq!("Invalid length 0 - must be 64 bytes", err.to_string());
+    }
+
     #[test]
     fn same_as_unchecked() {
         assert_eq!(
@@ -280,4 +641,149 @@ mod tests {
         let a = Alphabet::try_from(alphabet).unwrap();
         assert_eq!(alphabet, a.as_str())
     }
+
+    #[test]
+    fn transposition_distance_counts_swaps_and_replacements() {
+        let distance = |alphabet: &str| {
+            Alphabet::new(alphabet)
+                .unwrap()
+                .transposition_distance(&STANDARD)
+        };
+
+        assert_eq!(0, STANDARD.transposition_distance(&STANDARD));
+        // a 3-cycle takes 2 swaps
+        assert_eq!(
+            2,
+            distance("BCADEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/")
+        );
+        // two separate swaps
+        assert_eq!(
+            2,
+            distance("BACDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789/+")
+        );
+        // `+` moves to where `/` was, and `/` is replaced
+        assert_eq!(
+            2,
+            distance("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789.+")
+        );
+        // reversed, every symbol is swapped with another
+        assert_eq!(
+            32,
+            distance("/+9876543210zyxwvutsrqponmlkjihgfedcbaZYXWVUTSRQPONMLKJIHGFEDCBA")
+        );
+        assert_eq!(2, URL_SAFE.transposition_distance(&IMAP_MUTF7));
+    }
+
+    #[test]
+    fn transposition_distance_is_at_most_the_changes_made() {
+        use rand::{seq::SliceRandom, Rng};
+
+        let mut rng = rand::thread_rng();
+        let unused: Vec<u8> = (32..127)
+            .filter(|&b| b != PAD_BYTE && !STANDARD.symbols.contains(&b))
+            .collect();
+
+        for _ in 0..1_000 {
+            let mut symbols = STANDARD.symbols;
+            let changes = rng.gen_range(0..10);
+            for _ in 0..changes {
+                if rng.gen() {
+                    symbols.swap(rng.gen_range(0..64), rng.gen_range(0..64));
+                } else {
+                    let replacement = *unused.choose(&mut rng).unwrap();
+                    if !symbols.contains(&replacement) {
+                        symbols[rng.gen_range(0..64)] = replacement;
+                    }
+                }
+            }
+            let changed = Alphabet::new(core::str::from_utf8(&symbols).unwrap()).unwrap();
+
+            let distance = changed.transposition_distance(&STANDARD);
+            assert!(distance <= changes);
+            assert_eq!(distance, STANDARD.transposition_distance(&changed));
+            let moved = symbols
+                .iter()
+                .zip(STANDARD.symbols.iter())
+                .filter(|(a, b)| a != b)
+                .count();
+            // each change moves at most 2 symbols, and each symbol needs at most one change
+            assert!(moved <= 2 * distance && distance <= moved);
+        }
+    }
+
+    #[test]
+    fn validate_distinctiveness_rejects_near_named_alphabets() {
+        for &(_, alphabet) in NAMED.iter() {
+            assert_eq!(Ok(()), alphabet.validate_distinctiveness(usize::MAX));
+        }
+
+        let near_url_safe =
+            Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789_-")
+                .unwrap();
+        assert_eq!(
+            Err(IndistinctAlphabetError {
+                near: "url-safe",
+                distance: 1
+            }),
+            near_url_safe.validate_distinctiveness(1)
+        );
+        assert_eq!(Ok(()), near_url_safe.validate_distinctiveness(0));
+
+        let reversed =
+            Alphabet::new("/+9876543210zyxwvutsrqponmlkjihgfedcbaZYXWVUTSRQPONMLKJIHGFEDCBA")
+                .unwrap();
+        assert_eq!(Ok(()), reversed.validate_distinctiveness(16));
+        assert_eq!(
+            "Alphabet is only 32 swaps or replacements away from the standard alphabet",
+            reversed
+                .validate_distinctiveness(32)
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn by_name_finds_constants() {
+        for &(name, alphabet) in NAMED.iter() {
+            assert_eq!(Some(alphabet), by_name(name));
+        }
+        assert_eq!(Some(&IMAP_MUTF7), by_name("Imap_MUTF7"));
+        assert_eq!(None, by_name("url safe"));
+        assert_eq!(None, by_name(""));
+    }
+
+    #[cfg(feature = "alphabet-registry")]
+    #[test]
+    fn register_is_process_wide() {
+        static CUSTOM: Alphabet = Alphabet::from_str_unchecked(
+            "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz+/",
+        );
+        static OTHER: Alphabet = Alphabet::from_str_unchecked(
+            "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
+        );
+
+        assert!(!register("URL_SAFE", &CUSTOM));
+        assert!(register("custom_test", &CUSTOM));
+        assert!(!register("Custom-Test", &STANDARD));
+        assert_eq!(Some(&CUSTOM), by_name("custom-test"));
+
+        // visible on other threads, and registered there for this one
+        std::thread::spawn(|| {
+            assert_eq!(Some(&CUSTOM), by_name("CUSTOM_TEST"));
+            assert!(register("other-thread-test", &OTHER));
+        })
+        .join()
+        .unwrap();
+        assert_eq!(Some(&OTHER), by_name("other_thread_test"));
+
+        // only one of several threads racing to register a name wins
+        let winners = (0..8)
+            .map(|_| std::thread::spawn(|| register("race-test", &OTHER)))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|t| t.join().unwrap())
+            .filter(|&won| won)
+            .count();
+        assert_eq!(1, winners);
+    }
 }
@@ -0,0 +1,117 @@
+//! Encoding and decoding single blocks of 3 bytes to and from 4 symbols.
+//!
+//! These are the primitives the engines are built on, for formats that need to handle blocks
+//! individually, e.g. to interleave checksums between them. Padding, partial blocks, and line
+//! wrapping are left to the caller; use an [`Engine`](crate::Engine) to handle whole inputs.
+//!
+//! # Examples
+//!
+//! ```
+//! use base64::block;
+//!
+//! assert_eq!(*b"TWFu", block::STANDARD.encode_block(b"Man"));
+//! assert_eq!(Ok(*b"Man"), block::STANDARD.decode_block(b"TWFu"));
+//! ```
+
+use crate::{
+    alphabet::{self, Alphabet},
+    engine::general_purpose::{decode_table, encode_table, INVALID_VALUE},
+    DecodeError,
+};
+
+/// Encodes and decodes blocks with a particular [`Alphabet`].
+///
+/// Its lookup tables can also be shared with engines via
+/// [`GeneralPurpose::from_block_codec`](crate::engine::GeneralPurpose::from_block_codec).
+#[derive(Debug, Clone)]
+pub struct BlockCodec {
+    pub(crate) encode_table: [u8; 64],
+    pub(crate) decode_table: [u8; 256],
+}
+
+impl BlockCodec {
+    /// Create a `BlockCodec` for `alphabet`.
+    #[must_use]
+    pub const fn new(alphabet: &Alphabet) -> Self {
+        Self {
+            encode_table: encode_table(alphabet),
+            decode_table: decode_table(alphabet),
+        }
+    }
+
+    /// Encode 3 bytes as 4 symbols.
+    pub fn encode_block(&self, input: &[u8; 3]) -> [u8; 4] {
+        let n = u32::from(input[0]) << 16 | u32::from(input[1]) << 8 | u32::from(input[2]);
+
+        [
+            self.encode_table[(n >> 18) as usize & 0x3F],
+            self.encode_table[(n >> 12) as usize & 0x3F],
+            self.encode_table[(n >> 6) as usize & 0x3F],
+            self.encode_table[n as usize & 0x3F],
+        ]
+    }
+
+    /// Decode 4 symbols to 3 bytes.
+    ///
+    /// Returns [`DecodeError::InvalidByte`] with the offset in the block of the first byte that
+    /// isn't in the alphabet. Padding isn't in the alphabet, so it's reported the same way.
+    pub fn decode_block(&self, input: &[u8; 4]) -> Result<[u8; 3], DecodeError> {
+        let mut n = 0_u32;
+        for (i, &symbol) in input.iter().enumerate() {
+            let value = self.decode_table[usize::from(symbol)];
+            if value == INVALID_VALUE {
+                return Err(DecodeError::InvalidByte(i, symbol));
+            }
+            n = n << 6 | u32::from(value);
+        }
+
+        Ok([(n >> 16) as u8, (n >> 8) as u8, n as u8])
+    }
+}
+
+/// A [`BlockCodec`] for the [`alphabet::STANDARD`] alphabet.
+pub const STANDARD: BlockCodec = BlockCodec::new(&alphabet::STANDARD);
+
+/// A [`BlockCodec`] for the [`alphabet::URL_SAFE`] alphabet.
+pub const URL_SAFE: BlockCodec = BlockCodec::new(&alphabet::URL_SAFE);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{general_purpose, Engine as _};
+
+    #[test]
+    fn matches_engine_for_every_block() {
+        let engine = general_purpose::STANDARD;
+        let mut decoded = [0_u8; 3];
+        for a in 0..=255 {
+            for &b in &[0_u8, 1, 0x7F, 0x80, 0xFE, 0xFF] {
+                for &c in &[0_u8, 0x55, 0xAA, 0xFF] {
+                    let input = [a, b, c];
+                    let encoded = STANDARD.encode_block(&input);
+
+                    let mut expected = [0_u8; 4];
+                    assert_eq!(4, engine.encode_slice(input, &mut expected).unwrap());
+                    assert_eq!(expected, encoded);
+
+                    assert_eq!(Ok(input), STANDARD.decode_block(&encoded));
+                    assert_eq!(3, engine.decode_slice(encoded, &mut decoded).unwrap());
+                    assert_eq!(input, decoded);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn decode_block_invalid_byte() {
+        assert_eq!(
+            Err(DecodeError::InvalidByte(2, b'-')),
+            STANDARD.decode_block(b"ab-_")
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidByte(3, b'=')),
+            URL_SAFE.decode_block(b"ab-=")
+        );
+        assert_eq!(Ok([0x69, 0xBF, 0xBF]), URL_SAFE.decode_block(b"ab-_"));
+    }
+}
@@ -1,19 +1,9 @@
 use crate::{
     encode::add_padding,
-    engine::{Config, Engine},
+    engine::{Config, Engine, Padding},
+    sink::EncodeSink,
 };
-#[cfg(any(feature = "alloc", test))]
-use alloc::string::String;
-#[cfg(any(feature = "alloc", test))]
-use core::str;
-
-/// The output mechanism for `ChunkedEncoder`'s encoded bytes.
-pub trait Sink {
-    type Error;
-
-    /// Handle a chunk of encoded base64 data (as UTF-8 bytes)
-    fn write_encoded_bytes(&mut self, encoded: &[u8]) -> Result<(), Self::Error>;
-}
+use core::cmp;
 
 /// A base64 encoder that emits encoded bytes in chunks without heap allocation.
 pub struct ChunkedEncoder<'e, E: Engine + ?Sized> {
@@ -25,49 +15,68 @@ impl<'e, E: Engine + ?Sized> ChunkedEncoder<'e, E> {
         ChunkedEncoder { engine }
     }
 
-    pub fn encode<S: Sink>(&self, bytes: &[u8], sink: &mut S) -> Result<(), S::Error> {
+    pub fn encode<S: EncodeSink + ?Sized>(
+        &self,
+        bytes: &[u8],
+        sink: &mut S,
+    ) -> Result<(), S::Error> {
         const BUF_SIZE: usize = 1024;
         const CHUNK_SIZE: usize = BUF_SIZE / 4 * 3;
 
+        let line_len = self.engine.config().encode_line_wrap();
+        let line_ending = self.engine.config().encode_line_ending().as_bytes();
+        let mut column = 0;
         let mut buf = [0; BUF_SIZE];
         for chunk in bytes.chunks(CHUNK_SIZE) {
             let mut len = self.engine.internal_encode(chunk, &mut buf);
-            if chunk.len() != CHUNK_SIZE && self.engine.config().encode_padding() {
+            if let (true, Padding::Padded(padding_byte)) =
+                (chunk.len() != CHUNK_SIZE, self.engine.config().padding())
+            {
                 // Final, potentially partial, chunk.
                 // Only need to consider if padding is needed on a partial chunk since full chunk
                 // is a multiple of 3, which therefore won't be padded.
                 // Pad output to multiple of four bytes if required by config.
-                len += add_padding(len, &mut buf[len..]);
+                len += add_padding(len, &mut buf[len..], padding_byte);
             }
-            sink.write_encoded_bytes(&buf[..len])?;
+            if line_len == 0 {
+                sink.write_encoded(&buf[..len])?;
+            } else {
+                column = write_lines(sink, &buf[..len], line_len, column, line_ending)?;
+            }
+        }
+
+        if !bytes.is_empty() && self.engine.config().encode_trailing_newline() {
+            sink.write_encoded(line_ending)?;
         }
 
         Ok(())
     }
 }
 
-// A really simple sink that just appends to a string
-#[cfg(any(feature = "alloc", test))]
-pub(crate) struct StringSink<'a> {
-    string: &'a mut String,
-}
+/// Write `encoded` to `sink` broken into lines of `line_len` ending in `line_ending`, where `column`
+/// bytes have already been written on the current line.
+///
+/// Returns the column after writing `encoded`.
+pub(crate) fn write_lines<S: EncodeSink + ?Sized>(
+    sink: &mut S,
+    mut encoded: &[u8],
+    line_len: usize,
+    mut column: usize,
+    line_ending: &[u8],
+) -> Result<usize, S::Error> {
+    while !encoded.is_empty() {
+        if column == line_len {
+            sink.write_encoded(line_ending)?;
+            column = 0;
+        }
 
-#[cfg(any(feature = "alloc", test))]
-impl<'a> StringSink<'a> {
-    pub(crate) fn new(s: &mut String) -> StringSink<'_> {
-        StringSink { string: s }
+        let (line, rest) = encoded.split_at(cmp::min(line_len - column, encoded.len()));
+        sink.write_encoded(line)?;
+        column += line.len();
+        encoded = rest;
     }
-}
-
-#[cfg(any(feature = "alloc", test))]
-impl<'a> Sink for StringSink<'a> {
-    type Error = ();
 
-    fn write_encoded_bytes(&mut self, s: &[u8]) -> Result<(), Self::Error> {
-        self.string.push_str(str::from_utf8(s).unwrap());
-
-        Ok(())
-    }
+    Ok(column)
 }
 
 #[cfg(test)]
@@ -111,6 +120,28 @@ pub mod tests {
         assert_eq!("Zm9vYmFy", chunked_encode_str(b"foobar", PAD));
     }
 
+    #[test]
+    fn chunked_encode_wrapped_matches_normal_encode() {
+        let mut rng = rand::rngs::SmallRng::from_entropy();
+        let input_len_range = Uniform::new(0, 3_000);
+
+        for _ in 0..2_000 {
+            let input: Vec<u8> = (0..input_len_range.sample(&mut rng))
+                .map(|_| rng.gen())
+                .collect();
+            let config = PAD
+                .with_encode_padding(rng.gen())
+                .with_encode_line_wrap(rng.gen_range(0..100))
+                .with_encode_trailing_newline(rng.gen())
+                .with_encode_line_ending(rng.gen());
+            let engine = GeneralPurpose::new(&STANDARD, config);
+
+            let mut s = String::new();
+            ChunkedEncoder::new(&engine).encode(&input, &mut s).unwrap();
+            assert_eq!(engine.encode(&input), s);
+        }
+    }
+
     #[test]
     fn chunked_encode_matches_normal_encode_random_string_sink() {
         let helper = StringSinkTestHelper;
@@ -144,10 +175,9 @@ pub mod tests {
     fn chunked_encode_str(bytes: &[u8], config: GeneralPurposeConfig) -> String {
         let mut s = String::new();
 
-        let mut sink = StringSink::new(&mut s);
         let engine = GeneralPurpose::new(&STANDARD, config);
         let encoder = ChunkedEncoder::new(&engine);
-        encoder.encode(bytes, &mut sink).unwrap();
+        encoder.encode(bytes, &mut s).unwrap();
 
         s
     }
@@ -163,8 +193,7 @@ pub mod tests {
         fn encode_to_string<E: Engine>(&self, engine: &E, bytes: &[u8]) -> String {
             let encoder = ChunkedEncoder::new(engine);
             let mut s = String::new();
-            let mut sink = StringSink::new(&mut s);
-            encoder.encode(bytes, &mut sink).unwrap();
+            encoder.encode(bytes, &mut s).unwrap();
 
             s
         }
@@ -0,0 +1,155 @@
+use crate::{engine::Config, DecodeError, Engine};
+use alloc::{string::String, vec::Vec};
+
+/// Join separately encoded segments into a single encoding of their decoded payloads.
+///
+/// Simply concatenating base64 strings only works if every segment but the last encodes a multiple
+/// of 3 bytes. Otherwise, the padding and partial quads in the middle have to be re-aligned, which
+/// is what this does: each part is decoded with `engine` in turn, and all but the last 0-2 bytes
+/// are encoded right away, with the leftover bytes carried into the next part. Only one part's
+/// decoded bytes are held at a time, in a buffer reused for every part. If `engine` wraps its
+/// output into lines, the line breaks can't be re-aligned this way, so the whole payload is decoded
+/// before it's encoded.
+///
+/// This is useful for assembling an upload that arrives as base64 chunks from a client that
+/// encoded each chunk on its own.
+///
+/// # Errors
+///
+/// Any error from decoding a part with `engine`. Offsets in [`DecodeError::InvalidByte`],
+/// [`DecodeError::InvalidLastSymbol`], and [`DecodeError::PaddingNotAtEnd`] are relative to the
+/// concatenated parts, so they can be reported against `parts.concat()`.
+///
+/// # Examples
+///
+/// ```
+/// use base64::{engine::general_purpose::STANDARD, Engine as _};
+///
+/// let parts = [STANDARD.encode(b"hello"), STANDARD.encode(b" world")];
+/// assert_eq!(["aGVsbG8=", "IHdvcmxk"], parts);
+///
+/// assert_eq!(
+///     Ok(STANDARD.encode(b"hello world")),
+///     base64::concat(&STANDARD, &[&parts[0], &parts[1]])
+/// );
+/// assert_eq!(
+///     Err(base64::DecodeError::InvalidByte(9, b'!')),
+///     base64::concat(&STANDARD, &["aGVsbG8=", "I!dvcmxk"])
+/// );
+/// ```
+pub fn concat<E: Engine + ?Sized>(engine: &E, parts: &[&str]) -> Result<String, DecodeError> {
+    let mut output = String::new();
+    // bytes carried over from the previous part, followed by the current part's decoded bytes
+    let mut decoded = Vec::new();
+    let mut part_offset = 0;
+
+    for part in parts {
+        engine
+            .decode_vec(part, &mut decoded)
+            .map_err(|e| offset_error(e, part_offset))?;
+        part_offset += part.len();
+
+        let config = engine.config();
+        let aligned_len = if config.encode_line_wrap() == 0 && !config.encode_trailing_newline() {
+            decoded.len() - decoded.len() % 3
+        } else {
+            0
+        };
+        // a multiple of 3 bytes is never padded, so more output can be appended after it
+        engine.encode_string(&decoded[..aligned_len], &mut output);
+        let _ = decoded.drain(..aligned_len);
+    }
+
+    engine.encode_string(&decoded, &mut output);
+
+    Ok(output)
+}
+
+/// Shift the offset in `error` from being within a part to within the concatenated parts.
+fn offset_error(error: DecodeError, part_offset: usize) -> DecodeError {
+    match error {
+        DecodeError::InvalidByte(offset, byte) => {
+            DecodeError::InvalidByte(part_offset + offset, byte)
+        }
+        DecodeError::InvalidLastSymbol {
+            offset,
+            symbol,
+            symbol_value,
+        } => DecodeError::InvalidLastSymbol {
+            offset: part_offset + offset,
+            symbol,
+            symbol_value,
+        },
+        DecodeError::PaddingNotAtEnd(offset) => DecodeError::PaddingNotAtEnd(part_offset + offset),
+        e => e,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alphabet,
+        engine::{
+            general_purpose::{PAD, STANDARD, STANDARD_NO_PAD, URL_SAFE},
+            GeneralPurpose,
+        },
+    };
+    use rand::{Rng, RngCore};
+
+    #[test]
+    fn concat_matches_encoding_the_joined_payloads() {
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..1_000 {
+            let mut payload = Vec::new();
+            let mut parts = Vec::new();
+            for _ in 0..rng.gen_range(0..5) {
+                let mut part = vec![0; rng.gen_range(0..20)];
+                rng.fill_bytes(&mut part);
+                payload.extend_from_slice(&part);
+                parts.push(STANDARD.encode(&part));
+            }
+            let parts: Vec<&str> = parts.iter().map(String::as_str).collect();
+
+            assert_eq!(Ok(STANDARD.encode(&payload)), concat(&STANDARD, &parts));
+        }
+    }
+
+    #[test]
+    fn concat_uses_the_engine_config() {
+        assert_eq!(
+            Ok("YWJj".to_string()),
+            concat(&STANDARD_NO_PAD, &["YQ", "YmM"])
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidPadding),
+            concat(&STANDARD_NO_PAD, &["YQ==", "YmM"])
+        );
+        assert_eq!(Ok("-_8=".to_string()), concat(&URL_SAFE, &["-w==", "_w=="]));
+        assert_eq!(Ok(String::new()), concat(&STANDARD, &[]));
+        assert_eq!(Ok(String::new()), concat(&STANDARD, &["", ""]));
+
+        let wrapped = GeneralPurpose::new(&alphabet::STANDARD, PAD.with_encode_line_wrap(4));
+        assert_eq!(
+            Ok("YWJj\nZGVm\nZw==".to_string()),
+            concat(&wrapped, &["YWJj\nZA==", "ZWZn"])
+        );
+    }
+
+    #[test]
+    fn concat_error_offsets_are_relative_to_the_joined_parts() {
+        assert_eq!(
+            Err(DecodeError::InvalidByte(6, b'*')),
+            concat(&STANDARD, &["YQ==", "Ym*j"])
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidLastSymbol {
+                offset: 9,
+                symbol: b'N',
+                symbol_value: 13,
+            }),
+            concat(&STANDARD, &["YQ==", "YWJj", "YN=="])
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use crate::Engine;
+
+/// Decode `encoded_input` with `engine` and check whether it decodes to `expected_bytes`, taking
+/// the same time wherever the decoded bytes differ from `expected_bytes`.
+///
+/// This is for checking untrusted input against a secret, like the signature on a webhook request
+/// against the HMAC computed for it. Comparing the base64 strings or the decoded bytes with `==`
+/// stops at the first difference, so how long it takes reveals how much of a guess is right, and
+/// the secret can be found a byte at a time. Here the comparison reads every byte.
+///
+/// Only the comparison is constant-time: decoding `encoded_input` isn't, but its timing only
+/// depends on `encoded_input` itself, which the caller sent. `false` is returned right away if
+/// `encoded_input` doesn't decode, or if it decodes to a different number of bytes than
+/// `expected_bytes`, so the length of `expected_bytes` isn't hidden. That's fine for signatures and
+/// MACs, whose length is public. Encodings that don't decode with `engine`, like ones with
+/// non-canonical trailing bits when they aren't allowed, never match.
+///
+/// # Examples
+///
+/// ```
+/// use base64::engine::general_purpose::STANDARD;
+///
+/// let expected_mac = [0x5B, 0x1C, 0xE8, 0x0F];
+///
+/// assert!(base64::ct_eq(&STANDARD, "WxzoDw==", expected_mac));
+/// assert!(!base64::ct_eq(&STANDARD, "WxzoDA==", expected_mac));
+/// assert!(!base64::ct_eq(&STANDARD, "Wxzo", expected_mac));
+/// assert!(!base64::ct_eq(&STANDARD, "not base64", expected_mac));
+/// ```
+pub fn ct_eq<E: Engine + ?Sized, T: AsRef<[u8]>, U: AsRef<[u8]>>(
+    engine: &E,
+    encoded_input: T,
+    expected_bytes: U,
+) -> bool {
+    match engine.decode(encoded_input) {
+        Ok(decoded) => bytes_ct_eq(&decoded, expected_bytes.as_ref()),
+        Err(_) => false,
+    }
+}
+
+/// Whether `a` and `b` are equal, reading every byte even after a difference is found.
+fn bytes_ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    // accumulate the differences rather than branching on each of them
+    let diff = a.iter().zip(b).fold(0_u8, |diff, (x, y)| diff | (x ^ y));
+    diff == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alphabet,
+        engine::{
+            general_purpose::{PAD, STANDARD},
+            GeneralPurpose,
+        },
+        tests::random_engine,
+    };
+    use rand::{Rng, RngCore};
+
+    #[test]
+    fn ct_eq_matches_only_the_expected_bytes() {
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..1_000 {
+            let engine = random_engine(&mut rng);
+            let mut expected = vec![0; rng.gen_range(0..100)];
+            rng.fill_bytes(&mut expected);
+            let encoded = engine.encode(&expected);
+
+            assert!(ct_eq(&engine, &encoded, &expected));
+
+            if !expected.is_empty() {
+                let mut other = expected.clone();
+                let i = rng.gen_range(0..other.len());
+                other[i] ^= 1 << rng.gen_range(0..8);
+                assert!(!ct_eq(&engine, &encoded, &other));
+                assert!(!ct_eq(&engine, engine.encode(&other), &expected));
+                assert!(!ct_eq(&engine, &encoded, &expected[1..]));
+            }
+
+            let mut longer = expected.clone();
+            longer.push(rng.gen());
+            assert!(!ct_eq(&engine, &encoded, &longer));
+        }
+    }
+
+    #[test]
+    fn ct_eq_rejects_input_that_does_not_decode() {
+        assert!(!ct_eq(&STANDARD, "aGVsbG8*", b"hello"));
+        assert!(ct_eq(&STANDARD, "aGVsbG8=", b"hello"));
+        // non-canonical trailing bits only match when the engine allows them
+        assert!(!ct_eq(&STANDARD, "aGVsbG9=", b"hello"));
+        let lenient = GeneralPurpose::new(
+            &alphabet::STANDARD,
+            PAD.with_decode_allow_trailing_bits(true),
+        );
+        assert!(ct_eq(&lenient, "aGVsbG9=", b"hello"));
+    }
+
+    #[test]
+    fn bytes_ct_eq_compares_every_position() {
+        assert!(bytes_ct_eq(b"", b""));
+        assert!(bytes_ct_eq(b"abc", b"abc"));
+        assert!(!bytes_ct_eq(b"abc", b"ab"));
+        assert!(!bytes_ct_eq(b"xbc", b"abc"));
+        assert!(!bytes_ct_eq(b"abx", b"abc"));
+    }
+}
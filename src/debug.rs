@@ -0,0 +1,223 @@
+//! Readable reports of why some input failed to decode.
+//!
+//! [`annotate`] lays out malformed input as a hex dump with the bytes the error is about marked,
+//! followed by whatever decoded cleanly before them, which is usually enough to tell truncation
+//! from corruption or the wrong alphabet at a glance.
+//!
+//! # Examples
+//!
+//! ```
+//! use base64::{debug, engine::general_purpose::STANDARD, Engine as _};
+//!
+//! let input = "aGVsbG8gd29y*GQ=";
+//! let error = STANDARD.decode(input).unwrap_err();
+//! print!("{}", debug::annotate(input, &error, &STANDARD));
+//! ```
+//!
+//! prints
+//!
+//! ```text
+//! Invalid symbol 42, offset 12.
+//! 00000000  61 47 56 73 62 47 38 67 64 32 39 79 2a 47 51 3d  |aGVsbG8gd29y*GQ=|
+//!                                               ^^                        ^
+//! decoded before the error (9 bytes):
+//! 00000000  68 65 6c 6c 6f 20 77 6f 72                       |hello wor|
+//! ```
+
+use crate::{
+    engine::{Config, Engine},
+    DecodeError,
+};
+use alloc::{format, string::String};
+use core::{cmp, fmt::Write as _, ops::Range};
+
+/// Bytes per line of the hex dump.
+const BYTES_PER_LINE: usize = 16;
+
+/// Describe `error`, which came from decoding `input` with `engine`, with a hex dump of `input`
+/// marking the bytes it's about and a dump of what decodes from the whole chunks before them.
+///
+/// The engine is only used to find padding and to decode the prefix. Errors that aren't about a
+/// particular part of the input, like [`DecodeError::UnexpectedDecodedLength`], are shown with
+/// nothing marked and no prefix.
+pub fn annotate<T: AsRef<[u8]>, E: Engine>(input: T, error: &DecodeError, engine: &E) -> String {
+    let input = input.as_ref();
+    let region = error_region(input, error, engine);
+
+    let mut report = format!("{}\n", error);
+    hex_dump(&mut report, input, region.clone());
+
+    if let Some(region) = region {
+        // the whole chunks before the chunk with the error decode on their own, unless an earlier
+        // byte was skipped as whitespace or the like and shifted the chunk boundaries
+        let prefix_len = region.start / 4 * 4;
+        if let (true, Ok(decoded)) = (prefix_len > 0, engine.decode(&input[..prefix_len])) {
+            let _ = writeln!(
+                report,
+                "decoded before the error ({} bytes):",
+                decoded.len()
+            );
+            hex_dump(&mut report, &decoded, None);
+        }
+    }
+
+    report
+}
+
+/// The bytes of `input` that `error` is about, if it's about any in particular.
+fn error_region<E: Engine>(input: &[u8], error: &DecodeError, engine: &E) -> Option<Range<usize>> {
+    let region = match *error {
+        DecodeError::InvalidByte(offset, _)
+        | DecodeError::InvalidLastSymbol { offset, .. }
+        | DecodeError::PaddingNotAtEnd(offset) => offset..offset + 1,
+        // symbols of the last, incomplete chunk
+        DecodeError::InvalidLength(len) => len / 4 * 4..len,
+        DecodeError::InvalidPadding => {
+            let padding_byte = engine.config().padding().decode_byte();
+            let start = input.iter().position(|&b| b == padding_byte)?;
+            let end = input.iter().rposition(|&b| b == padding_byte)? + 1;
+            start..end
+        }
+        DecodeError::InputTooLong { .. } | DecodeError::UnexpectedDecodedLength { .. } => {
+            return None
+        }
+    };
+
+    // the offsets in errors are always within the input, but don't panic if they aren't
+    let end = cmp::min(region.end, input.len());
+    if region.start < end {
+        Some(region.start..end)
+    } else {
+        None
+    }
+}
+
+/// Append a hex dump of `bytes` to `report`, with `marked` underlined in lines of `^` markers.
+fn hex_dump(report: &mut String, bytes: &[u8], marked: Option<Range<usize>>) {
+    let marked = marked.unwrap_or(0..0);
+
+    for (line_index, line) in bytes.chunks(BYTES_PER_LINE).enumerate() {
+        let line_start = line_index * BYTES_PER_LINE;
+
+        let _ = write!(report, "{:08x} ", line_start);
+        for byte in line {
+            let _ = write!(report, " {:02x}", byte);
+        }
+        for _ in line.len()..BYTES_PER_LINE {
+            report.push_str("   ");
+        }
+        report.push_str("  |");
+        report.extend(line.iter().map(|&b| {
+            if b.is_ascii_graphic() || b == b' ' {
+                char::from(b)
+            } else {
+                '.'
+            }
+        }));
+        report.push_str("|\n");
+
+        let line_range = line_start..line_start + line.len();
+        if marked.start < line_range.end && line_range.start < marked.end {
+            let is_marked =
+                |i: usize| marked.start <= line_start + i && line_start + i < marked.end;
+
+            // under the offset column, then aligned with the hex and text of each byte
+            let mut markers = " ".repeat(9);
+            for i in 0..BYTES_PER_LINE {
+                markers.push_str(if is_marked(i) { " ^^" } else { "   " });
+            }
+            markers.push_str("   ");
+            markers.extend((0..line.len()).map(|i| if is_marked(i) { '^' } else { ' ' }));
+            report.push_str(markers.trim_end());
+            report.push('\n');
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alphabet,
+        engine::{
+            general_purpose::{GeneralPurpose, NO_PAD, STANDARD},
+            DecodePaddingMode,
+        },
+    };
+    use alloc::vec::Vec;
+
+    #[test]
+    fn marks_invalid_byte_and_decodes_prefix() {
+        let input = "aGVsbG8gd29y*GQ=";
+        let error = STANDARD.decode(input).unwrap_err();
+
+        assert_eq!(
+            "Invalid symbol 42, offset 12.\n\
+             00000000  61 47 56 73 62 47 38 67 64 32 39 79 2a 47 51 3d  |aGVsbG8gd29y*GQ=|\n\
+             \x20                                             ^^                        ^\n\
+             decoded before the error (9 bytes):\n\
+             00000000  68 65 6c 6c 6f 20 77 6f 72                       |hello wor|\n",
+            annotate(input, &error, &STANDARD)
+        );
+    }
+
+    #[test]
+    fn marks_on_the_line_with_the_error() {
+        let mut input = STANDARD.encode([0xAB; 30]).into_bytes();
+        input[33] = b'\x01';
+        let error = STANDARD.decode(&input).unwrap_err();
+        assert_eq!(DecodeError::InvalidByte(33, 1), error);
+
+        let report = annotate(&input, &error, &STANDARD);
+        let lines: Vec<&str> = report.lines().collect();
+        // the error, then three lines of input with the marker under the third
+        assert_eq!(
+            "00000020  71 01 75 72 71 36 75 72                          |q.urq6ur|",
+            lines[3]
+        );
+        assert_eq!(
+            "             ^^                                              ^",
+            lines[4]
+        );
+        assert_eq!("decoded before the error (24 bytes):", lines[5]);
+    }
+
+    #[test]
+    fn marks_padding_and_incomplete_chunks() {
+        let engine = GeneralPurpose::new(
+            &alphabet::STANDARD,
+            NO_PAD.with_decode_padding_mode(DecodePaddingMode::RequireNone),
+        );
+        let error = engine.decode("aGVsbG8=").unwrap_err();
+        assert_eq!(DecodeError::InvalidPadding, error);
+        assert_eq!(
+            "Invalid padding\n\
+             00000000  61 47 56 73 62 47 38 3d                          |aGVsbG8=|\n\
+             \x20                              ^^                                  ^\n\
+             decoded before the error (3 bytes):\n\
+             00000000  68 65 6c                                         |hel|\n",
+            annotate("aGVsbG8=", &error, &engine)
+        );
+
+        let error = STANDARD.decode("aGVsb").unwrap_err();
+        assert_eq!(DecodeError::InvalidLength(5), error);
+        let report = annotate("aGVsb", &error, &STANDARD);
+        assert!(report.contains(
+            "|aGVsb|\n                      ^^                                        ^\n"
+        ));
+        assert!(report.ends_with("decoded before the error (3 bytes):\n00000000  68 65 6c                                         |hel|\n"));
+    }
+
+    #[test]
+    fn unlocated_errors_mark_nothing() {
+        let error = DecodeError::UnexpectedDecodedLength {
+            expected: 4,
+            actual: Some(2),
+        };
+        assert_eq!(
+            "Decoded length 2 doesn't match expected length 4\n\
+             00000000  61 47 6b 3d                                      |aGk=|\n",
+            annotate("aGk=", &error, &STANDARD)
+        );
+    }
+}
@@ -1,12 +1,18 @@
+#[cfg(any(feature = "alloc", test))]
+use crate::engine::Config;
 use crate::engine::{general_purpose::STANDARD, DecodeEstimate, Engine};
 #[cfg(any(feature = "alloc", test))]
-use alloc::vec::Vec;
+use alloc::{format, string::String, vec::Vec};
 use core::fmt;
 #[cfg(any(feature = "std", test))]
 use std::error;
 
 /// Errors that can occur while decoding.
+///
+/// More variants may be added in the future, so matches must include a wildcard arm.
 #[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
 pub enum DecodeError {
     /// An invalid byte was found in the input. The offset and offending byte are provided.
     ///
@@ -41,6 +47,31 @@ pub enum DecodeError {
     /// The nature of the padding was not as configured: absent or incorrect when it must be
     /// canonical, or present when it must be absent, etc.
     InvalidPadding,
+    /// Padding was found before the last symbol of the input. The offset of the first padding
+    /// byte is provided.
+    ///
+    /// This is only reported with [`EmbeddedPaddingMode::Strict`](crate::engine::EmbeddedPaddingMode::Strict);
+    /// otherwise such padding is a [`DecodeError::InvalidByte`].
+    PaddingNotAtEnd(usize),
+    /// The input was longer than the engine is configured to accept.
+    ///
+    /// See [`crate::engine::GeneralPurposeConfig::with_max_encoded_len`].
+    InputTooLong {
+        /// Length of the input
+        len: usize,
+        /// The maximum input length allowed
+        max: usize,
+    },
+    /// The decoded output wasn't the length that was required.
+    ///
+    /// See `Engine::decode_array()`, which requires the `const-generics` feature.
+    UnexpectedDecodedLength {
+        /// The required length
+        expected: usize,
+        /// The decoded length, or `None` if it was longer than `expected`, in which case decoding
+        /// stopped before finding out by how much.
+        actual: Option<usize>,
+    },
 }
 
 impl fmt::Display for DecodeError {
@@ -72,6 +103,28 @@ impl fmt::Display for DecodeError {
                 )
             }
             Self::InvalidPadding => write!(f, "Invalid padding"),
+            Self::PaddingNotAtEnd(offset) => {
+                write!(
+                    f,
+                    "Padding at offset {} before the end of the input",
+                    offset
+                )
+            }
+            Self::InputTooLong { len, max } => {
+                write!(f, "Input length {} exceeds maximum of {}", len, max)
+            }
+            Self::UnexpectedDecodedLength {
+                expected,
+                actual: Some(actual),
+            } => write!(
+                f,
+                "Decoded length {} doesn't match expected length {}",
+                actual, expected
+            ),
+            Self::UnexpectedDecodedLength {
+                expected,
+                actual: None,
+            } => write!(f, "Decoded length exceeds expected length {}", expected),
         }
     }
 }
@@ -83,23 +136,135 @@ impl fmt::Debug for DecodeError {
     }
 }
 
+#[cfg(any(feature = "alloc", test))]
+impl DecodeError {
+    /// Render the error along with the region of `input` it refers to, with a caret under the
+    /// offending byte, in the style of compiler diagnostics.
+    ///
+    /// `input` and `engine` should be the input and engine that produced this error; the engine's
+    /// padding byte locates [`DecodeError::InvalidPadding`]. Long inputs are shortened to a window
+    /// around the error, and non-printable bytes are escaped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use base64::{Engine as _, engine::general_purpose::STANDARD};
+    ///
+    /// let input = "aGV!sbG8=";
+    /// let err = STANDARD.decode(input).unwrap_err();
+    /// assert_eq!(
+    ///     "error: Invalid symbol 33, offset 3.\n  |\n  | aGV!sbG8=\n  |    ^\n",
+    ///     err.render(input, &STANDARD)
+    /// );
+    /// ```
+    pub fn render<T: AsRef<[u8]>, E: Engine + ?Sized>(&self, input: T, engine: &E) -> String {
+        fn inner(err: &DecodeError, input: &[u8], padding_byte: u8) -> String {
+            // bytes of context shown on each side of the error
+            const CONTEXT: usize = 24;
+
+            let position = match *err {
+                DecodeError::InvalidByte(offset, _) => offset,
+                DecodeError::InvalidLastSymbol { offset, .. } => offset,
+                DecodeError::PaddingNotAtEnd(offset) => offset,
+                DecodeError::InvalidPadding => input
+                    .iter()
+                    .position(|&b| b == padding_byte)
+                    .unwrap_or(input.len()),
+                // the problem is the input ending too soon
+                DecodeError::InvalidLength(_) => input.len(),
+                // the first byte past the limit
+                DecodeError::InputTooLong { max, .. } => max,
+                // the input as a whole is the wrong length
+                DecodeError::UnexpectedDecodedLength { .. } => input.len(),
+            }
+            .min(input.len());
+
+            let start = position.saturating_sub(CONTEXT);
+            let end = input.len().min(position.saturating_add(CONTEXT + 1));
+
+            let mut snippet = String::new();
+            let mut caret_column = 0;
+            if start > 0 {
+                snippet.push_str("...");
+            }
+            for (offset, &b) in input[start..end].iter().enumerate() {
+                if start + offset == position {
+                    caret_column = snippet.len();
+                }
+                snippet.extend(core::ascii::escape_default(b).map(char::from));
+            }
+            if end == position {
+                caret_column = snippet.len();
+            }
+            if end < input.len() {
+                snippet.push_str("...");
+            }
+
+            format!(
+                "error: {}\n  |\n  | {}\n  | {:width$}^\n",
+                err,
+                snippet,
+                "",
+                width = caret_column
+            )
+        }
+
+        inner(
+            self,
+            input.as_ref(),
+            engine.config().padding().decode_byte(),
+        )
+    }
+}
+
 #[cfg(any(feature = "std", test))]
 impl error::Error for DecodeError {}
 
 /// Errors that can occur while decoding into a slice.
+///
+/// Decoding failures are wrapped in [`DecodeSliceError::DecodeError`] and are also reported as the
+/// error's `source()` when the `std` feature is enabled.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
 pub enum DecodeSliceError {
     /// A [`DecodeError`] occurred
     DecodeError(DecodeError),
     /// The provided slice is too small.
-    OutputSliceTooSmall,
+    OutputSliceTooSmall {
+        /// An output length that is large enough to decode the input.
+        ///
+        /// This is conservative in the same way as [`decoded_len_estimate`], so the actual
+        /// decoded length may be up to 2 bytes smaller.
+        needed: usize,
+    },
+}
+
+impl DecodeSliceError {
+    /// Returns the underlying [`DecodeError`], or `None` if the output slice was too small.
+    pub fn into_decode_error(self) -> Option<DecodeError> {
+        match self {
+            Self::DecodeError(e) => Some(e),
+            Self::OutputSliceTooSmall { .. } => None,
+        }
+    }
+
+    /// Returns the output length to grow to before retrying, or `None` if the input was invalid.
+    pub fn output_len_needed(&self) -> Option<usize> {
+        match *self {
+            Self::DecodeError(_) => None,
+            Self::OutputSliceTooSmall { needed } => Some(needed),
+        }
+    }
 }
 
 impl fmt::Display for DecodeSliceError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::DecodeError(e) => write!(f, "DecodeError: {}", e),
-            Self::OutputSliceTooSmall => write!(f, "Output slice too small"),
+            Self::OutputSliceTooSmall { needed } => {
+                write!(f, "Output slice too small, {} bytes needed", needed)
+            }
         }
     }
 }
@@ -109,7 +274,7 @@ impl error::Error for DecodeSliceError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
             DecodeSliceError::DecodeError(e) => Some(e),
-            DecodeSliceError::OutputSliceTooSmall => None,
+            DecodeSliceError::OutputSliceTooSmall { .. } => None,
         }
     }
 }
@@ -120,6 +285,271 @@ impl From<DecodeError> for DecodeSliceError {
     }
 }
 
+/// Every invalid byte found in an input that failed to decode.
+///
+/// Produced by [`Engine::decode_report_all`].
+#[cfg(any(feature = "alloc", test))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodeReport {
+    error: DecodeError,
+    invalid_bytes: Vec<(usize, u8)>,
+    truncated: bool,
+}
+
+#[cfg(any(feature = "alloc", test))]
+impl DecodeReport {
+    /// The maximum number of invalid bytes recorded in a report.
+    pub const MAX_INVALID_BYTES: usize = 256;
+
+    /// Scan all of `input` for bytes that `engine` does not accept as symbols.
+    pub(crate) fn new<E: Engine + ?Sized>(engine: &E, input: &[u8], error: DecodeError) -> Self {
+        let mut invalid_bytes = Vec::new();
+        let mut truncated = false;
+
+        // misplaced padding is reported by the first error
+        let mut offset = 0;
+        while let Some(invalid) = engine.find_invalid(&input[offset..]) {
+            if invalid_bytes.len() == Self::MAX_INVALID_BYTES {
+                truncated = true;
+                break;
+            }
+            offset += invalid;
+            invalid_bytes.push((offset, input[offset]));
+            offset += 1;
+        }
+
+        Self {
+            error,
+            invalid_bytes,
+            truncated,
+        }
+    }
+
+    /// The first error encountered, as [`Engine::decode`] would have returned.
+    pub fn error(&self) -> &DecodeError {
+        &self.error
+    }
+
+    /// The offset and value of each byte in the input that is not a symbol in the engine's
+    /// alphabet, in input order.
+    ///
+    /// Padding bytes are not included, even if misplaced; see [`DecodeReport::error`] for those.
+    pub fn invalid_bytes(&self) -> &[(usize, u8)] {
+        &self.invalid_bytes
+    }
+
+    /// Returns `true` if there were more than [`DecodeReport::MAX_INVALID_BYTES`] invalid bytes,
+    /// so only the first ones were recorded.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+#[cfg(any(feature = "alloc", test))]
+impl fmt::Display for DecodeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}{} invalid bytes)",
+            self.error,
+            if self.truncated { "more than " } else { "" },
+            self.invalid_bytes.len()
+        )
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl error::Error for DecodeReport {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// A summary of the formatting of input decoded with [`Engine::decode_with_stats`].
+#[cfg(any(feature = "alloc", test))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DecodeStats {
+    /// The number of ASCII whitespace bytes that were skipped, including line endings
+    pub skipped_whitespace: usize,
+    /// The number of padding bytes
+    pub padding_chars: usize,
+    /// The number of lines, counting a final line without a line ending
+    pub lines: usize,
+    /// The number of other bytes that were skipped because the engine's config ignores them, like
+    /// those set with
+    /// [`GeneralPurposeConfig::with_decode_ignore_bytes`](crate::engine::GeneralPurposeConfig::with_decode_ignore_bytes)
+    pub skipped_ignored: usize,
+}
+
+#[cfg(any(feature = "alloc", test))]
+impl DecodeStats {
+    /// Returns `input` without whitespace or bytes that `ignores`, along with the stats for
+    /// `input`, counting `padding_byte` as padding.
+    pub(crate) fn strip_skipped<F: Fn(u8) -> bool>(
+        input: &[u8],
+        padding_byte: u8,
+        ignores: F,
+    ) -> (Vec<u8>, Self) {
+        let mut stats = Self::default();
+        let mut stripped = Vec::with_capacity(input.len());
+        let mut line_has_content = false;
+
+        for &b in input {
+            if b == b'\n' {
+                stats.lines += 1;
+                line_has_content = false;
+            } else {
+                line_has_content = true;
+            }
+
+            if b.is_ascii_whitespace() {
+                stats.skipped_whitespace += 1;
+            } else if ignores(b) {
+                stats.skipped_ignored += 1;
+            } else {
+                if b == padding_byte {
+                    stats.padding_chars += 1;
+                }
+                stripped.push(b);
+            }
+        }
+
+        if line_has_content {
+            stats.lines += 1;
+        }
+
+        (stripped, stats)
+    }
+
+    /// Map an error for the output of [`DecodeStats::strip_skipped`] onto the original input.
+    pub(crate) fn map_error<F: Fn(u8) -> bool>(
+        input: &[u8],
+        ignores: F,
+        error: DecodeError,
+    ) -> DecodeError {
+        // offset in `input` of the `offset`th byte that wasn't skipped
+        let original_offset = |offset: usize| {
+            input
+                .iter()
+                .enumerate()
+                .filter(|&(_, &b)| !b.is_ascii_whitespace() && !ignores(b))
+                .nth(offset)
+                .map(|(i, _)| i)
+                .unwrap_or(input.len())
+        };
+
+        match error {
+            DecodeError::InvalidByte(offset, byte) => {
+                DecodeError::InvalidByte(original_offset(offset), byte)
+            }
+            DecodeError::InvalidLastSymbol {
+                offset,
+                symbol,
+                symbol_value,
+            } => DecodeError::InvalidLastSymbol {
+                offset: original_offset(offset),
+                symbol,
+                symbol_value,
+            },
+            DecodeError::PaddingNotAtEnd(offset) => {
+                DecodeError::PaddingNotAtEnd(original_offset(offset))
+            }
+            e => e,
+        }
+    }
+}
+
+/// The contents of a JSON string with its escapes replaced, for [`Engine::decode_json_escaped`].
+#[cfg(any(feature = "alloc", test))]
+pub(crate) struct JsonUnescaped {
+    /// The unescaped bytes, without escaped whitespace
+    pub(crate) bytes: Vec<u8>,
+    /// The offset in the escaped input of each of `bytes`, or of the `\` of its escape
+    offsets: Vec<usize>,
+}
+
+#[cfg(any(feature = "alloc", test))]
+impl JsonUnescaped {
+    /// Replace the escapes in `input`, skipping those that stand for whitespace.
+    ///
+    /// Escapes that are malformed or stand for characters outside ASCII, which can't be base64,
+    /// are reported as an invalid `\` at the start of the escape.
+    pub(crate) fn new(input: &[u8]) -> Result<Self, DecodeError> {
+        let mut bytes = Vec::with_capacity(input.len());
+        let mut offsets = Vec::with_capacity(input.len());
+
+        let mut i = 0;
+        while i < input.len() {
+            let start = i;
+            let byte = if input[i] == b'\\' {
+                let invalid = DecodeError::InvalidByte(start, b'\\');
+                let (byte, escape_len) = match input.get(i + 1).ok_or(invalid.clone())? {
+                    b'"' => (b'"', 2),
+                    b'\\' => (b'\\', 2),
+                    b'/' => (b'/', 2),
+                    b'b' => (0x08, 2),
+                    b'f' => (0x0C, 2),
+                    b'n' => (b'\n', 2),
+                    b'r' => (b'\r', 2),
+                    b't' => (b'\t', 2),
+                    b'u' => {
+                        let code_point = input
+                            .get(i + 2..i + 6)
+                            // `from_str_radix` also accepts a leading `+`, which JSON doesn't
+                            .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+                            .and_then(|hex| core::str::from_utf8(hex).ok())
+                            .and_then(|hex| u16::from_str_radix(hex, 16).ok())
+                            // only ASCII can be base64
+                            .filter(|&c| c < 0x80)
+                            .ok_or(invalid)?;
+                        // checked to be ASCII
+                        (code_point as u8, 6)
+                    }
+                    _ => return Err(invalid),
+                };
+                i += escape_len;
+
+                // line wrapped base64 in JSON has escaped line endings
+                if byte.is_ascii_whitespace() {
+                    continue;
+                }
+                byte
+            } else {
+                i += 1;
+                input[start]
+            };
+
+            bytes.push(byte);
+            offsets.push(start);
+        }
+
+        Ok(Self { bytes, offsets })
+    }
+
+    /// Map an error for [`JsonUnescaped::bytes`] onto the escaped input.
+    pub(crate) fn map_error(&self, error: DecodeError) -> DecodeError {
+        match error {
+            DecodeError::InvalidByte(offset, byte) => {
+                DecodeError::InvalidByte(self.offsets[offset], byte)
+            }
+            DecodeError::InvalidLastSymbol {
+                offset,
+                symbol,
+                symbol_value,
+            } => DecodeError::InvalidLastSymbol {
+                offset: self.offsets[offset],
+                symbol,
+                symbol_value,
+            },
+            DecodeError::PaddingNotAtEnd(offset) => {
+                DecodeError::PaddingNotAtEnd(self.offsets[offset])
+            }
+            e => e,
+        }
+    }
+}
+
 /// Decode base64 using the [`STANDARD` engine](STANDARD).
 ///
 /// See [`Engine::decode`].
@@ -295,23 +725,261 @@ mod tests {
         }
     }
 
+    #[test]
+    fn decode_rejects_input_longer_than_max_encoded_len() {
+        let engine = GeneralPurpose::new(
+            &alphabet::STANDARD,
+            general_purpose::PAD.with_max_encoded_len(8),
+        );
+
+        assert_eq!(b"abcdef", &engine.decode("YWJjZGVm").unwrap()[..]);
+        // rejected before the invalid byte is seen
+        assert_eq!(
+            DecodeError::InputTooLong { len: 9, max: 8 },
+            engine.decode("!WJjZGVmZ").unwrap_err()
+        );
+        assert_eq!(
+            DecodeSliceError::DecodeError(DecodeError::InputTooLong { len: 12, max: 8 }),
+            engine
+                .decode_slice("YWJjZGVmZ2hp", &mut [0; 9])
+                .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn decode_with_stats_counts_formatting() {
+        let (decoded, stats) = STANDARD.decode_with_stats("").unwrap();
+        assert!(decoded.is_empty());
+        assert_eq!(DecodeStats::default(), stats);
+
+        let (decoded, stats) = STANDARD.decode_with_stats("  YWJj\nZGVm\nZw==\n").unwrap();
+        assert_eq!(b"abcdefg", &decoded[..]);
+        assert_eq!(
+            DecodeStats {
+                skipped_whitespace: 5,
+                padding_chars: 2,
+                lines: 3,
+                skipped_ignored: 0,
+            },
+            stats
+        );
+    }
+
+    #[test]
+    fn decode_with_stats_counts_ignored_bytes() {
+        let engine = GeneralPurpose::new(
+            &alphabet::STANDARD,
+            general_purpose::PAD.with_decode_ignore_bytes(b".:"),
+        );
+        let (decoded, stats) = engine.decode_with_stats("YWJj.ZGVm:\nZw==").unwrap();
+        assert_eq!(b"abcdefg", &decoded[..]);
+        assert_eq!(
+            DecodeStats {
+                skipped_whitespace: 1,
+                padding_chars: 2,
+                lines: 2,
+                skipped_ignored: 2,
+            },
+            stats
+        );
+
+        assert_eq!(
+            DecodeError::InvalidByte(12, b'!'),
+            engine.decode_with_stats("YWJj.ZGVm:\nZ!==").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn decode_with_meta_counts_padding_and_ignored_bytes() {
+        let (decoded, meta) = STANDARD.decode_with_meta("YWJjZGVmZw==").unwrap();
+        assert_eq!(b"abcdefg", &decoded[..]);
+        assert_eq!(2, meta.padding_len());
+        assert_eq!(0, meta.skipped_ignored());
+        let meta = STANDARD.decode_with_meta("YWJj").unwrap().1;
+        assert_eq!((0, 0), (meta.padding_len(), meta.skipped_ignored()));
+
+        let engine = GeneralPurpose::new(
+            &alphabet::STANDARD,
+            general_purpose::PAD_INDIFFERENT.with_decode_ignore_bytes(b".:"),
+        );
+        let (decoded, meta) = engine.decode_with_meta("YWJj.ZGVm:Zw=.=:").unwrap();
+        assert_eq!(b"abcdefg", &decoded[..]);
+        assert_eq!((2, 4), (meta.padding_len(), meta.skipped_ignored()));
+        let meta = engine.decode_with_meta("YWJj.ZGVmZw").unwrap().1;
+        assert_eq!((0, 1), (meta.padding_len(), meta.skipped_ignored()));
+
+        // the same metadata as decoding into a slice
+        let mut buf = [0; 8];
+        assert_eq!(
+            Ok(meta),
+            engine.decode_slice_with_meta("YWJj.ZGVmZw", &mut buf)
+        );
+    }
+
+    #[test]
+    fn decode_with_meta_errors_like_decode() {
+        // whitespace isn't skipped, unlike with decode_with_stats
+        for input in &["YW Jj", "YWJj\n", "YWJjZ!==", "YWJjZ", "YWJjZw="] {
+            assert_eq!(
+                STANDARD.decode(input).unwrap_err(),
+                STANDARD.decode_with_meta(input).unwrap_err()
+            );
+        }
+    }
+
+    #[test]
+    fn decode_with_stats_error_offsets_include_whitespace() {
+        assert_eq!(
+            DecodeError::InvalidByte(9, b'!'),
+            STANDARD.decode_with_stats("YW Jj\n\tZG!m").unwrap_err()
+        );
+        assert_eq!(
+            DecodeError::InvalidLastSymbol {
+                offset: 4,
+                symbol: b'B',
+                symbol_value: 1,
+            },
+            STANDARD.decode_with_stats("Y\nA B=").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn decode_json_escaped_round_trip() {
+        let mut rng = rand::rngs::SmallRng::from_entropy();
+
+        for _ in 0..1_000 {
+            let orig_data: Vec<u8> = (0..rng.gen_range(0..200)).map(|_| rng.gen()).collect();
+
+            // escape a random selection of bytes the way JSON encoders might, and break lines
+            let mut escaped = String::new();
+            for (i, b) in STANDARD.encode(&orig_data).bytes().enumerate() {
+                if i > 0 && i % 64 == 0 {
+                    escaped.push_str(if rng.gen() { "\\n" } else { "\\r\\n" });
+                }
+                match (b, rng.gen_range(0..4)) {
+                    (b'/', 0) => escaped.push_str("\\/"),
+                    (_, 1) => escaped.push_str(&format!("\\u{:04x}", b)),
+                    (_, 2) => escaped.push_str(&format!("\\u{:04X}", b)),
+                    _ => escaped.push(char::from(b)),
+                }
+            }
+
+            assert_eq!(orig_data, STANDARD.decode_json_escaped(&escaped).unwrap());
+        }
+    }
+
+    #[test]
+    fn decode_json_escaped_error_offsets_are_in_the_input() {
+        assert_eq!(
+            DecodeError::InvalidByte(6, b'*'),
+            STANDARD.decode_json_escaped(r"YW\nJj*ZGVm").unwrap_err()
+        );
+        // escaped bytes are reported at their escape
+        assert_eq!(
+            DecodeError::InvalidByte(2, b'*'),
+            STANDARD.decode_json_escaped(r"YW\u002aJj").unwrap_err()
+        );
+        assert_eq!(
+            DecodeError::InvalidByte(7, b'!'),
+            STANDARD.decode_json_escaped(r"YWJj\tZ!==").unwrap_err()
+        );
+        assert_eq!(
+            DecodeError::InvalidLastSymbol {
+                offset: 1,
+                symbol: b'B',
+                symbol_value: 1,
+            },
+            STANDARD.decode_json_escaped(r"YB==").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn decode_json_escaped_rejects_bad_escapes() {
+        for input in &[
+            r"YWJj\",
+            r"YWJj\x",
+            r"YWJj\u00",
+            r"YWJj\u00g1",
+            r"YWJj\u+041",
+            r"YWJj\u00e9",
+            r"YWJj\ud83d\ude00",
+        ] {
+            assert_eq!(
+                DecodeError::InvalidByte(4, b'\\'),
+                STANDARD.decode_json_escaped(input).unwrap_err(),
+                "{}",
+                input
+            );
+        }
+        // escaped quotes and backslashes are fine as escapes, but not as base64
+        assert_eq!(
+            DecodeError::InvalidByte(4, b'"'),
+            STANDARD.decode_json_escaped(r#"YWJj\"=="#).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn render_points_at_error() {
+        let input = "AAAA".repeat(10) + "AA\nA";
+        let err = STANDARD.decode(&input).unwrap_err();
+        assert_eq!(DecodeError::InvalidByte(42, b'\n'), err);
+        assert_eq!(
+            "error: Invalid symbol 10, offset 42.\n  |\n  \
+             | ...AAAAAAAAAAAAAAAAAAAAAAAA\\nA\n  |                            ^\n",
+            err.render(&input, &STANDARD)
+        );
+
+        let input = "AAAAA";
+        let err = STANDARD.decode(input).unwrap_err();
+        assert_eq!(
+            "error: Invalid input length: 5\n  |\n  | AAAAA\n  |      ^\n",
+            err.render(input, &STANDARD)
+        );
+
+        assert_eq!(
+            "error: Invalid padding\n  |\n  | AA=\n  |   ^\n",
+            DecodeError::InvalidPadding.render("AA=", &STANDARD)
+        );
+
+        // the caret is under the engine's padding byte, whatever it is
+        let dotted = GeneralPurpose::new(
+            &alphabet::STANDARD,
+            general_purpose::PAD
+                .with_padding(crate::engine::Padding::Padded(b'.'))
+                .with_decode_padding_mode(crate::engine::DecodePaddingMode::RequireNone),
+        );
+        let input = "AA..";
+        let err = dotted.decode(input).unwrap_err();
+        assert_eq!(DecodeError::InvalidPadding, err);
+        assert_eq!(
+            "error: Invalid padding\n  |\n  | AA..\n  |   ^\n",
+            err.render(input, &dotted)
+        );
+    }
+
     #[test]
     fn decode_slice_output_length_errors() {
         for num_quads in 1..100 {
             let input = "AAAA".repeat(num_quads);
             let mut vec = vec![0; (num_quads - 1) * 3];
             assert_eq!(
-                DecodeSliceError::OutputSliceTooSmall,
+                DecodeSliceError::OutputSliceTooSmall {
+                    needed: num_quads * 3
+                },
                 STANDARD.decode_slice(&input, &mut vec).unwrap_err()
             );
             vec.push(0);
             assert_eq!(
-                DecodeSliceError::OutputSliceTooSmall,
+                DecodeSliceError::OutputSliceTooSmall {
+                    needed: num_quads * 3
+                },
                 STANDARD.decode_slice(&input, &mut vec).unwrap_err()
             );
             vec.push(0);
             assert_eq!(
-                DecodeSliceError::OutputSliceTooSmall,
+                DecodeSliceError::OutputSliceTooSmall {
+                    needed: num_quads * 3
+                },
                 STANDARD.decode_slice(&input, &mut vec).unwrap_err()
             );
             vec.push(0);
@@ -415,20 +1083,61 @@ mod coverage_gaming {
             },
             DecodeError::InvalidPadding,
         );
+        let _ = format!("{}", DecodeError::InputTooLong { len: 1, max: 0 });
+        let _ = format!(
+            "{} {}",
+            DecodeError::UnexpectedDecodedLength {
+                expected: 1,
+                actual: Some(0)
+            },
+            DecodeError::UnexpectedDecodedLength {
+                expected: 1,
+                actual: None
+            },
+        );
     }
 
     #[test]
     fn decode_slice_error() {
-        let _ = format!("{:?}", DecodeSliceError::OutputSliceTooSmall.clone());
+        let _ = format!(
+            "{:?}",
+            DecodeSliceError::OutputSliceTooSmall { needed: 0 }.clone()
+        );
         let _ = format!(
             "{} {}",
-            DecodeSliceError::OutputSliceTooSmall,
+            DecodeSliceError::OutputSliceTooSmall { needed: 0 },
             DecodeSliceError::DecodeError(DecodeError::InvalidPadding)
         );
-        let _ = DecodeSliceError::OutputSliceTooSmall.source();
+        let _ = DecodeSliceError::OutputSliceTooSmall { needed: 0 }.source();
         let _ = DecodeSliceError::DecodeError(DecodeError::InvalidPadding).source();
     }
 
+    #[test]
+    fn decode_slice_error_source_chain() {
+        assert!(DecodeSliceError::OutputSliceTooSmall { needed: 3 }
+            .source()
+            .is_none());
+
+        let e = DecodeSliceError::DecodeError(DecodeError::InvalidLength(3));
+        let source = e.source().unwrap();
+        assert_eq!(
+            Some(&DecodeError::InvalidLength(3)),
+            source.downcast_ref::<DecodeError>()
+        );
+        assert!(source.source().is_none());
+    }
+
+    #[test]
+    fn decode_slice_error_accessors() {
+        let e = DecodeSliceError::from(DecodeError::InvalidPadding);
+        assert_eq!(None, e.output_len_needed());
+        assert_eq!(Some(DecodeError::InvalidPadding), e.into_decode_error());
+
+        let e = DecodeSliceError::OutputSliceTooSmall { needed: 6 };
+        assert_eq!(Some(6), e.output_len_needed());
+        assert_eq!(None, e.into_decode_error());
+    }
+
     #[test]
     fn deprecated_fns() {
         let _ = decode("");
@@ -41,10 +41,10 @@ struct FormatterSink<'a, 'b: 'a> {
     f: &'a mut Formatter<'b>,
 }
 
-impl<'a, 'b: 'a> super::chunked_encoder::Sink for FormatterSink<'a, 'b> {
+impl<'a, 'b: 'a> super::sink::EncodeSink for FormatterSink<'a, 'b> {
     type Error = fmt::Error;
 
-    fn write_encoded_bytes(&mut self, encoded: &[u8]) -> Result<(), Self::Error> {
+    fn write_encoded(&mut self, encoded: &[u8]) -> Result<(), Self::Error> {
         // Avoid unsafe. If max performance is needed, write your own display wrapper that uses
         // unsafe here to gain about 10-15%.
         self.f
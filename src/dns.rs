@@ -0,0 +1,108 @@
+//! Helpers for base64 in DNS TXT records.
+//!
+//! A TXT record is made of one or more character-strings of at most 255 bytes each, so longer
+//! base64 values (like DKIM public keys) have to be split across several strings and joined again
+//! by whoever reads them. Standard base64 with padding is used, as is conventional for DKIM.
+//!
+//! # Examples
+//!
+//! ```
+//! let key = [0x42_u8; 300];
+//!
+//! let segments = base64::dns::encode_txt(&key);
+//! assert_eq!(2, segments.len());
+//! assert_eq!(257, segments[0].len());
+//!
+//! assert_eq!(&key[..], &base64::dns::decode_txt(&segments).unwrap()[..]);
+//! ```
+
+use crate::{engine::general_purpose::STANDARD, DecodeError, Engine};
+use alloc::{string::String, vec::Vec};
+
+/// The maximum length of a single DNS character-string.
+pub const MAX_SEGMENT_LEN: usize = 255;
+
+/// Encode `input` as standard base64 split into double-quoted segments of at most
+/// [`MAX_SEGMENT_LEN`] characters each, not counting the quotes.
+///
+/// The segments are ready to be written into a zone file as the data of a TXT record.
+pub fn encode_txt<T: AsRef<[u8]>>(input: T) -> Vec<String> {
+    STANDARD
+        .encode_chunks(input, MAX_SEGMENT_LEN)
+        .iter()
+        .map(|chunk| {
+            let mut segment = String::with_capacity(chunk.len() + 2);
+            segment.push('"');
+            segment.push_str(chunk);
+            segment.push('"');
+            segment
+        })
+        .collect()
+}
+
+/// Join the segments of a TXT record and decode them as standard base64.
+///
+/// Whitespace around each segment and a surrounding pair of double quotes, if present, are
+/// removed, so this accepts the output of [`encode_txt`] as well as segments that were already
+/// unquoted by a DNS library.
+///
+/// Offsets in any returned error are relative to the joined, unquoted segments.
+pub fn decode_txt<I, S>(segments: I) -> Result<Vec<u8>, DecodeError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut joined = String::new();
+    for segment in segments {
+        let segment = segment.as_ref().trim();
+        let unquoted = if segment.len() >= 2 && segment.starts_with('"') && segment.ends_with('"') {
+            &segment[1..segment.len() - 1]
+        } else {
+            segment
+        };
+        joined.push_str(unquoted);
+    }
+
+    STANDARD.decode(joined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn encode_txt_segments_are_quoted_and_bounded() {
+        let mut rng = rand::rngs::SmallRng::from_entropy();
+
+        for len in 0..1_000 {
+            let input: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            let segments = encode_txt(&input);
+
+            for segment in &segments {
+                assert!(segment.starts_with('"') && segment.ends_with('"'));
+                assert!(segment.len() <= MAX_SEGMENT_LEN + 2);
+            }
+            assert_eq!(
+                STANDARD.encode(&input),
+                segments
+                    .iter()
+                    .map(|s| s.trim_matches('"'))
+                    .collect::<String>()
+            );
+            assert_eq!(input, decode_txt(&segments).unwrap());
+        }
+    }
+
+    #[test]
+    fn decode_txt_accepts_unquoted_and_padded_segments() {
+        assert_eq!(
+            b"hello world",
+            &decode_txt(&[" \"aGVsbG8g\" ", "d29ybGQ="]).unwrap()[..]
+        );
+        assert_eq!(
+            DecodeError::InvalidByte(8, b'"'),
+            decode_txt(&["aGVsbG8g", "\"d29ybGQ="]).unwrap_err()
+        );
+    }
+}
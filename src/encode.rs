@@ -1,13 +1,12 @@
 #[cfg(any(feature = "alloc", test))]
 use alloc::string::String;
-use core::fmt;
+use core::{cmp, fmt};
 #[cfg(any(feature = "std", test))]
 use std::error;
 
 #[cfg(any(feature = "alloc", test))]
 use crate::engine::general_purpose::STANDARD;
-use crate::engine::{Config, Engine};
-use crate::PAD_BYTE;
+use crate::engine::{Config, Engine, Padding};
 
 /// Encode arbitrary octets as base64 using the [`STANDARD` engine](STANDARD).
 ///
@@ -56,12 +55,110 @@ pub fn encode_engine_slice<E: Engine, T: AsRef<[u8]>>(
     engine.encode_slice(input, output_buf)
 }
 
-/// B64-encode and pad (if configured).
+/// Encoded base64 text that can be iterated over in fixed-size chunks.
+///
+/// Produced by [`Engine::encode_chunks`].
+#[cfg(any(feature = "alloc", test))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EncodedChunks {
+    encoded: String,
+    chunk_len: usize,
+}
+
+#[cfg(any(feature = "alloc", test))]
+impl EncodedChunks {
+    /// Panics if `chunk_len` is 0.
+    pub(crate) fn new(encoded: String, chunk_len: usize) -> Self {
+        assert!(chunk_len > 0, "chunk_len must be nonzero");
+        Self { encoded, chunk_len }
+    }
+
+    /// Returns an iterator over the encoded text in chunks of `chunk_len` bytes.
+    ///
+    /// The last chunk will be shorter if the encoded length isn't a multiple of `chunk_len`.
+    pub fn iter(&self) -> Chunks<'_> {
+        Chunks {
+            remaining: &self.encoded,
+            chunk_len: self.chunk_len,
+        }
+    }
+
+    /// The number of chunks.
+    pub fn len(&self) -> usize {
+        chunk_count(self.encoded.len(), self.chunk_len)
+    }
+
+    /// Returns `true` if there are no chunks, i.e. the encoded text is empty.
+    pub fn is_empty(&self) -> bool {
+        self.encoded.is_empty()
+    }
+
+    /// The complete encoded text.
+    pub fn as_str(&self) -> &str {
+        &self.encoded
+    }
+
+    /// Returns the complete encoded text.
+    pub fn into_string(self) -> String {
+        self.encoded
+    }
+}
+
+#[cfg(any(feature = "alloc", test))]
+impl<'a> IntoIterator for &'a EncodedChunks {
+    type Item = &'a str;
+    type IntoIter = Chunks<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over chunks of encoded base64 text.
+///
+/// See [`EncodedChunks::iter`].
+#[derive(Clone, Debug)]
+pub struct Chunks<'a> {
+    remaining: &'a str,
+    chunk_len: usize,
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+
+        // base64 is ascii, so any index is a char boundary
+        let (chunk, rest) = self
+            .remaining
+            .split_at(self.chunk_len.min(self.remaining.len()));
+        self.remaining = rest;
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = chunk_count(self.remaining.len(), self.chunk_len);
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for Chunks<'a> {}
+
+/// The number of chunks of at most `chunk_len` bytes that `len` bytes split into, without
+/// overflowing for a `chunk_len` near `usize::MAX`.
+fn chunk_count(len: usize, chunk_len: usize) -> usize {
+    len / chunk_len + usize::from(len % chunk_len != 0)
+}
+
+/// B64-encode, pad, and wrap lines (if configured).
 ///
 /// This helper exists to avoid recalculating `encoded_size`, which is relatively expensive on short
 /// inputs.
 ///
-/// `encoded_size` is the encoded size calculated for `input`.
+/// `encoded_size` is the [`Engine::encoded_len`] of `input`.
 ///
 /// `output` must be of size `encoded_size`.
 ///
@@ -76,17 +173,85 @@ pub(crate) fn encode_with_padding<E: Engine + ?Sized>(
 
     let b64_bytes_written = engine.internal_encode(input, output);
 
-    let padding_bytes = if engine.config().encode_padding() {
-        add_padding(b64_bytes_written, &mut output[b64_bytes_written..])
-    } else {
-        0
+    pad_and_wrap(output, b64_bytes_written, engine, expected_encoded_size);
+}
+
+/// Like [`encode_with_padding`], but encodes the concatenation of `parts` without copying them
+/// together first.
+///
+/// `encoded_size` is the [`Engine::encoded_len`] of the total length of `parts`.
+pub(crate) fn encode_scattered_with_padding<E: Engine + ?Sized>(
+    parts: &[&[u8]],
+    output: &mut [u8],
+    engine: &E,
+    expected_encoded_size: usize,
+) {
+    debug_assert_eq!(expected_encoded_size, output.len());
+
+    // bytes from the end of previous parts that don't make up a whole 3 byte group yet
+    let mut carry = [0_u8; 3];
+    let mut carry_len = 0;
+    let mut b64_bytes_written = 0;
+    for &part in parts {
+        let mut part = part;
+        if carry_len > 0 {
+            let take = cmp::min(carry.len() - carry_len, part.len());
+            carry[carry_len..carry_len + take].copy_from_slice(&part[..take]);
+            carry_len += take;
+            part = &part[take..];
+            if carry_len < carry.len() {
+                continue;
+            }
+
+            b64_bytes_written += engine.internal_encode(&carry, &mut output[b64_bytes_written..]);
+        }
+
+        let (groups, rest) = part.split_at(part.len() / 3 * 3);
+        b64_bytes_written += engine.internal_encode(groups, &mut output[b64_bytes_written..]);
+        carry[..rest.len()].copy_from_slice(rest);
+        carry_len = rest.len();
+    }
+    b64_bytes_written +=
+        engine.internal_encode(&carry[..carry_len], &mut output[b64_bytes_written..]);
+
+    pad_and_wrap(output, b64_bytes_written, engine, expected_encoded_size);
+}
+
+/// Pad and wrap the `b64_bytes_written` bytes of encoded output at the start of `output`.
+fn pad_and_wrap<E: Engine + ?Sized>(
+    output: &mut [u8],
+    b64_bytes_written: usize,
+    engine: &E,
+    expected_encoded_size: usize,
+) {
+    let padding_bytes = match engine.config().padding() {
+        Padding::Padded(padding_byte) => add_padding(
+            b64_bytes_written,
+            &mut output[b64_bytes_written..],
+            padding_byte,
+        ),
+        Padding::Unpadded => 0,
     };
 
     let encoded_bytes = b64_bytes_written
         .checked_add(padding_bytes)
         .expect("usize overflow when calculating b64 length");
 
-    debug_assert_eq!(expected_encoded_size, encoded_bytes);
+    let config = engine.config();
+    let line_ending = config.encode_line_ending().as_bytes();
+    let mut wrapped_bytes = wrap_lines(
+        output,
+        encoded_bytes,
+        config.encode_line_wrap(),
+        0,
+        line_ending,
+    );
+    if encoded_bytes > 0 && config.encode_trailing_newline() {
+        output[wrapped_bytes..wrapped_bytes + line_ending.len()].copy_from_slice(line_ending);
+        wrapped_bytes += line_ending.len();
+    }
+
+    debug_assert_eq!(expected_encoded_size, wrapped_bytes);
 }
 
 /// Calculate the base64 encoded length for a given input length, optionally including any
@@ -94,6 +259,9 @@ pub(crate) fn encode_with_padding<E: Engine + ?Sized>(
 ///
 /// Returns `None` if the encoded length can't be represented in `usize`. This will happen for
 /// input lengths in approximately the top quarter of the range of `usize`.
+///
+/// This doesn't include line endings; see [`Engine::encoded_len`] for the length of the output of
+/// an engine that wraps lines.
 #[must_use]
 pub const fn encoded_len(bytes_len: usize, padding: bool) -> Option<usize> {
     let rem = bytes_len % 3;
@@ -125,25 +293,133 @@ pub const fn encoded_len(bytes_len: usize, padding: bool) -> Option<usize> {
     }
 }
 
+/// Calculate the base64 encoded length for a given input length, for use as an array length.
+///
+/// This is [`encoded_len`] without the `Option`, which can't be unwrapped in a `const` on older
+/// compilers. Line endings aren't included.
+///
+/// # Panics
+///
+/// Panics if the encoded length can't be represented in `usize`. When used in a `const` like an
+/// array length, that's a compile error instead.
+///
+/// # Examples
+///
+/// ```
+/// const ENCODED_KEY_LEN: usize = base64::encoded_array_len(32, true);
+///
+/// let encoded_key = [0_u8; ENCODED_KEY_LEN];
+/// assert_eq!(44, encoded_key.len());
+/// ```
+#[must_use]
+pub const fn encoded_array_len(bytes_len: usize, padding: bool) -> usize {
+    let (len, overflow) = match encoded_len(bytes_len, padding) {
+        Some(len) => (len, false),
+        None => (0, true),
+    };
+    const_panic_if!(overflow, "usize overflow when calculating encoded length");
+    len
+}
+
 /// Write padding characters.
 /// `unpadded_output_len` is the size of the unpadded but base64 encoded data.
 /// `output` is the slice where padding should be written, of length at least 2.
+/// `padding_byte` is the byte to pad with, normally `=`.
 ///
 /// Returns the number of padding bytes written.
-pub(crate) fn add_padding(unpadded_output_len: usize, output: &mut [u8]) -> usize {
+pub(crate) fn add_padding(
+    unpadded_output_len: usize,
+    output: &mut [u8],
+    padding_byte: u8,
+) -> usize {
     let pad_bytes = (4 - (unpadded_output_len % 4)) % 4;
     // for just a couple bytes, this has better performance than using
     // .fill(), or iterating over mutable refs, which call memset()
     #[allow(clippy::needless_range_loop)]
     for i in 0..pad_bytes {
-        output[i] = PAD_BYTE;
+        output[i] = padding_byte;
     }
 
     pad_bytes
 }
 
+/// Returns the number of line endings needed to wrap `encoded_len` more bytes of output into lines
+/// of `line_len`, when `column` bytes have already been written on the current line.
+///
+/// Line endings are only written before more output, so a full last line doesn't get one.
+pub(crate) fn line_breaks(encoded_len: usize, line_len: usize, column: usize) -> usize {
+    if line_len == 0 || encoded_len == 0 {
+        0
+    } else {
+        // `(column + encoded_len - 1) / line_len`, without overflowing near `usize::MAX`: since
+        // `column <= line_len`, the column can only push the last byte onto one more line
+        let last = encoded_len - 1;
+        last / line_len + usize::from(last % line_len >= line_len - column)
+    }
+}
+
+/// Returns the column on the current line after writing `encoded_len` more bytes of output,
+/// starting at `column`.
+#[cfg(any(feature = "std", test))]
+pub(crate) fn column_after(encoded_len: usize, line_len: usize, column: usize) -> usize {
+    if line_len == 0 || encoded_len == 0 {
+        column
+    } else {
+        // `(column + encoded_len - 1) % line_len + 1`, without overflowing as in `line_breaks`
+        let rem = (encoded_len - 1) % line_len;
+        if rem >= line_len - column {
+            rem - (line_len - column) + 1
+        } else {
+            rem + column + 1
+        }
+    }
+}
+
+/// Break the `len` bytes of encoded output at the start of `buf` into lines of `line_len` ending in
+/// `line_ending`, moving them towards the end of `buf` to make room for the line endings. `column`
+/// is the number of bytes already written on the current line.
+///
+/// Returns the length of the wrapped output, which `buf` must be long enough to hold.
+pub(crate) fn wrap_lines(
+    buf: &mut [u8],
+    len: usize,
+    line_len: usize,
+    column: usize,
+    line_ending: &[u8],
+) -> usize {
+    let breaks = line_breaks(len, line_len, column);
+    if breaks == 0 {
+        return len;
+    }
+
+    let wrapped_len = len + breaks * line_ending.len();
+    // index in the unwrapped output of the first byte after a line ending
+    let first_break = if column == 0 {
+        line_len
+    } else {
+        line_len - column
+    };
+
+    // work backwards so nothing is overwritten before it's moved
+    let mut read_end = len;
+    let mut write_end = wrapped_len;
+    for line in (0..breaks).rev() {
+        let line_start = first_break + line * line_len;
+        let line_bytes = read_end - line_start;
+        buf.copy_within(line_start..read_end, write_end - line_bytes);
+        write_end -= line_bytes + line_ending.len();
+        buf[write_end..write_end + line_ending.len()].copy_from_slice(line_ending);
+        read_end = line_start;
+    }
+    debug_assert_eq!(read_end, write_end);
+
+    wrapped_len
+}
+
 /// Errors that can occur while encoding into a slice.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
 pub enum EncodeSliceError {
     /// The provided slice is too small.
     OutputSliceTooSmall,
@@ -166,8 +442,12 @@ mod tests {
 
     use crate::{
         alphabet,
-        engine::general_purpose::{GeneralPurpose, NO_PAD, STANDARD},
+        engine::{
+            general_purpose::{GeneralPurpose, NO_PAD, PAD, STANDARD},
+            LineEnding,
+        },
         tests::{assert_encode_sanity, random_config, random_engine},
+        PAD_BYTE,
     };
     use rand::{
         distributions::{Distribution, Uniform},
@@ -232,6 +512,198 @@ mod tests {
         assert_eq!(None, encoded_len(usize::MAX, true));
     }
 
+    #[test]
+    fn encoded_array_len_matches_encoded_len() {
+        for len in 0..100 {
+            for &padding in &[true, false] {
+                assert_eq!(
+                    encoded_len(len, padding).unwrap(),
+                    encoded_array_len(len, padding)
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "usize overflow when calculating encoded length")]
+    fn encoded_array_len_overflow_panics() {
+        let _ = encoded_array_len(usize::MAX, true);
+    }
+
+    #[test]
+    fn engine_encoded_len_near_usize_max() {
+        // reference implementation in wider arithmetic that can't overflow
+        fn expected(input_len: usize, padding: bool, line_len: usize) -> Option<usize> {
+            let input_len = input_len as u128;
+            let len = if padding {
+                (input_len + 2) / 3 * 4
+            } else {
+                (input_len * 4 + 2) / 3
+            };
+            let breaks = if line_len == 0 || len == 0 {
+                0
+            } else {
+                (len - 1) / line_len as u128
+            };
+            let total = len + breaks;
+            if total > usize::MAX as u128 {
+                None
+            } else {
+                Some(total as usize)
+            }
+        }
+
+        let boundary = usize::MAX / 4 * 3;
+        let input_lens = (0..10)
+            .chain(boundary / 2 - 10..boundary / 2 + 10)
+            .chain(boundary - 10..boundary + 10)
+            .chain(usize::MAX - 10..=usize::MAX);
+        for input_len in input_lens {
+            for &line_len in &[0, 1, 2, 3, 4, 64, 76, usize::MAX / 2, usize::MAX] {
+                for &padding in &[false, true] {
+                    let engine = GeneralPurpose::new(
+                        &alphabet::STANDARD,
+                        PAD.with_encode_padding(padding)
+                            .with_encode_line_wrap(line_len),
+                    );
+                    assert_eq!(
+                        expected(input_len, padding, line_len),
+                        engine.encoded_len(input_len),
+                        "input_len {} line_len {} padding {}",
+                        input_len,
+                        line_len,
+                        padding
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn line_breaks_and_column_near_usize_max() {
+        for &encoded_len in &[1, 2, usize::MAX - 1, usize::MAX] {
+            for &line_len in &[1, 2, 3, usize::MAX - 1, usize::MAX] {
+                for &column in &[0, 1, line_len - 1, line_len] {
+                    let end = column as u128 + encoded_len as u128 - 1;
+                    assert_eq!(
+                        (end / line_len as u128) as usize,
+                        line_breaks(encoded_len, line_len, column)
+                    );
+                    assert_eq!(
+                        (end % line_len as u128 + 1) as usize,
+                        column_after(encoded_len, line_len, column)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn encode_wrapped_lines() {
+        let engine = |line_len| {
+            GeneralPurpose::new(&alphabet::STANDARD, NO_PAD.with_encode_line_wrap(line_len))
+        };
+
+        assert_eq!("", engine(4).encode(b""));
+        assert_eq!("Zm9v", engine(4).encode(b"foo"));
+        assert_eq!("Zm9v\nYg", engine(4).encode(b"foob"));
+        assert_eq!("Zm9vYmFy", engine(8).encode(b"foobar"));
+        assert_eq!("Z\nm\n9\nv", engine(1).encode(b"foo"));
+        assert_eq!("Zm9\nvYm\nFy", engine(3).encode(b"foobar"));
+        assert_eq!(
+            "Zm9vYmFy\nYg==",
+            GeneralPurpose::new(&alphabet::STANDARD, PAD.with_encode_line_wrap(8))
+                .encode(b"foobarb")
+        );
+    }
+
+    #[test]
+    fn encode_trailing_newline() {
+        let engine = |line_len| {
+            GeneralPurpose::new(
+                &alphabet::STANDARD,
+                PAD.with_encode_line_wrap(line_len)
+                    .with_encode_trailing_newline(true),
+            )
+        };
+
+        assert_eq!("", engine(0).encode(b""));
+        assert_eq!("Zm9vYg==\n", engine(0).encode(b"foob"));
+        assert_eq!("Zm9v\nYg==\n", engine(4).encode(b"foob"));
+        // a full last line gets just the one line ending
+        assert_eq!("Zm9v\nYmFy\n", engine(4).encode(b"foobar"));
+        assert_eq!(Some(10), engine(4).encoded_len(6));
+        assert_eq!(
+            b"foobar",
+            &engine(4).decode("Zm9v\r\nYmFy\r\n").unwrap()[..]
+        );
+    }
+
+    #[test]
+    fn encode_crlf_line_endings() {
+        let engine = GeneralPurpose::new(
+            &alphabet::STANDARD,
+            PAD.with_encode_line_wrap(4)
+                .with_encode_line_ending(LineEnding::CRLF),
+        );
+
+        assert_eq!("Zm9v\r\nYg==", engine.encode(b"foob"));
+        assert_eq!(Some(10), engine.encoded_len(4));
+
+        let engine = GeneralPurpose::new(
+            &alphabet::STANDARD,
+            engine.config().with_encode_trailing_newline(true),
+        );
+        assert_eq!("Zm9v\r\nYmFy\r\n", engine.encode(b"foobar"));
+        assert_eq!(Some(12), engine.encoded_len(6));
+    }
+
+    #[test]
+    fn wrap_lines_continues_partial_line() {
+        let mut buf = *b"abcdefg_____";
+        assert_eq!(9, wrap_lines(&mut buf, 7, 3, 1, b"\n"));
+        assert_eq!(b"ab\ncde\nfg", &buf[..9]);
+        assert_eq!(2, column_after(7, 3, 1));
+
+        // a full line only gets a line ending before more output
+        let mut buf = *b"abc__";
+        assert_eq!(4, wrap_lines(&mut buf, 3, 3, 3, b"\n"));
+        assert_eq!(b"\nabc", &buf[..4]);
+        assert_eq!(3, column_after(3, 3, 3));
+        assert_eq!(3, column_after(0, 3, 3));
+    }
+
+    #[test]
+    fn engine_encoded_len_matches_wrapped_output() {
+        let mut rng = rand::rngs::SmallRng::from_entropy();
+
+        for _ in 0..10_000 {
+            let input: Vec<u8> = (0..rng.gen_range(0..500)).map(|_| rng.gen()).collect();
+            let engine = GeneralPurpose::new(
+                &alphabet::STANDARD,
+                random_config(&mut rng)
+                    .with_encode_line_wrap(rng.gen_range(0..80))
+                    .with_encode_trailing_newline(rng.gen())
+                    .with_encode_line_ending(rng.gen()),
+            );
+
+            let encoded = engine.encode(&input);
+            assert_eq!(Some(encoded.len()), engine.encoded_len(input.len()));
+
+            let mut buf = vec![0; encoded.len()];
+            assert_eq!(Ok(encoded.len()), engine.encode_slice(&input, &mut buf));
+            assert_eq!(encoded.as_bytes(), &buf[..]);
+            if !encoded.is_empty() {
+                assert_eq!(
+                    Err(EncodeSliceError::OutputSliceTooSmall),
+                    engine.encode_slice(&input, &mut buf[..encoded.len() - 1])
+                );
+            }
+
+            assert_eq!(input, engine.decode(&encoded).unwrap());
+        }
+    }
+
     #[test]
     fn encode_engine_string_into_nonempty_buffer_doesnt_clobber_prefix() {
         let mut orig_data = Vec::new();
@@ -450,7 +922,7 @@ mod tests {
 
             let orig_output_buf = output.clone();
 
-            let bytes_written = add_padding(unpadded_output_len, &mut output);
+            let bytes_written = add_padding(unpadded_output_len, &mut output, PAD_BYTE);
 
             // make sure the part beyond bytes_written is the same garbage it was before
             assert_eq!(orig_output_buf[bytes_written..], output[bytes_written..]);
@@ -490,4 +962,45 @@ mod tests {
                 .replace('/', ",")
         );
     }
+
+    #[test]
+    fn encode_chunks_reassembles_to_encoded() {
+        let mut rng = rand::rngs::SmallRng::from_entropy();
+
+        for _ in 0..1_000 {
+            let engine = random_engine(&mut rng);
+            let input: Vec<u8> = (0..rng.gen_range(0..500)).map(|_| rng.gen()).collect();
+            let chunk_len = rng.gen_range(1..300);
+
+            let chunks = engine.encode_chunks(&input, chunk_len);
+            let collected: Vec<&str> = chunks.iter().collect();
+
+            assert_eq!(chunks.len(), collected.len());
+            assert_eq!(chunks.len(), chunks.iter().len());
+            assert!(collected
+                .iter()
+                .all(|c| !c.is_empty() && c.len() <= chunk_len));
+            assert!(collected[..collected.len().saturating_sub(1)]
+                .iter()
+                .all(|c| c.len() == chunk_len));
+            assert_eq!(engine.encode(&input), collected.concat());
+            assert_eq!(engine.encode(&input), chunks.into_string());
+        }
+    }
+
+    #[test]
+    fn encode_chunks_max_len_is_one_chunk() {
+        let chunks = STANDARD.encode_chunks(b"hello", usize::MAX);
+        assert_eq!(1, chunks.len());
+        assert_eq!(1, chunks.iter().len());
+        assert_eq!(vec!["aGVsbG8="], chunks.iter().collect::<Vec<_>>());
+
+        assert_eq!(0, STANDARD.encode_chunks(b"", usize::MAX).len());
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk_len must be nonzero")]
+    fn encode_chunks_zero_len_panics() {
+        let _ = STANDARD.encode_chunks(b"abc", 0);
+    }
 }
@@ -0,0 +1,566 @@
+//! Engines that wrap another engine to handle light variations on its format.
+//!
+//! Each adapter implements [`Engine`] by delegating to the engine it wraps, so it can be used
+//! anywhere an engine can, and adapters can be nested.
+//!
+//! # Examples
+//!
+//! ```
+//! use base64::{engine::{adapters::PrefixSuffix, general_purpose}, Engine as _};
+//!
+//! let engine = PrefixSuffix::new(general_purpose::STANDARD, "{base64}", "");
+//! assert_eq!("{base64}aGk=", engine.encode(b"hi"));
+//! assert_eq!(b"hi", &engine.decode("{base64}aGk=").unwrap()[..]);
+//! ```
+
+use crate::{
+    alphabet::{Alphabet, ALPHABET_SIZE},
+    engine::{DecodeMetadata, Engine},
+    sink::EncodeSink,
+    DecodeError, DecodeSliceError, EncodeSliceError,
+};
+use alloc::{string::String, vec::Vec};
+
+/// Adds a fixed prefix and suffix around encoded output, and strips them from input to decode.
+///
+/// When decoding, the prefix and suffix are each stripped if present, and the rest of the input is
+/// decoded by the wrapped engine. Offsets in errors refer to the whole input, including the prefix.
+///
+/// The framing is added and stripped by the methods that encode or decode a whole input at once,
+/// like [`Engine::encode`] and [`Engine::decode`]. Streaming types like
+/// [`EncoderWriter`](crate::write::EncoderWriter) and
+/// [`DecoderReader`](crate::read::DecoderReader) process their input in chunks, so they don't see
+/// the start and end of the whole input: use them with the wrapped engine, and write or read the
+/// prefix and suffix separately.
+#[derive(Debug, Clone)]
+pub struct PrefixSuffix<E> {
+    engine: E,
+    prefix: String,
+    suffix: String,
+}
+
+impl<E: Engine> PrefixSuffix<E> {
+    /// Wrap `engine` to add `prefix` before and `suffix` after the encoded output.
+    pub fn new<P: Into<String>, S: Into<String>>(engine: E, prefix: P, suffix: S) -> Self {
+        Self {
+            engine,
+            prefix: prefix.into(),
+            suffix: suffix.into(),
+        }
+    }
+
+    /// The wrapped engine.
+    pub fn inner(&self) -> &E {
+        &self.engine
+    }
+
+    /// Strip the prefix and suffix from `input`, if present.
+    ///
+    /// Returns the remaining input and the length of the prefix that was stripped.
+    fn strip<'i>(&self, input: &'i [u8]) -> (&'i [u8], usize) {
+        let prefix_len = if input.starts_with(self.prefix.as_bytes()) {
+            self.prefix.len()
+        } else {
+            0
+        };
+        let input = &input[prefix_len..];
+        let input = if input.ends_with(self.suffix.as_bytes()) {
+            &input[..input.len() - self.suffix.len()]
+        } else {
+            input
+        };
+
+        (input, prefix_len)
+    }
+}
+
+impl<E: Engine> Engine for PrefixSuffix<E> {
+    type Config = E::Config;
+    type DecodeEstimate = E::DecodeEstimate;
+
+    fn internal_encode(&self, input: &[u8], output: &mut [u8]) -> usize {
+        self.engine.internal_encode(input, output)
+    }
+
+    fn internal_decoded_len_estimate(&self, input_len: usize) -> Self::DecodeEstimate {
+        self.engine.internal_decoded_len_estimate(input_len)
+    }
+
+    fn internal_decode(
+        &self,
+        input: &[u8],
+        output: &mut [u8],
+        _estimate: Self::DecodeEstimate,
+    ) -> Result<DecodeMetadata, DecodeSliceError> {
+        let (body, prefix_len) = self.strip(input);
+
+        self.engine
+            .internal_decode(
+                body,
+                output,
+                self.engine.internal_decoded_len_estimate(body.len()),
+            )
+            .map(|metadata| DecodeMetadata {
+                padding_offset: metadata.padding_offset.map(|offset| offset + prefix_len),
+                ..metadata
+            })
+            .map_err(|e| match e {
+                DecodeSliceError::DecodeError(e) => {
+                    DecodeSliceError::DecodeError(shift_offsets(e, prefix_len))
+                }
+                e => e,
+            })
+    }
+
+    fn config(&self) -> &Self::Config {
+        self.engine.config()
+    }
+
+    fn preferred_block_size(&self) -> usize {
+        self.engine.preferred_block_size()
+    }
+
+    fn encoded_len(&self, input_len: usize) -> Option<usize> {
+        self.engine
+            .encoded_len(input_len)?
+            .checked_add(self.prefix.len())?
+            .checked_add(self.suffix.len())
+    }
+
+    fn encode_into<T: AsRef<[u8]>, S: EncodeSink + ?Sized>(
+        &self,
+        input: T,
+        sink: &mut S,
+    ) -> Result<usize, S::Error> {
+        let input = input.as_ref();
+        let encoded_len = self
+            .encoded_len(input.len())
+            .expect("usize overflow when calculating buffer size");
+
+        // reserve everything first so that nothing is written if it doesn't fit
+        sink.reserve(encoded_len)?;
+        sink.write_encoded(self.prefix.as_bytes())?;
+        let _ = self.engine.encode_into(input, sink)?;
+        sink.write_encoded(self.suffix.as_bytes())?;
+
+        Ok(encoded_len)
+    }
+
+    fn encode_scattered(&self, parts: &[&[u8]]) -> String {
+        let mut output = self.prefix.clone();
+        output.push_str(&self.engine.encode_scattered(parts));
+        output.push_str(&self.suffix);
+        output
+    }
+
+    fn encode_scattered_slice(
+        &self,
+        parts: &[&[u8]],
+        output_buf: &mut [u8],
+    ) -> Result<usize, EncodeSliceError> {
+        let encoded_len = super::scattered_len(parts)
+            .and_then(|len| self.encoded_len(len))
+            .ok_or(EncodeSliceError::OutputSliceTooSmall)?;
+        if output_buf.len() < encoded_len {
+            return Err(EncodeSliceError::OutputSliceTooSmall);
+        }
+
+        let (prefix, rest) = output_buf.split_at_mut(self.prefix.len());
+        prefix.copy_from_slice(self.prefix.as_bytes());
+        let body_len = self.engine.encode_scattered_slice(parts, rest)?;
+        rest[body_len..body_len + self.suffix.len()].copy_from_slice(self.suffix.as_bytes());
+
+        Ok(encoded_len)
+    }
+}
+
+/// Accepts input to decode in either case by folding ASCII letters to one case first.
+///
+/// This is useful with custom alphabets that only use one case of each letter, to accept input
+/// that has been through a case-insensitive system. Encoding is unchanged.
+#[derive(Debug, Clone)]
+pub struct CaseFolding<E> {
+    engine: E,
+    upper: bool,
+}
+
+impl<E: Engine> CaseFolding<E> {
+    /// Wrap `engine` to fold ASCII letters in input to decode to upper case.
+    pub fn upper(engine: E) -> Self {
+        Self {
+            engine,
+            upper: true,
+        }
+    }
+
+    /// Wrap `engine` to fold ASCII letters in input to decode to lower case.
+    pub fn lower(engine: E) -> Self {
+        Self {
+            engine,
+            upper: false,
+        }
+    }
+
+    /// The wrapped engine.
+    pub fn inner(&self) -> &E {
+        &self.engine
+    }
+}
+
+impl<E: Engine> Engine for CaseFolding<E> {
+    type Config = E::Config;
+    type DecodeEstimate = E::DecodeEstimate;
+
+    fn internal_encode(&self, input: &[u8], output: &mut [u8]) -> usize {
+        self.engine.internal_encode(input, output)
+    }
+
+    fn internal_decoded_len_estimate(&self, input_len: usize) -> Self::DecodeEstimate {
+        self.engine.internal_decoded_len_estimate(input_len)
+    }
+
+    fn internal_decode(
+        &self,
+        input: &[u8],
+        output: &mut [u8],
+        estimate: Self::DecodeEstimate,
+    ) -> Result<DecodeMetadata, DecodeSliceError> {
+        let folded: Vec<u8> = input
+            .iter()
+            .map(|b| {
+                if self.upper {
+                    b.to_ascii_uppercase()
+                } else {
+                    b.to_ascii_lowercase()
+                }
+            })
+            .collect();
+
+        // the folded input is the same length, so offsets in errors still apply to `input`, but
+        // report the bytes as they were
+        self.engine
+            .internal_decode(&folded, output, estimate)
+            .map_err(|e| match e {
+                DecodeSliceError::DecodeError(DecodeError::InvalidByte(offset, _)) => {
+                    DecodeError::InvalidByte(offset, input[offset]).into()
+                }
+                DecodeSliceError::DecodeError(DecodeError::InvalidLastSymbol {
+                    offset,
+                    symbol_value,
+                    ..
+                }) => DecodeError::InvalidLastSymbol {
+                    offset,
+                    symbol: input[offset],
+                    symbol_value,
+                }
+                .into(),
+                e => e,
+            })
+    }
+
+    fn config(&self) -> &Self::Config {
+        self.engine.config()
+    }
+
+    fn preferred_block_size(&self) -> usize {
+        self.engine.preferred_block_size()
+    }
+}
+
+/// Substitutes the symbols of encoded output with those of another alphabet, and substitutes them
+/// back in input to decode.
+///
+/// Some software obfuscates base64 by rotating or shuffling the symbols of the standard alphabet
+/// after encoding. Wrapping an engine for the original alphabet with the same substitution reads
+/// and writes that format, and undoes rotations without first working out the alphabet they
+/// produce.
+///
+/// Bytes outside the alphabets, like padding and line endings, are left as they are. Errors
+/// report bytes as they were in the input, before substitution.
+///
+/// # Examples
+///
+/// ```
+/// use base64::{alphabet, engine::{adapters::SymbolSubstitution, general_purpose}, Engine as _};
+///
+/// // every symbol one place later in the alphabet: `a` is written as `b`, `/` as `A`
+/// let engine = SymbolSubstitution::rotate(general_purpose::STANDARD, &alphabet::STANDARD, 1);
+/// assert_eq!("bHl=", engine.encode(b"hi"));
+/// assert_eq!(b"hi", &engine.decode("bHl=").unwrap()[..]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SymbolSubstitution<E> {
+    engine: E,
+    encode_map: [u8; 256],
+    decode_map: [u8; 256],
+}
+
+impl<E: Engine> SymbolSubstitution<E> {
+    /// Wrap `engine`, whose alphabet is `from`, to write each symbol of `from` as the symbol at the
+    /// same index in `to`.
+    pub fn new(engine: E, from: &Alphabet, to: &Alphabet) -> Self {
+        Self::from_symbols(engine, &from.symbols, &to.symbols)
+    }
+
+    /// Wrap `engine`, whose alphabet is `alphabet`, to write each symbol as the symbol `shift`
+    /// places later in `alphabet`, wrapping around at the end.
+    pub fn rotate(engine: E, alphabet: &Alphabet, shift: usize) -> Self {
+        let mut rotated = [0_u8; ALPHABET_SIZE];
+        for (i, symbol) in rotated.iter_mut().enumerate() {
+            *symbol = alphabet.symbols[(i + shift) % ALPHABET_SIZE];
+        }
+
+        Self::from_symbols(engine, &alphabet.symbols, &rotated)
+    }
+
+    /// The wrapped engine.
+    pub fn inner(&self) -> &E {
+        &self.engine
+    }
+
+    fn from_symbols(engine: E, from: &[u8; ALPHABET_SIZE], to: &[u8; ALPHABET_SIZE]) -> Self {
+        let mut encode_map = [0_u8; 256];
+        for (byte, mapped) in encode_map.iter_mut().enumerate() {
+            // `byte` is always < 256
+            *mapped = byte as u8;
+        }
+        for (&f, &t) in from.iter().zip(to.iter()) {
+            encode_map[usize::from(f)] = t;
+        }
+
+        // Symbols of `to` that aren't in `from` would now be written by two bytes, so map them to
+        // the symbols of `from` that aren't in `to`, of which there are as many. That keeps the
+        // map a permutation of all bytes, so it can be inverted, and input using symbols of `from`
+        // that `to` lacks is still invalid after substituting back.
+        let unused = from.iter().filter(|b| !to.contains(b));
+        for (&t, &f) in to.iter().filter(|b| !from.contains(b)).zip(unused) {
+            encode_map[usize::from(t)] = f;
+        }
+
+        let mut decode_map = [0_u8; 256];
+        for (byte, &mapped) in encode_map.iter().enumerate() {
+            // `byte` is always < 256
+            decode_map[usize::from(mapped)] = byte as u8;
+        }
+
+        Self {
+            engine,
+            encode_map,
+            decode_map,
+        }
+    }
+}
+
+impl<E: Engine> Engine for SymbolSubstitution<E> {
+    type Config = E::Config;
+    type DecodeEstimate = E::DecodeEstimate;
+
+    fn internal_encode(&self, input: &[u8], output: &mut [u8]) -> usize {
+        let len = self.engine.internal_encode(input, output);
+        for b in &mut output[..len] {
+            *b = self.encode_map[usize::from(*b)];
+        }
+
+        len
+    }
+
+    fn internal_decoded_len_estimate(&self, input_len: usize) -> Self::DecodeEstimate {
+        self.engine.internal_decoded_len_estimate(input_len)
+    }
+
+    fn internal_decode(
+        &self,
+        input: &[u8],
+        output: &mut [u8],
+        estimate: Self::DecodeEstimate,
+    ) -> Result<DecodeMetadata, DecodeSliceError> {
+        let substituted: Vec<u8> = input
+            .iter()
+            .map(|&b| self.decode_map[usize::from(b)])
+            .collect();
+
+        // as with `CaseFolding`, offsets still apply to `input`, but report the bytes as they were
+        self.engine
+            .internal_decode(&substituted, output, estimate)
+            .map_err(|e| match e {
+                DecodeSliceError::DecodeError(DecodeError::InvalidByte(offset, _)) => {
+                    DecodeError::InvalidByte(offset, input[offset]).into()
+                }
+                DecodeSliceError::DecodeError(DecodeError::InvalidLastSymbol {
+                    offset,
+                    symbol_value,
+                    ..
+                }) => DecodeError::InvalidLastSymbol {
+                    offset,
+                    symbol: input[offset],
+                    symbol_value,
+                }
+                .into(),
+                e => e,
+            })
+    }
+
+    fn config(&self) -> &Self::Config {
+        self.engine.config()
+    }
+
+    fn preferred_block_size(&self) -> usize {
+        self.engine.preferred_block_size()
+    }
+}
+
+/// Returns `e` with its offset, if any, moved `by` bytes later in the input.
+fn shift_offsets(e: DecodeError, by: usize) -> DecodeError {
+    match e {
+        DecodeError::InvalidByte(offset, byte) => DecodeError::InvalidByte(offset + by, byte),
+        DecodeError::InvalidLastSymbol {
+            offset,
+            symbol,
+            symbol_value,
+        } => DecodeError::InvalidLastSymbol {
+            offset: offset + by,
+            symbol,
+            symbol_value,
+        },
+        DecodeError::PaddingNotAtEnd(offset) => DecodeError::PaddingNotAtEnd(offset + by),
+        e => e,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alphabet::{self, Alphabet},
+        engine::general_purpose::{GeneralPurpose, NO_PAD, PAD, STANDARD},
+        write::EncoderWriter,
+        EncodeSliceError,
+    };
+    use rand::{seq::SliceRandom, Rng, RngCore};
+    use std::io::Write as _;
+
+    #[test]
+    fn prefix_suffix_round_trip() {
+        let engine = PrefixSuffix::new(STANDARD, "{b64:", "}");
+        for len in 0..20 {
+            let input: Vec<u8> = (0..len).collect();
+            let encoded = engine.encode(&input);
+            assert_eq!(format!("{{b64:{}}}", STANDARD.encode(&input)), encoded);
+            assert_eq!(Some(encoded.len()), engine.encoded_len(input.len()));
+            assert_eq!(input, engine.decode(&encoded).unwrap());
+
+            let mut buf = vec![0; encoded.len()];
+            assert_eq!(
+                encoded.len(),
+                engine.encode_slice(&input, &mut buf).unwrap()
+            );
+            assert_eq!(encoded.as_bytes(), &buf[..]);
+            assert_eq!(
+                Err(EncodeSliceError::OutputSliceTooSmall),
+                engine.encode_slice(&input, &mut buf[1..])
+            );
+
+            let (head, tail) = input.split_at(input.len() / 2);
+            assert_eq!(encoded, engine.encode_scattered(&[head, tail]));
+            assert_eq!(
+                Ok(encoded.len()),
+                engine.encode_scattered_slice(&[head, tail], &mut buf)
+            );
+            assert_eq!(encoded.as_bytes(), &buf[..]);
+        }
+
+        // the framing is optional when decoding
+        assert_eq!(b"hi", &engine.decode("aGk=").unwrap()[..]);
+        assert_eq!(b"hi", &engine.decode("{b64:aGk=").unwrap()[..]);
+    }
+
+    #[test]
+    fn prefix_suffix_error_offsets() {
+        let engine = PrefixSuffix::new(STANDARD, "{b64:", "}");
+        assert_eq!(
+            Err(DecodeError::InvalidByte(6, b'*')),
+            engine.decode("{b64:a*k=}")
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidByte(0, b'[')),
+            engine.decode("[b64:aGk=}")
+        );
+    }
+
+    #[test]
+    fn case_folding_decodes_either_case() {
+        // upper case letters only, so lower case input is invalid unless folded
+        let alphabet =
+            Alphabet::new("0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ!#$%&()*,.:;<>?@[]^{|}~-_+/'")
+                .unwrap();
+        let engine = CaseFolding::upper(GeneralPurpose::new(&alphabet, NO_PAD));
+
+        let encoded = engine.encode(b"hello world");
+        assert_eq!(encoded, engine.inner().encode(b"hello world"));
+        assert_eq!(
+            b"hello world",
+            &engine.decode(encoded.to_ascii_lowercase()).unwrap()[..]
+        );
+        assert!(engine.inner().decode(encoded.to_ascii_lowercase()).is_err());
+
+        assert_eq!(Err(DecodeError::InvalidByte(1, b'"')), engine.decode("a\""));
+    }
+
+    #[test]
+    fn symbol_substitution_matches_substituted_alphabet() {
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..100 {
+            let mut shuffled = alphabet::STANDARD.symbols;
+            shuffled.shuffle(&mut rng);
+            let shuffled = Alphabet::new(std::str::from_utf8(&shuffled).unwrap()).unwrap();
+            let engine = SymbolSubstitution::new(STANDARD, &alphabet::STANDARD, &shuffled);
+            let expected = GeneralPurpose::new(&shuffled, PAD);
+
+            let mut input = vec![0; rng.gen_range(0..100)];
+            rng.fill_bytes(&mut input);
+            let encoded = expected.encode(&input);
+            assert_eq!(encoded, engine.encode(&input));
+            assert_eq!(input, engine.decode(&encoded).unwrap());
+
+            let mut writer = EncoderWriter::new(Vec::new(), &engine);
+            writer.write_all(&input).unwrap();
+            assert_eq!(encoded.as_bytes(), &writer.finish().unwrap()[..]);
+        }
+    }
+
+    #[test]
+    fn symbol_substitution_rotates() {
+        let engine = SymbolSubstitution::rotate(STANDARD, &alphabet::STANDARD, 1);
+        assert_eq!("bHl=", engine.encode(b"hi"));
+        assert_eq!("AAAA", engine.encode([0xFF; 3]));
+
+        let full_turn = SymbolSubstitution::rotate(STANDARD, &alphabet::STANDARD, 64 + 3);
+        let three = SymbolSubstitution::rotate(STANDARD, &alphabet::STANDARD, 3);
+        assert_eq!(three.encode(b"hello"), full_turn.encode(b"hello"));
+        assert_eq!(
+            "aGVsbG8=",
+            SymbolSubstitution::rotate(STANDARD, &alphabet::STANDARD, 0).encode(b"hello")
+        );
+    }
+
+    #[test]
+    fn symbol_substitution_to_other_symbols() {
+        // `-_` replace `+/`, so those are no longer valid input
+        let engine = SymbolSubstitution::new(STANDARD, &alphabet::STANDARD, &alphabet::URL_SAFE);
+        assert_eq!("-_-_", engine.encode([0xFB, 0xFF, 0xBF]));
+        assert_eq!(vec![0xFB, 0xFF, 0xBF], engine.decode("-_-_").unwrap());
+        assert_eq!(
+            Err(DecodeError::InvalidByte(1, b'/')),
+            engine.decode("-/-_")
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidLastSymbol {
+                offset: 1,
+                symbol: b'b',
+                symbol_value: 27,
+            }),
+            engine.decode("Zb==")
+        );
+    }
+}
@@ -0,0 +1,303 @@
+//! An engine that picks one of two engines by the length of its input.
+//!
+//! Engines with wide unrolled or vectorized loops tend to be fastest on long input but carry setup
+//! costs that dominate on short input, where a simpler engine can win. [`Adaptive`] holds one of
+//! each and dispatches every encode and decode to one or the other, so a single engine value
+//! performs well across input sizes.
+//!
+//! # Examples
+//!
+//! ```
+//! use base64::{
+//!     alphabet,
+//!     engine::{adaptive::Adaptive, general_purpose, Engine as _, GeneralPurpose},
+//! };
+//!
+//! let engine = Adaptive::new(
+//!     GeneralPurpose::new(&alphabet::STANDARD, general_purpose::PAD),
+//!     GeneralPurpose::new(&alphabet::STANDARD, general_purpose::PAD),
+//! )
+//! .with_threshold(128);
+//!
+//! assert_eq!("aGk=", engine.encode(b"hi"));
+//! assert_eq!(b"hi", &engine.decode("aGk=").unwrap()[..]);
+//! ```
+
+use crate::{
+    engine::{DecodeEstimate, DecodeMetadata, Engine},
+    DecodeSliceError,
+};
+
+/// The default input length, in bytes, from which the large input engine is used.
+///
+/// Below this, the unrolled loops of [`GeneralPurpose`](super::GeneralPurpose) run only a
+/// couple of times, if at all.
+pub const DEFAULT_THRESHOLD: usize = 64;
+
+/// Dispatches to `small` for input shorter than a threshold, and to `large` otherwise.
+///
+/// The threshold applies to the length of the input of each operation: raw bytes when encoding
+/// and base64 when decoding. Wrappers that process input in chunks, like
+/// [`EncoderWriter`](crate::write::EncoderWriter), dispatch each chunk separately.
+///
+/// The two engines must be interchangeable, i.e. have the same alphabet and config, since which
+/// one handles some input is an implementation detail. The config of `small` is the one reported
+/// by [`Engine::config`], and so the one wrappers use for padding and line wrapping.
+#[derive(Debug, Clone)]
+pub struct Adaptive<S, L> {
+    small: S,
+    large: L,
+    threshold: usize,
+}
+
+impl<S: Engine, L: Engine> Adaptive<S, L> {
+    /// Use `small` for input shorter than [`DEFAULT_THRESHOLD`] and `large` for the rest.
+    pub fn new(small: S, large: L) -> Self {
+        Self {
+            small,
+            large,
+            threshold: DEFAULT_THRESHOLD,
+        }
+    }
+
+    /// Use `small` for input shorter than `threshold` bytes and `large` for the rest.
+    ///
+    /// A threshold of 0 always uses `large`, and `usize::MAX` always uses `small`.
+    pub fn with_threshold(self, threshold: usize) -> Self {
+        Self { threshold, ..self }
+    }
+
+    /// The input length from which `large` is used.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// The engine used for short input.
+    pub fn small(&self) -> &S {
+        &self.small
+    }
+
+    /// The engine used for long input.
+    pub fn large(&self) -> &L {
+        &self.large
+    }
+
+    fn is_small(&self, input_len: usize) -> bool {
+        input_len < self.threshold
+    }
+}
+
+/// The decode estimate of whichever engine [`Adaptive`] dispatches to.
+#[derive(Debug)]
+pub enum AdaptiveEstimate<S, L> {
+    /// Estimate of the engine for short input
+    Small(S),
+    /// Estimate of the engine for long input
+    Large(L),
+}
+
+impl<S: DecodeEstimate, L: DecodeEstimate> DecodeEstimate for AdaptiveEstimate<S, L> {
+    fn decoded_len_estimate(&self) -> usize {
+        match self {
+            Self::Small(estimate) => estimate.decoded_len_estimate(),
+            Self::Large(estimate) => estimate.decoded_len_estimate(),
+        }
+    }
+}
+
+impl<S: Engine, L: Engine> Engine for Adaptive<S, L> {
+    type Config = S::Config;
+    type DecodeEstimate = AdaptiveEstimate<S::DecodeEstimate, L::DecodeEstimate>;
+
+    fn internal_encode(&self, input: &[u8], output: &mut [u8]) -> usize {
+        if self.is_small(input.len()) {
+            self.small.internal_encode(input, output)
+        } else {
+            self.large.internal_encode(input, output)
+        }
+    }
+
+    fn internal_decoded_len_estimate(&self, input_len: usize) -> Self::DecodeEstimate {
+        if self.is_small(input_len) {
+            AdaptiveEstimate::Small(self.small.internal_decoded_len_estimate(input_len))
+        } else {
+            AdaptiveEstimate::Large(self.large.internal_decoded_len_estimate(input_len))
+        }
+    }
+
+    fn internal_decode(
+        &self,
+        input: &[u8],
+        output: &mut [u8],
+        estimate: Self::DecodeEstimate,
+    ) -> Result<DecodeMetadata, DecodeSliceError> {
+        // the estimate was made for this input's length, so it already picked the engine
+        match estimate {
+            AdaptiveEstimate::Small(estimate) => {
+                self.small.internal_decode(input, output, estimate)
+            }
+            AdaptiveEstimate::Large(estimate) => {
+                self.large.internal_decode(input, output, estimate)
+            }
+        }
+    }
+
+    fn config(&self) -> &Self::Config {
+        self.small.config()
+    }
+
+    fn preferred_block_size(&self) -> usize {
+        // chunks are only ever long input
+        self.large.preferred_block_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alphabet,
+        engine::{
+            general_purpose::STANDARD,
+            naive::{Naive, NaiveConfig},
+            DecodePaddingMode,
+        },
+    };
+    use rand::{Rng, RngCore};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Counts the encodes and decodes it delegates to the wrapped engine
+    struct Counting<E> {
+        engine: E,
+        encodes: AtomicUsize,
+        decodes: AtomicUsize,
+    }
+
+    impl<E> Counting<E> {
+        fn new(engine: E) -> Self {
+            Self {
+                engine,
+                encodes: AtomicUsize::new(0),
+                decodes: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    impl<E: Engine> Engine for Counting<E> {
+        type Config = E::Config;
+        type DecodeEstimate = E::DecodeEstimate;
+
+        fn internal_encode(&self, input: &[u8], output: &mut [u8]) -> usize {
+            let _ = self.encodes.fetch_add(1, Ordering::Relaxed);
+            self.engine.internal_encode(input, output)
+        }
+
+        fn internal_decoded_len_estimate(&self, input_len: usize) -> Self::DecodeEstimate {
+            self.engine.internal_decoded_len_estimate(input_len)
+        }
+
+        fn internal_decode(
+            &self,
+            input: &[u8],
+            output: &mut [u8],
+            estimate: Self::DecodeEstimate,
+        ) -> Result<DecodeMetadata, DecodeSliceError> {
+            let _ = self.decodes.fetch_add(1, Ordering::Relaxed);
+            self.engine.internal_decode(input, output, estimate)
+        }
+
+        fn config(&self) -> &Self::Config {
+            self.engine.config()
+        }
+    }
+
+    fn naive_standard() -> Naive {
+        Naive::new(
+            &alphabet::STANDARD,
+            NaiveConfig {
+                encode_padding: true,
+                decode_allow_trailing_bits: false,
+                decode_padding_mode: DecodePaddingMode::RequireCanonical,
+            },
+        )
+    }
+
+    #[test]
+    fn dispatches_by_input_length() {
+        let engine = Adaptive::new(Counting::new(naive_standard()), Counting::new(STANDARD))
+            .with_threshold(8);
+
+        let _ = engine.encode([0; 7]);
+        let _ = engine.decode("AAAAAA==").unwrap();
+        assert_eq!(
+            (1, 0),
+            (
+                engine.small().encodes.load(Ordering::Relaxed),
+                engine.large().encodes.load(Ordering::Relaxed)
+            )
+        );
+        assert_eq!(
+            (0, 1),
+            (
+                engine.small().decodes.load(Ordering::Relaxed),
+                engine.large().decodes.load(Ordering::Relaxed)
+            )
+        );
+
+        let _ = engine.encode([0; 8]);
+        let _ = engine.decode("AAAA").unwrap();
+        assert_eq!(
+            (1, 1),
+            (
+                engine.small().encodes.load(Ordering::Relaxed),
+                engine.large().encodes.load(Ordering::Relaxed)
+            )
+        );
+        assert_eq!(
+            (1, 1),
+            (
+                engine.small().decodes.load(Ordering::Relaxed),
+                engine.large().decodes.load(Ordering::Relaxed)
+            )
+        );
+    }
+
+    #[test]
+    fn matches_either_engine() {
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..1_000 {
+            let engine =
+                Adaptive::new(naive_standard(), STANDARD).with_threshold(rng.gen_range(0..200));
+
+            let mut data = vec![0; rng.gen_range(0..300)];
+            rng.fill_bytes(&mut data);
+            let encoded = STANDARD.encode(&data);
+            assert_eq!(encoded, engine.encode(&data));
+            assert_eq!(data, engine.decode(&encoded).unwrap());
+
+            let mut invalid = encoded.into_bytes();
+            if !invalid.is_empty() {
+                let index = rng.gen_range(0..invalid.len());
+                invalid[index] = b'*';
+                assert_eq!(STANDARD.decode(&invalid), engine.decode(&invalid));
+            }
+        }
+    }
+
+    #[test]
+    fn thresholds_at_extremes_use_one_engine() {
+        let data = [0x5A; 100];
+
+        let always_large =
+            Adaptive::new(Counting::new(STANDARD), Counting::new(STANDARD)).with_threshold(0);
+        let _ = always_large.encode([]);
+        let _ = always_large.encode(data);
+        assert_eq!(0, always_large.small().encodes.load(Ordering::Relaxed));
+
+        let always_small = Adaptive::new(Counting::new(STANDARD), Counting::new(STANDARD))
+            .with_threshold(usize::MAX);
+        let _ = always_small.encode(data);
+        assert_eq!(0, always_small.large().encodes.load(Ordering::Relaxed));
+    }
+}
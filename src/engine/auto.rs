@@ -0,0 +1,115 @@
+//! Runtime selection of the fastest engine on the current CPU.
+//!
+//! Engine performance depends on the CPU, so for long-running services it can be worth spending a
+//! couple of milliseconds at startup measuring which of several equivalent engines is fastest.
+//!
+//! # Examples
+//!
+//! ```
+//! use base64::{alphabet, engine::{auto, general_purpose, Engine as _, GeneralPurpose}};
+//!
+//! let candidates = [
+//!     GeneralPurpose::new(&alphabet::STANDARD, general_purpose::PAD),
+//!     GeneralPurpose::new(&alphabet::STANDARD, general_purpose::PAD_INDIFFERENT),
+//! ];
+//! let engine = auto::benchmark_and_select(&candidates).unwrap();
+//! assert_eq!("aGk=", engine.encode(b"hi"));
+//! ```
+
+use crate::engine::Engine;
+use std::time::{Duration, Instant};
+
+/// Size of the input encoded and decoded for each measurement.
+const SAMPLE_LEN: usize = 3 * 1024;
+/// Number of measurements per candidate. The fastest one is used to reduce noise.
+const ROUNDS: usize = 5;
+
+/// Measure each of `candidates` encoding and decoding a few KiB of data, and return the one with
+/// the best time, or `None` if `candidates` is empty.
+///
+/// The candidates are expected to be interchangeable, i.e. produce the same output, since the
+/// choice depends on timing. The whole calibration takes on the order of a millisecond per
+/// candidate. Candidates that fail to encode the sample are only selected if all of them do.
+pub fn benchmark_and_select<E: Engine>(candidates: &[E]) -> Option<&E> {
+    // arbitrary but not trivially compressible input
+    let input: Vec<u8> = (0..SAMPLE_LEN)
+        .map(|i| (i as u8).wrapping_mul(167) ^ (i >> 8) as u8)
+        .collect();
+    let mut encoded = Vec::new();
+    let mut decoded = vec![0; SAMPLE_LEN];
+
+    candidates
+        .iter()
+        .map(|engine| {
+            // line wrapping and prefixes make the output longer, so size it for each engine
+            encoded.resize(engine.encoded_len(SAMPLE_LEN).unwrap_or(0), 0);
+            let time = (0..ROUNDS)
+                .map(|_| measure(engine, &input, &mut encoded, &mut decoded))
+                .min()
+                .flatten();
+            (engine, time)
+        })
+        // `None` sorts first, so put failures last
+        .min_by_key(|&(_, time)| (time.is_none(), time))
+        .map(|(engine, _)| engine)
+}
+
+/// The time to encode and decode `input`, or `None` if `engine` can't encode it into `encoded`.
+fn measure<E: Engine>(
+    engine: &E,
+    input: &[u8],
+    encoded: &mut [u8],
+    decoded: &mut [u8],
+) -> Option<Duration> {
+    let start = Instant::now();
+    let encoded_len = engine.encode_slice(input, encoded).ok()?;
+    // candidates are assumed to round trip; correctness is not what is being measured here
+    let _ = engine.decode_slice(&encoded[..encoded_len], decoded);
+    Some(start.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alphabet,
+        engine::{
+            general_purpose::{GeneralPurpose, NO_PAD, PAD},
+            LineEnding,
+        },
+    };
+
+    #[test]
+    fn empty_candidates_selects_none() {
+        let candidates: [GeneralPurpose; 0] = [];
+        assert!(benchmark_and_select(&candidates).is_none());
+    }
+
+    #[test]
+    fn selects_one_of_the_candidates() {
+        let candidates = [
+            GeneralPurpose::new(&alphabet::URL_SAFE, PAD),
+            GeneralPurpose::new(&alphabet::URL_SAFE, NO_PAD),
+        ];
+        let selected = benchmark_and_select(&candidates).unwrap();
+        assert!(candidates.iter().any(|c| core::ptr::eq(c, selected)));
+    }
+
+    #[test]
+    fn measures_engines_with_longer_output() {
+        let candidates = [
+            GeneralPurpose::new(
+                &alphabet::STANDARD,
+                PAD.with_encode_line_wrap(1)
+                    .with_encode_line_ending(LineEnding::CRLF),
+            ),
+            GeneralPurpose::new(&alphabet::STANDARD, PAD.with_encode_line_wrap(76)),
+        ];
+        for engine in &candidates {
+            let mut encoded = vec![0; engine.encoded_len(SAMPLE_LEN).unwrap()];
+            let mut decoded = vec![0; SAMPLE_LEN];
+            assert!(measure(engine, &[0; SAMPLE_LEN], &mut encoded, &mut decoded).is_some());
+        }
+        assert!(benchmark_and_select(&candidates).is_some());
+    }
+}
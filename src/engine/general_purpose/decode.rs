@@ -1,7 +1,8 @@
 use crate::{
     engine::{general_purpose::INVALID_VALUE, DecodeEstimate, DecodeMetadata, DecodePaddingMode},
-    DecodeError, DecodeSliceError, PAD_BYTE,
+    DecodeError, DecodeSliceError,
 };
+use core::cmp;
 
 #[doc(hidden)]
 pub struct GeneralPurposeEstimate {
@@ -12,14 +13,19 @@ pub struct GeneralPurposeEstimate {
 
 impl GeneralPurposeEstimate {
     pub(crate) fn new(encoded_len: usize) -> Self {
-        let rem = encoded_len % 4;
         Self {
-            rem,
-            conservative_decoded_len: (encoded_len / 4 + usize::from(rem > 0)) * 3,
+            rem: encoded_len % 4,
+            conservative_decoded_len: conservative_decoded_len(encoded_len),
         }
     }
 }
 
+/// The decoded length of `encoded_len` bytes of input, assuming any partial trailing quad decodes
+/// to a full 3 bytes.
+pub(crate) fn conservative_decoded_len(encoded_len: usize) -> usize {
+    (encoded_len / 4 + usize::from(encoded_len % 4 > 0)) * 3
+}
+
 impl DecodeEstimate for GeneralPurposeEstimate {
     fn decoded_len_estimate(&self) -> usize {
         self.conservative_decoded_len
@@ -37,11 +43,17 @@ pub(crate) fn decode_helper(
     estimate: &GeneralPurposeEstimate,
     output: &mut [u8],
     decode_table: &[u8; 256],
+    padding_byte: u8,
     decode_allow_trailing_bits: bool,
     padding_mode: DecodePaddingMode,
 ) -> Result<DecodeMetadata, DecodeSliceError> {
-    let input_complete_nonterminal_quads_len =
-        complete_quads_len(input, estimate.rem, output.len(), decode_table)?;
+    let input_complete_nonterminal_quads_len = complete_quads_len(
+        input,
+        estimate.rem,
+        output.len(),
+        decode_table,
+        padding_byte,
+    )?;
 
     const UNROLLED_INPUT_CHUNK_SIZE: usize = 32;
     const UNROLLED_OUTPUT_CHUNK_SIZE: usize = UNROLLED_INPUT_CHUNK_SIZE / 4 * 3;
@@ -115,11 +127,124 @@ pub(crate) fn decode_helper(
         output,
         output_complete_quad_len,
         decode_table,
+        padding_byte,
         decode_allow_trailing_bits,
         padding_mode,
     )
 }
 
+/// The number of symbols [`decode_skipping`] gathers up to decode at a time.
+const SKIPPING_BLOCK_LEN: usize = 1024;
+
+/// Decode `input` like [`decode_helper`], but skipping bytes for which `ignore` returns `true`
+/// given their offset and value.
+///
+/// Symbols are gathered into blocks on the stack and decoded a block at a time, so this doesn't
+/// allocate. Offsets in errors and metadata refer to `input`, including skipped bytes.
+pub(crate) fn decode_skipping<F: Fn(usize, u8) -> bool>(
+    input: &[u8],
+    output: &mut [u8],
+    decode_table: &[u8; 256],
+    padding_byte: u8,
+    decode_allow_trailing_bits: bool,
+    padding_mode: DecodePaddingMode,
+    ignore: F,
+) -> Result<DecodeMetadata, DecodeSliceError> {
+    let symbols_len = input
+        .iter()
+        .enumerate()
+        .filter(|&(i, &b)| !ignore(i, b))
+        .count();
+    let mut symbols = input
+        .iter()
+        .enumerate()
+        .filter(|&(i, &b)| !ignore(i, b))
+        .map(|(_, &b)| b);
+    let mut block = [0_u8; SKIPPING_BLOCK_LEN];
+    let mut block_start = 0;
+    let mut decoded_len = 0;
+
+    loop {
+        let block_len = cmp::min(SKIPPING_BLOCK_LEN, symbols_len - block_start);
+        for (dest, symbol) in block[..block_len].iter_mut().zip(&mut symbols) {
+            *dest = symbol;
+        }
+        let is_last_block = block_start + block_len == symbols_len;
+
+        // Earlier blocks are complete quads, so padding is only valid in the last one. Let it
+        // through here, without checking the bits before it, so it's reported the same way as
+        // padding in the middle of any input.
+        let metadata = decode_helper(
+            &block[..block_len],
+            &GeneralPurposeEstimate::new(block_len),
+            &mut output[decoded_len..],
+            decode_table,
+            padding_byte,
+            decode_allow_trailing_bits || !is_last_block,
+            if is_last_block {
+                padding_mode
+            } else {
+                DecodePaddingMode::Indifferent
+            },
+        )
+        .map_err(|e| match e {
+            DecodeSliceError::DecodeError(e) => DecodeSliceError::DecodeError(match e {
+                DecodeError::InvalidByte(offset, byte) => DecodeError::InvalidByte(
+                    input_offset(input, block_start + offset, &ignore),
+                    byte,
+                ),
+                DecodeError::InvalidLength(len) => DecodeError::InvalidLength(block_start + len),
+                DecodeError::InvalidLastSymbol {
+                    offset,
+                    symbol,
+                    symbol_value,
+                } => DecodeError::InvalidLastSymbol {
+                    offset: input_offset(input, block_start + offset, &ignore),
+                    symbol,
+                    symbol_value,
+                },
+                DecodeError::PaddingNotAtEnd(offset) => {
+                    DecodeError::PaddingNotAtEnd(input_offset(input, block_start + offset, &ignore))
+                }
+                e => e,
+            }),
+            DecodeSliceError::OutputSliceTooSmall { .. } => DecodeSliceError::OutputSliceTooSmall {
+                needed: conservative_decoded_len(symbols_len),
+            },
+        })?;
+
+        decoded_len += metadata.decoded_len;
+        let padding_offset = metadata
+            .padding_offset
+            .map(|offset| input_offset(input, block_start + offset, &ignore));
+
+        match padding_offset {
+            Some(offset) if !is_last_block => {
+                return Err(DecodeError::InvalidByte(offset, padding_byte).into())
+            }
+            _ if is_last_block => {
+                return Ok(DecodeMetadata {
+                    decoded_len,
+                    padding_offset,
+                    ..metadata
+                })
+            }
+            _ => block_start += block_len,
+        }
+    }
+}
+
+/// Returns the offset in `input` of the symbol at `symbol_offset` among the bytes that aren't
+/// ignored.
+fn input_offset<F: Fn(usize, u8) -> bool>(input: &[u8], symbol_offset: usize, ignore: &F) -> usize {
+    input
+        .iter()
+        .enumerate()
+        .filter(|&(i, &b)| !ignore(i, b))
+        .nth(symbol_offset)
+        .map_or(input.len(), |(offset, _)| offset)
+}
+
 /// Returns the length of complete quads, except for the last one, even if it is complete.
 ///
 /// Returns an error if the output len is not big enough for decoding those complete quads, or if
@@ -133,6 +258,7 @@ pub(crate) fn complete_quads_len(
     input_len_rem: usize,
     output_len: usize,
     decode_table: &[u8; 256],
+    padding_byte: u8,
 ) -> Result<usize, DecodeSliceError> {
     debug_assert!(input.len() % 4 == input_len_rem);
 
@@ -140,7 +266,7 @@ pub(crate) fn complete_quads_len(
     if input_len_rem == 1 {
         let last_byte = input[input.len() - 1];
         // exclude pad bytes; might be part of padding that extends from earlier in the input
-        if last_byte != PAD_BYTE && decode_table[usize::from(last_byte)] == INVALID_VALUE {
+        if last_byte != padding_byte && decode_table[usize::from(last_byte)] == INVALID_VALUE {
             return Err(DecodeError::InvalidByte(input.len() - 1, last_byte).into());
         }
     };
@@ -157,7 +283,9 @@ pub(crate) fn complete_quads_len(
 
     // check that everything except the last quad handled by decode_suffix will fit
     if output_len < input_complete_nonterminal_quads_len / 4 * 3 {
-        return Err(DecodeSliceError::OutputSliceTooSmall);
+        return Err(DecodeSliceError::OutputSliceTooSmall {
+            needed: conservative_decoded_len(input.len()),
+        });
     };
     Ok(input_complete_nonterminal_quads_len)
 }
@@ -169,6 +297,10 @@ pub(crate) fn complete_quads_len(
 /// accurately)
 /// `decode_table` is the lookup table for the particular base64 alphabet.
 /// `output` will have its first 6 bytes overwritten
+///
+/// Each symbol is validated and translated by the same table lookup: valid morsels only use the
+/// low 6 bits, and [`INVALID_VALUE`] sets the top 2, so the morsels are OR'd together and checked
+/// once for the whole chunk. Only a chunk with an invalid byte is scanned again, to report it.
 // yes, really inline (worth 30-50% speedup)
 #[inline(always)]
 fn decode_chunk_8(
@@ -177,74 +309,42 @@ fn decode_chunk_8(
     decode_table: &[u8; 256],
     output: &mut [u8],
 ) -> Result<(), DecodeError> {
-    let morsel = decode_table[usize::from(input[0])];
-    if morsel == INVALID_VALUE {
-        return Err(DecodeError::InvalidByte(index_at_start_of_input, input[0]));
-    }
-    let mut accum = u64::from(morsel) << 58;
-
-    let morsel = decode_table[usize::from(input[1])];
-    if morsel == INVALID_VALUE {
-        return Err(DecodeError::InvalidByte(
-            index_at_start_of_input + 1,
-            input[1],
-        ));
-    }
-    accum |= u64::from(morsel) << 52;
-
-    let morsel = decode_table[usize::from(input[2])];
-    if morsel == INVALID_VALUE {
-        return Err(DecodeError::InvalidByte(
-            index_at_start_of_input + 2,
-            input[2],
-        ));
-    }
-    accum |= u64::from(morsel) << 46;
-
-    let morsel = decode_table[usize::from(input[3])];
-    if morsel == INVALID_VALUE {
-        return Err(DecodeError::InvalidByte(
-            index_at_start_of_input + 3,
-            input[3],
-        ));
-    }
-    accum |= u64::from(morsel) << 40;
-
-    let morsel = decode_table[usize::from(input[4])];
-    if morsel == INVALID_VALUE {
-        return Err(DecodeError::InvalidByte(
-            index_at_start_of_input + 4,
-            input[4],
-        ));
-    }
-    accum |= u64::from(morsel) << 34;
-
-    let morsel = decode_table[usize::from(input[5])];
-    if morsel == INVALID_VALUE {
-        return Err(DecodeError::InvalidByte(
-            index_at_start_of_input + 5,
-            input[5],
-        ));
-    }
-    accum |= u64::from(morsel) << 28;
-
-    let morsel = decode_table[usize::from(input[6])];
-    if morsel == INVALID_VALUE {
-        return Err(DecodeError::InvalidByte(
-            index_at_start_of_input + 6,
-            input[6],
+    let morsels = [
+        decode_table[usize::from(input[0])],
+        decode_table[usize::from(input[1])],
+        decode_table[usize::from(input[2])],
+        decode_table[usize::from(input[3])],
+        decode_table[usize::from(input[4])],
+        decode_table[usize::from(input[5])],
+        decode_table[usize::from(input[6])],
+        decode_table[usize::from(input[7])],
+    ];
+    if (morsels[0]
+        | morsels[1]
+        | morsels[2]
+        | morsels[3]
+        | morsels[4]
+        | morsels[5]
+        | morsels[6]
+        | morsels[7])
+        & INVALID_FLAGS
+        != 0
+    {
+        return Err(first_invalid_byte(
+            &input[..8],
+            index_at_start_of_input,
+            decode_table,
         ));
     }
-    accum |= u64::from(morsel) << 22;
 
-    let morsel = decode_table[usize::from(input[7])];
-    if morsel == INVALID_VALUE {
-        return Err(DecodeError::InvalidByte(
-            index_at_start_of_input + 7,
-            input[7],
-        ));
-    }
-    accum |= u64::from(morsel) << 16;
+    let accum = u64::from(morsels[0]) << 58
+        | u64::from(morsels[1]) << 52
+        | u64::from(morsels[2]) << 46
+        | u64::from(morsels[3]) << 40
+        | u64::from(morsels[4]) << 34
+        | u64::from(morsels[5]) << 28
+        | u64::from(morsels[6]) << 22
+        | u64::from(morsels[7]) << 16;
 
     output[..6].copy_from_slice(&accum.to_be_bytes()[..6]);
 
@@ -259,44 +359,90 @@ fn decode_chunk_4(
     decode_table: &[u8; 256],
     output: &mut [u8],
 ) -> Result<(), DecodeError> {
-    let morsel = decode_table[usize::from(input[0])];
-    if morsel == INVALID_VALUE {
-        return Err(DecodeError::InvalidByte(index_at_start_of_input, input[0]));
-    }
-    let mut accum = u32::from(morsel) << 26;
-
-    let morsel = decode_table[usize::from(input[1])];
-    if morsel == INVALID_VALUE {
-        return Err(DecodeError::InvalidByte(
-            index_at_start_of_input + 1,
-            input[1],
-        ));
-    }
-    accum |= u32::from(morsel) << 20;
-
-    let morsel = decode_table[usize::from(input[2])];
-    if morsel == INVALID_VALUE {
-        return Err(DecodeError::InvalidByte(
-            index_at_start_of_input + 2,
-            input[2],
+    let morsels = [
+        decode_table[usize::from(input[0])],
+        decode_table[usize::from(input[1])],
+        decode_table[usize::from(input[2])],
+        decode_table[usize::from(input[3])],
+    ];
+    if (morsels[0] | morsels[1] | morsels[2] | morsels[3]) & INVALID_FLAGS != 0 {
+        return Err(first_invalid_byte(
+            &input[..4],
+            index_at_start_of_input,
+            decode_table,
         ));
     }
-    accum |= u32::from(morsel) << 14;
 
-    let morsel = decode_table[usize::from(input[3])];
-    if morsel == INVALID_VALUE {
-        return Err(DecodeError::InvalidByte(
-            index_at_start_of_input + 3,
-            input[3],
-        ));
-    }
-    accum |= u32::from(morsel) << 8;
+    let accum = u32::from(morsels[0]) << 26
+        | u32::from(morsels[1]) << 20
+        | u32::from(morsels[2]) << 14
+        | u32::from(morsels[3]) << 8;
 
     output[..3].copy_from_slice(&accum.to_be_bytes()[..3]);
 
     Ok(())
 }
 
+/// Bits of a decode table entry that are only set in [`INVALID_VALUE`].
+const INVALID_FLAGS: u8 = !0x3F;
+
+/// The error for the first byte in `input` that isn't in the alphabet, which the caller has found
+/// there is one of.
+// kept out of line so the checks in the hot loops stay small
+#[cold]
+#[inline(never)]
+fn first_invalid_byte(
+    input: &[u8],
+    index_at_start_of_input: usize,
+    decode_table: &[u8; 256],
+) -> DecodeError {
+    let (offset, &byte) = input
+        .iter()
+        .enumerate()
+        .find(|&(_, &b)| decode_table[usize::from(b)] == INVALID_VALUE)
+        .expect("a chunk with invalid flags set has an invalid byte");
+
+    DecodeError::InvalidByte(index_at_start_of_input + offset, byte)
+}
+
+/// The offset of the first byte of `input` that is neither in the alphabet of `decode_table`,
+/// `padding_byte`, nor a byte that `ignores`.
+///
+/// Like [`decode_chunk_8`], chunks are classified by OR'ing their decode table entries together
+/// and checking the result once, so valid input costs a table lookup per byte and a branch per
+/// chunk. Only a chunk with a byte outside the alphabet is scanned again, to see if that byte is
+/// padding or ignored. This is a plain scalar loop, not SIMD, since the crate has no unsafe code
+/// for intrinsics, though the compiler may still unroll the lookups.
+pub(crate) fn find_invalid<F: Fn(u8) -> bool>(
+    input: &[u8],
+    decode_table: &[u8; 256],
+    padding_byte: u8,
+    ignores: F,
+) -> Option<usize> {
+    const CHUNK_SIZE: usize = 32;
+
+    input
+        .chunks(CHUNK_SIZE)
+        .enumerate()
+        .filter(|(_, chunk)| {
+            chunk
+                .iter()
+                .fold(0, |flags, &b| flags | decode_table[usize::from(b)])
+                & INVALID_FLAGS
+                != 0
+        })
+        .find_map(|(chunk_index, chunk)| {
+            chunk
+                .iter()
+                .position(|&b| {
+                    decode_table[usize::from(b)] == INVALID_VALUE
+                        && b != padding_byte
+                        && !ignores(b)
+                })
+                .map(|offset| chunk_index * CHUNK_SIZE + offset)
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -321,6 +467,30 @@ mod tests {
         assert_eq!(&vec![b'f', b'o', b'o', 3], &output);
     }
 
+    #[test]
+    fn decode_chunks_report_first_invalid_byte() {
+        let valid = b"Zm9vYmFy";
+        for offset in 0..8 {
+            let mut input = *valid;
+            input[offset] = b'*';
+            // a later invalid byte doesn't hide the first
+            if offset < 7 {
+                input[7] = b'-';
+            }
+
+            assert_eq!(
+                Err(DecodeError::InvalidByte(100 + offset, b'*')),
+                decode_chunk_8(&input, 100, &STANDARD.decode_table, &mut [0_u8; 6])
+            );
+            if offset < 4 {
+                assert_eq!(
+                    Err(DecodeError::InvalidByte(100 + offset, b'*')),
+                    decode_chunk_4(&input[..4], 100, &STANDARD.decode_table, &mut [0_u8; 3])
+                );
+            }
+        }
+    }
+
     #[test]
     fn estimate_short_lengths() {
         for (range, decoded_len_estimate) in [
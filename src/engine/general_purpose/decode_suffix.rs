@@ -1,6 +1,9 @@
 use crate::{
-    engine::{general_purpose::INVALID_VALUE, DecodeMetadata, DecodePaddingMode},
-    DecodeError, DecodeSliceError, PAD_BYTE,
+    engine::{
+        general_purpose::{decode::conservative_decoded_len, INVALID_VALUE},
+        DecodeMetadata, DecodePaddingMode,
+    },
+    DecodeError, DecodeSliceError,
 };
 
 /// Decode the last 0-4 bytes, checking for trailing set bits and padding per the provided
@@ -8,12 +11,14 @@ use crate::{
 ///
 /// Returns the decode metadata representing the total number of bytes decoded, including the ones
 /// indicated as already written by `output_index`.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn decode_suffix(
     input: &[u8],
     input_index: usize,
     output: &mut [u8],
     mut output_index: usize,
     decode_table: &[u8; 256],
+    padding_byte: u8,
     decode_allow_trailing_bits: bool,
     padding_mode: DecodePaddingMode,
 ) -> Result<DecodeMetadata, DecodeSliceError> {
@@ -31,7 +36,7 @@ pub(crate) fn decode_suffix(
 
     for (leftover_index, &b) in input[input_index..].iter().enumerate() {
         // '=' padding
-        if b == PAD_BYTE {
+        if b == padding_byte {
             // There can be bad padding bytes in a few ways:
             // 1 - Padding with non-padding characters after it
             // 2 - Padding after zero or one characters in the current quad (should only
@@ -69,7 +74,7 @@ pub(crate) fn decode_suffix(
         // erroneous padding.
         if padding_bytes_count > 0 {
             return Err(
-                DecodeError::InvalidByte(input_index + first_padding_offset, PAD_BYTE).into(),
+                DecodeError::InvalidByte(input_index + first_padding_offset, padding_byte).into(),
             );
         }
 
@@ -150,16 +155,25 @@ pub(crate) fn decode_suffix(
         leftover_num <<= 8;
         *output
             .get_mut(output_index)
-            .ok_or(DecodeSliceError::OutputSliceTooSmall)? = hi_byte;
+            .ok_or(DecodeSliceError::OutputSliceTooSmall {
+                needed: conservative_decoded_len(input.len()),
+            })? = hi_byte;
         output_index += 1;
     }
 
-    Ok(DecodeMetadata::new(
-        output_index,
-        if padding_bytes_count > 0 {
-            Some(input_index + first_padding_offset)
-        } else {
-            None
-        },
-    ))
+    // 2 symbols have 4 bits left over from a byte, and 3 have 2
+    let trailing_bits_len = morsels_in_leftover * 6 - leftover_bytes_to_append * 8;
+
+    Ok(DecodeMetadata {
+        padding_len: padding_bytes_count,
+        trailing_bits: last_symbol_value & ((1 << trailing_bits_len) - 1),
+        ..DecodeMetadata::new(
+            output_index,
+            if padding_bytes_count > 0 {
+                Some(input_index + first_padding_offset)
+            } else {
+                None
+            },
+        )
+    })
 }
@@ -4,10 +4,13 @@
 use crate::{
     alphabet,
     alphabet::Alphabet,
-    engine::{Config, DecodeMetadata, DecodePaddingMode},
-    DecodeSliceError,
+    block::BlockCodec,
+    engine::{Config, DecodeMetadata, DecodePaddingMode, EmbeddedPaddingMode, LineEnding, Padding},
+    DecodeError, DecodeSliceError, PAD_BYTE,
 };
-use core::convert::TryInto;
+use core::{convert::TryInto, fmt};
+#[cfg(any(feature = "std", test))]
+use std::error;
 
 pub(crate) mod decode;
 pub(crate) mod decode_suffix;
@@ -34,16 +37,220 @@ impl GeneralPurpose {
     ///
     /// While not very expensive to initialize, ideally these should be cached
     /// if the engine will be used repeatedly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the config's padding byte (see [`GeneralPurposeConfig::with_padding`]) is one of
+    /// the alphabet's symbols, or isn't printable ASCII other than space. Use
+    /// [`GeneralPurpose::try_new`] if the config comes from user input.
     #[must_use]
     pub const fn new(alphabet: &Alphabet, config: GeneralPurposeConfig) -> Self {
+        check_padding_byte(&alphabet.symbols, config.padding_byte);
+
         Self {
             encode_table: encode_table(alphabet),
             decode_table: decode_table(alphabet),
             config,
         }
     }
+
+    /// Create a `GeneralPurpose` engine from an [Alphabet], returning an error instead of
+    /// panicking if the config can't be used with it.
+    ///
+    /// The padding byte is the only setting that depends on the alphabet. Every other value of
+    /// every setting is valid, e.g. a line wrap length of 0 means no wrapping.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ConfigError`] if the config's padding byte is one of the alphabet's symbols, or
+    /// isn't printable ASCII other than space.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64::{alphabet, engine::{general_purpose::{self, ConfigError}, GeneralPurpose, Padding}};
+    ///
+    /// let config = general_purpose::PAD.with_padding(Padding::Padded(b'-'));
+    /// assert!(GeneralPurpose::try_new(&alphabet::STANDARD, config).is_ok());
+    /// assert_eq!(
+    ///     Some(ConfigError::PaddingInAlphabet(b'-')),
+    ///     GeneralPurpose::try_new(&alphabet::URL_SAFE, config).err()
+    /// );
+    /// ```
+    pub const fn try_new(
+        alphabet: &Alphabet,
+        config: GeneralPurposeConfig,
+    ) -> Result<Self, ConfigError> {
+        match padding_byte_error(&alphabet.symbols, config.padding_byte) {
+            Some(error) => Err(error),
+            None => Ok(Self {
+                encode_table: encode_table(alphabet),
+                decode_table: decode_table(alphabet),
+                config,
+            }),
+        }
+    }
+
+    /// Create a `GeneralPurpose` engine that uses the lookup tables of a [`BlockCodec`].
+    ///
+    /// The tables are copied rather than built from an alphabet, so a `BlockCodec` in a `const`
+    /// can be built once at compile time and reused for engines whose config is only known at
+    /// runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`GeneralPurpose::new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64::{alphabet::Alphabet, block::BlockCodec, engine::{general_purpose, GeneralPurpose}, Engine as _};
+    ///
+    /// const CUSTOM: BlockCodec = match Alphabet::new("0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz-_") {
+    ///     Ok(alphabet) => BlockCodec::new(&alphabet),
+    ///     Err(_) => panic!("invalid alphabet"),
+    /// };
+    ///
+    /// let engine = GeneralPurpose::from_block_codec(&CUSTOM, general_purpose::PAD);
+    /// assert_eq!("OW==", engine.encode(b"b"));
+    /// ```
+    #[must_use]
+    pub const fn from_block_codec(codec: &BlockCodec, config: GeneralPurposeConfig) -> Self {
+        check_padding_byte(&codec.encode_table, config.padding_byte);
+
+        Self {
+            encode_table: codec.encode_table,
+            decode_table: codec.decode_table,
+            config,
+        }
+    }
+
+    /// Create a `GeneralPurpose` engine that uses the lookup tables of a [`BlockCodec`], returning
+    /// an error instead of panicking if the config can't be used with it.
+    ///
+    /// # Errors
+    ///
+    /// As for [`GeneralPurpose::try_new`].
+    pub const fn try_from_block_codec(
+        codec: &BlockCodec,
+        config: GeneralPurposeConfig,
+    ) -> Result<Self, ConfigError> {
+        match padding_byte_error(&codec.encode_table, config.padding_byte) {
+            Some(error) => Err(error),
+            None => Ok(Self {
+                encode_table: codec.encode_table,
+                decode_table: codec.decode_table,
+                config,
+            }),
+        }
+    }
+
+    /// Returns `self`, after checking that its encoded output can be put in XML or HTML text and
+    /// attribute values without escaping.
+    ///
+    /// The standard and URL-safe alphabets always pass; this is for engines with a custom
+    /// alphabet or padding byte. When the engine is defined in a `const`, the check is done at
+    /// compile time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a symbol of the alphabet, or the padding byte if encoding pads, is one of `<`,
+    /// `>`, `&`, `'`, or `"`. In a `const`, that's a compile error instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64::{alphabet, engine::{general_purpose, GeneralPurpose}};
+    ///
+    /// const ENGINE: GeneralPurpose =
+    ///     GeneralPurpose::new(&alphabet::URL_SAFE, general_purpose::PAD).assert_markup_safe();
+    /// ```
+    ///
+    /// An alphabet with `&` in it doesn't compile:
+    ///
+    /// ```compile_fail
+    /// use base64::{alphabet::Alphabet, engine::{general_purpose, GeneralPurpose}};
+    ///
+    /// const AMPERSAND: Alphabet = match Alphabet::new("&BCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/") {
+    ///     Ok(alphabet) => alphabet,
+    ///     Err(_) => panic!("invalid alphabet"),
+    /// };
+    /// const ENGINE: GeneralPurpose =
+    ///     GeneralPurpose::new(&AMPERSAND, general_purpose::PAD).assert_markup_safe();
+    /// ```
+    #[must_use]
+    pub const fn assert_markup_safe(self) -> Self {
+        let mut unsafe_byte =
+            self.config.encode_padding && is_markup_special(self.config.padding_byte);
+        let mut index = 0;
+        while index < 64 {
+            unsafe_byte |= is_markup_special(self.encode_table[index]);
+            index += 1;
+        }
+        const_panic_if!(
+            unsafe_byte,
+            "encoded output must not contain <, >, &, ', or \""
+        );
+
+        self
+    }
 }
 
+/// Returns `true` if `byte` has to be escaped in XML or HTML text or attribute values.
+const fn is_markup_special(byte: u8) -> bool {
+    matches!(byte, b'<' | b'>' | b'&' | b'\'' | b'"')
+}
+
+/// Panics if `padding_byte` is one of `symbols`, or isn't printable ASCII other than space.
+const fn check_padding_byte(symbols: &[u8; 64], padding_byte: u8) {
+    const_panic_if!(
+        padding_byte_error(symbols, padding_byte).is_some(),
+        "padding byte must be printable and not in the alphabet"
+    );
+}
+
+/// Returns why `padding_byte` can't be used with `symbols`, if it can't.
+const fn padding_byte_error(symbols: &[u8; 64], padding_byte: u8) -> Option<ConfigError> {
+    if !(padding_byte > b' ' && padding_byte <= b'~') {
+        return Some(ConfigError::UnprintablePadding(padding_byte));
+    }
+    let mut index = 0;
+    while index < 64 {
+        if symbols[index] == padding_byte {
+            return Some(ConfigError::PaddingInAlphabet(padding_byte));
+        }
+        index += 1;
+    }
+
+    None
+}
+
+/// Errors that can occur when creating a [`GeneralPurpose`] engine with
+/// [`GeneralPurpose::try_new`] or [`GeneralPurpose::try_from_block_codec`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// The padding byte isn't printable ASCII other than space.
+    UnprintablePadding(u8),
+    /// The padding byte is one of the alphabet's symbols.
+    PaddingInAlphabet(u8),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnprintablePadding(b) => write!(f, "Unprintable padding byte: {:#04x}", b),
+            Self::PaddingInAlphabet(b) => {
+                write!(f, "Padding byte is in the alphabet: {:#04x}", b)
+            }
+        }
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl error::Error for ConfigError {}
+
 impl super::Engine for GeneralPurpose {
     type Config = GeneralPurposeConfig;
     type DecodeEstimate = GeneralPurposeEstimate;
@@ -177,11 +384,38 @@ impl super::Engine for GeneralPurpose {
         output: &mut [u8],
         estimate: Self::DecodeEstimate,
     ) -> Result<DecodeMetadata, DecodeSliceError> {
+        if input.len() > self.config.decode_max_encoded_len {
+            return Err(DecodeError::InputTooLong {
+                len: input.len(),
+                max: self.config.decode_max_encoded_len,
+            }
+            .into());
+        }
+
+        if self.config.decode_embedded_padding != EmbeddedPaddingMode::Invalid {
+            if let Some((padding_offset, data_end)) = self.embedded_padding(input) {
+                return self.decode_embedded_padding(input, output, padding_offset, data_end);
+            }
+        }
+
+        if self.config.ignores_any() && input.iter().any(|&b| self.config.decode_ignores(b)) {
+            return decode::decode_skipping(
+                input,
+                output,
+                &self.decode_table,
+                self.config.padding().decode_byte(),
+                self.config.decode_allow_trailing_bits,
+                self.config.decode_padding_mode,
+                |_, b| self.config.decode_ignores(b),
+            );
+        }
+
         decode::decode_helper(
             input,
             &estimate,
             output,
             &self.decode_table,
+            self.config.padding().decode_byte(),
             self.config.decode_allow_trailing_bits,
             self.config.decode_padding_mode,
         )
@@ -190,6 +424,21 @@ impl super::Engine for GeneralPurpose {
     fn config(&self) -> &Self::Config {
         &self.config
     }
+
+    fn find_invalid<T: AsRef<[u8]>>(&self, input: T) -> Option<usize> {
+        decode::find_invalid(
+            input.as_ref(),
+            &self.decode_table,
+            self.config.padding().decode_byte(),
+            |b| self.config.decode_ignores(b),
+        )
+    }
+
+    fn preferred_block_size(&self) -> usize {
+        // the unrolled loops in `internal_encode()` and `decode_helper()` encode 24 bytes to, and
+        // decode them from, 32 symbols at a time
+        24
+    }
 }
 
 /// Returns a table mapping a 6-bit index to the ASCII byte encoding of the index
@@ -227,6 +476,64 @@ pub(crate) const fn decode_table(alphabet: &Alphabet) -> [u8; 256] {
     decode_table
 }
 
+impl GeneralPurpose {
+    /// Returns the offset of the first padding byte in `input` if it comes before the last symbol,
+    /// along with the offset just past that symbol, or `None` if there's no such padding.
+    fn embedded_padding(&self, input: &[u8]) -> Option<(usize, usize)> {
+        let padding_byte = self.config.padding().decode_byte();
+        let data_end = input
+            .iter()
+            .rposition(|&b| b != padding_byte && !self.config.decode_ignores(b))?
+            + 1;
+        let padding_offset = input[..data_end].iter().position(|&b| b == padding_byte)?;
+        Some((padding_offset, data_end))
+    }
+
+    /// Decode `input`, which has padding at `padding_offset` before the last symbol, just before
+    /// `data_end`, according to the config's [`EmbeddedPaddingMode`].
+    #[cold]
+    fn decode_embedded_padding(
+        &self,
+        input: &[u8],
+        output: &mut [u8],
+        padding_offset: usize,
+        data_end: usize,
+    ) -> Result<DecodeMetadata, DecodeSliceError> {
+        let padding_byte = self.config.padding().decode_byte();
+        match self.config.decode_embedded_padding {
+            EmbeddedPaddingMode::Invalid => {
+                Err(DecodeError::InvalidByte(padding_offset, padding_byte).into())
+            }
+            EmbeddedPaddingMode::Strict => Err(DecodeError::PaddingNotAtEnd(padding_offset).into()),
+            EmbeddedPaddingMode::Terminate => {
+                // the padding is the first, so there's none before the end of what's left
+                let end = padding_offset
+                    + input[padding_offset..]
+                        .iter()
+                        .take_while(|&&b| b == padding_byte || self.config.decode_ignores(b))
+                        .count();
+                super::Engine::internal_decode(
+                    self,
+                    &input[..end],
+                    output,
+                    GeneralPurposeEstimate::new(end),
+                )
+            }
+            EmbeddedPaddingMode::Ignore => decode::decode_skipping(
+                input,
+                output,
+                &self.decode_table,
+                padding_byte,
+                self.config.decode_allow_trailing_bits,
+                self.config.decode_padding_mode,
+                |offset, b| {
+                    (b == padding_byte && offset < data_end) || self.config.decode_ignores(b)
+                },
+            ),
+        }
+    }
+}
+
 #[inline]
 fn read_u64(s: &[u8]) -> u64 {
     u64::from_be_bytes(s[..8].try_into().unwrap())
@@ -244,11 +551,66 @@ fn read_u64(s: &[u8]) -> u64 {
 /// The constants [PAD] and [`NO_PAD`] cover most use cases.
 ///
 /// To specify the characters used, see [Alphabet].
-#[derive(Clone, Copy, Debug)]
+///
+/// With the `serde` feature, configs can be serialized and deserialized using the names of the
+/// `with_*` settings, e.g. `encode_padding` or `decode_padding_mode`. Settings that are missing
+/// when deserializing take their default values. See [`EngineSpec`](crate::spec::EngineSpec) to
+/// include the alphabet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct GeneralPurposeConfig {
     encode_padding: bool,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "is_default_padding_byte")
+    )]
+    padding_byte: u8,
     decode_allow_trailing_bits: bool,
     decode_padding_mode: DecodePaddingMode,
+    // not all formats can represent `usize::MAX`, so leave out the default of no limit
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_unlimited"))]
+    decode_max_encoded_len: usize,
+    encode_line_wrap: usize,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_false"))]
+    encode_trailing_newline: bool,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "is_default_line_ending")
+    )]
+    encode_line_ending: LineEnding,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "ByteSet::is_empty"))]
+    decode_ignore_bytes: ByteSet,
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip_serializing_if = "is_default_embedded_padding")
+    )]
+    decode_embedded_padding: EmbeddedPaddingMode,
+}
+
+#[cfg(feature = "serde")]
+fn is_default_padding_byte(byte: &u8) -> bool {
+    *byte == PAD_BYTE
+}
+
+#[cfg(feature = "serde")]
+fn is_unlimited(max_len: &usize) -> bool {
+    *max_len == usize::MAX
+}
+
+#[cfg(feature = "serde")]
+fn is_false(flag: &bool) -> bool {
+    !*flag
+}
+
+#[cfg(feature = "serde")]
+fn is_default_line_ending(line_ending: &LineEnding) -> bool {
+    *line_ending == LineEnding::LF
+}
+
+#[cfg(feature = "serde")]
+fn is_default_embedded_padding(mode: &EmbeddedPaddingMode) -> bool {
+    *mode == EmbeddedPaddingMode::Invalid
 }
 
 impl GeneralPurposeConfig {
@@ -262,8 +624,15 @@ impl GeneralPurposeConfig {
         Self {
             // RFC states that padding must be applied by default
             encode_padding: true,
+            padding_byte: PAD_BYTE,
             decode_allow_trailing_bits: false,
             decode_padding_mode: DecodePaddingMode::RequireCanonical,
+            decode_max_encoded_len: usize::MAX,
+            encode_line_wrap: 0,
+            encode_trailing_newline: false,
+            encode_line_ending: LineEnding::LF,
+            decode_ignore_bytes: ByteSet::EMPTY,
+            decode_embedded_padding: EmbeddedPaddingMode::Invalid,
         }
     }
 
@@ -277,6 +646,8 @@ impl GeneralPurposeConfig {
     ///
     /// For new applications, consider not using padding if the decoders you're using don't require
     /// padding to be present.
+    ///
+    /// This keeps the padding byte set by [`GeneralPurposeConfig::with_padding`], if any.
     #[must_use]
     pub const fn with_encode_padding(self, padding: bool) -> Self {
         Self {
@@ -285,6 +656,41 @@ impl GeneralPurposeConfig {
         }
     }
 
+    /// Create a new config based on `self` with updated padding.
+    ///
+    /// [`Padding::Padded`] enables padding with the given byte, which is also the byte recognized
+    /// as padding when decoding. [`Padding::Unpadded`] is the same as
+    /// `with_encode_padding(false)`.
+    ///
+    /// The padding byte must be printable ASCII other than space, and not one of the symbols of
+    /// the alphabet the config is used with, or [`GeneralPurpose::new`] will panic and
+    /// [`GeneralPurpose::try_new`] will return an error.
+    ///
+    /// ```
+    /// use base64::{Engine as _, alphabet, engine::{general_purpose, GeneralPurpose, Padding}};
+    ///
+    /// let engine = GeneralPurpose::new(
+    ///     &alphabet::URL_SAFE,
+    ///     general_purpose::PAD.with_padding(Padding::Padded(b'.')),
+    /// );
+    /// assert_eq!("aGk.", engine.encode(b"hi"));
+    /// assert_eq!(b"hi", &engine.decode("aGk.").unwrap()[..]);
+    /// ```
+    #[must_use]
+    pub const fn with_padding(self, padding: Padding) -> Self {
+        match padding {
+            Padding::Padded(padding_byte) => Self {
+                encode_padding: true,
+                padding_byte,
+                ..self
+            },
+            Padding::Unpadded => Self {
+                encode_padding: false,
+                ..self
+            },
+        }
+    }
+
     /// Create a new config based on `self` with an updated `decode_allow_trailing_bits` setting.
     ///
     /// Most users will not need to configure this. It's useful if you need to decode base64
@@ -320,6 +726,177 @@ impl GeneralPurposeConfig {
             ..self
         }
     }
+
+    /// Create a new config based on `self` with an updated limit on the length of input accepted
+    /// when decoding.
+    ///
+    /// Input longer than `max_len` bytes is rejected with [`DecodeError::InputTooLong`] before any
+    /// decoding is done, which is a cheap first line of defense against untrusted input that's
+    /// unreasonably large. The default is no limit.
+    ///
+    /// The limit applies to each decode operation on the engine. A
+    /// [`DecoderReader`](crate::read::DecoderReader) decodes its input in chunks, so it is not
+    /// limited in total length by this setting.
+    #[must_use]
+    pub const fn with_max_encoded_len(self, max_len: usize) -> Self {
+        Self {
+            decode_max_encoded_len: max_len,
+            ..self
+        }
+    }
+
+    /// Create a new config based on `self` with an updated `encode_line_wrap` setting.
+    ///
+    /// If `line_len` is nonzero, encoded output is broken into lines of `line_len` bytes separated
+    /// by `\n`, as in MIME (76) or PEM (64). There's no line ending after the last line. If
+    /// `line_len` is 0, which is the default, the output is a single line.
+    ///
+    /// When wrapping is enabled, `\r` and `\n` are skipped when decoding, so both `\n` and
+    /// `\r\n` line endings are accepted.
+    ///
+    /// ```
+    /// use base64::{Engine as _, alphabet, engine::{general_purpose, GeneralPurpose}};
+    ///
+    /// let engine = GeneralPurpose::new(
+    ///     &alphabet::STANDARD,
+    ///     general_purpose::PAD.with_encode_line_wrap(8),
+    /// );
+    /// assert_eq!("aGVsbG8g\nd29ybGQ=", engine.encode(b"hello world"));
+    /// assert_eq!(b"hello world", &engine.decode("aGVsbG8g\r\nd29ybGQ=").unwrap()[..]);
+    /// ```
+    #[must_use]
+    pub const fn with_encode_line_wrap(self, line_len: usize) -> Self {
+        Self {
+            encode_line_wrap: line_len,
+            ..self
+        }
+    }
+
+    /// Create a new config based on `self` with an updated `encode_trailing_newline` setting.
+    ///
+    /// If `trailing_newline` is `true`, a line ending is written after the last line of nonempty
+    /// encoded output: PEM ends with one, whereas MIME bodies usually don't. Along with wrapping at
+    /// 76, this matches the output of GNU coreutils' `base64`, so that encoded files can be
+    /// compared byte for byte. The default is `false`.
+    ///
+    /// As with wrapping, `\r` and `\n` are skipped when decoding if this is enabled.
+    ///
+    /// ```
+    /// use base64::{Engine as _, alphabet, engine::{general_purpose, GeneralPurpose}};
+    ///
+    /// let engine = GeneralPurpose::new(
+    ///     &alphabet::STANDARD,
+    ///     general_purpose::PAD
+    ///         .with_encode_line_wrap(8)
+    ///         .with_encode_trailing_newline(true),
+    /// );
+    /// assert_eq!("aGVsbG8g\nd29ybGQ=\n", engine.encode(b"hello world"));
+    /// assert_eq!("", engine.encode(b""));
+    /// assert_eq!(b"hello world", &engine.decode("aGVsbG8g\nd29ybGQ=\n").unwrap()[..]);
+    /// ```
+    #[must_use]
+    pub const fn with_encode_trailing_newline(self, trailing_newline: bool) -> Self {
+        Self {
+            encode_trailing_newline: trailing_newline,
+            ..self
+        }
+    }
+
+    /// Create a new config based on `self` with an updated `encode_line_ending` setting.
+    ///
+    /// This is the line ending written between lines when
+    /// [wrapping](GeneralPurposeConfig::with_encode_line_wrap), and after the last line if
+    /// [`GeneralPurposeConfig::with_encode_trailing_newline`] is enabled. The default is
+    /// [`LineEnding::LF`], as PEM uses, whereas MIME uses [`LineEnding::CRLF`]. Both are accepted
+    /// when decoding either way.
+    ///
+    /// ```
+    /// use base64::{Engine as _, alphabet, engine::{general_purpose, GeneralPurpose, LineEnding}};
+    ///
+    /// let engine = GeneralPurpose::new(
+    ///     &alphabet::STANDARD,
+    ///     general_purpose::PAD
+    ///         .with_encode_line_wrap(8)
+    ///         .with_encode_line_ending(LineEnding::CRLF),
+    /// );
+    /// assert_eq!("aGVsbG8g\r\nd29ybGQ=", engine.encode(b"hello world"));
+    /// ```
+    #[must_use]
+    pub const fn with_encode_line_ending(self, line_ending: LineEnding) -> Self {
+        Self {
+            encode_line_ending: line_ending,
+            ..self
+        }
+    }
+
+    /// Create a new config based on `self` that skips the given `bytes` when decoding, replacing
+    /// any set before.
+    ///
+    /// This is for formats that put separators in base64, like `.` or `:` between groups of
+    /// symbols. Skipped bytes are counted in
+    /// [`DecodeStats::skipped_ignored`](crate::DecodeStats::skipped_ignored). The line endings
+    /// skipped because of [`GeneralPurposeConfig::with_encode_line_wrap`] are still skipped too.
+    ///
+    /// Bytes that are symbols of the alphabet or the padding byte are skipped rather than
+    /// decoded, so they shouldn't be included.
+    ///
+    /// ```
+    /// use base64::{Engine as _, alphabet, engine::{general_purpose, GeneralPurpose}};
+    ///
+    /// let engine = GeneralPurpose::new(
+    ///     &alphabet::STANDARD,
+    ///     general_purpose::PAD.with_decode_ignore_bytes(b".:"),
+    /// );
+    /// assert_eq!(b"hello world", &engine.decode("aGVs.bG8g:d29y.bGQ=").unwrap()[..]);
+    /// ```
+    #[must_use]
+    pub const fn with_decode_ignore_bytes(self, bytes: &[u8]) -> Self {
+        Self {
+            decode_ignore_bytes: ByteSet::from_bytes(bytes),
+            ..self
+        }
+    }
+
+    /// Create a new config based on `self` with an updated setting for how padding before the last
+    /// symbol of the input is handled when decoding.
+    ///
+    /// The default, [`EmbeddedPaddingMode::Invalid`], reports it as an invalid byte. Like the
+    /// length limit, this applies to each decode operation on the engine; a
+    /// [`DecoderReader`](crate::read::DecoderReader) still reports symbols after padding in an
+    /// earlier chunk as [`DecodeError::InvalidByte`].
+    ///
+    /// ```
+    /// use base64::{Engine as _, alphabet, engine::{general_purpose, EmbeddedPaddingMode, GeneralPurpose}};
+    ///
+    /// let terminate = GeneralPurpose::new(
+    ///     &alphabet::STANDARD,
+    ///     general_purpose::PAD.with_decode_embedded_padding(EmbeddedPaddingMode::Terminate),
+    /// );
+    /// assert_eq!(b"hi", &terminate.decode("aGk=trailer").unwrap()[..]);
+    ///
+    /// let ignore = GeneralPurpose::new(
+    ///     &alphabet::STANDARD,
+    ///     general_purpose::PAD.with_decode_embedded_padding(EmbeddedPaddingMode::Ignore),
+    /// );
+    /// assert_eq!(b"hello", &ignore.decode("aGVs=bG8=").unwrap()[..]);
+    /// ```
+    #[must_use]
+    pub const fn with_decode_embedded_padding(self, mode: EmbeddedPaddingMode) -> Self {
+        Self {
+            decode_embedded_padding: mode,
+            ..self
+        }
+    }
+
+    /// Returns `true` if any bytes are skipped when decoding.
+    fn ignores_any(&self) -> bool {
+        self.writes_line_endings() || !self.decode_ignore_bytes.is_empty()
+    }
+
+    /// Returns `true` if encoded output has any line endings, which are then skipped when decoding.
+    fn writes_line_endings(&self) -> bool {
+        self.encode_line_wrap > 0 || self.encode_trailing_newline
+    }
 }
 
 impl Default for GeneralPurposeConfig {
@@ -333,6 +910,102 @@ impl Config for GeneralPurposeConfig {
     fn encode_padding(&self) -> bool {
         self.encode_padding
     }
+
+    fn padding(&self) -> Padding {
+        if self.encode_padding {
+            Padding::Padded(self.padding_byte)
+        } else {
+            Padding::Unpadded
+        }
+    }
+
+    fn encode_line_wrap(&self) -> usize {
+        self.encode_line_wrap
+    }
+
+    fn encode_trailing_newline(&self) -> bool {
+        self.encode_trailing_newline
+    }
+
+    fn encode_line_ending(&self) -> LineEnding {
+        self.encode_line_ending
+    }
+
+    fn decode_ignores(&self, byte: u8) -> bool {
+        (self.writes_line_endings() && (byte == b'\r' || byte == b'\n'))
+            || self.decode_ignore_bytes.contains(byte)
+    }
+
+    fn decode_ignores_any(&self) -> bool {
+        self.ignores_any()
+    }
+}
+
+/// A set of bytes, stored as a bitmap so that configs stay `Copy` and can be built in `const`.
+///
+/// With the `serde` feature, it's serialized as a sequence of the bytes in it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct ByteSet([u64; 4]);
+
+impl ByteSet {
+    const EMPTY: Self = Self([0; 4]);
+
+    const fn from_bytes(bytes: &[u8]) -> Self {
+        let mut bits = [0_u64; 4];
+        let mut index = 0;
+        while index < bytes.len() {
+            let byte = bytes[index] as usize;
+            bits[byte / 64] |= 1 << (byte % 64);
+            index += 1;
+        }
+
+        Self(bits)
+    }
+
+    fn contains(&self, byte: u8) -> bool {
+        let byte = usize::from(byte);
+        self.0[byte / 64] & (1 << (byte % 64)) != 0
+    }
+
+    fn is_empty(&self) -> bool {
+        *self == Self::EMPTY
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ByteSet {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq((0..=u8::MAX).filter(|&b| self.contains(b)))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ByteSet {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ByteSetVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for ByteSetVisitor {
+            type Value = ByteSet;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.write_str("a sequence of bytes")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<ByteSet, A::Error> {
+                let mut set = ByteSet::EMPTY;
+                while let Some(byte) = seq.next_element::<u8>()? {
+                    let byte = usize::from(byte);
+                    set.0[byte / 64] |= 1 << (byte % 64);
+                }
+                Ok(set)
+            }
+        }
+
+        deserializer.deserialize_seq(ByteSetVisitor)
+    }
 }
 
 /// A [`GeneralPurpose`] engine using the [`alphabet::STANDARD`] base64 alphabet and [`PAD`] config.
@@ -1,17 +1,32 @@
 //! Provides the [Engine] abstraction and out of the box implementations.
-#[cfg(any(feature = "alloc", test))]
-use crate::chunked_encoder;
 use crate::{
-    encode::{encode_with_padding, EncodeSliceError},
-    encoded_len, DecodeError, DecodeSliceError,
+    aligned::AlignedEncoder,
+    chunked_encoder::ChunkedEncoder,
+    encode::{encode_scattered_with_padding, encode_with_padding, line_breaks, EncodeSliceError},
+    encoded_len,
+    ring::RingSlice,
+    sink::EncodeSink,
+    DecodeError, DecodeSliceError, PAD_BYTE,
 };
 #[cfg(any(feature = "alloc", test))]
+use crate::{decode::JsonUnescaped, DecodeReport, DecodeStats, EncodedChunks};
+#[cfg(any(feature = "alloc", test))]
 use alloc::vec::Vec;
 
+#[cfg(feature = "allocator_api")]
+use alloc::alloc::Allocator;
 #[cfg(any(feature = "alloc", test))]
-use alloc::{string::String, vec};
+use alloc::{borrow::Cow, string::String, vec};
+#[cfg(any(feature = "std", test))]
+use {crate::read::DecoderReader, std::io};
 
+#[cfg(any(feature = "alloc", test))]
+pub mod adapters;
+pub mod adaptive;
+#[cfg(any(feature = "std", test))]
+pub mod auto;
 pub mod general_purpose;
+pub mod presets;
 
 #[cfg(test)]
 mod naive;
@@ -94,6 +109,116 @@ pub trait Engine: Send + Sync {
     /// Returns the config for this engine.
     fn config(&self) -> &Self::Config;
 
+    /// Returns the number of bytes the engine encodes at a time in its fastest path, which is also
+    /// what it decodes from `preferred_block_size() / 3 * 4` symbols at a time.
+    ///
+    /// Wrappers that split input into chunks, like [`io::copy_encode`](crate::io::copy_encode),
+    /// use a multiple of this so that each chunk goes through the fast path as much as possible
+    /// rather than ending in a short block handled the slow way.
+    ///
+    /// This must be a multiple of 3, so chunks of that length encode without padding; wrappers
+    /// round any other value down to a multiple of 3. The default is 3, i.e. a single base64 block.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{Engine as _, engine::general_purpose};
+    ///
+    /// let block_size = general_purpose::STANDARD.preferred_block_size();
+    /// assert_eq!(0, block_size % 3);
+    /// ```
+    fn preferred_block_size(&self) -> usize {
+        3
+    }
+
+    /// Returns the length of the output of encoding `input_len` bytes with this engine, including
+    /// padding and line endings as configured.
+    ///
+    /// Returns `None` if the length can't be represented in `usize`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{Engine as _, alphabet, engine::{general_purpose, GeneralPurpose}};
+    ///
+    /// assert_eq!(Some(16), general_purpose::STANDARD.encoded_len(10));
+    ///
+    /// let wrapping = GeneralPurpose::new(
+    ///     &alphabet::STANDARD,
+    ///     general_purpose::PAD.with_encode_line_wrap(8),
+    /// );
+    /// assert_eq!(Some(17), wrapping.encoded_len(10));
+    /// ```
+    fn encoded_len(&self, input_len: usize) -> Option<usize> {
+        let config = self.config();
+        let len = encoded_len(input_len, config.encode_padding())?;
+
+        let trailing_newline = usize::from(len > 0 && config.encode_trailing_newline());
+        (line_breaks(len, config.encode_line_wrap(), 0) + trailing_newline)
+            .checked_mul(config.encode_line_ending().as_bytes().len())
+            .and_then(|endings_len| len.checked_add(endings_len))
+    }
+
+    /// Encode arbitrary octets as base64, appending the output to `sink`.
+    ///
+    /// This is what the other encode methods use, so it works with any [`EncodeSink`]: a `String`,
+    /// `Vec<u8>`, or `&mut [u8]`, which is advanced past the output, or a custom implementation.
+    ///
+    /// Returns the number of bytes written, or the sink's error if the output doesn't fit, in which
+    /// case nothing has been written.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{Engine as _, engine::general_purpose};
+    ///
+    /// let mut buf = b"data:".to_vec();
+    /// general_purpose::STANDARD.encode_into(b"hello", &mut buf).unwrap();
+    /// assert_eq!(b"data:aGVsbG8=", &buf[..]);
+    /// ```
+    fn encode_into<T: AsRef<[u8]>, S: EncodeSink + ?Sized>(
+        &self,
+        input: T,
+        sink: &mut S,
+    ) -> Result<usize, S::Error> {
+        fn inner<E, S>(engine: &E, input_bytes: &[u8], sink: &mut S) -> Result<usize, S::Error>
+        where
+            E: Engine + ?Sized,
+            S: EncodeSink + ?Sized,
+        {
+            let encoded_size = engine
+                .encoded_len(input_bytes.len())
+                .expect("usize overflow when calculating buffer size");
+
+            sink.reserve(encoded_size)?;
+            match sink.output_buffer(encoded_size) {
+                Some(output) => encode_with_padding(input_bytes, output, engine, encoded_size),
+                None => ChunkedEncoder::new(engine).encode(input_bytes, sink)?,
+            }
+
+            Ok(encoded_size)
+        }
+
+        inner(self, input.as_ref(), sink)
+    }
+
+    /// Create an [`AlignedEncoder`] that encodes input pushed to it in pieces of any length and
+    /// writes the output to `sink` only in multiples of 4 bytes, for word-aligned destinations.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{Engine as _, engine::general_purpose};
+    ///
+    /// let mut encoder = general_purpose::STANDARD.encode_aligned(Vec::new());
+    /// encoder.write(b"hel").unwrap();
+    /// encoder.write(b"lo").unwrap();
+    /// assert_eq!(b"aGVsbG8=", &encoder.finish().unwrap()[..]);
+    /// ```
+    fn encode_aligned<S: EncodeSink>(&self, sink: S) -> AlignedEncoder<'_, Self, S> {
+        AlignedEncoder::new(self, sink)
+    }
+
     /// Encode arbitrary octets as base64 using the provided `Engine`.
     /// Returns a `String`.
     ///
@@ -113,21 +238,54 @@ pub trait Engine: Send + Sync {
     #[cfg(any(feature = "alloc", test))]
     #[inline]
     fn encode<T: AsRef<[u8]>>(&self, input: T) -> String {
-        fn inner<E>(engine: &E, input_bytes: &[u8]) -> String
+        let mut buf = Vec::new();
+        let _ = self
+            .encode_into(input, &mut buf)
+            .unwrap_or_else(|e| match e {});
+
+        String::from_utf8(buf).expect("Invalid UTF8")
+    }
+
+    /// Encode arbitrary octets as base64 into a `Vec` allocated with `alloc`.
+    ///
+    /// This is like [`encode`](Engine::encode), but for output that should live in a custom
+    /// allocator, like an arena that is freed at the end of a request. A `Vec` is returned because
+    /// `String` doesn't support custom allocators; it is always ASCII.
+    ///
+    /// Requires the `allocator_api` feature, which needs a nightly compiler.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// #![feature(allocator_api)]
+    ///
+    /// use base64::{Engine as _, engine::general_purpose};
+    /// use std::alloc::Global;
+    ///
+    /// let encoded = general_purpose::STANDARD.encode_in(b"hello", Global);
+    /// assert_eq!(b"aGVsbG8=", &encoded[..]);
+    /// ```
+    #[cfg(feature = "allocator_api")]
+    fn encode_in<T: AsRef<[u8]>, A: Allocator>(&self, input: T, alloc: A) -> Vec<u8, A> {
+        fn inner<E, A>(engine: &E, input_bytes: &[u8], alloc: A) -> Vec<u8, A>
         where
             E: Engine + ?Sized,
+            A: Allocator,
         {
-            let encoded_size = encoded_len(input_bytes.len(), engine.config().encode_padding())
-                .expect("integer overflow when calculating buffer size");
-
-            let mut buf = vec![0; encoded_size];
+            let encoded_size = engine
+                .encoded_len(input_bytes.len())
+                .expect("usize overflow when calculating buffer size");
+            let mut buffer = Vec::with_capacity_in(encoded_size, alloc);
+            buffer.resize(encoded_size, 0);
 
-            encode_with_padding(input_bytes, &mut buf[..], engine, encoded_size);
+            let _ = engine
+                .encode_slice(input_bytes, &mut buffer)
+                .expect("Vec is sized exactly");
 
-            String::from_utf8(buf).expect("Invalid UTF8")
+            buffer
         }
 
-        inner(self, input.as_ref())
+        inner(self, input.as_ref(), alloc)
     }
 
     /// Encode arbitrary octets as base64 into a supplied `String`.
@@ -153,18 +311,32 @@ pub trait Engine: Send + Sync {
     #[cfg(any(feature = "alloc", test))]
     #[inline]
     fn encode_string<T: AsRef<[u8]>>(&self, input: T, output_buf: &mut String) {
-        fn inner<E>(engine: &E, input_bytes: &[u8], output_buf: &mut String)
-        where
-            E: Engine + ?Sized,
-        {
-            let mut sink = chunked_encoder::StringSink::new(output_buf);
-
-            chunked_encoder::ChunkedEncoder::new(engine)
-                .encode(input_bytes, &mut sink)
-                .expect("Writing to a String shouldn't fail");
-        }
+        let _ = self
+            .encode_into(input, output_buf)
+            .unwrap_or_else(|e| match e {});
+    }
 
-        inner(self, input.as_ref(), output_buf);
+    /// Encode arbitrary octets as base64, to be consumed in chunks of at most `chunk_len` bytes.
+    ///
+    /// This is useful for protocols that limit the size of each field, like the 255 byte strings
+    /// in DNS TXT records.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{Engine as _, engine::general_purpose};
+    ///
+    /// let chunks = general_purpose::STANDARD.encode_chunks(b"hello world~", 6);
+    /// let collected: Vec<&str> = chunks.iter().collect();
+    /// assert_eq!(vec!["aGVsbG", "8gd29y", "bGR+"], collected);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_len` is 0.
+    #[cfg(any(feature = "alloc", test))]
+    fn encode_chunks<T: AsRef<[u8]>>(&self, input: T, chunk_len: usize) -> EncodedChunks {
+        EncodedChunks::new(self.encode(input), chunk_len)
     }
 
     /// Encode arbitrary octets as base64 into a supplied slice.
@@ -173,6 +345,10 @@ pub trait Engine: Send + Sync {
     /// This is useful if you wish to avoid allocation entirely (e.g. encoding into a stack-resident
     /// or statically-allocated buffer).
     ///
+    /// Unlike the other encode methods, this doesn't panic if the length of the output would
+    /// overflow `usize`, which is possible with line wrapping on 32-bit targets: it returns
+    /// [`EncodeSliceError::OutputSliceTooSmall`] instead.
+    ///
     /// # Example
     ///
     #[cfg_attr(feature = "alloc", doc = "```")]
@@ -196,33 +372,123 @@ pub trait Engine: Send + Sync {
         input: T,
         output_buf: &mut [u8],
     ) -> Result<usize, EncodeSliceError> {
-        fn inner<E>(
-            engine: &E,
-            input_bytes: &[u8],
-            output_buf: &mut [u8],
-        ) -> Result<usize, EncodeSliceError>
-        where
-            E: Engine + ?Sized,
-        {
-            let encoded_size = encoded_len(input_bytes.len(), engine.config().encode_padding())
-                .expect("usize overflow when calculating buffer size");
+        let input = input.as_ref();
+        if self.encoded_len(input.len()).is_none() {
+            // no slice is long enough for output whose length overflows `usize`
+            return Err(EncodeSliceError::OutputSliceTooSmall);
+        }
 
-            if output_buf.len() < encoded_size {
-                return Err(EncodeSliceError::OutputSliceTooSmall);
-            }
+        let mut output_buf = output_buf;
+        self.encode_into(input, &mut output_buf)
+    }
 
-            let b64_output = &mut output_buf[0..encoded_size];
+    /// Encode a fixed-size array into a fixed-size array of base64, without allocating.
+    ///
+    /// `OUT` must be the padded or unpadded encoded length of `IN` bytes, which
+    /// [`encoded_array_len`](crate::encoded_array_len) calculates. Any other length is a compile
+    /// error.
+    ///
+    /// Requires the `const-generics` feature, which needs a newer compiler than the crate's MSRV.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the engine's output has a different length than `OUT`: if `OUT` is the padded
+    /// length but the engine doesn't pad or vice versa, or if the engine wraps lines.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{encoded_array_len, engine::general_purpose, Engine as _};
+    ///
+    /// let encoded: [u8; encoded_array_len(5, true)] =
+    ///     general_purpose::STANDARD.encode_array(b"hello");
+    /// assert_eq!(b"aGVsbG8=", &encoded);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use base64::{engine::general_purpose, Engine as _};
+    ///
+    /// // 5 bytes encode to 7 or 8 bytes of base64, never 6
+    /// let encoded: [u8; 6] = general_purpose::STANDARD.encode_array(b"hello");
+    /// ```
+    #[cfg(feature = "const-generics")]
+    fn encode_array<const IN: usize, const OUT: usize>(&self, input: &[u8; IN]) -> [u8; OUT] {
+        let out_len = EncodedArrayLen::<IN, OUT>::OUT_LEN;
 
-            encode_with_padding(input_bytes, b64_output, engine, encoded_size);
+        let mut output = [0; OUT];
+        match self.encode_slice(input, &mut output) {
+            Ok(len) if len == out_len => output,
+            _ => panic!(
+                "engine output for {} bytes doesn't have the array length {}",
+                IN, OUT
+            ),
+        }
+    }
 
-            Ok(encoded_size)
+    /// Encode the concatenation of `parts` as base64, without copying them together first.
+    ///
+    /// This is useful for data that's already split across several buffers, like a header and a
+    /// body. Returns a `String`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{Engine as _, engine::general_purpose};
+    ///
+    /// let encoded = general_purpose::STANDARD.encode_scattered(&[b"hello", b" ", b"world"]);
+    /// assert_eq!(general_purpose::STANDARD.encode(b"hello world"), encoded);
+    /// ```
+    #[cfg(any(feature = "alloc", test))]
+    fn encode_scattered(&self, parts: &[&[u8]]) -> String {
+        let encoded_size = scattered_len(parts)
+            .and_then(|len| self.encoded_len(len))
+            .expect("usize overflow when calculating buffer size");
+        let mut buf = vec![0; encoded_size];
+
+        encode_scattered_with_padding(parts, &mut buf, self, encoded_size);
+
+        String::from_utf8(buf).expect("Invalid UTF8")
+    }
+
+    /// Encode the concatenation of `parts` as base64 into a supplied slice, without copying them
+    /// together first.
+    ///
+    /// Returns the number of bytes written, or an error if `output_buf` is too small.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{Engine as _, engine::general_purpose};
+    ///
+    /// let mut buf = [0; 16];
+    /// let len = general_purpose::STANDARD
+    ///     .encode_scattered_slice(&[b"hello", b" ", b"world"], &mut buf)
+    ///     .unwrap();
+    /// assert_eq!(b"aGVsbG8gd29ybGQ=", &buf[..len]);
+    /// ```
+    fn encode_scattered_slice(
+        &self,
+        parts: &[&[u8]],
+        output_buf: &mut [u8],
+    ) -> Result<usize, EncodeSliceError> {
+        let encoded_size = scattered_len(parts)
+            .and_then(|len| self.encoded_len(len))
+            .ok_or(EncodeSliceError::OutputSliceTooSmall)?;
+        if output_buf.len() < encoded_size {
+            return Err(EncodeSliceError::OutputSliceTooSmall);
         }
 
-        inner(self, input.as_ref(), output_buf)
+        encode_scattered_with_padding(parts, &mut output_buf[..encoded_size], self, encoded_size);
+
+        Ok(encoded_size)
     }
 
     /// Decode the input into a new `Vec`.
     ///
+    /// A `&str` input is decoded as its bytes, without a separate UTF-8 or ASCII check: bytes that
+    /// aren't in the alphabet are found as part of decoding, so input that is already known to be
+    /// valid ASCII doesn't need a faster path.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -253,7 +519,7 @@ pub trait Engine: Send + Sync {
                 .internal_decode(input_bytes, &mut buffer, estimate)
                 .map_err(|e| match e {
                     DecodeSliceError::DecodeError(e) => e,
-                    DecodeSliceError::OutputSliceTooSmall => {
+                    DecodeSliceError::OutputSliceTooSmall { .. } => {
                         unreachable!("Vec is sized conservatively")
                     }
                 })?
@@ -267,6 +533,347 @@ pub trait Engine: Send + Sync {
         inner(self, input.as_ref())
     }
 
+    /// Decode the input into a `Vec` allocated with `alloc`.
+    ///
+    /// This is like [`decode`](Engine::decode), but for output that should live in a custom
+    /// allocator, like an arena that is freed at the end of a request.
+    ///
+    /// Requires the `allocator_api` feature, which needs a nightly compiler.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// #![feature(allocator_api)]
+    ///
+    /// use base64::{Engine as _, engine::general_purpose};
+    /// use std::alloc::Global;
+    ///
+    /// let decoded = general_purpose::STANDARD.decode_in("aGVsbG8=", Global).unwrap();
+    /// assert_eq!(b"hello", &decoded[..]);
+    /// ```
+    #[cfg(feature = "allocator_api")]
+    fn decode_in<T: AsRef<[u8]>, A: Allocator>(
+        &self,
+        input: T,
+        alloc: A,
+    ) -> Result<Vec<u8, A>, DecodeError> {
+        fn inner<E, A>(engine: &E, input_bytes: &[u8], alloc: A) -> Result<Vec<u8, A>, DecodeError>
+        where
+            E: Engine + ?Sized,
+            A: Allocator,
+        {
+            let estimate = engine.internal_decoded_len_estimate(input_bytes.len());
+            let mut buffer = Vec::with_capacity_in(estimate.decoded_len_estimate(), alloc);
+            buffer.resize(estimate.decoded_len_estimate(), 0);
+
+            let bytes_written = engine
+                .internal_decode(input_bytes, &mut buffer, estimate)
+                .map_err(|e| match e {
+                    DecodeSliceError::DecodeError(e) => e,
+                    DecodeSliceError::OutputSliceTooSmall { .. } => {
+                        unreachable!("Vec is sized conservatively")
+                    }
+                })?
+                .decoded_len;
+
+            buffer.truncate(bytes_written);
+
+            Ok(buffer)
+        }
+
+        inner(self, input.as_ref(), alloc)
+    }
+
+    /// Decode the input into an array, which must be exactly filled by the decoded output.
+    ///
+    /// This is convenient for fixed-size values like keys and IDs. The array length is usually
+    /// inferred.
+    ///
+    /// Requires the `const-generics` feature, which needs a newer compiler than the crate's MSRV.
+    ///
+    /// Returns [`DecodeError::UnexpectedDecodedLength`] if the input decodes to a different length.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{Engine as _, engine::general_purpose, DecodeError};
+    ///
+    /// let id: [u8; 4] = general_purpose::STANDARD.decode_array("AAECAw==").unwrap();
+    /// assert_eq!([0, 1, 2, 3], id);
+    ///
+    /// assert_eq!(
+    ///     Err(DecodeError::UnexpectedDecodedLength { expected: 4, actual: Some(3) }),
+    ///     general_purpose::STANDARD.decode_array::<_, 4>("AAEC"),
+    /// );
+    /// ```
+    #[cfg(feature = "const-generics")]
+    fn decode_array<T: AsRef<[u8]>, const N: usize>(
+        &self,
+        input: T,
+    ) -> Result<[u8; N], DecodeError> {
+        fn inner<E>(engine: &E, input_bytes: &[u8], output: &mut [u8]) -> Result<(), DecodeError>
+        where
+            E: Engine + ?Sized,
+        {
+            let expected = output.len();
+            let actual = match engine.decode_slice(input_bytes, output) {
+                Ok(len) if len == expected => return Ok(()),
+                Ok(len) => Some(len),
+                Err(DecodeSliceError::DecodeError(e)) => return Err(e),
+                Err(DecodeSliceError::OutputSliceTooSmall { .. }) => None,
+            };
+
+            Err(DecodeError::UnexpectedDecodedLength { expected, actual })
+        }
+
+        let mut output = [0; N];
+        inner(self, input.as_ref(), &mut output)?;
+
+        Ok(output)
+    }
+
+    /// Decode the input, borrowing from it rather than allocating if possible.
+    ///
+    /// No engine in this crate can do that, so they always return [`Cow::Owned`] with the same
+    /// output as [`Engine::decode`]. The method exists for engines that sometimes can, e.g. ones
+    /// that pass through input that's already binary, which can override it to return
+    /// [`Cow::Borrowed`] without callers having to change.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{Engine as _, engine::general_purpose};
+    ///
+    /// let decoded = general_purpose::STANDARD.decode_cow("aGVsbG8=").unwrap();
+    /// assert_eq!(b"hello", &decoded[..]);
+    /// ```
+    #[cfg(any(feature = "alloc", test))]
+    fn decode_cow<'i, T: AsRef<[u8]> + ?Sized>(
+        &self,
+        input: &'i T,
+    ) -> Result<Cow<'i, [u8]>, DecodeError> {
+        self.decode(input.as_ref()).map(Cow::Owned)
+    }
+
+    /// Decode the `input` into a new `Vec`, reporting every invalid byte if decoding fails.
+    ///
+    /// This is slower than [`Engine::decode`] on invalid input since the whole input is scanned,
+    /// but lets interactive tools highlight all problems at once rather than one per attempt.
+    /// At most [`DecodeReport::MAX_INVALID_BYTES`] invalid bytes are recorded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{Engine as _, engine::general_purpose};
+    ///
+    /// let report = general_purpose::STANDARD.decode_report_all("aG!s*G8=").unwrap_err();
+    /// assert_eq!(&[(2, b'!'), (4, b'*')], report.invalid_bytes());
+    /// assert_eq!(&base64::DecodeError::InvalidByte(2, b'!'), report.error());
+    /// ```
+    #[cfg(any(feature = "alloc", test))]
+    fn decode_report_all<T: AsRef<[u8]>>(&self, input: T) -> Result<Vec<u8>, DecodeReport> {
+        fn inner<E>(engine: &E, input_bytes: &[u8]) -> Result<Vec<u8>, DecodeReport>
+        where
+            E: Engine + ?Sized,
+        {
+            engine
+                .decode(input_bytes)
+                .map_err(|e| DecodeReport::new(engine, input_bytes, e))
+        }
+
+        inner(self, input.as_ref())
+    }
+
+    /// Decode the `input` into a new `Vec`, skipping ASCII whitespace and any bytes the config
+    /// ignores, and report how many of each were skipped, how much padding, and how many lines
+    /// there were.
+    ///
+    /// This is useful for linting input that is accepted but not tidy, like base64 that's been
+    /// wrapped or indented. Offsets in errors refer to `input`, including its whitespace.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{Engine as _, engine::general_purpose};
+    ///
+    /// let (bytes, stats) = general_purpose::STANDARD
+    ///     .decode_with_stats("aGVs\r\nbG8g\r\nd29y bGQ=")
+    ///     .unwrap();
+    /// assert_eq!(b"hello world", &bytes[..]);
+    /// assert_eq!(5, stats.skipped_whitespace);
+    /// assert_eq!(1, stats.padding_chars);
+    /// assert_eq!(3, stats.lines);
+    /// ```
+    #[cfg(any(feature = "alloc", test))]
+    fn decode_with_stats<T: AsRef<[u8]>>(
+        &self,
+        input: T,
+    ) -> Result<(Vec<u8>, DecodeStats), DecodeError> {
+        fn inner<E>(engine: &E, input_bytes: &[u8]) -> Result<(Vec<u8>, DecodeStats), DecodeError>
+        where
+            E: Engine + ?Sized,
+        {
+            let config = engine.config();
+            let ignores = |b| config.decode_ignores(b);
+            let (stripped, stats) =
+                DecodeStats::strip_skipped(input_bytes, config.padding().decode_byte(), ignores);
+
+            engine
+                .decode(stripped)
+                .map(|decoded| (decoded, stats))
+                .map_err(|e| DecodeStats::map_error(input_bytes, ignores, e))
+        }
+
+        inner(self, input.as_ref())
+    }
+
+    /// Decode the input into a new `Vec`, along with [`DecodeMetadata`] about the padding, trailing
+    /// bits, and ignored bytes it had.
+    ///
+    /// Decoding is exactly like [`Engine::decode`], errors included, so this is useful to enforce a
+    /// policy stricter than the engine's in one call, like rejecting padding or ignored bytes that
+    /// the engine accepts without re-encoding to compare.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{Engine as _, engine::general_purpose};
+    ///
+    /// let (bytes, meta) = general_purpose::STANDARD_PAD_INDIFFERENT
+    ///     .decode_with_meta("aGVsbG8gd29ybGQ=")
+    ///     .unwrap();
+    /// assert_eq!(b"hello world", &bytes[..]);
+    /// assert_eq!(1, meta.padding_len());
+    /// assert_eq!(0, meta.skipped_ignored());
+    /// ```
+    #[cfg(any(feature = "alloc", test))]
+    fn decode_with_meta<T: AsRef<[u8]>>(
+        &self,
+        input: T,
+    ) -> Result<(Vec<u8>, DecodeMetadata), DecodeError> {
+        fn inner<E>(
+            engine: &E,
+            input_bytes: &[u8],
+        ) -> Result<(Vec<u8>, DecodeMetadata), DecodeError>
+        where
+            E: Engine + ?Sized,
+        {
+            let estimate = engine.internal_decoded_len_estimate(input_bytes.len());
+            let mut buffer = vec![0; estimate.decoded_len_estimate()];
+
+            let metadata = engine
+                .internal_decode(input_bytes, &mut buffer, estimate)
+                .map_err(|e| match e {
+                    DecodeSliceError::DecodeError(e) => e,
+                    DecodeSliceError::OutputSliceTooSmall { .. } => {
+                        unreachable!("Vec is sized conservatively")
+                    }
+                })?
+                .count_ignored(input_bytes, engine.config());
+
+            buffer.truncate(metadata.decoded_len);
+
+            Ok((buffer, metadata))
+        }
+
+        inner(self, input.as_ref())
+    }
+
+    /// Decode only the first `n` bytes of the input into a new `Vec`, along with the number of
+    /// bytes of input that were consumed to decode them.
+    ///
+    /// Only the quads needed for those bytes are decoded, so this is cheap even for huge input,
+    /// e.g. to check the magic number of a file before decoding all of it. Fewer than `n` bytes are
+    /// returned if the input decodes to fewer. Since the rest of the input is never looked at,
+    /// errors after the consumed input aren't reported.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{Engine as _, engine::general_purpose};
+    ///
+    /// let (prefix, consumed) = general_purpose::STANDARD
+    ///     .decode_prefix("aGVsbG8gd29ybGQ=", 4)
+    ///     .unwrap();
+    /// assert_eq!(b"hell", &prefix[..]);
+    /// assert_eq!(8, consumed);
+    /// ```
+    #[cfg(any(feature = "alloc", test))]
+    fn decode_prefix<T: AsRef<[u8]>>(
+        &self,
+        input: T,
+        n: usize,
+    ) -> Result<(Vec<u8>, usize), DecodeError> {
+        fn inner<E>(
+            engine: &E,
+            input_bytes: &[u8],
+            n: usize,
+        ) -> Result<(Vec<u8>, usize), DecodeError>
+        where
+            E: Engine + ?Sized,
+        {
+            let quads = n / 3 + usize::from(n % 3 != 0);
+            let mut symbols_needed = quads.saturating_mul(4);
+            let config = engine.config();
+
+            let mut consumed = input_bytes.len();
+            for (offset, &b) in input_bytes.iter().enumerate() {
+                if symbols_needed == 0 {
+                    consumed = offset;
+                    break;
+                }
+                if !config.decode_ignores(b) {
+                    symbols_needed -= 1;
+                }
+            }
+
+            // anything but all of the input is a whole number of quads, so it needs no padding
+            let mut decoded = engine.decode(&input_bytes[..consumed])?;
+            decoded.truncate(n);
+            Ok((decoded, consumed))
+        }
+
+        inner(self, input.as_ref(), n)
+    }
+
+    /// Decode the contents of a JSON string, as it appears between the quotes in raw JSON, into a
+    /// new `Vec`.
+    ///
+    /// JSON escapes like `\/` or `\u003d` are replaced by the bytes they stand for first, so base64
+    /// fields can be pulled out of logs or other raw JSON without a JSON parser. Escaped whitespace
+    /// like the `\n` in line wrapped base64 is skipped. Offsets in errors refer to `input`; for a
+    /// byte that was escaped, the offset of the escape's `\`.
+    ///
+    /// Escapes that are malformed, or that stand for characters outside ASCII and so can't be
+    /// base64, are reported as [`DecodeError::InvalidByte`] with the `\` that starts them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{Engine as _, engine::general_purpose};
+    ///
+    /// // the value of `"greeting"` in `{"greeting": "aGVsbG8g\nd29ybGQ="}`
+    /// let field = r"aGVsbG8g\nd29ybGQ=";
+    /// assert_eq!(
+    ///     b"hello world",
+    ///     &general_purpose::STANDARD.decode_json_escaped(field).unwrap()[..]
+    /// );
+    /// ```
+    #[cfg(any(feature = "alloc", test))]
+    fn decode_json_escaped<T: AsRef<[u8]>>(&self, input: T) -> Result<Vec<u8>, DecodeError> {
+        fn inner<E>(engine: &E, input_bytes: &[u8]) -> Result<Vec<u8>, DecodeError>
+        where
+            E: Engine + ?Sized,
+        {
+            let unescaped = JsonUnescaped::new(input_bytes)?;
+            engine
+                .decode(&unescaped.bytes)
+                .map_err(|e| unescaped.map_error(e))
+        }
+
+        inner(self, input.as_ref())
+    }
+
     /// Decode the `input` into the supplied `buffer`.
     ///
     /// Writes into the supplied `Vec`, which may allocate if its internal buffer isn't big enough.
@@ -324,7 +931,7 @@ pub trait Engine: Send + Sync {
                 .internal_decode(input_bytes, buffer_slice, estimate)
                 .map_err(|e| match e {
                     DecodeSliceError::DecodeError(e) => e,
-                    DecodeSliceError::OutputSliceTooSmall => {
+                    DecodeSliceError::OutputSliceTooSmall { .. } => {
                         unreachable!("Vec is sized conservatively")
                     }
                 })?
@@ -338,6 +945,105 @@ pub trait Engine: Send + Sync {
         inner(self, input.as_ref(), buffer)
     }
 
+    /// Decode the `input` into `output` in chunks, without holding all of the decoded bytes in
+    /// memory.
+    ///
+    /// Returns the number of bytes written. As with [`DecoderReader`], a decode error is reported
+    /// as an [`io::Error`] of kind [`io::ErrorKind::InvalidData`] wrapping the [`DecodeError`], and
+    /// the bytes decoded before it may already have been written.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{Engine as _, engine::general_purpose};
+    ///
+    /// let mut decoded = Vec::new();
+    /// let len = general_purpose::STANDARD
+    ///     .decode_write("aGVsbG8gd29ybGQ=", &mut decoded)
+    ///     .unwrap();
+    /// assert_eq!(11, len);
+    /// assert_eq!(b"hello world", &decoded[..]);
+    /// ```
+    #[cfg(any(feature = "std", test))]
+    fn decode_write<T: AsRef<[u8]>, W: io::Write + ?Sized>(
+        &self,
+        input: T,
+        output: &mut W,
+    ) -> io::Result<usize>
+    where
+        Self: Sized,
+    {
+        fn inner<E, W>(engine: &E, input_bytes: &[u8], output: &mut W) -> io::Result<usize>
+        where
+            E: Engine,
+            W: io::Write + ?Sized,
+        {
+            let mut decoder = DecoderReader::new(input_bytes, engine);
+            // decoded output is shorter than the input slice, so it fits in usize
+            io::copy(&mut decoder, output).map(|len| len as usize)
+        }
+
+        inner(self, input.as_ref(), output)
+    }
+
+    /// Decode the `input` in chunks, passing each chunk of decoded bytes to `sink`.
+    ///
+    /// Returns the total number of decoded bytes. This is useful for feeding decoded bytes to a
+    /// hasher, e.g. with `|chunk| hasher.update(chunk)` for a [`digest`][] hasher, so that
+    /// verifying a signature over a huge base64 payload doesn't require decoding it all into memory
+    /// first. Use [`Engine::decode_write`] if the destination is an [`io::Write`].
+    ///
+    /// If decoding fails, `sink` may already have been passed the bytes decoded before the error.
+    ///
+    /// [`digest`]: https://crates.io/crates/digest
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{Engine as _, engine::general_purpose};
+    /// use std::{collections::hash_map::DefaultHasher, hash::Hasher};
+    ///
+    /// let mut hasher = DefaultHasher::new();
+    /// let len = general_purpose::STANDARD
+    ///     .decode_chunked("aGVsbG8gd29ybGQ=", |chunk| hasher.write(chunk))
+    ///     .unwrap();
+    /// assert_eq!(11, len);
+    ///
+    /// let mut expected = DefaultHasher::new();
+    /// expected.write(b"hello world");
+    /// assert_eq!(expected.finish(), hasher.finish());
+    /// ```
+    #[cfg(any(feature = "std", test))]
+    fn decode_chunked<T: AsRef<[u8]>, F: FnMut(&[u8])>(
+        &self,
+        input: T,
+        sink: F,
+    ) -> Result<usize, DecodeError>
+    where
+        Self: Sized,
+    {
+        /// Passes everything written to a closure
+        struct FnWriter<F>(F);
+
+        impl<F: FnMut(&[u8])> io::Write for FnWriter<F> {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                (self.0)(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        self.decode_write(input, &mut FnWriter(sink)).map_err(|e| {
+            // reading from a slice and writing to FnWriter can't fail, so it's a DecodeError
+            *e.into_inner()
+                .and_then(|e| e.downcast::<DecodeError>().ok())
+                .expect("Only decode errors are possible")
+        })
+    }
+
     /// Decode the input into the provided output slice.
     ///
     /// Returns the number of bytes written to the slice, or an error if `output` is smaller than
@@ -375,6 +1081,51 @@ pub trait Engine: Send + Sync {
         inner(self, input.as_ref(), output)
     }
 
+    /// Decode the input into the provided output slice, like [`Engine::decode_slice`], along with
+    /// [`DecodeMetadata`] about the padding, trailing bits, and ignored bytes of the input.
+    ///
+    /// This is useful to check how input was encoded without parsing it again, e.g. to reject
+    /// non-canonical input that the engine is configured to accept.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{Engine as _, engine::general_purpose};
+    ///
+    /// let mut buffer = [0; 16];
+    /// let meta = general_purpose::STANDARD
+    ///     .decode_slice_with_meta("aGVsbG8=", &mut buffer)
+    ///     .unwrap();
+    /// assert_eq!(b"hello", &buffer[..meta.decoded_len()]);
+    /// assert_eq!(Some(7), meta.padding_offset());
+    /// assert_eq!(1, meta.padding_len());
+    /// assert_eq!(0, meta.trailing_bits());
+    /// ```
+    fn decode_slice_with_meta<T: AsRef<[u8]>>(
+        &self,
+        input: T,
+        output: &mut [u8],
+    ) -> Result<DecodeMetadata, DecodeSliceError> {
+        fn inner<E>(
+            engine: &E,
+            input_bytes: &[u8],
+            output: &mut [u8],
+        ) -> Result<DecodeMetadata, DecodeSliceError>
+        where
+            E: Engine + ?Sized,
+        {
+            engine
+                .internal_decode(
+                    input_bytes,
+                    output,
+                    engine.internal_decoded_len_estimate(input_bytes.len()),
+                )
+                .map(|metadata| metadata.count_ignored(input_bytes, engine.config()))
+        }
+
+        inner(self, input.as_ref(), output)
+    }
+
     /// Decode the input into the provided output slice.
     ///
     /// Returns the number of bytes written to the slice.
@@ -408,7 +1159,7 @@ pub trait Engine: Send + Sync {
                 .map(|dm| dm.decoded_len)
                 .map_err(|e| match e {
                     DecodeSliceError::DecodeError(e) => e,
-                    DecodeSliceError::OutputSliceTooSmall => {
+                    DecodeSliceError::OutputSliceTooSmall { .. } => {
                         panic!("Output slice is too small")
                     }
                 })
@@ -416,6 +1167,86 @@ pub trait Engine: Send + Sync {
 
         inner(self, input.as_ref(), output)
     }
+
+    /// Decode the input into a ring buffer, wrapping around to the start of the buffer at the end.
+    ///
+    /// Returns the number of bytes written, after which the ring's [`head`](RingSlice::head) is
+    /// just past them. This is for fixed-memory pipelines where the output region is circular,
+    /// like a DMA ring. See the [`ring`](crate::ring) module.
+    ///
+    /// When the output wraps around, the input is decoded in parts, split where the output does.
+    /// Each part is checked as for [`Engine::decode_slice`], so a limit set with
+    /// [`with_max_encoded_len`](crate::engine::GeneralPurposeConfig::with_max_encoded_len) applies
+    /// to each part rather than the whole input, and if the input has several errors, the one
+    /// reported may be from an earlier part than with `decode_slice`. Offsets in errors are in the
+    /// whole input.
+    ///
+    /// # Errors
+    ///
+    /// [`DecodeSliceError::OutputSliceTooSmall`] if the decoded output may not fit in the ring's
+    /// [`available`](RingSlice::available) space, or any [`DecodeError`] in the input. The ring's
+    /// position is unchanged, though some of its available space may have been written to.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{Engine as _, engine::general_purpose, ring::RingSlice};
+    ///
+    /// let mut buf = [0; 8];
+    /// // resume at offset 6, with 2 bytes at the end and 4 at the start free
+    /// let mut ring = RingSlice::with_head(&mut buf, 6, 6);
+    /// assert_eq!(5, general_purpose::STANDARD.decode_into_ring("aGVsbG8=", &mut ring).unwrap());
+    /// assert_eq!(3, ring.head());
+    /// assert_eq!(1, ring.available());
+    /// assert_eq!(b"llo\0\0\0he", &buf);
+    /// ```
+    fn decode_into_ring<T: AsRef<[u8]>>(
+        &self,
+        input: T,
+        ring: &mut RingSlice<'_>,
+    ) -> Result<usize, DecodeSliceError> {
+        crate::ring::decode_into_ring(self, input.as_ref(), ring)
+    }
+
+    /// Returns the offset of the first byte of `input` that is neither a symbol of the alphabet,
+    /// the padding byte, nor a byte the config ignores, or `None` if there is no such byte.
+    ///
+    /// This is a quick check for bytes that can't be base64 at all, to sanitize input or find
+    /// where base64 embedded in other text ends. It doesn't check where padding is or the length
+    /// of the input, so input it accepts can still fail to decode.
+    ///
+    /// The default implementation classifies each distinct byte value by decoding it. Engines with
+    /// a faster way override it, like [`GeneralPurpose`], which looks each byte up in its decode
+    /// table but only branches once per chunk of bytes. Neither is vectorized: it's still a scalar
+    /// scan, a byte at a time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use base64::{Engine as _, engine::general_purpose};
+    ///
+    /// assert_eq!(None, general_purpose::STANDARD.find_invalid("aGVsbG8="));
+    /// assert_eq!(Some(2), general_purpose::STANDARD.find_invalid("aG-sbG8="));
+    /// assert_eq!(Some(8), general_purpose::STANDARD.find_invalid("aGVsbG8=\r\n"));
+    /// ```
+    fn find_invalid<T: AsRef<[u8]>>(&self, input: T) -> Option<usize> {
+        fn inner<E>(engine: &E, input_bytes: &[u8]) -> Option<usize>
+        where
+            E: Engine + ?Sized,
+        {
+            let padding_byte = engine.config().padding().decode_byte();
+            // whether each byte value is valid, filled in lazily
+            let mut is_valid = [None; 256];
+
+            input_bytes.iter().position(|&byte| {
+                let valid = *is_valid[usize::from(byte)]
+                    .get_or_insert_with(|| byte == padding_byte || decodes_as_symbol(engine, byte));
+                !valid
+            })
+        }
+
+        inner(self, input.as_ref())
+    }
 }
 
 /// The minimal level of configuration that engines must support.
@@ -429,6 +1260,92 @@ pub trait Config {
     // leaking an implementation detail to the user, and it's hopefully more convenient
     // to have to only pass one thing (the engine) to any part of the API.
     fn encode_padding(&self) -> bool;
+
+    /// Returns the padding added after the encoded output, and recognized when decoding.
+    ///
+    /// The default is `=` padding if [`Config::encode_padding`] is `true`.
+    fn padding(&self) -> Padding {
+        if self.encode_padding() {
+            Padding::Padded(PAD_BYTE)
+        } else {
+            Padding::Unpadded
+        }
+    }
+
+    /// Returns the length of the lines encoded output is broken into, or 0 if it isn't broken into
+    /// lines.
+    ///
+    /// Like padding, line endings are written outside the engine's `encode()`.
+    fn encode_line_wrap(&self) -> usize {
+        0
+    }
+
+    /// Returns `true` if a line ending is written after the last line of nonempty encoded output.
+    ///
+    /// Like other line endings, it's written outside the engine's `encode()`.
+    fn encode_trailing_newline(&self) -> bool {
+        false
+    }
+
+    /// Returns the line ending written between lines, and after the last one if
+    /// [`Config::encode_trailing_newline`] is `true`.
+    fn encode_line_ending(&self) -> LineEnding {
+        LineEnding::LF
+    }
+
+    /// Returns `true` if `byte` is skipped when decoding rather than treated as invalid, like the
+    /// line endings in wrapped input.
+    ///
+    /// Engines skip these bytes themselves; this lets wrappers like
+    /// [`DecoderReader`](crate::read::DecoderReader) know which bytes aren't base64 symbols.
+    fn decode_ignores(&self, _byte: u8) -> bool {
+        false
+    }
+
+    /// Returns `true` if [`Config::decode_ignores`] may be `true` for some byte.
+    ///
+    /// Wrappers like [`DecoderReader`](crate::read::DecoderReader) check this to skip looking for
+    /// ignored bytes when there can't be any. The default is `true`, which is always correct;
+    /// override it to return `false` when nothing is ignored.
+    fn decode_ignores_any(&self) -> bool {
+        true
+    }
+}
+
+/// Checks at compile time that `OUT` is an encoded length of `IN` bytes, when `OUT_LEN` is used.
+#[cfg(feature = "const-generics")]
+struct EncodedArrayLen<const IN: usize, const OUT: usize>;
+
+#[cfg(feature = "const-generics")]
+impl<const IN: usize, const OUT: usize> EncodedArrayLen<IN, OUT> {
+    const OUT_LEN: usize = {
+        assert!(
+            OUT == crate::encoded_array_len(IN, true) || OUT == crate::encoded_array_len(IN, false),
+            "output array length is not an encoded length of the input array length"
+        );
+        OUT
+    };
+}
+
+/// Returns the total length of `parts`, or `None` if it overflows `usize`.
+pub(crate) fn scattered_len(parts: &[&[u8]]) -> Option<usize> {
+    parts
+        .iter()
+        .try_fold(0_usize, |len, part| len.checked_add(part.len()))
+}
+
+/// Returns `true` if `engine` decodes `byte` as a symbol, i.e. a quad of nothing but `byte` is
+/// valid input.
+pub(crate) fn decodes_as_symbol<E: Engine + ?Sized>(engine: &E, byte: u8) -> bool {
+    let quad = [byte; 4];
+    let mut output = [0_u8; 3];
+    engine
+        .internal_decode(
+            &quad,
+            &mut output,
+            engine.internal_decoded_len_estimate(quad.len()),
+        )
+        .is_ok()
 }
 
 /// The decode estimate used by an engine implementation. Users do not need to interact with this;
@@ -445,34 +1362,190 @@ pub trait DecodeEstimate {
     fn decoded_len_estimate(&self) -> usize;
 }
 
+/// The padding used to make the length of encoded output a multiple of 4.
+///
+/// This is configured separately from the [`Alphabet`](crate::alphabet::Alphabet) since it's not
+/// part of it: the same symbols are used with and without padding, and some formats pad with a
+/// byte other than `=`. See [`GeneralPurposeConfig::with_padding`].
+///
+/// How padding is handled when decoding is controlled by [`DecodePaddingMode`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Padding {
+    /// Pad with the given byte, normally `=`.
+    Padded(u8),
+    /// Don't pad. Input is still checked for `=` padding when decoding.
+    Unpadded,
+}
+
+impl Padding {
+    /// Returns the byte that is treated as padding when decoding.
+    pub(crate) const fn decode_byte(self) -> u8 {
+        match self {
+            Self::Padded(byte) => byte,
+            Self::Unpadded => PAD_BYTE,
+        }
+    }
+}
+
 /// Controls how pad bytes are handled when decoding.
 ///
 /// Each [Engine] must support at least the behavior indicated by
 /// [`DecodePaddingMode::RequireCanonical`], and may support other modes.
+///
+/// With the `serde` feature, modes are serialized as `indifferent`, `require_canonical`, and
+/// `require_none`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum DecodePaddingMode {
     /// Canonical padding is allowed, but any fewer padding bytes than that is also allowed.
     Indifferent,
-    /// Padding must be canonical (0, 1, or 2 `=` as needed to produce a 4 byte suffix).
+    /// Padding must be canonical (0, 1, or 2 pad bytes as needed to produce a 4 byte suffix).
     RequireCanonical,
     /// Padding must be absent -- for when you want predictable padding, without any wasted bytes.
     RequireNone,
 }
 
-/// Metadata about the result of a decode operation
-#[derive(PartialEq, Eq, Debug)]
+/// Controls how pad bytes before the last symbol of the input are handled when decoding, like the
+/// `=` in `QQ==QUJD`, which can come from concatenating separately encoded values.
+///
+/// Padding at the end of the input is always handled according to the [`DecodePaddingMode`].
+///
+/// With the `serde` feature, modes are serialized as `invalid`, `strict`, `terminate`, and
+/// `ignore`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum EmbeddedPaddingMode {
+    /// The padding is an invalid byte like any other that isn't a symbol, reported as
+    /// [`DecodeError::InvalidByte`]. This is the default.
+    Invalid,
+    /// The padding is reported as [`DecodeError::PaddingNotAtEnd`], so it can be told apart from
+    /// other invalid bytes.
+    Strict,
+    /// The padding marks the end of the data, as MIME allows: decoding stops after it and any
+    /// padding that directly follows, and the rest of the input is not looked at.
+    Terminate,
+    /// The padding is skipped, as if it weren't there.
+    Ignore,
+}
+
+/// The line ending written between lines of wrapped output, and after the last line if
+/// [`Config::encode_trailing_newline`] is set. See
+/// [`GeneralPurposeConfig::with_encode_line_ending`].
+///
+/// With the `serde` feature, line endings are serialized as `lf` and `crlf`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum LineEnding {
+    /// `\n`, as in PEM. This is the default.
+    LF,
+    /// `\r\n`, as in MIME.
+    CRLF,
+}
+
+impl LineEnding {
+    /// The length of the longest line ending, for sizing buffers.
+    #[cfg(any(feature = "std", test))]
+    pub(crate) const MAX_LEN: usize = 2;
+
+    /// Returns the bytes of the line ending.
+    pub const fn as_bytes(self) -> &'static [u8] {
+        match self {
+            Self::LF => b"\n",
+            Self::CRLF => b"\r\n",
+        }
+    }
+}
+
+/// Metadata about the result of a decode operation, from [`Engine::decode_slice_with_meta`] and
+/// [`Engine::decode_with_meta`].
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
 pub struct DecodeMetadata {
     /// Number of decoded bytes output
     pub(crate) decoded_len: usize,
     /// Offset of the first padding byte in the input, if any
     pub(crate) padding_offset: Option<usize>,
+    /// Number of padding bytes at the end of the input
+    pub(crate) padding_len: usize,
+    /// The low bits of the last symbol that didn't make up a whole decoded byte
+    pub(crate) trailing_bits: u8,
+    /// Number of bytes skipped because the engine's config ignores them
+    pub(crate) skipped_ignored: usize,
 }
 
 impl DecodeMetadata {
-    pub(crate) fn new(decoded_bytes: usize, padding_index: Option<usize>) -> Self {
+    /// Create metadata for `decoded_len` bytes of output, with the first padding byte at
+    /// `padding_offset` in the input, if there was any padding.
+    ///
+    /// This is for implementing [`Engine::internal_decode`]. The other values are 0 unless set
+    /// with [`with_padding_len`](Self::with_padding_len) and
+    /// [`with_trailing_bits`](Self::with_trailing_bits). The number of ignored bytes is counted by
+    /// the `*_with_meta` methods themselves.
+    pub const fn new(decoded_len: usize, padding_offset: Option<usize>) -> Self {
+        Self {
+            decoded_len,
+            padding_offset,
+            padding_len: 0,
+            trailing_bits: 0,
+            skipped_ignored: 0,
+        }
+    }
+
+    /// Set the number of padding bytes at the end of the input.
+    pub const fn with_padding_len(self, padding_len: usize) -> Self {
+        Self {
+            padding_len,
+            ..self
+        }
+    }
+
+    /// Set the bits of the last symbol that were discarded, as the low 2 or 4 bits.
+    pub const fn with_trailing_bits(self, trailing_bits: u8) -> Self {
         Self {
-            decoded_len: decoded_bytes,
-            padding_offset: padding_index,
+            trailing_bits,
+            ..self
         }
     }
+
+    /// Count the bytes in `input`, which decoded to this, that the engine's config ignores.
+    fn count_ignored<C: Config + ?Sized>(self, input: &[u8], config: &C) -> Self {
+        Self {
+            skipped_ignored: input.iter().filter(|&&b| config.decode_ignores(b)).count(),
+            ..self
+        }
+    }
+
+    /// Returns the number of decoded bytes written to the output.
+    pub fn decoded_len(&self) -> usize {
+        self.decoded_len
+    }
+
+    /// Returns the offset in the input of the first padding byte, if there was any padding.
+    pub fn padding_offset(&self) -> Option<usize> {
+        self.padding_offset
+    }
+
+    /// Returns the number of padding bytes at the end of the input.
+    pub fn padding_len(&self) -> usize {
+        self.padding_len
+    }
+
+    /// Returns the bits of the last symbol that were discarded because they didn't make up a whole
+    /// byte, as the low 2 or 4 bits.
+    ///
+    /// These are always 0 in a canonical encoding, and can only be nonzero if the engine allows
+    /// it, as with
+    /// [`GeneralPurposeConfig::with_decode_allow_trailing_bits`](crate::engine::GeneralPurposeConfig::with_decode_allow_trailing_bits).
+    pub fn trailing_bits(&self) -> u8 {
+        self.trailing_bits
+    }
+
+    /// Returns the number of bytes that were skipped because the engine's config ignores them,
+    /// like those set with
+    /// [`GeneralPurposeConfig::with_decode_ignore_bytes`](crate::engine::GeneralPurposeConfig::with_decode_ignore_bytes).
+    pub fn skipped_ignored(&self) -> usize {
+        self.skipped_ignored
+    }
 }
@@ -4,7 +4,7 @@ use crate::{
         general_purpose::{self, decode_table, encode_table},
         Config, DecodeEstimate, DecodeMetadata, DecodePaddingMode, Engine,
     },
-    DecodeError, DecodeSliceError,
+    DecodeError, DecodeSliceError, PAD_BYTE,
 };
 use std::ops::{BitAnd, BitOr, Shl, Shr};
 
@@ -117,6 +117,7 @@ impl Engine for Naive {
             estimate.rem,
             output.len(),
             &self.decode_table,
+            PAD_BYTE,
         )?;
 
         const BOTTOM_BYTE: u32 = 0xFF;
@@ -146,6 +147,7 @@ impl Engine for Naive {
             output,
             complete_nonterminal_quads_len / 4 * 3,
             &self.decode_table,
+            PAD_BYTE,
             self.config.decode_allow_trailing_bits,
             self.config.decode_padding_mode,
         )
@@ -192,4 +194,8 @@ impl Config for NaiveConfig {
     fn encode_padding(&self) -> bool {
         self.encode_padding
     }
+
+    fn decode_ignores_any(&self) -> bool {
+        false
+    }
 }
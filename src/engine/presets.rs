@@ -0,0 +1,181 @@
+//! Ready-made engines for formats that need more than an alphabet and padding.
+//!
+//! Each of these is a [`GeneralPurpose`] engine, so one can be tweaked further by building a new
+//! engine from its [`config`](crate::Engine::config) and the alphabet it uses.
+//!
+//! # Examples
+//!
+//! ```
+//! use base64::{engine::presets::PEM, Engine as _};
+//!
+//! let der = vec![0x30; 100];
+//! let body = PEM.encode(&der);
+//! assert!(body.lines().all(|line| line.len() <= 64));
+//! assert!(body.ends_with('\n'));
+//!
+//! assert_eq!(der, PEM.decode(&body).unwrap());
+//! ```
+
+use crate::{
+    alphabet,
+    engine::{general_purpose::PAD, EmbeddedPaddingMode, GeneralPurpose, LineEnding},
+};
+
+/// The bytes that are whitespace in PEM: space, tab, line endings, vertical tab, and form feed.
+const PEM_WHITESPACE: &[u8] = b" \t\r\n\x0B\x0C";
+
+/// An engine for the base64 text in PEM files, as in [RFC 7468](https://www.rfc-editor.org/rfc/rfc7468).
+///
+/// Encoding uses the [`alphabet::STANDARD`] alphabet with padding, in lines of 64 characters, each
+/// ending in `\n`, including the last one, so the output can go between the `-----BEGIN` and
+/// `-----END` lines as is.
+///
+/// Decoding requires canonical padding but skips whitespace anywhere, as the RFC's lax parsers do,
+/// so lines of any length with `\n` or `\r\n` line endings are accepted, as is indentation.
+pub const PEM: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    PAD.with_encode_line_wrap(64)
+        .with_encode_line_ending(LineEnding::LF)
+        .with_encode_trailing_newline(true)
+        .with_decode_ignore_bytes(PEM_WHITESPACE),
+);
+
+/// The number of bytes that aren't symbols of the standard alphabet or `=`.
+const NON_ALPHABET_LEN: usize = 256 - 65;
+
+/// Every byte that isn't a symbol of the standard alphabet or `=`.
+const NON_ALPHABET: [u8; NON_ALPHABET_LEN] = non_alphabet_bytes();
+
+const fn non_alphabet_bytes() -> [u8; NON_ALPHABET_LEN] {
+    let mut bytes = [0; NON_ALPHABET_LEN];
+    let mut len = 0;
+    let mut byte = 0;
+    while byte <= u8::MAX as usize {
+        let b = byte as u8;
+        if !matches!(b, b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'+' | b'/' | b'=') {
+            bytes[len] = b;
+            len += 1;
+        }
+        byte += 1;
+    }
+
+    bytes
+}
+
+/// An engine for base64 Content-Transfer-Encoding in MIME, as in
+/// [RFC 2045](https://www.rfc-editor.org/rfc/rfc2045#section-6.8).
+///
+/// Encoding uses the [`alphabet::STANDARD`] alphabet with padding, in lines of 76 characters
+/// separated by `\r\n`, with no line ending after the last line.
+///
+/// Decoding ignores every byte that isn't in the alphabet, as the RFC requires, so line endings,
+/// whitespace, and stray characters added by mail transports are skipped. Padding marks the end of
+/// the data, and anything after it is ignored too, as with [`EmbeddedPaddingMode::Terminate`].
+pub const MIME: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    PAD.with_encode_line_wrap(76)
+        .with_encode_line_ending(LineEnding::CRLF)
+        .with_decode_ignore_bytes(&NON_ALPHABET)
+        .with_decode_embedded_padding(EmbeddedPaddingMode::Terminate),
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{engine::Config, DecodeError, Engine};
+
+    #[test]
+    fn pem_encodes_64_char_lines_ending_in_lf() {
+        let encoded = PEM.encode([0xAB; 100]);
+        let lines: Vec<&str> = encoded.split_terminator('\n').collect();
+
+        assert_eq!(3, lines.len());
+        assert_eq!(64, lines[0].len());
+        assert_eq!(64, lines[1].len());
+        assert_eq!("q6urqw==", lines[2]);
+        assert!(encoded.ends_with("==\n"));
+        assert!(!encoded.contains('\r'));
+
+        // 48 bytes fill a line exactly
+        assert_eq!(65, PEM.encode([0; 48]).len());
+        assert_eq!("", PEM.encode([]));
+    }
+
+    #[test]
+    fn pem_decodes_lax_whitespace() {
+        let expected = b"hello world, from a certificate".to_vec();
+        let encoded = PEM.encode(&expected);
+
+        assert_eq!(expected, PEM.decode(&encoded).unwrap());
+        assert_eq!(expected, PEM.decode(encoded.replace('\n', "\r\n")).unwrap());
+        assert_eq!(
+            expected,
+            PEM.decode("  aGVsbG8gd29y\tbGQsIGZy\x0Bb20gYSBj\x0CZXJ0aWZpY2F0ZQ==\r\n")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn pem_decode_is_otherwise_strict() {
+        assert_eq!(Err(DecodeError::InvalidPadding), PEM.decode("aGVsbG8\n"));
+        assert_eq!(
+            Err(DecodeError::InvalidByte(4, b'-')),
+            PEM.decode("aGVs-bG8=")
+        );
+        assert!(PEM.config().encode_padding());
+    }
+
+    #[test]
+    fn non_alphabet_bytes_are_everything_but_symbols_and_padding() {
+        for b in 0..=u8::MAX {
+            assert_eq!(
+                !alphabet::STANDARD.as_str().as_bytes().contains(&b) && b != b'=',
+                NON_ALPHABET.contains(&b),
+                "byte {}",
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn mime_encodes_76_char_lines_separated_by_crlf() {
+        let encoded = MIME.encode([0xAB; 200]);
+        let lines: Vec<&str> = encoded.split("\r\n").collect();
+
+        assert_eq!(4, lines.len());
+        assert!(lines[..3].iter().all(|line| line.len() == 76));
+        assert_eq!("q6urq6urq6urq6urq6urq6urq6urq6urq6urq6s=", lines[3]);
+        // no line ending after the last line, even when it's full
+        assert_eq!(76, MIME.encode([0; 57]).len());
+        assert_eq!(Some(78 + 4), MIME.encoded_len(60));
+    }
+
+    #[test]
+    fn mime_decode_ignores_non_alphabet_bytes() {
+        // RFC 4648 test vectors
+        for &(decoded, encoded) in &[
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(encoded, MIME.encode(decoded));
+            assert_eq!(decoded.as_bytes(), &MIME.decode(encoded).unwrap()[..]);
+        }
+
+        // "any characters outside of the base64 alphabet are to be ignored"
+        assert_eq!(
+            b"foobar",
+            &MIME.decode(&b"Zm9v\r\n YmFy \t!\x00\xFF-_."[..]).unwrap()[..]
+        );
+        // "the occurrence of any "=" characters may be taken as evidence that the end of the
+        // data has been reached"
+        assert_eq!(b"foob", &MIME.decode("Zm9vYg==\r\nZm9v").unwrap()[..]);
+
+        let long = [0x5A; 1_000];
+        assert_eq!(&long[..], &MIME.decode(MIME.encode(long)).unwrap()[..]);
+    }
+}
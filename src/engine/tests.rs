@@ -8,18 +8,22 @@ use rand::{
 };
 use rstest::rstest;
 use rstest_reuse::{apply, template};
-use std::{collections, fmt, io::Read as _};
+use std::{
+    collections, fmt,
+    io::{self, Read as _},
+};
 
 use crate::{
     alphabet::{Alphabet, STANDARD},
     encode::add_padding,
     encoded_len,
     engine::{
-        general_purpose, naive, Config, DecodeEstimate, DecodeMetadata, DecodePaddingMode, Engine,
+        general_purpose, naive, Config, DecodeEstimate, DecodeMetadata, DecodePaddingMode,
+        EmbeddedPaddingMode, Engine, Padding,
     },
     read::DecoderReader,
     tests::{assert_encode_sanity, random_alphabet, random_config},
-    DecodeError, DecodeSliceError, PAD_BYTE,
+    DecodeError, DecodeSliceError, EncodeSliceError, PAD_BYTE,
 };
 
 // the case::foo syntax includes the "foo" in the generated test method names
@@ -100,7 +104,7 @@ fn rfc_test_vectors_std_alphabet<E: EngineWrapper>(engine_wrapper: E) {
                 &encoded_without_padding,
                 &std::str::from_utf8(&encode_buf[0..encode_len]).unwrap()
             );
-            let pad_len = add_padding(encode_len, &mut encode_buf[encode_len..]);
+            let pad_len = add_padding(encode_len, &mut encode_buf[encode_len..], PAD_BYTE);
             assert_eq!(encoded.as_bytes(), &encode_buf[..encode_len + pad_len]);
 
             let decode_len = engine
@@ -124,6 +128,26 @@ fn rfc_test_vectors_std_alphabet<E: EngineWrapper>(engine_wrapper: E) {
     }
 }
 
+#[apply(all_engines)]
+fn preferred_block_size_chunks_encode_independently<E: EngineWrapper>(engine_wrapper: E) {
+    let mut rng = seeded_rng();
+    let engine = E::standard();
+    let block_size = engine.preferred_block_size();
+    assert!(block_size > 0);
+    assert_eq!(0, block_size % 3);
+
+    for _ in 0..100 {
+        let len = rng.gen_range(0..10 * block_size);
+        let data: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+
+        let chunked: String = data
+            .chunks(block_size * rng.gen_range(1..4))
+            .map(|chunk| engine.encode(chunk))
+            .collect();
+        assert_eq!(engine.encode(&data), chunked);
+    }
+}
+
 #[apply(all_engines)]
 fn roundtrip_random<E: EngineWrapper>(engine_wrapper: E) {
     let mut rng = seeded_rng();
@@ -212,6 +236,7 @@ fn encode_doesnt_write_extra_bytes<E: EngineWrapper>(engine_wrapper: E) {
             add_padding(
                 encoded_len_no_pad,
                 &mut encode_buf[prefix_len + encoded_len_no_pad..],
+                PAD_BYTE,
             )
         } else {
             0
@@ -429,7 +454,7 @@ fn decode_detect_invalid_last_symbol_every_possible_two_symbols<E: EngineWrapper
     for b in 0_u8..=255 {
         let mut b64 = vec![0_u8; 4];
         assert_eq!(2, engine.internal_encode(&[b], &mut b64[..]));
-        let _ = add_padding(2, &mut b64[2..]);
+        let _ = add_padding(2, &mut b64[2..], PAD_BYTE);
 
         assert!(base64_to_bytes.insert(b64, vec![b]).is_none());
     }
@@ -498,7 +523,7 @@ fn decode_detect_invalid_last_symbol_every_possible_three_symbols<E: EngineWrapp
             bytes[1] = b2;
             let mut b64 = vec![0_u8; 4];
             assert_eq!(3, engine.internal_encode(&bytes, &mut b64[..]));
-            let _ = add_padding(3, &mut b64[3..]);
+            let _ = add_padding(3, &mut b64[3..], PAD_BYTE);
 
             let mut v = Vec::with_capacity(2);
             v.extend_from_slice(&bytes[..]);
@@ -678,6 +703,27 @@ fn decode_invalid_byte_error<E: EngineWrapper>(engine_wrapper: E) {
     }
 }
 
+/// Invalid bytes are reported at their exact offset wherever they fall in the blocks that decoders
+/// process at once, not just at the start of the block.
+#[apply(all_engines)]
+fn decode_invalid_byte_exact_offset_in_every_position<E: EngineWrapper>(engine_wrapper: E) {
+    let engine = E::standard();
+    // several of the largest blocks any engine processes, plus a partial one
+    let encoded = engine.encode([0xA5_u8; 3 * 100]).into_bytes();
+
+    for index in 0..encoded.len() {
+        let mut input = encoded.clone();
+        input[index] = b'*';
+
+        assert_eq!(
+            Err(DecodeError::InvalidByte(index, b'*')),
+            engine.decode(&input),
+            "index {}",
+            index
+        );
+    }
+}
+
 /// Any amount of padding anywhere before the final non padding character = invalid byte at first
 /// pad byte.
 /// From this and [decode_padding_before_final_non_padding_char_error_invalid_byte_at_first_pad_non_canonical_padding_suffix_all_modes],
@@ -1179,8 +1225,8 @@ fn inner_decode_reports_padding_position<E: EngineWrapper>(engine_wrapper: E) {
                     _ => unreachable!(),
                 };
             assert_eq!(
-                Ok(DecodeMetadata::new(decoded_bytes, Some(pad_position))),
-                decode_res
+                Ok((decoded_bytes, Some(pad_position))),
+                decode_res.map(|metadata| (metadata.decoded_len, metadata.padding_offset))
             );
         }
     }
@@ -1257,20 +1303,23 @@ fn decode_slice_checked_fails_gracefully_at_all_output_lengths<E: EngineWrapper>
             let mut decode_buf = Vec::with_capacity(original_len);
             for decode_buf_len in 0..original_len {
                 decode_buf.resize(decode_buf_len, 0);
-                assert_eq!(
-                    DecodeSliceError::OutputSliceTooSmall,
-                    engine
-                        .decode_slice(&encoded, &mut decode_buf[..])
-                        .unwrap_err(),
-                    "original len: {}, encoded len: {}, buf len: {}, mode: {:?}",
-                    original_len,
-                    encoded.len(),
-                    decode_buf_len,
-                    mode
-                );
+                let err = engine
+                    .decode_slice(&encoded, &mut decode_buf[..])
+                    .unwrap_err();
+                let needed = err.output_len_needed().unwrap_or_else(|| {
+                    panic!(
+                        "original len: {}, encoded len: {}, buf len: {}, mode: {:?}, err: {:?}",
+                        original_len,
+                        encoded.len(),
+                        decode_buf_len,
+                        mode,
+                        err
+                    )
+                });
+                assert!(needed >= original_len);
                 // internal method works the same
                 assert_eq!(
-                    DecodeSliceError::OutputSliceTooSmall,
+                    Some(needed),
                     engine
                         .internal_decode(
                             encoded.as_bytes(),
@@ -1278,7 +1327,23 @@ fn decode_slice_checked_fails_gracefully_at_all_output_lengths<E: EngineWrapper>
                             engine.internal_decoded_len_estimate(encoded.len())
                         )
                         .unwrap_err()
+                        .output_len_needed()
+                );
+            }
+
+            // the reported length is enough to retry with
+            if original_len > 0 {
+                let needed = engine
+                    .decode_slice(&encoded, &mut [])
+                    .unwrap_err()
+                    .output_len_needed()
+                    .unwrap();
+                decode_buf.resize(needed, 0);
+                assert_eq!(
+                    original_len,
+                    engine.decode_slice(&encoded, &mut decode_buf[..]).unwrap()
                 );
+                assert_eq!(original, decode_buf[..original_len]);
             }
 
             decode_buf.resize(original_len, 0);
@@ -1292,6 +1357,812 @@ fn decode_slice_checked_fails_gracefully_at_all_output_lengths<E: EngineWrapper>
     }
 }
 
+#[apply(all_engines)]
+fn encode_scattered_matches_encode<E: EngineWrapper>(engine_wrapper: E) {
+    let mut rng = seeded_rng();
+    let mut orig_data = Vec::new();
+    let mut encoded_slice = Vec::new();
+
+    for _ in 0..1_000 {
+        orig_data.clear();
+        let len = rng.gen_range(0..1_000);
+        fill_rand_len(&mut orig_data, &mut rng, len);
+        let engine = E::random(&mut rng);
+        let encoded = engine.encode(&orig_data);
+
+        // split into parts of random length, including empty ones and ones shorter than a group
+        let mut parts = Vec::new();
+        let mut rest = &orig_data[..];
+        while !rest.is_empty() {
+            let (part, after) = rest.split_at(rng.gen_range(0..=rest.len().min(10)));
+            parts.push(part);
+            rest = after;
+        }
+
+        assert_eq!(encoded, engine.encode_scattered(&parts));
+
+        encoded_slice.clear();
+        encoded_slice.resize(encoded.len() + 10, 0);
+        assert_eq!(
+            Ok(encoded.len()),
+            engine.encode_scattered_slice(&parts, &mut encoded_slice)
+        );
+        assert_eq!(encoded.as_bytes(), &encoded_slice[..encoded.len()]);
+        if !encoded.is_empty() {
+            assert_eq!(
+                Err(EncodeSliceError::OutputSliceTooSmall),
+                engine.encode_scattered_slice(&parts, &mut encoded_slice[..encoded.len() - 1])
+            );
+        }
+    }
+}
+
+#[cfg(feature = "const-generics")]
+#[apply(all_engines)]
+fn decode_array_requires_exact_length<E: EngineWrapper>(engine_wrapper: E) {
+    let mut rng = seeded_rng();
+
+    for _ in 0..1_000 {
+        let engine = E::random(&mut rng);
+        let mut orig_data = [0_u8; 16];
+        rng.fill(&mut orig_data[..]);
+
+        let encoded = engine.encode(orig_data);
+        assert_eq!(Ok(orig_data), engine.decode_array::<_, 16>(&encoded));
+
+        assert_eq!(
+            Err(DecodeError::UnexpectedDecodedLength {
+                expected: 17,
+                actual: Some(16)
+            }),
+            engine.decode_array::<_, 17>(&encoded)
+        );
+        assert_eq!(
+            Err(DecodeError::UnexpectedDecodedLength {
+                expected: 15,
+                actual: None
+            }),
+            engine.decode_array::<_, 15>(&encoded)
+        );
+    }
+
+    assert_eq!(
+        Err(DecodeError::InvalidByte(1, b'*')),
+        E::standard().decode_array::<_, 3>("Y*Jj")
+    );
+}
+
+#[apply(all_engines)]
+fn decode_cow_matches_decode<E: EngineWrapper>(engine_wrapper: E) {
+    let mut rng = seeded_rng();
+    let mut orig_data = Vec::new();
+
+    for _ in 0..1_000 {
+        orig_data.clear();
+        let len = rng.gen_range(0..1_000);
+        fill_rand_len(&mut orig_data, &mut rng, len);
+        let engine = E::random(&mut rng);
+        let encoded = engine.encode(&orig_data);
+
+        assert_eq!(orig_data, &*engine.decode_cow(&encoded).unwrap());
+    }
+
+    assert_eq!(
+        Err(DecodeError::InvalidByte(1, b'*')),
+        E::standard().decode_cow("Y*Jj")
+    );
+}
+
+#[apply(all_engines)]
+fn decode_write_matches_decode<E: EngineWrapper>(engine_wrapper: E) {
+    let mut rng = seeded_rng();
+    let mut orig_data = Vec::new();
+    let mut decoded = Vec::new();
+
+    for _ in 0..1_000 {
+        orig_data.clear();
+        decoded.clear();
+        let len = rng.gen_range(0..10_000);
+        fill_rand_len(&mut orig_data, &mut rng, len);
+        let engine = E::random(&mut rng);
+        let encoded = engine.encode(&orig_data);
+
+        assert_eq!(
+            orig_data.len(),
+            engine.decode_write(&encoded, &mut decoded).unwrap()
+        );
+        assert_eq!(orig_data, decoded);
+    }
+}
+
+#[apply(all_engines)]
+fn decode_write_reports_decode_error<E: EngineWrapper>(engine_wrapper: E) {
+    let engine = E::standard();
+    let mut input = engine.encode([0_u8; 3000]).into_bytes();
+    input[2500] = b'*';
+
+    let mut decoded = Vec::new();
+    let err = engine.decode_write(&input, &mut decoded).unwrap_err();
+    assert_eq!(io::ErrorKind::InvalidData, err.kind());
+    assert_eq!(
+        &DecodeError::InvalidByte(2500, b'*'),
+        err.get_ref()
+            .unwrap()
+            .downcast_ref::<DecodeError>()
+            .unwrap()
+    );
+    assert!(decoded.len() <= 2500 / 4 * 3);
+}
+
+#[apply(all_engines)]
+fn decode_chunked_matches_decode<E: EngineWrapper>(engine_wrapper: E) {
+    let mut rng = seeded_rng();
+    let mut orig_data = Vec::new();
+
+    for _ in 0..1_000 {
+        orig_data.clear();
+        let len = rng.gen_range(0..10_000);
+        fill_rand_len(&mut orig_data, &mut rng, len);
+        let engine = E::random(&mut rng);
+        let encoded = engine.encode(&orig_data);
+
+        let mut decoded = Vec::new();
+        let decoded_len = engine
+            .decode_chunked(&encoded, |chunk| decoded.extend_from_slice(chunk))
+            .unwrap();
+        assert_eq!(orig_data.len(), decoded_len);
+        assert_eq!(orig_data, decoded);
+    }
+
+    assert_eq!(
+        Err(DecodeError::InvalidByte(1, b'*')),
+        E::standard().decode_chunked("Y*Jj", |_| {})
+    );
+}
+
+#[apply(all_engines)]
+fn decode_prefix_matches_start_of_decode<E: EngineWrapper>(engine_wrapper: E) {
+    let mut rng = seeded_rng();
+    let mut orig_data = Vec::new();
+
+    for _ in 0..1_000 {
+        orig_data.clear();
+        let len = rng.gen_range(0..1_000);
+        fill_rand_len(&mut orig_data, &mut rng, len);
+        let engine = E::random(&mut rng);
+        let encoded = engine.encode(&orig_data);
+        let n = rng.gen_range(0..len + 10);
+
+        let (prefix, consumed) = engine.decode_prefix(&encoded, n).unwrap();
+        assert_eq!(&orig_data[..n.min(len)], &prefix[..]);
+        assert!(consumed <= encoded.len());
+        if n >= len {
+            assert_eq!(encoded.len(), consumed);
+        }
+    }
+
+    let engine = E::standard();
+    assert_eq!((Vec::new(), 0), engine.decode_prefix("YWJj", 0).unwrap());
+    assert_eq!(
+        (b"a".to_vec(), 4),
+        engine.decode_prefix("YWJjZGVm", 1).unwrap()
+    );
+    assert_eq!(
+        (b"abcd".to_vec(), 8),
+        engine.decode_prefix("YWJjZGVm", 4).unwrap()
+    );
+    // the rest of the input isn't checked
+    assert_eq!(
+        (b"abc".to_vec(), 4),
+        engine.decode_prefix("YWJj*", 3).unwrap()
+    );
+    assert_eq!(
+        Err(DecodeError::InvalidByte(5, b'*')),
+        engine.decode_prefix("YWJjZ*Vm", 4)
+    );
+}
+
+#[test]
+fn decode_prefix_counts_ignored_bytes_as_consumed() {
+    let engine = general_purpose::GeneralPurpose::new(
+        &STANDARD,
+        general_purpose::PAD.with_decode_ignore_bytes(b"\r\n"),
+    );
+
+    assert_eq!(
+        (b"abcd".to_vec(), 10),
+        engine.decode_prefix("YWJj\r\nZGVm\r\nZw==", 4).unwrap()
+    );
+    assert_eq!(
+        (b"abc".to_vec(), 4),
+        engine.decode_prefix("YWJj\r\nZGVm\r\nZw==", 3).unwrap()
+    );
+}
+
+#[apply(all_engines)]
+fn decode_slice_with_meta_reports_length_and_padding<E: EngineWrapper>(engine_wrapper: E) {
+    let mut rng = seeded_rng();
+    let engine = E::random(&mut rng);
+    let mut decoded = Vec::new();
+
+    for _ in 0..1_000 {
+        let orig: Vec<u8> = (0..rng.gen_range(0..100)).map(|_| rng.gen()).collect();
+        let encoded = engine.encode(&orig);
+        decoded.clear();
+        decoded.resize(orig.len(), 0);
+
+        let meta = engine
+            .decode_slice_with_meta(&encoded, &mut decoded)
+            .unwrap();
+
+        assert_eq!(orig.len(), meta.decoded_len());
+        assert_eq!(orig, decoded);
+        let padding_len = encoded.bytes().rev().take_while(|&b| b == PAD_BYTE).count();
+        assert_eq!(padding_len, meta.padding_len());
+        assert_eq!(
+            if padding_len > 0 {
+                Some(encoded.len() - padding_len)
+            } else {
+                None
+            },
+            meta.padding_offset()
+        );
+    }
+}
+
+#[test]
+fn decode_slice_with_meta_reports_trailing_bits() {
+    let engine = general_purpose::GeneralPurpose::new(
+        &STANDARD,
+        general_purpose::PAD
+            .with_decode_allow_trailing_bits(true)
+            .with_decode_padding_mode(DecodePaddingMode::Indifferent),
+    );
+    let mut decoded = [0; 3];
+
+    // 'P' is 0b001111, of which the last 4 bits are left over after 1 byte
+    let meta = engine.decode_slice_with_meta("AP==", &mut decoded).unwrap();
+    assert_eq!(1, meta.decoded_len());
+    assert_eq!(2, meta.padding_len());
+    assert_eq!(0b1111, meta.trailing_bits());
+
+    // 2 trailing bits after 2 bytes
+    let meta = engine.decode_slice_with_meta("AAB", &mut decoded).unwrap();
+    assert_eq!(2, meta.decoded_len());
+    assert_eq!(0, meta.padding_len());
+    assert_eq!(None, meta.padding_offset());
+    assert_eq!(0b01, meta.trailing_bits());
+
+    let meta = engine.decode_slice_with_meta("AAAA", &mut decoded).unwrap();
+    assert_eq!(0, meta.trailing_bits());
+}
+
+#[apply(all_engines)]
+fn decode_report_all_finds_every_invalid_byte<E: EngineWrapper>(engine_wrapper: E) {
+    let mut rng = seeded_rng();
+    let engine = E::standard();
+    let invalid = b"!*\n ~\xFF";
+
+    for _ in 0..1_000 {
+        let mut encoded = engine
+            .encode(
+                (0..rng.gen_range(1..100))
+                    .map(|_| rng.gen())
+                    .collect::<Vec<u8>>(),
+            )
+            .into_bytes();
+        let mut expected = Vec::new();
+        for _ in 0..rng.gen_range(1..10) {
+            let offset = rng.gen_range(0..encoded.len());
+            encoded.insert(offset, invalid[rng.gen_range(0..invalid.len())]);
+        }
+        for (offset, &b) in encoded.iter().enumerate() {
+            if invalid.contains(&b) {
+                expected.push((offset, b));
+            }
+        }
+
+        let report = engine.decode_report_all(&encoded).unwrap_err();
+        assert_eq!(&expected[..], report.invalid_bytes());
+        assert_eq!(&engine.decode(&encoded).unwrap_err(), report.error());
+        assert!(!report.is_truncated());
+    }
+}
+
+#[apply(all_engines)]
+fn decode_report_all_truncates_at_cap<E: EngineWrapper>(engine_wrapper: E) {
+    let engine = E::standard();
+    let input = vec![b'!'; crate::DecodeReport::MAX_INVALID_BYTES + 1];
+
+    let report = engine.decode_report_all(&input).unwrap_err();
+    assert_eq!(
+        crate::DecodeReport::MAX_INVALID_BYTES,
+        report.invalid_bytes().len()
+    );
+    assert!(report.is_truncated());
+
+    assert_eq!(b"abc", &engine.decode_report_all("YWJj").unwrap()[..]);
+}
+
+#[apply(all_engines)]
+fn find_invalid_finds_first_byte_outside_alphabet<E: EngineWrapper>(engine_wrapper: E) {
+    let mut rng = seeded_rng();
+    let engine = E::standard();
+    let invalid = b"!*\n -~\xFF";
+
+    for _ in 0..1_000 {
+        let mut encoded = engine
+            .encode(
+                (0..rng.gen_range(0..200))
+                    .map(|_| rng.gen())
+                    .collect::<Vec<u8>>(),
+            )
+            .into_bytes();
+        // padding is fine, wherever it is
+        assert_eq!(None, engine.find_invalid(&encoded));
+
+        let offset = rng.gen_range(0..=encoded.len());
+        encoded.insert(offset, invalid[rng.gen_range(0..invalid.len())]);
+        if rng.gen() {
+            let later = rng.gen_range(offset + 1..=encoded.len());
+            encoded.insert(later, invalid[rng.gen_range(0..invalid.len())]);
+        }
+        assert_eq!(Some(offset), engine.find_invalid(&encoded));
+    }
+}
+
+#[test]
+fn find_invalid_accepts_ignored_bytes() {
+    let engine = general_purpose::GeneralPurpose::new(
+        &STANDARD,
+        general_purpose::PAD.with_decode_ignore_bytes(b"\r\n"),
+    );
+
+    assert_eq!(None, engine.find_invalid("aGVs\r\nbG8=\r\n"));
+    assert_eq!(Some(6), engine.find_invalid("aGVs\r\n bG8=\r\n"));
+    assert_eq!(
+        Some(8),
+        general_purpose::STANDARD.find_invalid("aGVsbG8=\r\n")
+    );
+}
+
+#[test]
+fn decode_wrapped_lines_reports_errors_at_input_offsets() {
+    let mut rng = seeded_rng();
+
+    for _ in 0..1_000 {
+        let line_len = rng.gen_range(1..100);
+        let mode = all_pad_modes()[rng.gen_range(0..3)];
+        let unwrapped_engine = general_purpose::GeneralPurpose::new(
+            &STANDARD,
+            general_purpose::GeneralPurposeConfig::new()
+                .with_encode_padding(rng.gen())
+                .with_decode_padding_mode(mode),
+        );
+        let wrapped_engine = general_purpose::GeneralPurpose::new(
+            &STANDARD,
+            unwrapped_engine.config().with_encode_line_wrap(line_len),
+        );
+
+        let mut orig_data = Vec::new();
+        let len = rng.gen_range(1..5_000);
+        fill_rand_len(&mut orig_data, &mut rng, len);
+        let mut unwrapped = unwrapped_engine.encode(&orig_data).into_bytes();
+        // sometimes corrupt a symbol, or add padding in the middle of the input
+        match rng.gen_range(0..3) {
+            0 => {
+                let index = rng.gen_range(0..unwrapped.len());
+                unwrapped[index] = b'*';
+            }
+            1 => {
+                let index = rng.gen_range(0..unwrapped.len());
+                unwrapped[index] = PAD_BYTE;
+            }
+            _ => {}
+        }
+
+        let wrapped = unwrapped
+            .chunks(line_len)
+            .collect::<Vec<_>>()
+            .join(&b"\r\n"[..]);
+        let to_wrapped_offset = |offset: usize| offset + offset / line_len * 2;
+
+        let expected = unwrapped_engine.decode(&unwrapped).map_err(|e| match e {
+            DecodeError::InvalidByte(offset, byte) => {
+                DecodeError::InvalidByte(to_wrapped_offset(offset), byte)
+            }
+            DecodeError::InvalidLastSymbol {
+                offset,
+                symbol,
+                symbol_value,
+            } => DecodeError::InvalidLastSymbol {
+                offset: to_wrapped_offset(offset),
+                symbol,
+                symbol_value,
+            },
+            e => e,
+        });
+        assert_eq!(expected, wrapped_engine.decode(&wrapped));
+    }
+}
+
+#[test]
+fn decode_wrapped_lines_output_slice_too_small() {
+    let engine = general_purpose::GeneralPurpose::new(
+        &STANDARD,
+        general_purpose::PAD.with_encode_line_wrap(4),
+    );
+    let encoded = engine.encode([0_u8; 3_000]);
+
+    let mut output = [0_u8; 2_999];
+    assert_eq!(
+        Err(DecodeSliceError::OutputSliceTooSmall { needed: 3_000 }),
+        engine.decode_slice(&encoded, &mut output)
+    );
+}
+
+#[test]
+fn decode_ignores_any_matches_decode_ignores() {
+    let ignores_any = |config: general_purpose::GeneralPurposeConfig| {
+        assert_eq!(
+            (0..=255).any(|b| config.decode_ignores(b)),
+            config.decode_ignores_any()
+        );
+        config.decode_ignores_any()
+    };
+
+    assert!(!ignores_any(general_purpose::PAD));
+    assert!(ignores_any(general_purpose::PAD.with_encode_line_wrap(76)));
+    assert!(ignores_any(
+        general_purpose::PAD.with_encode_trailing_newline(true)
+    ));
+    assert!(ignores_any(
+        general_purpose::PAD.with_decode_ignore_bytes(b".")
+    ));
+}
+
+#[test]
+fn decode_ignore_bytes_skips_separators() {
+    let mut rng = seeded_rng();
+    let plain_engine = general_purpose::GeneralPurpose::new(&STANDARD, general_purpose::PAD);
+    let engine = general_purpose::GeneralPurpose::new(
+        &STANDARD,
+        general_purpose::PAD.with_decode_ignore_bytes(b".: "),
+    );
+
+    for _ in 0..1_000 {
+        let mut orig_data = Vec::new();
+        let len = rng.gen_range(0..3_000);
+        fill_rand_len(&mut orig_data, &mut rng, len);
+        let encoded = plain_engine.encode(&orig_data);
+
+        // put a random separator after a random number of symbols, repeatedly
+        let mut separated = Vec::new();
+        for &b in encoded.as_bytes() {
+            separated.push(b);
+            if rng.gen_range(0..4) == 0 {
+                separated.push(b".: "[rng.gen_range(0..3)]);
+            }
+        }
+
+        assert_eq!(orig_data, engine.decode(&separated).unwrap());
+        let mut output = vec![0; len];
+        assert_eq!(Ok(len), engine.decode_slice(&separated, &mut output));
+        assert_eq!(orig_data, output);
+
+        let mut decoded = Vec::new();
+        let _ = DecoderReader::new(&separated[..], &engine)
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(orig_data, decoded);
+    }
+
+    // other bytes are still invalid, at their offset in the separated input
+    assert_eq!(
+        Err(DecodeError::InvalidByte(5, b'-')),
+        engine.decode("YW.Jj-ZGVm")
+    );
+    // and the default config doesn't skip anything
+    assert_eq!(
+        Err(DecodeError::InvalidByte(2, b'.')),
+        plain_engine.decode("YW.Jj")
+    );
+}
+
+#[test]
+fn embedded_padding_modes() {
+    let engine = |mode| {
+        general_purpose::GeneralPurpose::new(
+            &STANDARD,
+            general_purpose::PAD.with_decode_embedded_padding(mode),
+        )
+    };
+
+    assert_eq!(
+        Err(DecodeError::InvalidByte(4, b'=')),
+        engine(EmbeddedPaddingMode::Invalid).decode("aGVs=bG8=")
+    );
+    assert_eq!(
+        Err(DecodeError::PaddingNotAtEnd(4)),
+        engine(EmbeddedPaddingMode::Strict).decode("aGVs=bG8=")
+    );
+    assert_eq!(
+        Ok(b"hi".to_vec()),
+        engine(EmbeddedPaddingMode::Terminate).decode("aGk=bG8=")
+    );
+    assert_eq!(
+        Ok(b"hello".to_vec()),
+        engine(EmbeddedPaddingMode::Ignore).decode("aGVs=bG8=")
+    );
+
+    // decoding stops after the padding, which is checked as usual
+    let terminate = engine(EmbeddedPaddingMode::Terminate);
+    assert_eq!(Ok(b"hi".to_vec()), terminate.decode("aGk=*"));
+    assert_eq!(
+        Err(DecodeError::InvalidByte(4, b'=')),
+        terminate.decode("aGVs=bG8=")
+    );
+    assert_eq!(
+        Err(DecodeError::InvalidByte(3, b'=')),
+        terminate.decode("aGk==aGk=")
+    );
+    assert_eq!(
+        Err(DecodeError::InvalidByte(1, b'*')),
+        terminate.decode("a*k=aGk=")
+    );
+}
+
+#[test]
+fn embedded_padding_modes_agree_without_embedded_padding() {
+    let mut rng = seeded_rng();
+    let modes = [
+        EmbeddedPaddingMode::Invalid,
+        EmbeddedPaddingMode::Strict,
+        EmbeddedPaddingMode::Terminate,
+        EmbeddedPaddingMode::Ignore,
+    ];
+
+    for _ in 0..1_000 {
+        let mut orig_data = Vec::new();
+        let len = rng.gen_range(0..100);
+        fill_rand_len(&mut orig_data, &mut rng, len);
+        let mut encoded = general_purpose::STANDARD.encode(&orig_data).into_bytes();
+        // sometimes with too much padding or an invalid byte
+        match rng.gen_range(0..4) {
+            0 => encoded.push(b'='),
+            1 => {
+                let offset = rng.gen_range(0..=encoded.len());
+                encoded.insert(offset, b'*');
+            }
+            _ => {}
+        }
+
+        // like `aGk=*`
+        let embedded = match (
+            encoded.iter().position(|&b| b == b'='),
+            encoded.iter().rposition(|&b| b != b'='),
+        ) {
+            (Some(padding), Some(last_symbol)) => padding < last_symbol,
+            _ => false,
+        };
+        if embedded {
+            continue;
+        }
+
+        let expected = general_purpose::STANDARD.decode(&encoded);
+        if expected.is_ok() {
+            assert_eq!(orig_data, expected.clone().unwrap());
+        }
+        for &mode in &modes {
+            let engine = general_purpose::GeneralPurpose::new(
+                &STANDARD,
+                general_purpose::PAD.with_decode_embedded_padding(mode),
+            );
+            assert_eq!(expected, engine.decode(&encoded), "{:?}", mode);
+        }
+    }
+}
+
+#[test]
+fn padding_not_at_end_offsets_are_in_the_input() {
+    let strict = general_purpose::GeneralPurpose::new(
+        &STANDARD,
+        general_purpose::PAD.with_decode_embedded_padding(EmbeddedPaddingMode::Strict),
+    );
+
+    assert_eq!(
+        DecodeError::PaddingNotAtEnd(6),
+        strict.decode_with_stats("aG\nVs =bG8=").unwrap_err()
+    );
+
+    let mut decoded = Vec::new();
+    let err = DecoderReader::new(&b"aGVsbG8gd29y=bGQ="[..], &strict)
+        .read_to_end(&mut decoded)
+        .unwrap_err();
+    assert_eq!(
+        Some(&DecodeError::PaddingNotAtEnd(12)),
+        err.get_ref().and_then(|e| e.downcast_ref::<DecodeError>())
+    );
+}
+
+#[test]
+fn custom_padding_byte_round_trip() {
+    let mut rng = seeded_rng();
+    let mut orig_data = Vec::new();
+
+    for _ in 0..1_000 {
+        let mode = if rng.gen() {
+            DecodePaddingMode::Indifferent
+        } else {
+            DecodePaddingMode::RequireCanonical
+        };
+        let default_engine = general_purpose::GeneralPurpose::new(
+            &STANDARD,
+            general_purpose::GeneralPurposeConfig::new().with_decode_padding_mode(mode),
+        );
+        let engine = general_purpose::GeneralPurpose::new(
+            &STANDARD,
+            default_engine
+                .config()
+                .with_padding(Padding::Padded(b'.'))
+                .with_encode_line_wrap(rng.gen_range(0..10)),
+        );
+
+        orig_data.clear();
+        let len = rng.gen_range(0..3_000);
+        fill_rand_len(&mut orig_data, &mut rng, len);
+
+        let encoded = engine.encode(&orig_data);
+        assert_eq!(
+            default_engine.encode(&orig_data).replace('=', "."),
+            encoded.replace('\n', "")
+        );
+        assert_eq!(orig_data, engine.decode(&encoded).unwrap());
+
+        let mut decoded = Vec::new();
+        let _ = DecoderReader::new(encoded.as_bytes(), &engine)
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(orig_data, decoded);
+
+        if len % 3 != 0 {
+            // `=` is no longer padding
+            let default_encoded = default_engine.encode(&orig_data);
+            let pad_offset = default_encoded.find('=').unwrap();
+            assert_eq!(
+                DecodeError::InvalidByte(pad_offset, b'='),
+                general_purpose::GeneralPurpose::new(
+                    &STANDARD,
+                    engine.config().with_encode_line_wrap(0)
+                )
+                .decode(&default_encoded)
+                .unwrap_err()
+            );
+        }
+    }
+}
+
+#[test]
+fn from_block_codec_matches_new() {
+    let mut rng = seeded_rng();
+    for alphabet in &[STANDARD, crate::alphabet::URL_SAFE] {
+        let codec = crate::block::BlockCodec::new(alphabet);
+        for config in &[general_purpose::PAD, general_purpose::NO_PAD] {
+            let expected = general_purpose::GeneralPurpose::new(alphabet, *config);
+            let engine = general_purpose::GeneralPurpose::from_block_codec(&codec, *config);
+
+            for _ in 0..100 {
+                let len = rng.gen_range(0..100);
+                let data: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+                let encoded = expected.encode(&data);
+                assert_eq!(encoded, engine.encode(&data));
+                assert_eq!(data, engine.decode(&encoded).unwrap());
+            }
+            assert_eq!(expected.decode("*"), engine.decode("*"));
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "padding byte must be printable and not in the alphabet")]
+fn from_block_codec_padding_byte_in_alphabet_panics() {
+    let _ = general_purpose::GeneralPurpose::from_block_codec(
+        &crate::block::STANDARD,
+        general_purpose::PAD.with_padding(Padding::Padded(b'/')),
+    );
+}
+
+#[test]
+#[should_panic(expected = "padding byte must be printable and not in the alphabet")]
+fn padding_byte_in_alphabet_panics() {
+    let _ = general_purpose::GeneralPurpose::new(
+        &STANDARD,
+        general_purpose::PAD.with_padding(Padding::Padded(b'+')),
+    );
+}
+
+#[test]
+#[should_panic(expected = "padding byte must be printable and not in the alphabet")]
+fn unprintable_padding_byte_panics() {
+    let _ = general_purpose::GeneralPurpose::new(
+        &STANDARD,
+        general_purpose::PAD.with_padding(Padding::Padded(b'\n')),
+    );
+}
+
+#[test]
+fn try_new_rejects_what_new_panics_on() {
+    use general_purpose::ConfigError;
+
+    assert_eq!(
+        Some(ConfigError::PaddingInAlphabet(b'+')),
+        general_purpose::GeneralPurpose::try_new(
+            &STANDARD,
+            general_purpose::PAD.with_padding(Padding::Padded(b'+')),
+        )
+        .err()
+    );
+    assert_eq!(
+        Some(ConfigError::UnprintablePadding(b' ')),
+        general_purpose::GeneralPurpose::try_from_block_codec(
+            &crate::block::STANDARD,
+            general_purpose::PAD.with_padding(Padding::Padded(b' ')),
+        )
+        .err()
+    );
+
+    let mut rng = seeded_rng();
+    for _ in 0..1_000 {
+        let alphabet = random_alphabet(&mut rng);
+        let padding_byte = rng.gen();
+        let config = random_config(&mut rng).with_padding(Padding::Padded(padding_byte));
+
+        let engine = general_purpose::GeneralPurpose::try_new(alphabet, config);
+        let invalid = !padding_byte.is_ascii_graphic()
+            || alphabet.as_str().as_bytes().contains(&padding_byte);
+        assert_eq!(invalid, engine.is_err());
+        if let Ok(engine) = engine {
+            assert_eq!(&config, engine.config());
+        }
+    }
+}
+
+#[test]
+fn assert_markup_safe_accepts_safe_engines() {
+    for alphabet in &[STANDARD, crate::alphabet::URL_SAFE, crate::alphabet::BCRYPT] {
+        let _ = general_purpose::GeneralPurpose::new(alphabet, general_purpose::PAD)
+            .assert_markup_safe();
+    }
+
+    // the padding byte doesn't matter if it's never written
+    let _ = general_purpose::GeneralPurpose::new(
+        &STANDARD,
+        general_purpose::PAD
+            .with_padding(Padding::Padded(b'&'))
+            .with_encode_padding(false),
+    )
+    .assert_markup_safe();
+}
+
+#[test]
+#[should_panic(expected = "encoded output must not contain <, >, &, ', or \"")]
+fn assert_markup_safe_rejects_markup_symbol() {
+    let alphabet =
+        Alphabet::new("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789<>").unwrap();
+    let _ =
+        general_purpose::GeneralPurpose::new(&alphabet, general_purpose::PAD).assert_markup_safe();
+}
+
+#[test]
+#[should_panic(expected = "encoded output must not contain <, >, &, ', or \"")]
+fn assert_markup_safe_rejects_markup_padding_byte() {
+    let _ = general_purpose::GeneralPurpose::new(
+        &STANDARD,
+        general_purpose::PAD.with_padding(Padding::Padded(b'"')),
+    )
+    .assert_markup_safe();
+}
+
 /// Returns a tuple of the original data length, the encoded data length (just data), and the length including padding.
 ///
 /// Vecs provided should be empty.
@@ -1311,7 +2182,12 @@ fn generate_random_encoded_data<E: Engine, R: rand::Rng, D: distributions::Distr
     let base_encoded_len = engine.internal_encode(&orig_data[..], &mut encode_buf[..]);
 
     let enc_len_with_padding = if padding {
-        base_encoded_len + add_padding(base_encoded_len, &mut encode_buf[base_encoded_len..])
+        base_encoded_len
+            + add_padding(
+                base_encoded_len,
+                &mut encode_buf[base_encoded_len..],
+                PAD_BYTE,
+            )
     } else {
         base_encoded_len
     };
@@ -1541,18 +2417,25 @@ impl<E: Engine> Engine for DecoderReaderEngine<E> {
                     .unwrap()
             })?;
         if output.len() < buf.len() {
-            return Err(DecodeSliceError::OutputSliceTooSmall);
+            return Err(DecodeSliceError::OutputSliceTooSmall { needed: buf.len() });
         }
         output[..buf.len()].copy_from_slice(&buf);
-        Ok(DecodeMetadata::new(
-            buf.len(),
-            input
+        Ok(DecodeMetadata {
+            padding_len: input
                 .iter()
-                .enumerate()
-                .filter(|(_offset, byte)| **byte == PAD_BYTE)
-                .map(|(offset, _byte)| offset)
-                .next(),
-        ))
+                .rev()
+                .take_while(|&&byte| byte == PAD_BYTE)
+                .count(),
+            ..DecodeMetadata::new(
+                buf.len(),
+                input
+                    .iter()
+                    .enumerate()
+                    .filter(|(_offset, byte)| **byte == PAD_BYTE)
+                    .map(|(offset, _byte)| offset)
+                    .next(),
+            )
+        })
     }
 
     fn config(&self) -> &Self::Config {
@@ -1605,6 +2488,67 @@ fn all_pad_modes() -> Vec<DecodePaddingMode> {
     ]
 }
 
+#[cfg(feature = "const-generics")]
+#[test]
+fn encode_array_matches_encode() {
+    fn check<const IN: usize, const PADDED: usize, const UNPADDED: usize>(
+        rng: &mut impl rand::Rng,
+    ) {
+        let mut input = [0_u8; IN];
+        rng.fill_bytes(&mut input);
+
+        let padded: [u8; PADDED] = general_purpose::STANDARD.encode_array(&input);
+        assert_eq!(general_purpose::STANDARD.encode(input).as_bytes(), &padded);
+        let unpadded: [u8; UNPADDED] = general_purpose::STANDARD_NO_PAD.encode_array(&input);
+        assert_eq!(
+            general_purpose::STANDARD_NO_PAD.encode(input).as_bytes(),
+            &unpadded
+        );
+    }
+
+    let mut rng = seeded_rng();
+    check::<0, 0, 0>(&mut rng);
+    check::<1, 4, 2>(&mut rng);
+    check::<2, 4, 3>(&mut rng);
+    check::<3, 4, 4>(&mut rng);
+    check::<32, { crate::encoded_array_len(32, true) }, { crate::encoded_array_len(32, false) }>(
+        &mut rng,
+    );
+}
+
+#[cfg(feature = "const-generics")]
+#[test]
+#[should_panic(expected = "doesn't have the array length")]
+fn encode_array_padding_mismatch_panics() {
+    let _: [u8; 8] = general_purpose::STANDARD_NO_PAD.encode_array(b"hello");
+}
+
+#[cfg(feature = "allocator_api")]
+#[apply(all_engines)]
+fn decode_in_and_encode_in_match_decode_and_encode<E: EngineWrapper>(engine_wrapper: E) {
+    use std::alloc::Global;
+
+    let mut rng = seeded_rng();
+    for _ in 0..1_000 {
+        let mut orig_data = Vec::new();
+        let len = rng.gen_range(0..100);
+        fill_rand_len(&mut orig_data, &mut rng, len);
+        let engine = E::random(&mut rng);
+
+        let encoded = engine.encode_in(&orig_data, Global);
+        assert_eq!(engine.encode(&orig_data).as_bytes(), &encoded[..]);
+        assert_eq!(
+            orig_data,
+            engine.decode_in(&encoded, Global).unwrap().to_vec()
+        );
+    }
+
+    assert_eq!(
+        Err(DecodeError::InvalidByte(1, b'*')),
+        E::standard().decode_in("a*==", Global)
+    );
+}
+
 fn pad_modes_allowing_padding() -> Vec<DecodePaddingMode> {
     vec![
         DecodePaddingMode::Indifferent,
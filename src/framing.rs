@@ -0,0 +1,376 @@
+//! Framing binary messages as lines of base64, for sending them over text-only channels like
+//! pipes between processes.
+//!
+//! Each frame is the base64 encoding of one message followed by a `\n`. Frames written with
+//! [`write_frame_with_len`] also start with the decoded length in decimal and a `:`, which
+//! [`read_frame_with_len`] checks, so truncated or corrupted frames are caught even when they
+//! happen to be valid base64.
+//!
+//! Frames are at most [`MAX_FRAME_LEN`] bytes long, so a stream without newlines can't make the
+//! reader buffer it all.
+//!
+//! # Examples
+//!
+//! ```
+//! use base64::{engine::general_purpose::STANDARD, framing};
+//!
+//! let mut pipe = Vec::new();
+//! framing::write_frame_with_len(&mut pipe, b"hello", &STANDARD).unwrap();
+//! framing::write_frame_with_len(&mut pipe, b"", &STANDARD).unwrap();
+//! assert_eq!(b"5:aGVsbG8=\n0:\n", &pipe[..]);
+//!
+//! let mut reader = &pipe[..];
+//! assert_eq!(
+//!     Some(b"hello".to_vec()),
+//!     framing::read_frame_with_len(&mut reader, &STANDARD).unwrap()
+//! );
+//! assert_eq!(
+//!     Some(Vec::new()),
+//!     framing::read_frame_with_len(&mut reader, &STANDARD).unwrap()
+//! );
+//! assert_eq!(None, framing::read_frame_with_len(&mut reader, &STANDARD).unwrap());
+//! ```
+
+use crate::{
+    engine::{Config, Engine},
+    DecodeError,
+};
+use std::{
+    io::{self, BufRead as _, ErrorKind, Read as _},
+    str,
+};
+
+/// Marks the end of each frame.
+const FRAME_END: u8 = b'\n';
+/// Separates the length header from the base64.
+const LEN_SEPARATOR: u8 = b':';
+
+/// The longest frame that can be written or read, in bytes of length header and base64, not
+/// counting the `\n`.
+///
+/// That's enough for messages of up to 48 MiB.
+pub const MAX_FRAME_LEN: usize = 64 * 1024 * 1024;
+
+/// Write `bytes` to `writer` as a frame of base64 and a `\n`.
+///
+/// # Errors
+///
+/// Errors from `writer` are returned. Engines configured to wrap lines are rejected with
+/// [`ErrorKind::InvalidInput`], since a line ending would end the frame early, as are messages
+/// whose frame would be longer than [`MAX_FRAME_LEN`].
+pub fn write_frame<W, E>(writer: &mut W, bytes: &[u8], engine: &E) -> io::Result<()>
+where
+    W: io::Write + ?Sized,
+    E: Engine,
+{
+    write_frame_inner(writer, None, bytes, engine)
+}
+
+/// Write `bytes` to `writer` as a frame of the length of `bytes`, a `:`, base64, and a `\n`.
+///
+/// # Errors
+///
+/// As for [`write_frame`].
+pub fn write_frame_with_len<W, E>(writer: &mut W, bytes: &[u8], engine: &E) -> io::Result<()>
+where
+    W: io::Write + ?Sized,
+    E: Engine,
+{
+    write_frame_inner(writer, Some(bytes.len()), bytes, engine)
+}
+
+/// Read a frame written by [`write_frame`] from `reader` and decode it.
+///
+/// Returns `None` if `reader` is at EOF before the start of a frame.
+///
+/// # Errors
+///
+/// Errors from `reader` are returned. A frame cut off by EOF results in
+/// [`ErrorKind::UnexpectedEof`], and invalid base64 in an error of kind
+/// [`ErrorKind::InvalidData`] wrapping a [`DecodeError`]. A frame longer than [`MAX_FRAME_LEN`]
+/// results in [`ErrorKind::InvalidData`] once that much has been read, leaving `reader` in the
+/// middle of the frame.
+pub fn read_frame<R, E>(reader: &mut R, engine: &E) -> io::Result<Option<Vec<u8>>>
+where
+    R: io::BufRead + ?Sized,
+    E: Engine,
+{
+    let line = match read_line(reader, MAX_FRAME_LEN)? {
+        Some(line) => line,
+        None => return Ok(None),
+    };
+
+    decode(engine, &line).map(Some)
+}
+
+/// Read a frame written by [`write_frame_with_len`] from `reader` and decode it.
+///
+/// Returns `None` if `reader` is at EOF before the start of a frame.
+///
+/// # Errors
+///
+/// As for [`read_frame`]. A missing or malformed length header results in
+/// [`ErrorKind::InvalidData`], and a frame that decodes to a different length than its header
+/// says in an error of kind [`ErrorKind::InvalidData`] wrapping
+/// [`DecodeError::UnexpectedDecodedLength`].
+pub fn read_frame_with_len<R, E>(reader: &mut R, engine: &E) -> io::Result<Option<Vec<u8>>>
+where
+    R: io::BufRead + ?Sized,
+    E: Engine,
+{
+    let line = match read_line(reader, MAX_FRAME_LEN)? {
+        Some(line) => line,
+        None => return Ok(None),
+    };
+
+    let separator = line
+        .iter()
+        .position(|&b| b == LEN_SEPARATOR)
+        .ok_or_else(|| invalid_header("frame has no length header"))?;
+    let expected = str::from_utf8(&line[..separator])
+        .ok()
+        // `parse()` allows a leading `+`, which `write_frame_with_len` never writes
+        .filter(|header| header.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|header| header.parse::<usize>().ok())
+        .ok_or_else(|| invalid_header("frame length header is not a decimal length"))?;
+
+    let decoded = decode(engine, &line[separator + 1..])?;
+    if decoded.len() != expected {
+        return Err(io::Error::new(
+            ErrorKind::InvalidData,
+            DecodeError::UnexpectedDecodedLength {
+                expected,
+                actual: Some(decoded.len()),
+            },
+        ));
+    }
+
+    Ok(Some(decoded))
+}
+
+fn write_frame_inner<W, E>(
+    writer: &mut W,
+    len_header: Option<usize>,
+    bytes: &[u8],
+    engine: &E,
+) -> io::Result<()>
+where
+    W: io::Write + ?Sized,
+    E: Engine,
+{
+    if engine.config().encode_line_wrap() != 0 || engine.config().encode_trailing_newline() {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            "frames can't be written with an engine that writes line endings",
+        ));
+    }
+
+    let mut frame = match len_header {
+        Some(len) => {
+            let mut frame = len.to_string();
+            frame.push(char::from(LEN_SEPARATOR));
+            frame
+        }
+        None => String::new(),
+    };
+    match engine.encoded_len(bytes.len()) {
+        Some(encoded_len) if encoded_len <= MAX_FRAME_LEN - frame.len() => {}
+        _ => {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "message is too long for a frame",
+            ))
+        }
+    }
+    engine.encode_string(bytes, &mut frame);
+    frame.push(char::from(FRAME_END));
+
+    // build the whole frame first so unbuffered writers like pipes get it in one write
+    writer.write_all(frame.as_bytes())
+}
+
+/// Read up to and including the next frame end, returning what came before it, which must be at
+/// most `max_len` bytes.
+fn read_line<R: io::BufRead + ?Sized>(
+    reader: &mut R,
+    max_len: usize,
+) -> io::Result<Option<Vec<u8>>> {
+    let mut line = Vec::new();
+    // room for the frame end after `max_len` bytes, and one more byte to tell if there's more
+    let limit = max_len.saturating_add(2) as u64;
+    if (&mut *reader)
+        .take(limit)
+        .read_until(FRAME_END, &mut line)?
+        == 0
+    {
+        return Ok(None);
+    }
+
+    if line.len() > max_len && line[max_len] != FRAME_END {
+        return Err(io::Error::new(
+            ErrorKind::InvalidData,
+            "frame is longer than MAX_FRAME_LEN",
+        ));
+    }
+    if line.pop() != Some(FRAME_END) {
+        return Err(io::Error::new(
+            ErrorKind::UnexpectedEof,
+            "frame is missing its terminating newline",
+        ));
+    }
+
+    Ok(Some(line))
+}
+
+fn decode<E: Engine>(engine: &E, b64: &[u8]) -> io::Result<Vec<u8>> {
+    engine
+        .decode(b64)
+        .map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+}
+
+fn invalid_header(msg: &'static str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alphabet,
+        engine::{general_purpose::STANDARD, GeneralPurpose},
+        tests::random_engine,
+    };
+    use rand::{Rng, RngCore};
+
+    #[test]
+    fn frames_roundtrip() {
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..100 {
+            let engine = random_engine(&mut rng);
+            if engine.config().encode_line_wrap() != 0 {
+                continue;
+            }
+
+            let messages: Vec<Vec<u8>> = (0..rng.gen_range(0..10))
+                .map(|_| {
+                    let mut message = vec![0; rng.gen_range(0..100)];
+                    rng.fill_bytes(&mut message);
+                    message
+                })
+                .collect();
+
+            let mut plain = Vec::new();
+            let mut with_len = Vec::new();
+            for message in &messages {
+                write_frame(&mut plain, message, &engine).unwrap();
+                write_frame_with_len(&mut with_len, message, &engine).unwrap();
+            }
+
+            let (mut plain, mut with_len) = (&plain[..], &with_len[..]);
+            for message in &messages {
+                assert_eq!(
+                    Some(message),
+                    read_frame(&mut plain, &engine).unwrap().as_ref()
+                );
+                assert_eq!(
+                    Some(message),
+                    read_frame_with_len(&mut with_len, &engine)
+                        .unwrap()
+                        .as_ref()
+                );
+            }
+            assert_eq!(None, read_frame(&mut plain, &engine).unwrap());
+            assert_eq!(None, read_frame_with_len(&mut with_len, &engine).unwrap());
+        }
+    }
+
+    #[test]
+    fn truncated_frame_is_unexpected_eof() {
+        let err = read_frame(&mut &b"aGVsbG8="[..], &STANDARD).unwrap_err();
+        assert_eq!(ErrorKind::UnexpectedEof, err.kind());
+    }
+
+    #[test]
+    fn invalid_base64_is_decode_error() {
+        let err = read_frame(&mut &b"aGV*bG8=\n"[..], &STANDARD).unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, err.kind());
+        assert_eq!(
+            &DecodeError::InvalidByte(3, b'*'),
+            err.into_inner()
+                .unwrap()
+                .downcast_ref::<DecodeError>()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn bad_len_header_is_invalid_data() {
+        for frame in &[
+            &b"aGVsbG8=\n"[..],
+            b":aGVsbG8=\n",
+            b"+5:aGVsbG8=\n",
+            b"x:aGVsbG8=\n",
+        ] {
+            let err = read_frame_with_len(&mut &frame[..], &STANDARD).unwrap_err();
+            assert_eq!(ErrorKind::InvalidData, err.kind());
+        }
+    }
+
+    #[test]
+    fn len_mismatch_is_unexpected_decoded_length() {
+        let err = read_frame_with_len(&mut &b"4:aGVsbG8=\n"[..], &STANDARD).unwrap_err();
+        assert_eq!(
+            &DecodeError::UnexpectedDecodedLength {
+                expected: 4,
+                actual: Some(5)
+            },
+            err.into_inner()
+                .unwrap()
+                .downcast_ref::<DecodeError>()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn long_frame_is_invalid_data() {
+        assert_eq!(
+            Some(b"aGVsbG8=".to_vec()),
+            read_line(&mut &b"aGVsbG8=\n"[..], 8).unwrap()
+        );
+
+        let mut reader = &b"aGVsbG8=\n"[..];
+        let err = read_line(&mut reader, 7).unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, err.kind());
+        // reading stops soon after the limit rather than at the end of the frame
+        assert!(reader.len() <= 1);
+
+        let mut endless = io::BufReader::new(io::repeat(b'A'));
+        let err = read_line(&mut endless, 1024).unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, err.kind());
+    }
+
+    #[test]
+    fn message_too_long_for_frame_is_rejected() {
+        let message = vec![0; MAX_FRAME_LEN / 4 * 3 + 1];
+        let err = write_frame(&mut Vec::new(), &message, &STANDARD).unwrap_err();
+        assert_eq!(ErrorKind::InvalidInput, err.kind());
+    }
+
+    #[test]
+    fn line_wrapping_engine_is_rejected() {
+        let engine = GeneralPurpose::new(
+            &alphabet::STANDARD,
+            STANDARD.config().with_encode_line_wrap(76),
+        );
+        let err = write_frame(&mut Vec::new(), b"hello", &engine).unwrap_err();
+        assert_eq!(ErrorKind::InvalidInput, err.kind());
+
+        let engine = GeneralPurpose::new(
+            &alphabet::STANDARD,
+            STANDARD.config().with_encode_trailing_newline(true),
+        );
+        let err = write_frame(&mut Vec::new(), b"hello", &engine).unwrap_err();
+        assert_eq!(ErrorKind::InvalidInput, err.kind());
+    }
+}
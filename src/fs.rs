@@ -0,0 +1,159 @@
+//! Encoding and decoding whole files.
+//!
+//! The input is streamed through [`copy_encode`] or [`copy_decode`] with large buffered reads and
+//! writes, and the output file is sized up front, so arbitrarily large files can be processed in
+//! bounded memory.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use base64::{engine::general_purpose::STANDARD, fs};
+//!
+//! # fn main() -> std::io::Result<()> {
+//! let encoded_len = fs::encode_file("photo.jpg", "photo.jpg.b64", &STANDARD)?;
+//! let decoded_len = fs::decode_file("photo.jpg.b64", "photo-copy.jpg", &STANDARD)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    engine::Engine,
+    io::{copy_decode, copy_encode},
+};
+use std::{
+    convert::TryFrom,
+    fs::File,
+    io::{self, BufReader, BufWriter, Write as _},
+    path::Path,
+};
+
+/// Size of the buffers used when reading and writing files.
+const BLOCK_SIZE: usize = 1024 * 1024;
+
+/// Encode the contents of the file at `input` into a new file at `output`, replacing it if it
+/// exists.
+///
+/// Returns the length of the encoded output.
+pub fn encode_file<E, P, Q>(input: P, output: Q, engine: &E) -> io::Result<u64>
+where
+    E: Engine,
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let input = File::open(input)?;
+    let input_len = input.metadata()?.len();
+    let output_len = usize::try_from(input_len)
+        .ok()
+        .and_then(|len| engine.encoded_len(len))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "input file is too large"))?;
+
+    let output = File::create(output)?;
+    // usize always fits in u64
+    output.set_len(output_len as u64)?;
+
+    let mut writer = BufWriter::with_capacity(BLOCK_SIZE, output);
+    let _ = copy_encode(
+        &mut BufReader::with_capacity(BLOCK_SIZE, input),
+        &mut writer,
+        engine,
+    )?;
+    writer.flush()?;
+
+    Ok(output_len as u64)
+}
+
+/// Decode the contents of the file at `input` into a new file at `output`, replacing it if it
+/// exists.
+///
+/// Returns the length of the decoded output.
+///
+/// Invalid input results in an error of kind [`io::ErrorKind::InvalidData`] wrapping a
+/// [`DecodeError`](crate::DecodeError), and `output` is left partially written.
+pub fn decode_file<E, P, Q>(input: P, output: Q, engine: &E) -> io::Result<u64>
+where
+    E: Engine,
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let input = File::open(input)?;
+    let input_len = input.metadata()?.len();
+
+    let output = File::create(output)?;
+    // the decoded length may be up to 2 bytes less, which is fixed up at the end
+    output.set_len(input_len / 4 * 3 + if input_len % 4 > 0 { 3 } else { 0 })?;
+
+    let mut writer = BufWriter::with_capacity(BLOCK_SIZE, output);
+    let decoded_len = copy_decode(
+        &mut BufReader::with_capacity(BLOCK_SIZE, input),
+        &mut writer,
+        engine,
+    )?;
+    writer.flush()?;
+    writer.get_ref().set_len(decoded_len)?;
+
+    Ok(decoded_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+        DecodeError,
+    };
+    use rand::{Rng, SeedableRng};
+    use std::{env, fs, path::PathBuf, process};
+
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("base64-fs-{}-{}", process::id(), name))
+    }
+
+    #[test]
+    fn encode_then_decode_file_roundtrip() {
+        let mut rng = rand::rngs::SmallRng::from_entropy();
+        let (plain, encoded, decoded) = (
+            temp_path("roundtrip-plain"),
+            temp_path("roundtrip-encoded"),
+            temp_path("roundtrip-decoded"),
+        );
+
+        for &len in &[0, 1, 2, 3, 1000, BLOCK_SIZE + 7] {
+            let data: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            fs::write(&plain, &data).unwrap();
+
+            for engine in &[STANDARD, URL_SAFE_NO_PAD] {
+                let encoded_len = encode_file(&plain, &encoded, engine).unwrap();
+                let encoded_contents = fs::read_to_string(&encoded).unwrap();
+                assert_eq!(engine.encode(&data), encoded_contents);
+                assert_eq!(encoded_contents.len() as u64, encoded_len);
+
+                assert_eq!(len as u64, decode_file(&encoded, &decoded, engine).unwrap());
+                assert_eq!(data, fs::read(&decoded).unwrap());
+            }
+        }
+
+        for path in &[plain, encoded, decoded] {
+            fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn decode_file_invalid_input() {
+        let (encoded, decoded) = (temp_path("invalid-encoded"), temp_path("invalid-decoded"));
+        fs::write(&encoded, "YWJj!").unwrap();
+
+        let err = decode_file(&encoded, &decoded, &STANDARD).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+        assert_eq!(
+            &DecodeError::InvalidByte(4, b'!'),
+            err.into_inner()
+                .unwrap()
+                .downcast_ref::<DecodeError>()
+                .unwrap()
+        );
+
+        for path in &[encoded, decoded] {
+            fs::remove_file(path).unwrap();
+        }
+    }
+}
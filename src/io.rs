@@ -0,0 +1,291 @@
+//! Encoding and decoding from a reader straight into a writer.
+//!
+//! [`copy_encode`] and [`copy_decode`] are the base64 equivalents of [`std::io::copy`]: they move
+//! data through fixed-size buffers on the stack whose lengths are multiples of a base64 chunk, so
+//! every full buffer is encoded or decoded in one go with no partial chunk carried over.
+//!
+//! # Examples
+//!
+//! ```
+//! use base64::{engine::general_purpose::STANDARD, io::{copy_decode, copy_encode}};
+//!
+//! let mut encoded = Vec::new();
+//! let encoded_len = copy_encode(&mut &b"hello world"[..], &mut encoded, &STANDARD).unwrap();
+//! assert_eq!(b"aGVsbG8gd29ybGQ=", &encoded[..]);
+//! assert_eq!(16, encoded_len);
+//!
+//! let mut decoded = Vec::new();
+//! let decoded_len = copy_decode(&mut &encoded[..], &mut decoded, &STANDARD).unwrap();
+//! assert_eq!(b"hello world", &decoded[..]);
+//! assert_eq!(11, decoded_len);
+//! ```
+
+use crate::{
+    encode::{add_padding, column_after, wrap_lines},
+    engine::{Config, Engine, LineEnding, Padding},
+    read::DecoderReader,
+};
+use std::io::{self, ErrorKind};
+
+/// Length of the raw data buffer: a multiple of 3, so a full buffer encodes without padding.
+const RAW_CHUNK_LEN: usize = 3 * 1024;
+/// Length of the encoding of `RAW_CHUNK_LEN` bytes: a multiple of 4.
+const ENCODED_CHUNK_LEN: usize = RAW_CHUNK_LEN / 3 * 4;
+/// Room for a full encoded chunk with a line ending after every byte, the worst case when wrapping
+/// lines of length 1.
+const WRAPPED_CHUNK_LEN: usize = ENCODED_CHUNK_LEN * (1 + LineEnding::MAX_LEN);
+
+/// The longest length up to `max_len` that's a whole number of `block_size` blocks, or `max_len`
+/// if no block fits.
+///
+/// `block_size` comes from [`Engine::preferred_block_size`], which should be a multiple of 3. It's
+/// rounded down to one regardless, since chunks of any other length would be padded in the middle
+/// of the output. `max_len` must be a multiple of 3.
+pub(crate) fn whole_blocks_len(block_size: usize, max_len: usize) -> usize {
+    let block_size = block_size / 3 * 3;
+    if block_size > 0 && block_size <= max_len {
+        max_len / block_size * block_size
+    } else {
+        max_len
+    }
+}
+
+/// Read everything from `reader` and write its base64 encoding to `writer`, including padding and
+/// line wrapping as configured in `engine`.
+///
+/// Returns the number of bytes written to `writer`.
+///
+/// # Errors
+///
+/// Errors other than [`ErrorKind::Interrupted`] from either `reader` or `writer` are returned
+/// immediately. Some of the output may have been written by then.
+pub fn copy_encode<R, W, E>(reader: &mut R, writer: &mut W, engine: &E) -> io::Result<u64>
+where
+    R: io::Read + ?Sized,
+    W: io::Write + ?Sized,
+    E: Engine,
+{
+    // keep every chunk but the last a whole number of the engine's blocks, if any fit
+    let chunk_len = whole_blocks_len(engine.preferred_block_size(), RAW_CHUNK_LEN);
+
+    let mut raw = [0_u8; RAW_CHUNK_LEN];
+    let mut encoded = [0_u8; WRAPPED_CHUNK_LEN];
+    let mut column = 0;
+    let mut written = 0_u64;
+
+    loop {
+        let raw_len = read_full(reader, &mut raw[..chunk_len])?;
+        let at_eof = raw_len < chunk_len;
+        if raw_len > 0 {
+            let mut encoded_len = engine.internal_encode(&raw[..raw_len], &mut encoded);
+            if let (true, Padding::Padded(padding_byte)) = (at_eof, engine.config().padding()) {
+                encoded_len += add_padding(encoded_len, &mut encoded[encoded_len..], padding_byte);
+            }
+
+            let line_len = engine.config().encode_line_wrap();
+            let line_ending = engine.config().encode_line_ending().as_bytes();
+            let wrapped_len = wrap_lines(&mut encoded, encoded_len, line_len, column, line_ending);
+            column = column_after(encoded_len, line_len, column);
+
+            writer.write_all(&encoded[..wrapped_len])?;
+            // usize always fits in u64
+            written += wrapped_len as u64;
+        }
+
+        if at_eof {
+            if written > 0 && engine.config().encode_trailing_newline() {
+                let line_ending = engine.config().encode_line_ending().as_bytes();
+                writer.write_all(line_ending)?;
+                written += line_ending.len() as u64;
+            }
+
+            return Ok(written);
+        }
+    }
+}
+
+/// Read base64 from `reader` until it's exhausted and write the decoded data to `writer`.
+///
+/// Returns the number of bytes written to `writer`.
+///
+/// # Errors
+///
+/// Invalid input results in an error of kind [`ErrorKind::InvalidData`] wrapping a
+/// [`DecodeError`](crate::DecodeError), as from [`DecoderReader`]. Errors other than
+/// [`ErrorKind::Interrupted`] from either `reader` or `writer` are returned immediately. Any data
+/// decoded before an error has already been written.
+pub fn copy_decode<R, W, E>(reader: &mut R, writer: &mut W, engine: &E) -> io::Result<u64>
+where
+    R: io::Read + ?Sized,
+    W: io::Write + ?Sized,
+    E: Engine,
+{
+    let mut decoder = DecoderReader::new(reader, engine);
+    let mut decoded = [0_u8; RAW_CHUNK_LEN];
+    let mut written = 0_u64;
+
+    loop {
+        let decoded_len = read_full(&mut decoder, &mut decoded)?;
+        writer.write_all(&decoded[..decoded_len])?;
+        // usize always fits in u64
+        written += decoded_len as u64;
+
+        if decoded_len < RAW_CHUNK_LEN {
+            return Ok(written);
+        }
+    }
+}
+
+/// Read into `buf` until it's full or `reader` is exhausted, retrying on
+/// [`ErrorKind::Interrupted`].
+///
+/// Returns the number of bytes read, which is less than `buf.len()` only at EOF.
+pub(crate) fn read_full<R: io::Read + ?Sized>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut len = 0;
+    while len < buf.len() {
+        match reader.read(&mut buf[len..]) {
+            Ok(0) => break,
+            Ok(read) => len += read,
+            Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alphabet,
+        engine::{general_purpose::STANDARD, GeneralPurpose},
+        tests::random_engine,
+        DecodeError,
+    };
+    use rand::{Rng, RngCore};
+    use std::cmp;
+
+    // Hands out at most a few bytes per read, and is interrupted every other call
+    struct TrickleRead<'a> {
+        data: &'a [u8],
+        interrupt: bool,
+    }
+
+    impl<'a> io::Read for TrickleRead<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(io::Error::new(ErrorKind::Interrupted, "interrupted"));
+            }
+            let len = cmp::min(cmp::min(7, buf.len()), self.data.len());
+            buf[..len].copy_from_slice(&self.data[..len]);
+            self.data = &self.data[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn copy_encode_matches_encode() {
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..200 {
+            let mut data = vec![0; rng.gen_range(0..4 * RAW_CHUNK_LEN)];
+            rng.fill_bytes(&mut data);
+            let engine = random_engine(&mut rng);
+
+            let mut encoded = Vec::new();
+            let written = copy_encode(&mut &data[..], &mut encoded, &engine).unwrap();
+            assert_eq!(engine.encode(&data).as_bytes(), &encoded[..]);
+            assert_eq!(encoded.len() as u64, written);
+        }
+    }
+
+    #[test]
+    fn whole_blocks_len_is_a_multiple_of_3() {
+        assert_eq!(3072, whole_blocks_len(3, 3072));
+        assert_eq!(3060, whole_blocks_len(45, 3072));
+        // a block size that isn't a multiple of 3 is rounded down to one
+        assert_eq!(3072, whole_blocks_len(4, 3072));
+        assert_eq!(3060, whole_blocks_len(46, 3072));
+        // no block fits
+        assert_eq!(3072, whole_blocks_len(0, 3072));
+        assert_eq!(3072, whole_blocks_len(2, 3072));
+        assert_eq!(3072, whole_blocks_len(3075, 3072));
+    }
+
+    #[test]
+    fn copy_encode_wraps_lines_across_chunks() {
+        let mut rng = rand::thread_rng();
+
+        for &line_len in &[1, 63, 64, 76, ENCODED_CHUNK_LEN + 1] {
+            let engine = GeneralPurpose::new(
+                &alphabet::STANDARD,
+                STANDARD
+                    .config()
+                    .with_encode_line_wrap(line_len)
+                    .with_encode_trailing_newline(rng.gen())
+                    .with_encode_line_ending(rng.gen()),
+            );
+            let mut data = vec![0; 3 * RAW_CHUNK_LEN + 1];
+            rng.fill_bytes(&mut data);
+
+            let mut encoded = Vec::new();
+            let _ = copy_encode(&mut &data[..], &mut encoded, &engine).unwrap();
+            assert_eq!(engine.encode(&data).as_bytes(), &encoded[..]);
+        }
+    }
+
+    #[test]
+    fn copy_decode_matches_decode() {
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..200 {
+            let mut data = vec![0; rng.gen_range(0..4 * RAW_CHUNK_LEN)];
+            rng.fill_bytes(&mut data);
+            let engine = random_engine(&mut rng);
+            let encoded = engine.encode(&data);
+
+            let mut decoded = Vec::new();
+            let written = copy_decode(&mut encoded.as_bytes(), &mut decoded, &engine).unwrap();
+            assert_eq!(data, decoded);
+            assert_eq!(data.len() as u64, written);
+        }
+    }
+
+    #[test]
+    fn copy_with_short_and_interrupted_reads() {
+        let mut data = vec![0; 2 * RAW_CHUNK_LEN + 2];
+        rand::thread_rng().fill_bytes(&mut data);
+        let encoded = STANDARD.encode(&data);
+
+        let mut copied = Vec::new();
+        let mut reader = TrickleRead {
+            data: &data,
+            interrupt: false,
+        };
+        let _ = copy_encode(&mut reader, &mut copied, &STANDARD).unwrap();
+        assert_eq!(encoded.as_bytes(), &copied[..]);
+
+        let mut copied = Vec::new();
+        let mut reader = TrickleRead {
+            data: encoded.as_bytes(),
+            interrupt: false,
+        };
+        let _ = copy_decode(&mut reader, &mut copied, &STANDARD).unwrap();
+        assert_eq!(data, copied);
+    }
+
+    #[test]
+    fn copy_decode_invalid_input() {
+        let err = copy_decode(&mut &b"YWJjZGVm*"[..], &mut Vec::new(), &STANDARD).unwrap_err();
+        assert_eq!(ErrorKind::InvalidData, err.kind());
+        assert_eq!(
+            &DecodeError::InvalidByte(8, b'*'),
+            err.into_inner()
+                .unwrap()
+                .downcast_ref::<DecodeError>()
+                .unwrap()
+        );
+    }
+}
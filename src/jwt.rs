@@ -0,0 +1,218 @@
+//! Splitting JSON Web Tokens into their decoded segments.
+//!
+//! A JWT in compact form is three segments of URL-safe base64 without padding, separated by `.`:
+//! a JSON header, the payload, and a signature. This takes care of the encoding only; the
+//! signature is returned as is, not checked, so use a JWT library to validate tokens.
+//!
+//! # Examples
+//!
+//! ```
+//! use base64::jwt::{self, JwtError, Segment};
+//!
+//! let token = "eyJhbGciOiJub25lIn0.eyJzdWIiOiJhbGljZSJ9.";
+//! let (header, payload, signature) = jwt::split_and_decode(token).unwrap();
+//! assert_eq!(b"{\"alg\":\"none\"}", &header[..]);
+//! assert_eq!(b"{\"sub\":\"alice\"}", &payload[..]);
+//! assert!(signature.is_empty());
+//!
+//! match jwt::split_and_decode("eyJhbGciOiJub25lIn0.eyJzdWIi*iJhbGljZSJ9.") {
+//!     Err(JwtError::InvalidSegment { segment, .. }) => assert_eq!(Segment::Payload, segment),
+//!     other => panic!("unexpected {:?}", other),
+//! }
+//! ```
+
+use crate::{engine::general_purpose::URL_SAFE_NO_PAD, DecodeError, Engine};
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(any(feature = "std", test))]
+use std::error;
+
+/// The number of segments in a compact JWT.
+const SEGMENT_COUNT: usize = 3;
+
+/// The decoded header, payload, and signature of a JWT.
+pub type DecodedSegments = (Vec<u8>, Vec<u8>, Vec<u8>);
+
+/// Split `token` on `.` into its header, payload, and signature, and decode each of them as
+/// URL-safe base64 without padding.
+///
+/// Surrounding whitespace, such as a trailing newline from a file, is ignored, but a leading
+/// `Bearer ` from an `Authorization` header is not, so strip it first.
+///
+/// # Errors
+///
+/// [`JwtError::SegmentCount`] if there aren't exactly three segments, or
+/// [`JwtError::InvalidSegment`] for the first segment that isn't valid base64.
+pub fn split_and_decode<T: AsRef<[u8]>>(token: T) -> Result<DecodedSegments, JwtError> {
+    let token = trim_ascii_whitespace(token.as_ref());
+
+    let count = token.split(|&b| b == b'.').count();
+    if count != SEGMENT_COUNT {
+        return Err(JwtError::SegmentCount(count));
+    }
+
+    let mut segments = token.split(|&b| b == b'.');
+    let mut decode_next = |segment| {
+        // there are exactly three segments
+        let b64 = segments.next().unwrap();
+        URL_SAFE_NO_PAD
+            .decode(b64)
+            .map_err(|error| JwtError::InvalidSegment { segment, error })
+    };
+
+    Ok((
+        decode_next(Segment::Header)?,
+        decode_next(Segment::Payload)?,
+        decode_next(Segment::Signature)?,
+    ))
+}
+
+/// `bytes` without leading or trailing ASCII whitespace.
+fn trim_ascii_whitespace(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    let end = bytes
+        .iter()
+        .rposition(|b| !b.is_ascii_whitespace())
+        .map_or(start, |i| i + 1);
+    &bytes[start..end]
+}
+
+/// A segment of a compact JWT.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Segment {
+    /// The JOSE header, which comes first
+    Header,
+    /// The payload, usually a JSON object of claims
+    Payload,
+    /// The signature or MAC over the header and payload
+    Signature,
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Header => "header",
+            Self::Payload => "payload",
+            Self::Signature => "signature",
+        })
+    }
+}
+
+/// Errors that can occur while splitting and decoding a JWT.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum JwtError {
+    /// The token didn't have exactly three `.`-separated segments. Holds the number it had.
+    SegmentCount(usize),
+    /// A segment wasn't valid base64.
+    InvalidSegment {
+        /// The segment that couldn't be decoded
+        segment: Segment,
+        /// Why it couldn't be decoded, with offsets relative to the start of the segment
+        error: DecodeError,
+    },
+}
+
+impl fmt::Display for JwtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SegmentCount(count) => {
+                write!(f, "JWT has {} segments, expected {}", count, SEGMENT_COUNT)
+            }
+            Self::InvalidSegment { segment, error } => {
+                write!(f, "Invalid JWT {}: {}", segment, error)
+            }
+        }
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl error::Error for JwtError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::SegmentCount(_) => None,
+            Self::InvalidSegment { error, .. } => Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, RngCore};
+
+    #[test]
+    fn split_and_decode_round_trip() {
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..1_000 {
+            let segments: Vec<Vec<u8>> = (0..SEGMENT_COUNT)
+                .map(|_| {
+                    let mut segment = vec![0; rng.gen_range(0..100)];
+                    rng.fill_bytes(&mut segment);
+                    segment
+                })
+                .collect();
+            let token = segments
+                .iter()
+                .map(|s| URL_SAFE_NO_PAD.encode(s))
+                .collect::<Vec<_>>()
+                .join(".");
+
+            let (header, payload, signature) = split_and_decode(&token).unwrap();
+            assert_eq!(segments, [header, payload, signature]);
+        }
+    }
+
+    #[test]
+    fn split_and_decode_ignores_surrounding_whitespace() {
+        assert_eq!(
+            Ok((b"a".to_vec(), b"b".to_vec(), Vec::new())),
+            split_and_decode(" YQ.Yg.\r\n")
+        );
+    }
+
+    #[test]
+    fn split_and_decode_counts_segments() {
+        for &(token, count) in &[
+            ("", 1),
+            ("YQ", 1),
+            ("YQ.Yg", 2),
+            ("YQ.Yg.Yw.ZA", 4),
+            ("..", 3),
+        ] {
+            let result = split_and_decode(token);
+            if count == SEGMENT_COUNT {
+                assert_eq!(Ok((Vec::new(), Vec::new(), Vec::new())), result);
+            } else {
+                assert_eq!(Err(JwtError::SegmentCount(count)), result);
+            }
+        }
+    }
+
+    #[test]
+    fn split_and_decode_reports_the_invalid_segment() {
+        assert_eq!(
+            Err(JwtError::InvalidSegment {
+                segment: Segment::Header,
+                error: DecodeError::InvalidByte(1, b'+'),
+            }),
+            split_and_decode("Y+.Yg.Yw")
+        );
+        // padding isn't allowed
+        assert_eq!(
+            Err(JwtError::InvalidSegment {
+                segment: Segment::Signature,
+                error: DecodeError::InvalidPadding,
+            }),
+            split_and_decode("YQ.Yg.Yw==")
+        );
+        assert_eq!(
+            "Invalid JWT payload: Invalid symbol 47, offset 0.",
+            split_and_decode("YQ./g.Yw").unwrap_err().to_string()
+        );
+    }
+}
@@ -248,6 +248,17 @@
 //! # Panics
 //!
 //! If length calculations result in overflowing `usize`, a panic will result.
+//!
+//! Otherwise, decoding never panics, whatever the input: invalid input and output slices that are
+//! too small are reported as errors. The exceptions are methods documented to panic on misuse, like
+//! [`Engine::decode_slice_unchecked`] with an output slice that's too small. Likewise, encoding into
+//! a slice that's too small returns an error. This is checked by property tests over arbitrary
+//! input, configurations, and output sizes.
+//!
+//! # Unsafe code
+//!
+//! This crate is `#![forbid(unsafe_code)]` in every configuration: there are no optimized unsafe
+//! code paths to opt out of, so no feature flag is needed to meet a no-unsafe policy.
 
 #![deny(
     missing_docs,
@@ -263,6 +274,7 @@
 // The desired state is to allow it only for the rstest_reuse import.
 #![allow(clippy::single_component_path_imports)]
 #![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 
 #[cfg(any(feature = "alloc", test))]
 extern crate alloc;
@@ -271,10 +283,55 @@ extern crate alloc;
 #[cfg(test)]
 use rstest_reuse;
 
+/// Panics with `$message` if `$condition` is true, in a const fn.
+///
+/// `panic!` is only allowed in const fn from Rust 1.57, so on older compilers this indexes out of
+/// bounds instead, which fails const evaluation just the same, but panics at runtime with "index
+/// out of bounds" rather than the message.
+macro_rules! const_panic_if {
+    ($condition:expr, $message:literal) => {{
+        #[cfg(const_panic)]
+        {
+            if $condition {
+                panic!($message);
+            }
+        }
+        #[cfg(not(const_panic))]
+        {
+            let _ = [$message][$condition as usize];
+        }
+    }};
+}
+
+pub mod aligned;
 mod chunked_encoder;
+#[cfg(any(feature = "alloc", test))]
+pub mod debug;
 pub mod display;
 #[cfg(any(feature = "std", test))]
+pub mod framing;
+#[cfg(any(feature = "std", test))]
+pub mod fs;
+#[cfg(any(feature = "std", test))]
+pub mod io;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(all(feature = "rayon", feature = "std"))]
+pub mod parallel;
+#[cfg(any(feature = "std", test))]
 pub mod read;
+pub mod ring;
+pub mod sink;
+#[cfg(any(feature = "alloc", test))]
+pub mod spec;
+#[cfg(any(feature = "testing", test))]
+pub mod testing;
+#[cfg(any(feature = "std", test))]
+pub mod tls;
+#[cfg(feature = "ufmt")]
+pub mod uwrite;
+#[cfg(any(feature = "alloc", test))]
+pub mod validate;
 #[cfg(any(feature = "std", test))]
 pub mod write;
 
@@ -282,21 +339,58 @@ pub mod engine;
 pub use engine::Engine;
 
 pub mod alphabet;
+pub mod block;
+
+#[cfg(any(feature = "alloc", test))]
+pub mod dns;
+#[cfg(any(feature = "alloc", test))]
+pub mod jwt;
+#[cfg(any(feature = "alloc", test))]
+pub mod ssh;
+#[cfg(any(feature = "alloc", test))]
+pub mod url;
 
 mod encode;
 #[allow(deprecated)]
 #[cfg(any(feature = "alloc", test))]
-pub use crate::encode::{encode, encode_engine, encode_engine_string};
+pub use crate::encode::{encode, encode_engine, encode_engine_string, EncodedChunks};
 #[allow(deprecated)]
-pub use crate::encode::{encode_engine_slice, encoded_len, EncodeSliceError};
+pub use crate::encode::{
+    encode_engine_slice, encoded_array_len, encoded_len, Chunks, EncodeSliceError,
+};
 
 mod decode;
 #[allow(deprecated)]
 #[cfg(any(feature = "alloc", test))]
-pub use crate::decode::{decode, decode_engine, decode_engine_vec};
+pub use crate::decode::{decode, decode_engine, decode_engine_vec, DecodeReport, DecodeStats};
 #[allow(deprecated)]
 pub use crate::decode::{decode_engine_slice, decoded_len_estimate, DecodeError, DecodeSliceError};
 
+#[cfg(any(feature = "alloc", test))]
+mod concat;
+#[cfg(any(feature = "alloc", test))]
+pub use crate::concat::concat;
+
+#[cfg(any(feature = "alloc", test))]
+mod ct_eq;
+#[cfg(any(feature = "alloc", test))]
+pub use crate::ct_eq::ct_eq;
+
+#[cfg(any(feature = "alloc", test))]
+mod sniff;
+#[cfg(any(feature = "alloc", test))]
+pub use crate::sniff::{sniff, MediaHint};
+
+#[cfg(any(feature = "alloc", test))]
+mod split;
+#[cfg(any(feature = "alloc", test))]
+pub use crate::split::split_at_decoded;
+
+#[cfg(any(feature = "alloc", test))]
+mod translate;
+#[cfg(any(feature = "alloc", test))]
+pub use crate::translate::translate;
+
 pub mod prelude;
 
 #[cfg(test)]
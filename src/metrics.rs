@@ -0,0 +1,621 @@
+//! Observing how much an engine encodes and decodes, with the `metrics` feature.
+//!
+//! Wrapping an engine in [`Observed`] reports every encode and decode it does to a
+//! [`CodecObserver`], so throughput and error rates can be exported to a metrics system from one
+//! place instead of at every call site.
+//!
+//! # Examples
+//!
+//! ```
+//! use base64::{
+//!     engine::general_purpose::STANDARD,
+//!     metrics::{CodecObserver, Observed},
+//!     DecodeSliceError, Engine as _,
+//! };
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//!
+//! #[derive(Default)]
+//! struct Counters {
+//!     decoded_bytes: AtomicUsize,
+//!     decode_errors: AtomicUsize,
+//! }
+//!
+//! impl CodecObserver for Counters {
+//!     fn decoded(&self, _input_len: usize, output_len: usize) {
+//!         self.decoded_bytes.fetch_add(output_len, Ordering::Relaxed);
+//!     }
+//!
+//!     fn decode_failed(&self, _input_len: usize, _error: &DecodeSliceError) {
+//!         self.decode_errors.fetch_add(1, Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! let counters = Counters::default();
+//! let engine = Observed::new(STANDARD, &counters);
+//! let mut buf = [0; 8];
+//! engine.decode_slice("aGVsbG8=", &mut buf).unwrap();
+//! engine.decode_slice("aGVs*G8=", &mut buf).unwrap_err();
+//!
+//! assert_eq!(5, counters.decoded_bytes.load(Ordering::Relaxed));
+//! assert_eq!(1, counters.decode_errors.load(Ordering::Relaxed));
+//! ```
+
+use crate::{
+    engine::{scattered_len, DecodeMetadata, Engine},
+    ring::RingSlice,
+    sink::EncodeSink,
+    DecodeError, DecodeSliceError, EncodeSliceError,
+};
+#[cfg(any(feature = "alloc", test))]
+use crate::{DecodeReport, DecodeStats, EncodedChunks};
+#[cfg(feature = "allocator_api")]
+use alloc::alloc::Allocator;
+#[cfg(any(feature = "alloc", test))]
+use alloc::{borrow::Cow, string::String, vec::Vec};
+#[cfg(any(feature = "std", test))]
+use std::io;
+
+/// Receives an event for each encode and decode done by an [`Observed`] engine.
+///
+/// Every method does nothing by default, so implement only the events of interest. Like engines,
+/// observers are shared between threads, so count with atomics or the like.
+pub trait CodecObserver: Send + Sync {
+    /// `input_len` bytes were encoded as `output_len` bytes of output.
+    ///
+    /// For the encode methods, the output is everything that was written, including padding, line
+    /// endings, and anything the wrapped engine adds, like a prefix. The streaming types encode
+    /// one chunk at a time and add padding and line endings separately, so each of their chunks
+    /// only counts the base64 symbols.
+    fn encoded(&self, input_len: usize, output_len: usize) {
+        let _ = (input_len, output_len);
+    }
+
+    /// `input_len` bytes of base64 were decoded to `output_len` bytes.
+    fn decoded(&self, input_len: usize, output_len: usize) {
+        let _ = (input_len, output_len);
+    }
+
+    /// Decoding `input_len` bytes of base64 failed with `error`.
+    fn decode_failed(&self, input_len: usize, error: &DecodeSliceError) {
+        let _ = (input_len, error);
+    }
+}
+
+impl<O: CodecObserver + ?Sized> CodecObserver for &O {
+    fn encoded(&self, input_len: usize, output_len: usize) {
+        (**self).encoded(input_len, output_len);
+    }
+
+    fn decoded(&self, input_len: usize, output_len: usize) {
+        (**self).decoded(input_len, output_len);
+    }
+
+    fn decode_failed(&self, input_len: usize, error: &DecodeSliceError) {
+        (**self).decode_failed(input_len, error);
+    }
+}
+
+/// Reports what the wrapped engine encodes and decodes to a [`CodecObserver`].
+///
+/// Every [`Engine`] method is forwarded to the wrapped engine, so anything it overrides, like the
+/// framing added by [`PrefixSuffix`](crate::engine::adapters::PrefixSuffix), still applies, and
+/// one event is reported per call. The streaming types like
+/// [`EncoderWriter`](crate::write::EncoderWriter) use the engine a chunk at a time, so they report
+/// one event per chunk rather than one for the whole stream.
+#[derive(Debug, Clone)]
+pub struct Observed<E, O> {
+    engine: E,
+    observer: O,
+}
+
+impl<E: Engine, O: CodecObserver> Observed<E, O> {
+    /// Wrap `engine` to report what it encodes and decodes to `observer`.
+    pub fn new(engine: E, observer: O) -> Self {
+        Self { engine, observer }
+    }
+
+    /// The wrapped engine.
+    pub fn inner(&self) -> &E {
+        &self.engine
+    }
+
+    /// The observer events are reported to.
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    #[cfg(any(feature = "alloc", test))]
+    fn report_encoded<T, F: FnOnce(&T) -> usize>(&self, input_len: usize, output: T, len: F) -> T {
+        self.observer.encoded(input_len, len(&output));
+        output
+    }
+
+    fn report_decoded<T, F: FnOnce(&T) -> usize>(
+        &self,
+        input_len: usize,
+        result: Result<T, DecodeSliceError>,
+        len: F,
+    ) -> Result<T, DecodeSliceError> {
+        match &result {
+            Ok(output) => self.observer.decoded(input_len, len(output)),
+            Err(e) => self.observer.decode_failed(input_len, e),
+        }
+        result
+    }
+
+    /// Like `report_decoded`, for the methods that only fail with a [`DecodeError`].
+    fn report_decoded_or_error<T, F: FnOnce(&T) -> usize>(
+        &self,
+        input_len: usize,
+        result: Result<T, DecodeError>,
+        len: F,
+    ) -> Result<T, DecodeError> {
+        match &result {
+            Ok(output) => self.observer.decoded(input_len, len(output)),
+            Err(e) => self
+                .observer
+                .decode_failed(input_len, &DecodeSliceError::DecodeError(e.clone())),
+        }
+        result
+    }
+}
+
+impl<E: Engine, O: CodecObserver> Engine for Observed<E, O> {
+    type Config = E::Config;
+    type DecodeEstimate = E::DecodeEstimate;
+
+    fn internal_encode(&self, input: &[u8], output: &mut [u8]) -> usize {
+        let written = self.engine.internal_encode(input, output);
+        self.observer.encoded(input.len(), written);
+        written
+    }
+
+    fn internal_decoded_len_estimate(&self, input_len: usize) -> Self::DecodeEstimate {
+        self.engine.internal_decoded_len_estimate(input_len)
+    }
+
+    fn internal_decode(
+        &self,
+        input: &[u8],
+        output: &mut [u8],
+        estimate: Self::DecodeEstimate,
+    ) -> Result<DecodeMetadata, DecodeSliceError> {
+        let result = self.engine.internal_decode(input, output, estimate);
+        match &result {
+            Ok(metadata) => self.observer.decoded(input.len(), metadata.decoded_len),
+            Err(e) => self.observer.decode_failed(input.len(), e),
+        }
+        result
+    }
+
+    fn config(&self) -> &Self::Config {
+        self.engine.config()
+    }
+
+    fn preferred_block_size(&self) -> usize {
+        self.engine.preferred_block_size()
+    }
+
+    fn encoded_len(&self, input_len: usize) -> Option<usize> {
+        self.engine.encoded_len(input_len)
+    }
+
+    fn encode_into<T: AsRef<[u8]>, S: EncodeSink + ?Sized>(
+        &self,
+        input: T,
+        sink: &mut S,
+    ) -> Result<usize, S::Error> {
+        let input = input.as_ref();
+        let written = self.engine.encode_into(input, sink)?;
+        self.observer.encoded(input.len(), written);
+        Ok(written)
+    }
+
+    // `encode_aligned()` isn't forwarded, as it encodes with this engine a chunk at a time like the
+    // other streaming types
+
+    #[cfg(any(feature = "alloc", test))]
+    fn encode<T: AsRef<[u8]>>(&self, input: T) -> String {
+        let input = input.as_ref();
+        self.report_encoded(input.len(), self.engine.encode(input), String::len)
+    }
+
+    #[cfg(feature = "allocator_api")]
+    fn encode_in<T: AsRef<[u8]>, A: Allocator>(&self, input: T, alloc: A) -> Vec<u8, A> {
+        let input = input.as_ref();
+        self.report_encoded(input.len(), self.engine.encode_in(input, alloc), Vec::len)
+    }
+
+    #[cfg(any(feature = "alloc", test))]
+    fn encode_string<T: AsRef<[u8]>>(&self, input: T, output_buf: &mut String) {
+        let input = input.as_ref();
+        let start = output_buf.len();
+        self.engine.encode_string(input, output_buf);
+        self.observer.encoded(input.len(), output_buf.len() - start);
+    }
+
+    #[cfg(any(feature = "alloc", test))]
+    fn encode_chunks<T: AsRef<[u8]>>(&self, input: T, chunk_len: usize) -> EncodedChunks {
+        let input = input.as_ref();
+        self.report_encoded(
+            input.len(),
+            self.engine.encode_chunks(input, chunk_len),
+            |chunks| chunks.as_str().len(),
+        )
+    }
+
+    fn encode_slice<T: AsRef<[u8]>>(
+        &self,
+        input: T,
+        output_buf: &mut [u8],
+    ) -> Result<usize, EncodeSliceError> {
+        let input = input.as_ref();
+        let written = self.engine.encode_slice(input, output_buf)?;
+        self.observer.encoded(input.len(), written);
+        Ok(written)
+    }
+
+    #[cfg(feature = "const-generics")]
+    fn encode_array<const IN: usize, const OUT: usize>(&self, input: &[u8; IN]) -> [u8; OUT] {
+        let output = self.engine.encode_array(input);
+        // the wrapped engine panics unless the output fills the array
+        self.observer.encoded(IN, OUT);
+        output
+    }
+
+    #[cfg(any(feature = "alloc", test))]
+    fn encode_scattered(&self, parts: &[&[u8]]) -> String {
+        let input_len = scattered_len(parts).unwrap_or(usize::MAX);
+        self.report_encoded(input_len, self.engine.encode_scattered(parts), String::len)
+    }
+
+    fn encode_scattered_slice(
+        &self,
+        parts: &[&[u8]],
+        output_buf: &mut [u8],
+    ) -> Result<usize, EncodeSliceError> {
+        let written = self.engine.encode_scattered_slice(parts, output_buf)?;
+        self.observer
+            .encoded(scattered_len(parts).unwrap_or(usize::MAX), written);
+        Ok(written)
+    }
+
+    #[cfg(any(feature = "alloc", test))]
+    fn decode<T: AsRef<[u8]>>(&self, input: T) -> Result<Vec<u8>, DecodeError> {
+        let input = input.as_ref();
+        self.report_decoded_or_error(input.len(), self.engine.decode(input), Vec::len)
+    }
+
+    #[cfg(feature = "allocator_api")]
+    fn decode_in<T: AsRef<[u8]>, A: Allocator>(
+        &self,
+        input: T,
+        alloc: A,
+    ) -> Result<Vec<u8, A>, DecodeError> {
+        let input = input.as_ref();
+        self.report_decoded_or_error(input.len(), self.engine.decode_in(input, alloc), Vec::len)
+    }
+
+    #[cfg(feature = "const-generics")]
+    fn decode_array<T: AsRef<[u8]>, const N: usize>(
+        &self,
+        input: T,
+    ) -> Result<[u8; N], DecodeError> {
+        let input = input.as_ref();
+        self.report_decoded_or_error(input.len(), self.engine.decode_array(input), |_| N)
+    }
+
+    #[cfg(any(feature = "alloc", test))]
+    fn decode_cow<'i, T: AsRef<[u8]> + ?Sized>(
+        &self,
+        input: &'i T,
+    ) -> Result<Cow<'i, [u8]>, DecodeError> {
+        let len = input.as_ref().len();
+        self.report_decoded_or_error(len, self.engine.decode_cow(input), |output| output.len())
+    }
+
+    #[cfg(any(feature = "alloc", test))]
+    fn decode_report_all<T: AsRef<[u8]>>(&self, input: T) -> Result<Vec<u8>, DecodeReport> {
+        let input = input.as_ref();
+        let result = self.engine.decode_report_all(input);
+        match &result {
+            Ok(output) => self.observer.decoded(input.len(), output.len()),
+            Err(report) => self.observer.decode_failed(
+                input.len(),
+                &DecodeSliceError::DecodeError(report.error().clone()),
+            ),
+        }
+        result
+    }
+
+    #[cfg(any(feature = "alloc", test))]
+    fn decode_with_stats<T: AsRef<[u8]>>(
+        &self,
+        input: T,
+    ) -> Result<(Vec<u8>, DecodeStats), DecodeError> {
+        let input = input.as_ref();
+        self.report_decoded_or_error(
+            input.len(),
+            self.engine.decode_with_stats(input),
+            |(output, _)| output.len(),
+        )
+    }
+
+    #[cfg(any(feature = "alloc", test))]
+    fn decode_with_meta<T: AsRef<[u8]>>(
+        &self,
+        input: T,
+    ) -> Result<(Vec<u8>, DecodeMetadata), DecodeError> {
+        let input = input.as_ref();
+        self.report_decoded_or_error(
+            input.len(),
+            self.engine.decode_with_meta(input),
+            |(output, _)| output.len(),
+        )
+    }
+
+    #[cfg(any(feature = "alloc", test))]
+    fn decode_prefix<T: AsRef<[u8]>>(
+        &self,
+        input: T,
+        n: usize,
+    ) -> Result<(Vec<u8>, usize), DecodeError> {
+        let input = input.as_ref();
+        let result = self.engine.decode_prefix(input, n);
+        // only the consumed part of the input was decoded
+        match &result {
+            Ok((output, consumed)) => self.observer.decoded(*consumed, output.len()),
+            Err(e) => self
+                .observer
+                .decode_failed(input.len(), &DecodeSliceError::DecodeError(e.clone())),
+        }
+        result
+    }
+
+    #[cfg(any(feature = "alloc", test))]
+    fn decode_json_escaped<T: AsRef<[u8]>>(&self, input: T) -> Result<Vec<u8>, DecodeError> {
+        let input = input.as_ref();
+        self.report_decoded_or_error(
+            input.len(),
+            self.engine.decode_json_escaped(input),
+            Vec::len,
+        )
+    }
+
+    #[cfg(any(feature = "alloc", test))]
+    fn decode_vec<T: AsRef<[u8]>>(
+        &self,
+        input: T,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), DecodeError> {
+        let input = input.as_ref();
+        let start = buffer.len();
+        let result = self.engine.decode_vec(input, buffer);
+        self.report_decoded_or_error(input.len(), result, |()| buffer.len() - start)
+    }
+
+    #[cfg(any(feature = "std", test))]
+    fn decode_write<T: AsRef<[u8]>, W: io::Write + ?Sized>(
+        &self,
+        input: T,
+        output: &mut W,
+    ) -> io::Result<usize>
+    where
+        Self: Sized,
+    {
+        let input = input.as_ref();
+        let result = self.engine.decode_write(input, output);
+        match &result {
+            Ok(written) => self.observer.decoded(input.len(), *written),
+            Err(e) => {
+                // errors from `output` aren't decode errors
+                if let Some(e) = e.get_ref().and_then(|e| e.downcast_ref::<DecodeError>()) {
+                    self.observer
+                        .decode_failed(input.len(), &DecodeSliceError::DecodeError(e.clone()));
+                }
+            }
+        }
+        result
+    }
+
+    #[cfg(any(feature = "std", test))]
+    fn decode_chunked<T: AsRef<[u8]>, F: FnMut(&[u8])>(
+        &self,
+        input: T,
+        sink: F,
+    ) -> Result<usize, DecodeError>
+    where
+        Self: Sized,
+    {
+        let input = input.as_ref();
+        self.report_decoded_or_error(
+            input.len(),
+            self.engine.decode_chunked(input, sink),
+            |&len| len,
+        )
+    }
+
+    fn decode_slice<T: AsRef<[u8]>>(
+        &self,
+        input: T,
+        output: &mut [u8],
+    ) -> Result<usize, DecodeSliceError> {
+        let input = input.as_ref();
+        self.report_decoded(
+            input.len(),
+            self.engine.decode_slice(input, output),
+            |&len| len,
+        )
+    }
+
+    fn decode_slice_with_meta<T: AsRef<[u8]>>(
+        &self,
+        input: T,
+        output: &mut [u8],
+    ) -> Result<DecodeMetadata, DecodeSliceError> {
+        let input = input.as_ref();
+        self.report_decoded(
+            input.len(),
+            self.engine.decode_slice_with_meta(input, output),
+            |metadata| metadata.decoded_len,
+        )
+    }
+
+    fn decode_slice_unchecked<T: AsRef<[u8]>>(
+        &self,
+        input: T,
+        output: &mut [u8],
+    ) -> Result<usize, DecodeError> {
+        let input = input.as_ref();
+        self.report_decoded_or_error(
+            input.len(),
+            self.engine.decode_slice_unchecked(input, output),
+            |&len| len,
+        )
+    }
+
+    fn decode_into_ring<T: AsRef<[u8]>>(
+        &self,
+        input: T,
+        ring: &mut RingSlice<'_>,
+    ) -> Result<usize, DecodeSliceError> {
+        let input = input.as_ref();
+        self.report_decoded(
+            input.len(),
+            self.engine.decode_into_ring(input, ring),
+            |&len| len,
+        )
+    }
+
+    fn find_invalid<T: AsRef<[u8]>>(&self, input: T) -> Option<usize> {
+        self.engine.find_invalid(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::{
+            adapters::PrefixSuffix,
+            general_purpose::{STANDARD, STANDARD_NO_PAD},
+        },
+        read::DecoderReader,
+        DecodeError,
+    };
+    use std::{io::Read as _, sync::Mutex, vec::Vec};
+
+    /// Records every event
+    #[derive(Default)]
+    struct Recorder {
+        events: Mutex<Vec<(&'static str, usize, usize)>>,
+    }
+
+    impl CodecObserver for Recorder {
+        fn encoded(&self, input_len: usize, output_len: usize) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(("encoded", input_len, output_len));
+        }
+
+        fn decoded(&self, input_len: usize, output_len: usize) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(("decoded", input_len, output_len));
+        }
+
+        fn decode_failed(&self, input_len: usize, error: &DecodeSliceError) {
+            let offset = match error {
+                DecodeSliceError::DecodeError(DecodeError::InvalidByte(offset, _)) => *offset,
+                _ => usize::MAX,
+            };
+            self.events
+                .lock()
+                .unwrap()
+                .push(("decode_failed", input_len, offset));
+        }
+    }
+
+    #[test]
+    fn observes_encoding_and_decoding() {
+        let recorder = Recorder::default();
+        let engine = Observed::new(STANDARD, &recorder);
+
+        assert_eq!("aGVsbG8=", engine.encode(b"hello"));
+        assert_eq!(b"hello", &engine.decode("aGVsbG8=").unwrap()[..]);
+        assert_eq!(
+            Err(DecodeError::InvalidByte(2, b'*')),
+            engine.decode("aG*sbG8=")
+        );
+        let mut small = [0; 2];
+        assert!(engine.decode_slice("aGVsbG8=", &mut small).is_err());
+
+        assert_eq!(
+            vec![
+                ("encoded", 5, 8),
+                ("decoded", 8, 5),
+                ("decode_failed", 8, 2),
+                ("decode_failed", 8, usize::MAX),
+            ],
+            *recorder.events.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn forwards_overridden_methods_and_reports_written_len() {
+        let recorder = Recorder::default();
+        let framed = PrefixSuffix::new(STANDARD, "<", ">");
+        let engine = Observed::new(framed.clone(), &recorder);
+
+        assert_eq!(framed.encoded_len(5), engine.encoded_len(5));
+        assert_eq!("<aGVsbG8=>", engine.encode(b"hello"));
+        assert_eq!("<aGVsbG8=>", engine.encode_scattered(&[b"he", b"llo"]));
+        let mut buf = [0; 10];
+        assert_eq!(Ok(10), engine.encode_slice(b"hello", &mut buf));
+        assert_eq!(b"<aGVsbG8=>", &buf);
+        assert_eq!(b"hello", &engine.decode("<aGVsbG8=>").unwrap()[..]);
+        assert_eq!(framed.find_invalid("<aG*s>"), engine.find_invalid("<aG*s>"));
+
+        assert_eq!(
+            vec![
+                ("encoded", 5, 10),
+                ("encoded", 5, 10),
+                ("encoded", 5, 10),
+                ("decoded", 10, 5),
+            ],
+            *recorder.events.lock().unwrap()
+        );
+    }
+
+    #[test]
+    fn observes_each_chunk_of_a_stream() {
+        let recorder = Recorder::default();
+        let engine = Observed::new(STANDARD_NO_PAD, &recorder);
+        let encoded = STANDARD_NO_PAD.encode([0xAB; 3000]);
+
+        let mut decoded = Vec::new();
+        let _ = DecoderReader::new(encoded.as_bytes(), &engine)
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(vec![0xAB; 3000], decoded);
+
+        let events = recorder.events.lock().unwrap();
+        assert!(events.len() > 1);
+        assert_eq!(
+            3000,
+            events
+                .iter()
+                .map(|&(_, _, output_len)| output_len)
+                .sum::<usize>()
+        );
+        assert_eq!(
+            encoded.len(),
+            events
+                .iter()
+                .map(|&(_, input_len, _)| input_len)
+                .sum::<usize>()
+        );
+    }
+}
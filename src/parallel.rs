@@ -0,0 +1,406 @@
+//! Encoding large streams on several threads.
+//!
+//! [`encode_reader_to_writer`] is like [`copy_encode`](crate::io::copy_encode), but encodes many
+//! large blocks of the input at once on a [rayon](https://docs.rs/rayon) thread pool, while the
+//! next blocks are read and the previous ones are written in order. This keeps both the disks and
+//! the CPUs busy when encoding multi-gigabyte files for backups or archives.
+//!
+//! # Examples
+//!
+//! ```
+//! use base64::{engine::general_purpose::STANDARD, parallel, Engine as _};
+//!
+//! let data = vec![0xAB; 3_000_000];
+//! let mut encoded = Vec::new();
+//! let encoded_len = parallel::encode_reader_to_writer(&data[..], &mut encoded, &STANDARD, 4).unwrap();
+//!
+//! assert_eq!(STANDARD.encode(&data).as_bytes(), &encoded[..]);
+//! assert_eq!(4_000_000, encoded_len);
+//! ```
+
+use crate::{
+    encode::{add_padding, line_breaks, wrap_lines},
+    engine::{Config, Engine, Padding},
+    io::{read_full, whole_blocks_len},
+};
+use rayon::prelude::*;
+use std::{convert::TryFrom, io};
+
+/// Length of the raw data in each block encoded on its own: a multiple of 3, so a full block
+/// encodes without padding.
+const RAW_BLOCK_LEN: usize = 3 * 256 * 1024;
+
+/// Read everything from `reader` and write its base64 encoding to `writer`, encoding blocks of it
+/// on `threads` threads, including padding and line wrapping as configured in `engine`.
+///
+/// With `threads` set to 0, rayon picks the number of threads, by default one per CPU. Each thread
+/// gets a block of 768 KiB at a time. So that reading and writing can overlap with encoding, four
+/// batches of one block per thread are held in memory: the input being encoded and the next input
+/// being read, and the encoding in progress and the previous one being written. That's about
+/// 3.5 MiB per thread, more if lines are wrapped.
+///
+/// Returns the number of bytes written to `writer`.
+///
+/// # Errors
+///
+/// Errors other than [`io::ErrorKind::Interrupted`] from either `reader` or `writer` are returned,
+/// as is an error of kind [`io::ErrorKind::Other`] if the thread pool can't be created. Some of the
+/// output may have been written by then.
+pub fn encode_reader_to_writer<R, W, E>(
+    reader: R,
+    writer: W,
+    engine: &E,
+    threads: usize,
+) -> io::Result<u64>
+where
+    R: io::Read + Send,
+    W: io::Write + Send,
+    E: Engine + Sync,
+{
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+
+    // keep every block but the last a whole number of the engine's blocks, if any fit
+    let block_len = whole_blocks_len(engine.preferred_block_size(), RAW_BLOCK_LEN);
+
+    pool.install(|| {
+        encode_blocks(
+            reader,
+            writer,
+            engine,
+            rayon::current_num_threads(),
+            block_len,
+        )
+    })
+}
+
+/// Encode `reader` into `writer` in batches of `batch_len` blocks of `block_len` bytes, encoding
+/// one batch while writing the previous one and reading the next.
+fn encode_blocks<R, W, E>(
+    mut reader: R,
+    mut writer: W,
+    engine: &E,
+    batch_len: usize,
+    block_len: usize,
+) -> io::Result<u64>
+where
+    R: io::Read + Send,
+    W: io::Write + Send,
+    E: Engine + Sync,
+{
+    debug_assert_eq!(0, block_len % 3);
+
+    let mut raw = Vec::new();
+    let mut at_eof = read_batch(&mut reader, &mut raw, batch_len, block_len)?;
+    let mut next_raw = Vec::new();
+    let mut encoded = Vec::new();
+    // the encoding of the previous batch, being written while the current one is encoded
+    let mut prev_encoded: Vec<Vec<u8>> = Vec::new();
+    // length of the input before the current batch
+    let mut raw_offset = 0_u64;
+    let mut written = 0_u64;
+
+    loop {
+        let ((), next) = rayon::join(
+            || encode_batch(engine, &raw, raw_offset, at_eof, &mut encoded),
+            || -> io::Result<Option<bool>> {
+                for block in &prev_encoded {
+                    writer.write_all(block)?;
+                }
+                if at_eof {
+                    Ok(None)
+                } else {
+                    read_batch(&mut reader, &mut next_raw, batch_len, block_len).map(Some)
+                }
+            },
+        );
+        let next = next?;
+        written += total_len(&prev_encoded);
+        raw_offset += total_len(&raw);
+        std::mem::swap(&mut prev_encoded, &mut encoded);
+
+        match next {
+            Some(next_at_eof) => {
+                std::mem::swap(&mut raw, &mut next_raw);
+                at_eof = next_at_eof;
+            }
+            None => {
+                for block in &prev_encoded {
+                    writer.write_all(block)?;
+                }
+                return Ok(written + total_len(&prev_encoded));
+            }
+        }
+    }
+}
+
+/// Read up to `batch_len` blocks of `block_len` bytes into `blocks`, reusing their allocations.
+///
+/// Returns whether `reader` is exhausted, in which case the last block may be short or empty.
+fn read_batch<R: io::Read>(
+    reader: &mut R,
+    blocks: &mut Vec<Vec<u8>>,
+    batch_len: usize,
+    block_len: usize,
+) -> io::Result<bool> {
+    blocks.resize_with(batch_len, Vec::new);
+
+    for i in 0..batch_len {
+        let block = &mut blocks[i];
+        block.resize(block_len, 0);
+        let len = read_full(reader, block)?;
+        block.truncate(len);
+
+        if len < block_len {
+            blocks.truncate(i + 1);
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Encode `blocks` into `encoded` in parallel, reusing its allocations. `raw_offset` is the length
+/// of the input before the first block, and `at_eof` whether the last block is the end of the
+/// input, so that it gets padded.
+fn encode_batch<E: Engine + Sync>(
+    engine: &E,
+    blocks: &[Vec<u8>],
+    raw_offset: u64,
+    at_eof: bool,
+    encoded: &mut Vec<Vec<u8>>,
+) {
+    encoded.resize_with(blocks.len(), Vec::new);
+    let block_len = blocks.first().map_or(0, Vec::len);
+
+    blocks
+        .par_iter()
+        .zip(encoded.par_iter_mut())
+        .enumerate()
+        .for_each(|(i, (block, output))| {
+            // every block before the last is full
+            let offset = raw_offset + (i * block_len) as u64;
+            let last = at_eof && i == blocks.len() - 1;
+            encode_block(engine, block, offset, last, output);
+        });
+}
+
+/// Encode `block`, which starts `raw_offset` bytes into the input, into `output`, wrapping lines
+/// where they fall in the output as a whole.
+fn encode_block<E: Engine>(
+    engine: &E,
+    block: &[u8],
+    raw_offset: u64,
+    last: bool,
+    output: &mut Vec<u8>,
+) {
+    let line_len = engine.config().encode_line_wrap();
+    let line_ending = engine.config().encode_line_ending().as_bytes();
+    // every block before this one was a multiple of 3 bytes
+    let column = column_at(raw_offset / 3 * 4, line_len);
+    let max_encoded_len = (block.len() + 2) / 3 * 4;
+    // the line ending after the last line, unless there's no output at all
+    let trailing_newline =
+        last && (raw_offset > 0 || !block.is_empty()) && engine.config().encode_trailing_newline();
+    output.resize(
+        max_encoded_len
+            + (line_breaks(max_encoded_len, line_len, column) + usize::from(trailing_newline))
+                * line_ending.len(),
+        0,
+    );
+
+    let mut encoded_len = engine.internal_encode(block, output);
+    if let (true, Padding::Padded(padding_byte)) = (last, engine.config().padding()) {
+        encoded_len += add_padding(encoded_len, &mut output[encoded_len..], padding_byte);
+    }
+
+    let mut wrapped_len = wrap_lines(output, encoded_len, line_len, column, line_ending);
+    if trailing_newline {
+        output[wrapped_len..wrapped_len + line_ending.len()].copy_from_slice(line_ending);
+        wrapped_len += line_ending.len();
+    }
+    output.truncate(wrapped_len);
+}
+
+/// Returns the column on the current line after `encoded_len` bytes of output from the start, as
+/// for `column_after()`, but for output too long for a `usize`.
+fn column_at(encoded_len: u64, line_len: usize) -> usize {
+    if line_len == 0 || encoded_len == 0 {
+        0
+    } else {
+        // the remainder is smaller than `line_len`, so it fits
+        usize::try_from((encoded_len - 1) % line_len as u64).expect("remainder fits in usize") + 1
+    }
+}
+
+/// Returns the total length of `blocks`.
+fn total_len(blocks: &[Vec<u8>]) -> u64 {
+    // usize always fits in u64
+    blocks.iter().map(|block| block.len() as u64).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alphabet,
+        engine::{general_purpose, GeneralPurpose},
+        tests::{random_alphabet, random_config},
+    };
+    use rand::{rngs::SmallRng, Rng, RngCore, SeedableRng};
+
+    // Hands out a few bytes per read, sometimes yielding the thread first, so reading overlaps with
+    // encoding differently from run to run
+    struct JitterRead<'a> {
+        data: &'a [u8],
+        rng: SmallRng,
+    }
+
+    impl<'a> io::Read for JitterRead<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.rng.gen() {
+                std::thread::yield_now();
+            }
+            let len = self
+                .rng
+                .gen_range(1..=50)
+                .min(buf.len())
+                .min(self.data.len());
+            buf[..len].copy_from_slice(&self.data[..len]);
+            self.data = &self.data[len..];
+            Ok(len)
+        }
+    }
+
+    // Takes a few bytes per write, sometimes yielding the thread first
+    struct JitterWrite {
+        written: Vec<u8>,
+        rng: SmallRng,
+    }
+
+    impl io::Write for JitterWrite {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.rng.gen() {
+                std::thread::yield_now();
+            }
+            let len = self.rng.gen_range(1..=50).min(buf.len());
+            self.written.extend_from_slice(&buf[..len]);
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn encode_blocks_matches_encode() {
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..1_000 {
+            let mut input = vec![0; rng.gen_range(0..1_000)];
+            rng.fill_bytes(&mut input);
+            let engine = GeneralPurpose::new(
+                random_alphabet(&mut rng),
+                random_config(&mut rng)
+                    .with_encode_line_wrap(rng.gen_range(0..10))
+                    .with_encode_trailing_newline(rng.gen())
+                    .with_encode_line_ending(rng.gen()),
+            );
+            let batch_len = rng.gen_range(1..5);
+            let block_len = rng.gen_range(1..30) * 3;
+
+            let mut encoded = Vec::new();
+            let written =
+                encode_blocks(&input[..], &mut encoded, &engine, batch_len, block_len).unwrap();
+
+            assert_eq!(engine.encode(&input).as_bytes(), &encoded[..]);
+            assert_eq!(encoded.len() as u64, written);
+        }
+    }
+
+    #[test]
+    fn encode_blocks_output_is_independent_of_scheduling() {
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..100 {
+            let mut input = vec![0; rng.gen_range(0..3_000)];
+            rng.fill_bytes(&mut input);
+            let engine = GeneralPurpose::new(
+                random_alphabet(&mut rng),
+                random_config(&mut rng)
+                    .with_encode_line_wrap(rng.gen_range(0..10))
+                    .with_encode_trailing_newline(rng.gen())
+                    .with_encode_line_ending(rng.gen()),
+            );
+            let expected = engine.encode(&input);
+            let threads = rng.gen_range(1..9);
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+
+            // many small blocks, so every batch hands off chunk boundaries between threads
+            for _ in 0..10 {
+                let batch_len = rng.gen_range(1..2 * threads + 1);
+                let block_len = rng.gen_range(1..10) * 3;
+                let reader = JitterRead {
+                    data: &input,
+                    rng: SmallRng::from_rng(&mut rng).unwrap(),
+                };
+                let mut writer = JitterWrite {
+                    written: Vec::new(),
+                    rng: SmallRng::from_rng(&mut rng).unwrap(),
+                };
+
+                let written = pool
+                    .install(|| encode_blocks(reader, &mut writer, &engine, batch_len, block_len))
+                    .unwrap();
+
+                assert_eq!(
+                    expected.as_bytes(),
+                    &writer.written[..],
+                    "threads {}, batch_len {}, block_len {}",
+                    threads,
+                    batch_len,
+                    block_len
+                );
+                assert_eq!(expected.len() as u64, written);
+            }
+        }
+    }
+
+    #[test]
+    fn encode_reader_to_writer_matches_encode() {
+        let mut rng = rand::thread_rng();
+        let mut input = vec![0; 2 * RAW_BLOCK_LEN + 1_000];
+        rng.fill_bytes(&mut input);
+        let engine = GeneralPurpose::new(
+            &alphabet::URL_SAFE,
+            general_purpose::PAD.with_encode_line_wrap(76),
+        );
+
+        for &threads in &[0, 1, 3] {
+            let mut encoded = Vec::new();
+            let written =
+                encode_reader_to_writer(&input[..], &mut encoded, &engine, threads).unwrap();
+
+            assert_eq!(engine.encode(&input).as_bytes(), &encoded[..]);
+            assert_eq!(encoded.len() as u64, written);
+        }
+    }
+
+    #[test]
+    fn column_at_matches_column_after() {
+        for line_len in 0..10 {
+            for len in 0..100 {
+                assert_eq!(
+                    crate::encode::column_after(len, line_len, 0),
+                    column_at(len as u64, line_len)
+                );
+            }
+        }
+    }
+}
@@ -1,13 +1,46 @@
-use crate::{engine::Engine, DecodeError, DecodeSliceError, PAD_BYTE};
-use std::{cmp, fmt, io};
+use crate::{
+    engine::{Config, Engine},
+    DecodeError, DecodeSliceError,
+};
+use std::{cmp, fmt, io, ops};
 
-// This should be large, but it has to fit on the stack.
+/// The default capacity of the buffer of base64 read from the delegate reader.
 pub(crate) const BUF_SIZE: usize = 1024;
 
 // 4 bytes of base64 data encode 3 bytes of raw data (modulo padding).
 const BASE64_CHUNK_SIZE: usize = 4;
 const DECODED_CHUNK_SIZE: usize = 3;
 
+/// Storage for the base64 read from the delegate reader: inline at the default capacity, so
+/// `DecoderReader::new()` doesn't allocate, or on the heap for a capacity picked with
+/// `DecoderReader::with_capacity()`.
+// Boxing the inline variant would defeat the point of it.
+#[allow(variant_size_differences, clippy::large_enum_variant)]
+enum B64Buffer {
+    Inline([u8; BUF_SIZE]),
+    Heap(Box<[u8]>),
+}
+
+impl ops::Deref for B64Buffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            B64Buffer::Inline(buf) => buf,
+            B64Buffer::Heap(buf) => buf,
+        }
+    }
+}
+
+impl ops::DerefMut for B64Buffer {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        match self {
+            B64Buffer::Inline(buf) => buf,
+            B64Buffer::Heap(buf) => buf,
+        }
+    }
+}
+
 /// A `Read` implementation that decodes base64 data read from an underlying reader.
 ///
 /// # Examples
@@ -30,17 +63,60 @@ const DECODED_CHUNK_SIZE: usize = 3;
 /// assert_eq!(b"asdf", &result[..]);
 ///
 /// ```
+///
+/// It also implements `BufRead`, so decoded data can be inspected before it's consumed:
+///
+/// ```
+/// use std::io::BufRead;
+/// use std::io::Cursor;
+/// use base64::engine::general_purpose;
+///
+/// let mut decoder = base64::read::DecoderReader::with_capacity(
+///     4096,
+///     Cursor::new(b"aGVsbG8Kd29ybGQK"),
+///     &general_purpose::STANDARD);
+///
+/// let mut line = String::new();
+/// decoder.read_line(&mut line).unwrap();
+/// assert_eq!("hello\n", line);
+/// assert_eq!(b"world\n", decoder.fill_buf().unwrap());
+/// ```
+///
+/// With [`DecoderReader::recover_from_errors`], invalid base64 is skipped rather than ending the
+/// read, and the errors are collected to be checked afterwards:
+///
+/// ```
+/// use std::io::Read;
+/// use base64::engine::general_purpose;
+///
+/// let mut decoder = base64::read::DecoderReader::new(
+///     &b"aGVs*G8gd29ybGQ="[..],
+///     &general_purpose::STANDARD,
+/// )
+/// .recover_from_errors();
+///
+/// let mut result = Vec::new();
+/// decoder.read_to_end(&mut result).unwrap();
+/// assert_eq!(b"helworld", &result[..]);
+/// assert_eq!(
+///     &[base64::DecodeError::InvalidByte(4, b'*')],
+///     decoder.recovered_errors()
+/// );
+/// ```
 pub struct DecoderReader<'e, E: Engine, R: io::Read> {
     engine: &'e E,
     /// Where b64 data is read from
     inner: R,
 
     /// Holds b64 data read from the delegate reader.
-    b64_buffer: [u8; BUF_SIZE],
+    b64_buffer: B64Buffer,
     /// The start of the pending buffered data in `b64_buffer`.
     b64_offset: usize,
     /// The amount of buffered b64 data after `b64_offset` in `b64_len`.
     b64_len: usize,
+    /// The number of base64 symbols in the buffered b64 data, counted once as it's read so that
+    /// each read doesn't scan the buffer again.
+    b64_symbols: usize,
     /// Since the caller may provide us with a buffer of size 1 or 2 that's too small to copy a
     /// decoded chunk in to, we have to be able to hang on to a few decoded bytes.
     /// Technically we only need to hold 2 bytes, but then we'd need a separate temporary buffer to
@@ -54,21 +130,47 @@ pub struct DecoderReader<'e, E: Engine, R: io::Read> {
     /// Input length consumed so far.
     /// Used to provide accurate offsets in errors
     input_consumed_len: usize,
+    /// Number of base64 symbols, i.e. input bytes the engine doesn't ignore, consumed so far.
+    /// Used to provide accurate lengths in errors
+    symbols_consumed_len: usize,
     /// offset of previously seen padding, if any
     padding_offset: Option<usize>,
+    /// Holds decoded data for `BufRead`. Only allocated on the first `fill_buf()`, so plain
+    /// `Read` use doesn't pay for it.
+    output_buffer: Vec<u8>,
+    /// Index of start of decoded data in `output_buffer`
+    output_offset: usize,
+    /// Length of decoded data after `output_offset` in `output_buffer`
+    output_len: usize,
+    /// Whether to skip the quad with a decoding error and carry on, rather than fail the read
+    recover: bool,
+    /// The errors skipped over so far when `recover` is set
+    recovered_errors: Vec<DecodeError>,
+    /// Set when a decode skipped invalid input and had nothing left to return, so the read goes
+    /// on to decode more rather than report EOF
+    skipped_all: bool,
+    /// Entered for each call, so events are attributed to this reader
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
 }
 
-// exclude b64_buffer as it's uselessly large
+// exclude b64_buffer and output_buffer as they're uselessly large
 impl<'e, E: Engine, R: io::Read> fmt::Debug for DecoderReader<'e, E, R> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("DecoderReader")
             .field("b64_offset", &self.b64_offset)
             .field("b64_len", &self.b64_len)
+            .field("b64_symbols", &self.b64_symbols)
             .field("decoded_chunk_buffer", &self.decoded_chunk_buffer)
             .field("decoded_offset", &self.decoded_offset)
             .field("decoded_len", &self.decoded_len)
             .field("input_consumed_len", &self.input_consumed_len)
+            .field("symbols_consumed_len", &self.symbols_consumed_len)
             .field("padding_offset", &self.padding_offset)
+            .field("output_offset", &self.output_offset)
+            .field("output_len", &self.output_len)
+            .field("recover", &self.recover)
+            .field("recovered_errors", &self.recovered_errors)
             .finish()
     }
 }
@@ -76,20 +178,78 @@ impl<'e, E: Engine, R: io::Read> fmt::Debug for DecoderReader<'e, E, R> {
 impl<'e, E: Engine, R: io::Read> DecoderReader<'e, E, R> {
     /// Create a new decoder that will read from the provided reader `r`.
     pub fn new(reader: R, engine: &'e E) -> Self {
+        Self::with_buffer(B64Buffer::Inline([0; BUF_SIZE]), reader, engine)
+    }
+
+    /// Create a new decoder that will read from the provided reader `r`, buffering up to
+    /// `capacity` bytes of base64 per `read()` from it.
+    ///
+    /// A larger capacity means fewer calls to the delegate reader, and more decoded data available
+    /// at once from `fill_buf()`, at the cost of memory: the decoder holds `capacity` bytes of
+    /// base64 and, once `BufRead` is used, up to 3/4 of that again in decoded bytes.
+    ///
+    /// Capacities smaller than one base64 chunk (4 bytes) are rounded up to it. Unlike `new()`,
+    /// which keeps its buffer inline, this allocates the buffer on the heap.
+    pub fn with_capacity(capacity: usize, reader: R, engine: &'e E) -> Self {
+        let buffer = vec![0; cmp::max(capacity, BASE64_CHUNK_SIZE)].into_boxed_slice();
+        Self::with_buffer(B64Buffer::Heap(buffer), reader, engine)
+    }
+
+    fn with_buffer(b64_buffer: B64Buffer, reader: R, engine: &'e E) -> Self {
+        #[cfg(feature = "tracing")]
+        let capacity = b64_buffer.len();
         DecoderReader {
             engine,
             inner: reader,
-            b64_buffer: [0; BUF_SIZE],
+            b64_buffer,
             b64_offset: 0,
             b64_len: 0,
+            b64_symbols: 0,
             decoded_chunk_buffer: [0; DECODED_CHUNK_SIZE],
             decoded_offset: 0,
             decoded_len: 0,
             input_consumed_len: 0,
+            symbols_consumed_len: 0,
             padding_offset: None,
+            output_buffer: Vec::new(),
+            output_offset: 0,
+            output_len: 0,
+            recover: false,
+            recovered_errors: Vec::new(),
+            skipped_all: false,
+            #[cfg(feature = "tracing")]
+            span: tracing::debug_span!("base64::DecoderReader", capacity),
         }
     }
 
+    /// Returns the number of bytes of base64 read from the delegate reader that can be buffered.
+    pub fn capacity(&self) -> usize {
+        self.b64_buffer.len()
+    }
+
+    /// Skip invalid base64 rather than failing the read.
+    ///
+    /// When a chunk of input fails to decode, the quad (4 symbols) with the error is skipped, and
+    /// decoding carries on from the next quad, so one corrupt record doesn't stop a batch job
+    /// reading millions of them. Errors are collected rather than returned; check them with
+    /// [`DecoderReader::recovered_errors`]. Errors from the delegate reader are still returned.
+    pub fn recover_from_errors(mut self) -> Self {
+        self.recover = true;
+        self
+    }
+
+    /// Returns the errors skipped over so far with [`DecoderReader::recover_from_errors`], in the
+    /// order they were found in the input.
+    pub fn recovered_errors(&self) -> &[DecodeError] {
+        &self.recovered_errors
+    }
+
+    /// Returns the errors skipped over so far with [`DecoderReader::recover_from_errors`], and
+    /// clears them, so that a long-running reader doesn't accumulate errors forever.
+    pub fn take_recovered_errors(&mut self) -> Vec<DecodeError> {
+        std::mem::take(&mut self.recovered_errors)
+    }
+
     /// Write as much as possible of the decoded buffer into the target buffer.
     /// Must only be called when there is something to write and space to write into.
     /// Returns a Result with the number of (decoded) bytes copied.
@@ -117,18 +277,92 @@ impl<'e, E: Engine, R: io::Read> DecoderReader<'e, E, R> {
     /// Must only be called when there is space to read into in the buffer.
     /// Returns the number of bytes read.
     fn read_from_delegate(&mut self) -> io::Result<usize> {
-        debug_assert!(self.b64_offset + self.b64_len < BUF_SIZE);
+        debug_assert!(self.b64_offset + self.b64_len < self.b64_buffer.len());
 
         let read = self
             .inner
             .read(&mut self.b64_buffer[self.b64_offset + self.b64_len..])?;
+        self.b64_symbols += self.buffered_symbols_between(self.b64_len, self.b64_len + read);
         self.b64_len += read;
+        #[cfg(feature = "tracing")]
+        tracing::trace!(read_len = read, "read base64 from delegate");
 
-        debug_assert!(self.b64_offset + self.b64_len <= BUF_SIZE);
+        debug_assert!(self.b64_offset + self.b64_len <= self.b64_buffer.len());
 
         Ok(read)
     }
 
+    /// Returns the number of buffered bytes that are base64 symbols rather than bytes ignored by the
+    /// engine, like line endings.
+    fn buffered_symbols(&self) -> usize {
+        self.b64_symbols
+    }
+
+    /// Returns the number of base64 symbols in the first `len` bytes of buffered data.
+    fn buffered_symbols_in(&self, len: usize) -> usize {
+        self.buffered_symbols_between(0, len)
+    }
+
+    /// Returns the number of base64 symbols from `start` to `end` in the buffered data.
+    fn buffered_symbols_between(&self, start: usize, end: usize) -> usize {
+        let config = self.engine.config();
+        if !config.decode_ignores_any() {
+            return end - start;
+        }
+
+        self.b64_buffer[self.b64_offset + start..self.b64_offset + end]
+            .iter()
+            .filter(|&&b| !config.decode_ignores(b))
+            .count()
+    }
+
+    /// Returns the length of the buffered data up to and including the `symbols`th base64 symbol,
+    /// or all of it if there are fewer symbols than that.
+    fn buffered_len_of_symbols(&self, symbols: usize) -> usize {
+        if symbols == 0 {
+            return 0;
+        }
+
+        let config = self.engine.config();
+        if !config.decode_ignores_any() {
+            return cmp::min(symbols, self.b64_len);
+        }
+
+        self.b64_buffer[self.b64_offset..self.b64_offset + self.b64_len]
+            .iter()
+            .enumerate()
+            .filter(|&(_, &b)| !config.decode_ignores(b))
+            .nth(symbols - 1)
+            .map_or(self.b64_len, |(i, _)| i + 1)
+    }
+
+    /// Remove the bytes ignored by the engine from the buffer, to make room to read more symbols
+    /// when it's full of them.
+    ///
+    /// This only happens with over a thousand ignored bytes between a few symbols, so rather than
+    /// track where they were, they're counted as consumed already. Offsets in errors in those few
+    /// symbols may be too large, but later offsets are correct.
+    fn drop_ignored(&mut self) {
+        let config = self.engine.config();
+        let mut kept_len = 0;
+        for i in self.b64_offset..self.b64_offset + self.b64_len {
+            let b = self.b64_buffer[i];
+            if !config.decode_ignores(b) {
+                self.b64_buffer[self.b64_offset + kept_len] = b;
+                kept_len += 1;
+            }
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            dropped_len = self.b64_len - kept_len,
+            "dropped ignored bytes to make room"
+        );
+        debug_assert_eq!(self.b64_symbols, kept_len);
+        self.input_consumed_len += self.b64_len - kept_len;
+        self.b64_len = kept_len;
+    }
+
     /// Decode the requested number of bytes from the b64 buffer into the provided buffer. It's the
     /// caller's responsibility to choose the number of b64 bytes to decode correctly.
     ///
@@ -137,12 +371,18 @@ impl<'e, E: Engine, R: io::Read> DecoderReader<'e, E, R> {
     /// # Panics
     ///
     /// panics if `buf` is too small
-    fn decode_to_buf(&mut self, b64_len_to_decode: usize, buf: &mut [u8]) -> io::Result<usize> {
+    fn decode_to_buf(
+        &mut self,
+        b64_len_to_decode: usize,
+        symbols_to_decode: usize,
+        buf: &mut [u8],
+    ) -> Result<usize, DecodeError> {
         debug_assert!(self.b64_len >= b64_len_to_decode);
-        debug_assert!(self.b64_offset + self.b64_len <= BUF_SIZE);
+        debug_assert!(self.b64_offset + self.b64_len <= self.b64_buffer.len());
         debug_assert!(!buf.is_empty());
 
         let b64_to_decode = &self.b64_buffer[self.b64_offset..self.b64_offset + b64_len_to_decode];
+        let padding_byte = self.engine.config().padding().decode_byte();
         let decode_metadata = self
             .engine
             .internal_decode(
@@ -159,8 +399,8 @@ impl<'e, E: Engine, R: io::Read> DecoderReader<'e, E, R> {
                                 // be correct, and we now find more padding that happens to be incorrect,
                                 // to be consistent with non-reader decodes, record the error at the first
                                 // padding
-                                (PAD_BYTE, Some(first_pad_offset)) => {
-                                    DecodeError::InvalidByte(first_pad_offset, PAD_BYTE)
+                                (b, Some(first_pad_offset)) if b == padding_byte => {
+                                    DecodeError::InvalidByte(first_pad_offset, padding_byte)
                                 }
                                 _ => {
                                     DecodeError::InvalidByte(self.input_consumed_len + offset, byte)
@@ -168,7 +408,7 @@ impl<'e, E: Engine, R: io::Read> DecoderReader<'e, E, R> {
                             }
                         }
                         DecodeError::InvalidLength(len) => {
-                            DecodeError::InvalidLength(self.input_consumed_len + len)
+                            DecodeError::InvalidLength(self.symbols_consumed_len + len)
                         }
                         DecodeError::InvalidLastSymbol {
                             offset,
@@ -180,73 +420,132 @@ impl<'e, E: Engine, R: io::Read> DecoderReader<'e, E, R> {
                             symbol_value,
                         },
                         DecodeError::InvalidPadding => DecodeError::InvalidPadding,
+                        DecodeError::PaddingNotAtEnd(offset) => DecodeError::PaddingNotAtEnd(
+                            self.padding_offset
+                                .unwrap_or(self.input_consumed_len + offset),
+                        ),
+                        DecodeError::InputTooLong { len, max } => {
+                            DecodeError::InputTooLong { len, max }
+                        }
+                        e @ DecodeError::UnexpectedDecodedLength { .. } => e,
                     }
                 }
-                DecodeSliceError::OutputSliceTooSmall => {
+                DecodeSliceError::OutputSliceTooSmall { .. } => {
                     unreachable!("buf is sized correctly in calling code")
                 }
-            })
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            })?;
 
         if let Some(offset) = self.padding_offset {
             // we've already seen padding
             if decode_metadata.decoded_len > 0 {
                 // we read more after already finding padding; report error at first padding byte
-                return Err(io::Error::new(
-                    io::ErrorKind::InvalidData,
-                    DecodeError::InvalidByte(offset, PAD_BYTE),
-                ));
+                return Err(DecodeError::InvalidByte(offset, padding_byte));
             }
         }
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            input_len = b64_len_to_decode,
+            decoded_len = decode_metadata.decoded_len,
+            "decoded chunk"
+        );
 
         self.padding_offset = self.padding_offset.or(decode_metadata
             .padding_offset
             .map(|offset| self.input_consumed_len + offset));
-        self.input_consumed_len += b64_len_to_decode;
-        self.b64_offset += b64_len_to_decode;
-        self.b64_len -= b64_len_to_decode;
-
-        debug_assert!(self.b64_offset + self.b64_len <= BUF_SIZE);
+        self.consume_b64(b64_len_to_decode, symbols_to_decode);
 
         Ok(decode_metadata.decoded_len)
     }
 
-    /// Unwraps this `DecoderReader`, returning the base reader which it reads base64 encoded
-    /// input from.
-    ///
-    /// Because `DecoderReader` performs internal buffering, the state of the inner reader is
-    /// unspecified. This function is mainly provided because the inner reader type may provide
-    /// additional functionality beyond the `Read` implementation which may still be useful.
-    pub fn into_inner(self) -> R {
-        self.inner
+    /// Mark the first `len` bytes of the b64 buffer, which hold `symbols` base64 symbols, as
+    /// consumed.
+    fn consume_b64(&mut self, len: usize, symbols: usize) {
+        debug_assert!(self.b64_len >= len);
+        debug_assert_eq!(self.buffered_symbols_in(len), symbols);
+
+        self.input_consumed_len += len;
+        self.symbols_consumed_len += symbols;
+        self.b64_symbols -= symbols;
+        self.b64_offset += len;
+        self.b64_len -= len;
+
+        debug_assert!(self.b64_offset + self.b64_len <= self.b64_buffer.len());
     }
-}
 
-impl<'e, E: Engine, R: io::Read> io::Read for DecoderReader<'e, E, R> {
-    /// Decode input from the wrapped reader.
-    ///
-    /// Under non-error circumstances, this returns `Ok` with the value being the number of bytes
-    /// written in `buf`.
-    ///
-    /// Where possible, this function buffers base64 to minimize the number of `read()` calls to the
-    /// delegate reader.
-    ///
-    /// # Errors
-    ///
-    /// Any errors emitted by the delegate reader are returned. Decoding errors due to invalid
-    /// base64 are also possible, and will have `io::ErrorKind::InvalidData`.
-    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        if buf.is_empty() {
-            return Ok(0);
-        }
+    /// Like `decode_to_buf()`, but if recovering from errors, a decoding error is recorded and the
+    /// quad it's in is skipped, after decoding the quads before it.
+    fn decode_or_recover(
+        &mut self,
+        b64_len_to_decode: usize,
+        symbols_to_decode: usize,
+        buf: &mut [u8],
+    ) -> io::Result<usize> {
+        let error = match self.decode_to_buf(b64_len_to_decode, symbols_to_decode, buf) {
+            Ok(decoded_len) => return Ok(decoded_len),
+            Err(e) if !self.recover => return Err(invalid_data(e)),
+            Err(e) => e,
+        };
+
+        let error_offset = match error {
+            DecodeError::InvalidByte(offset, _)
+            | DecodeError::InvalidLastSymbol { offset, .. }
+            | DecodeError::PaddingNotAtEnd(offset) => Some(offset),
+            _ => None,
+        };
+        let error_symbol = match error_offset {
+            Some(offset) if offset < self.input_consumed_len => {
+                // padding in an earlier chunk, followed by more base64: start over after it
+                self.padding_offset = None;
+                self.record_recovered(error, 0, 0);
+                return self.decode_or_recover(b64_len_to_decode, symbols_to_decode, buf);
+            }
+            Some(offset) => self.buffered_symbols_in(cmp::min(
+                offset - self.input_consumed_len,
+                b64_len_to_decode,
+            )),
+            // the error is about the end of the input, so blame the last symbol
+            None => symbols_to_decode.saturating_sub(1),
+        };
+
+        // the quads before the error decode fine
+        let valid_symbols = error_symbol - error_symbol % 4;
+        let valid_len = self.buffered_len_of_symbols(valid_symbols);
+        let decoded_len = if valid_len > 0 {
+            self.decode_or_recover(valid_len, valid_symbols, buf)?
+        } else {
+            0
+        };
 
-        // offset == BUF_SIZE when we copied it all last time
-        debug_assert!(self.b64_offset <= BUF_SIZE);
-        debug_assert!(self.b64_offset + self.b64_len <= BUF_SIZE);
-        debug_assert!(if self.b64_offset == BUF_SIZE {
+        let skip_len = cmp::min(
+            self.buffered_len_of_symbols(BASE64_CHUNK_SIZE),
+            b64_len_to_decode - valid_len,
+        );
+        let skip_symbols = cmp::min(BASE64_CHUNK_SIZE, symbols_to_decode - valid_symbols);
+        self.record_recovered(error, skip_len, skip_symbols);
+        self.skipped_all = decoded_len == 0;
+
+        Ok(decoded_len)
+    }
+
+    /// Record an error that was recovered from, and skip the first `skip_len` bytes of the b64
+    /// buffer that it was in, which hold `skip_symbols` symbols.
+    fn record_recovered(&mut self, error: DecodeError, skip_len: usize, skip_symbols: usize) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(%error, skip_len, "skipped invalid base64");
+        self.consume_b64(skip_len, skip_symbols);
+        self.recovered_errors.push(error);
+    }
+
+    /// Decode input from the wrapped reader into `buf`, once any data left behind by `fill_buf()`
+    /// is handed out.
+    fn read_decoded(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // offset == capacity when we copied it all last time
+        debug_assert!(self.b64_offset <= self.b64_buffer.len());
+        debug_assert!(self.b64_offset + self.b64_len <= self.b64_buffer.len());
+        debug_assert!(if self.b64_offset == self.b64_buffer.len() {
             self.b64_len == 0
         } else {
-            self.b64_len <= BUF_SIZE
+            self.b64_len <= self.b64_buffer.len()
         });
 
         debug_assert!(if self.decoded_len == 0 {
@@ -267,12 +566,24 @@ impl<'e, E: Engine, R: io::Read> io::Read for DecoderReader<'e, E, R> {
             self.flush_decoded_buf(buf)
         } else {
             let mut at_eof = false;
-            while self.b64_len < BASE64_CHUNK_SIZE {
+            while self.buffered_symbols() < BASE64_CHUNK_SIZE {
+                #[cfg(feature = "tracing")]
+                if self.b64_len > 0 {
+                    tracing::trace!(
+                        carried_len = self.b64_len,
+                        "carrying over an incomplete chunk of base64"
+                    );
+                }
+
                 // Copy any bytes we have to the start of the buffer.
                 self.b64_buffer
                     .copy_within(self.b64_offset..self.b64_offset + self.b64_len, 0);
                 self.b64_offset = 0;
 
+                if self.b64_len == self.b64_buffer.len() {
+                    self.drop_ignored();
+                }
+
                 // then fill in more data
                 let read = self.read_from_delegate()?;
                 if read == 0 {
@@ -282,18 +593,27 @@ impl<'e, E: Engine, R: io::Read> io::Read for DecoderReader<'e, E, R> {
                 }
             }
 
-            if self.b64_len == 0 {
+            let symbols = self.buffered_symbols();
+            if symbols == 0 {
                 debug_assert!(at_eof);
-                // we must be at EOF, and we have no data left to decode
+                // we must be at EOF, and we have no data left to decode other than ignored bytes
+                #[cfg(feature = "tracing")]
+                tracing::trace!(
+                    input_len = self.input_consumed_len + self.b64_len,
+                    "end of base64"
+                );
+                self.input_consumed_len += self.b64_len;
+                self.b64_offset += self.b64_len;
+                self.b64_len = 0;
                 return Ok(0);
             };
 
             debug_assert!(if at_eof {
                 // if we are at eof, we may not have a complete chunk
-                self.b64_len > 0
+                symbols > 0
             } else {
                 // otherwise, we must have at least one chunk
-                self.b64_len >= BASE64_CHUNK_SIZE
+                symbols >= BASE64_CHUNK_SIZE
             });
 
             debug_assert_eq!(0, self.decoded_len);
@@ -305,9 +625,15 @@ impl<'e, E: Engine, R: io::Read> io::Read for DecoderReader<'e, E, R> {
                 // if we are at eof, could have less than BASE64_CHUNK_SIZE, in which case we have
                 // to assume that these last few tokens are, in fact, valid (i.e. must be 2-4 b64
                 // tokens, not 1, since 1 token can't decode to 1 byte).
-                let to_decode = cmp::min(self.b64_len, BASE64_CHUNK_SIZE);
-
-                let decoded = self.decode_to_buf(to_decode, &mut decoded_chunk[..])?;
+                let symbols_to_decode = cmp::min(symbols, BASE64_CHUNK_SIZE);
+                let to_decode = self.buffered_len_of_symbols(symbols_to_decode);
+
+                let decoded =
+                    self.decode_or_recover(to_decode, symbols_to_decode, &mut decoded_chunk[..])?;
+                if decoded == 0 {
+                    // the chunk was skipped as invalid
+                    return Ok(0);
+                }
                 self.decoded_chunk_buffer[..decoded].copy_from_slice(&decoded_chunk[..decoded]);
 
                 self.decoded_offset = 0;
@@ -316,26 +642,125 @@ impl<'e, E: Engine, R: io::Read> io::Read for DecoderReader<'e, E, R> {
                 // can be less than 3 on last block due to padding
                 debug_assert!(decoded <= 3);
 
-                self.flush_decoded_buf(buf)
+                let flushed = self.flush_decoded_buf(buf);
+                #[cfg(feature = "tracing")]
+                if self.decoded_len > 0 {
+                    tracing::trace!(
+                        held_len = self.decoded_len,
+                        "holding decoded bytes for a later read"
+                    );
+                }
+                flushed
             } else {
-                let b64_bytes_that_can_decode_into_buf = (buf.len() / DECODED_CHUNK_SIZE)
+                let symbols_that_can_decode_into_buf = (buf.len() / DECODED_CHUNK_SIZE)
                     .checked_mul(BASE64_CHUNK_SIZE)
                     .expect("too many chunks");
-                debug_assert!(b64_bytes_that_can_decode_into_buf >= BASE64_CHUNK_SIZE);
+                debug_assert!(symbols_that_can_decode_into_buf >= BASE64_CHUNK_SIZE);
 
-                let b64_bytes_available_to_decode = if at_eof {
-                    self.b64_len
+                let symbols_available_to_decode = if at_eof {
+                    symbols
                 } else {
                     // only use complete chunks
-                    self.b64_len - self.b64_len % 4
+                    symbols - symbols % 4
                 };
 
-                let actual_decode_len = cmp::min(
-                    b64_bytes_that_can_decode_into_buf,
-                    b64_bytes_available_to_decode,
+                let symbols_to_decode = cmp::min(
+                    symbols_that_can_decode_into_buf,
+                    symbols_available_to_decode,
                 );
-                self.decode_to_buf(actual_decode_len, buf)
+                let actual_decode_len = self.buffered_len_of_symbols(symbols_to_decode);
+                self.decode_or_recover(actual_decode_len, symbols_to_decode, buf)
+            }
+        }
+    }
+
+    /// Unwraps this `DecoderReader`, returning the base reader which it reads base64 encoded
+    /// input from.
+    ///
+    /// Because `DecoderReader` performs internal buffering, the state of the inner reader is
+    /// unspecified. This function is mainly provided because the inner reader type may provide
+    /// additional functionality beyond the `Read` implementation which may still be useful.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+/// Wrap a decoding error as an `io::Error`.
+fn invalid_data(error: DecodeError) -> io::Error {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(%error, "invalid base64");
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+impl<'e, E: Engine, R: io::Read> io::Read for DecoderReader<'e, E, R> {
+    /// Decode input from the wrapped reader.
+    ///
+    /// Under non-error circumstances, this returns `Ok` with the value being the number of bytes
+    /// written in `buf`.
+    ///
+    /// Where possible, this function buffers base64 to minimize the number of `read()` calls to the
+    /// delegate reader.
+    ///
+    /// # Errors
+    ///
+    /// Any errors emitted by the delegate reader are returned. Decoding errors due to invalid
+    /// base64 are also possible, and will have `io::ErrorKind::InvalidData`.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        #[cfg(feature = "tracing")]
+        let _entered = self.span.clone().entered();
+
+        if self.output_len > 0 {
+            // `fill_buf()` left decoded data behind; hand that out before decoding more
+            let copy_len = cmp::min(self.output_len, buf.len());
+            buf[..copy_len].copy_from_slice(
+                &self.output_buffer[self.output_offset..self.output_offset + copy_len],
+            );
+            self.output_offset += copy_len;
+            self.output_len -= copy_len;
+
+            return Ok(copy_len);
+        }
+
+        loop {
+            let read = self.read_decoded(buf)?;
+            if !std::mem::replace(&mut self.skipped_all, false) {
+                return Ok(read);
+            }
+        }
+    }
+}
+
+impl<'e, E: Engine, R: io::Read> io::BufRead for DecoderReader<'e, E, R> {
+    /// Returns the buffered decoded data, decoding more from the wrapped reader if there is none.
+    ///
+    /// Decoding errors are reported as in `read()`.
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.output_len == 0 {
+            if self.output_buffer.is_empty() {
+                // every symbol in a full b64 buffer can be decoded in one go
+                let len = self.b64_buffer.len() / BASE64_CHUNK_SIZE * DECODED_CHUNK_SIZE;
+                self.output_buffer = vec![0; len];
             }
+
+            // take the buffer to read into it through the usual path without a double borrow
+            let mut output_buffer = std::mem::take(&mut self.output_buffer);
+            let read = io::Read::read(self, &mut output_buffer);
+            self.output_buffer = output_buffer;
+
+            self.output_offset = 0;
+            self.output_len = read?;
         }
+
+        Ok(&self.output_buffer[self.output_offset..self.output_offset + self.output_len])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = cmp::min(amt, self.output_len);
+        self.output_offset += amt;
+        self.output_len -= amt;
     }
 }
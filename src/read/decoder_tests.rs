@@ -1,6 +1,6 @@
 use std::{
     cmp,
-    io::{self, Read as _},
+    io::{self, BufRead as _, Read as _},
     iter,
 };
 
@@ -425,6 +425,260 @@ fn internal_padding_anywhere_error() {
     }
 }
 
+#[test]
+fn wrapped_lines_with_short_reads() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..2_000 {
+        let size = rng.gen_range(0..(5 * BUF_SIZE));
+        let mut bytes = vec![0; size];
+        rng.fill_bytes(&mut bytes[..]);
+        let mut decoded = vec![0; size * 3];
+
+        let engine = GeneralPurpose::new(
+            random_alphabet(&mut rng),
+            random_config(&mut rng).with_encode_line_wrap(rng.gen_range(1..100)),
+        );
+        let mut b64 = engine.encode(&bytes);
+        if rng.gen() {
+            b64 = b64.replace('\n', "\r\n");
+        }
+
+        let mut base_reader = io::Cursor::new(&b64[..]);
+        let mut short_delegate = RandomShortRead {
+            delegate: &mut base_reader,
+            rng: &mut rand::thread_rng(),
+        };
+        let mut decoder = DecoderReader::new(&mut short_delegate, &engine);
+
+        consume_with_short_reads_and_validate(&mut rng, &bytes[..], &mut decoded, &mut decoder);
+    }
+}
+
+#[test]
+fn wrapped_lines_report_errors_like_engine() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..1_000 {
+        let size = rng.gen_range(1..(5 * BUF_SIZE));
+        let mut bytes = vec![0; size];
+        rng.fill_bytes(&mut bytes[..]);
+
+        let engine = GeneralPurpose::new(
+            &alphabet::STANDARD,
+            STANDARD
+                .config()
+                .with_encode_line_wrap(rng.gen_range(1..100)),
+        );
+        let mut b64 = engine.encode(&bytes).into_bytes();
+        let invalid_index = loop {
+            let index = rng.gen_range(0..b64.len());
+            if b64[index] != b'\n' {
+                break index;
+            }
+        };
+        b64[invalid_index] = b'*';
+
+        let mut decoder = DecoderReader::new(io::Cursor::new(&b64), &engine);
+        let err = decoder.read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(
+            &engine.decode(&b64).unwrap_err(),
+            err.into_inner()
+                .unwrap()
+                .downcast_ref::<DecodeError>()
+                .unwrap()
+        );
+    }
+}
+
+#[test]
+fn buffer_full_of_line_endings() {
+    let engine = GeneralPurpose::new(
+        &alphabet::STANDARD,
+        STANDARD.config().with_encode_line_wrap(76),
+    );
+    let b64 = format!(
+        "\n{}Q{}UF{}B\n",
+        "\n".repeat(BUF_SIZE),
+        "\r\n".repeat(BUF_SIZE),
+        "\n".repeat(3)
+    );
+
+    let mut decoded = Vec::new();
+    let _ = DecoderReader::new(io::Cursor::new(&b64), &engine)
+        .read_to_end(&mut decoded)
+        .unwrap();
+    assert_eq!(b"AAA", &decoded[..]);
+}
+
+#[test]
+fn new_has_default_capacity() {
+    let decoder = DecoderReader::new(io::Cursor::new(b"QUFB"), &STANDARD);
+    assert_eq!(BUF_SIZE, decoder.capacity());
+}
+
+#[test]
+fn with_capacity_mixed_buf_read_and_read() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..1_000 {
+        let size = rng.gen_range(0..(5 * BUF_SIZE));
+        let mut bytes = vec![0; size];
+        rng.fill_bytes(&mut bytes[..]);
+
+        let engine = random_engine(&mut rng);
+        let b64 = engine.encode(&bytes);
+
+        let capacity = rng.gen_range(0..(3 * BUF_SIZE));
+        let mut base_reader = io::Cursor::new(&b64[..]);
+        let mut short_delegate = RandomShortRead {
+            delegate: &mut base_reader,
+            rng: &mut rand::thread_rng(),
+        };
+        let mut decoder = DecoderReader::with_capacity(capacity, &mut short_delegate, &engine);
+        assert_eq!(cmp::max(capacity, 4), decoder.capacity());
+        let max_buffered = decoder.capacity() / 4 * 3;
+
+        let mut decoded = Vec::new();
+        loop {
+            if rng.gen() {
+                let available = decoder.fill_buf().unwrap();
+                if available.is_empty() {
+                    break;
+                }
+                assert!(available.len() <= max_buffered);
+                let consume_len = rng.gen_range(0..=available.len());
+                decoded.extend_from_slice(&available[..consume_len]);
+                decoder.consume(consume_len);
+            } else {
+                let mut buf = vec![0; rng.gen_range(1..100)];
+                let read = decoder.read(&mut buf).unwrap();
+                if read == 0 {
+                    break;
+                }
+                decoded.extend_from_slice(&buf[..read]);
+            }
+        }
+
+        assert_eq!(bytes, decoded);
+    }
+}
+
+#[test]
+fn capacity_bounds_delegate_reads() {
+    let bytes = vec![0xAB; 30_000];
+    let b64 = STANDARD.encode(&bytes);
+
+    for &capacity in &[4, 100, BUF_SIZE, 16 * 1024] {
+        let mut delegate = CountingRead {
+            delegate: io::Cursor::new(&b64[..]),
+            reads: 0,
+            max_read_len: 0,
+        };
+        let mut decoded = Vec::new();
+        let mut decoder = DecoderReader::with_capacity(capacity, &mut delegate, &STANDARD);
+        let _ = decoder.read_to_end(&mut decoded).unwrap();
+        assert_eq!(bytes, decoded);
+
+        assert!(delegate.max_read_len <= capacity);
+        // one read per full buffer, plus the read that hits EOF
+        assert!(delegate.reads <= b64.len() / (capacity / 4 * 4) + 2);
+    }
+}
+
+#[test]
+fn fill_buf_reports_decode_errors() {
+    let mut decoder = DecoderReader::new(io::Cursor::new(b"YWJj*GVm"), &STANDARD);
+    let err = decoder.fill_buf().unwrap_err();
+    assert_eq!(io::ErrorKind::InvalidData, err.kind());
+    assert_eq!(
+        &DecodeError::InvalidByte(4, b'*'),
+        err.into_inner()
+            .unwrap()
+            .downcast_ref::<DecodeError>()
+            .unwrap()
+    );
+}
+
+#[test]
+fn recover_from_errors_skips_invalid_quads() {
+    let mut rng = rand::thread_rng();
+    let mut read_rng = rand::thread_rng();
+
+    for _ in 0..1_000 {
+        let quads = rng.gen_range(1..200);
+        let mut bytes = vec![0; quads * 3];
+        rng.fill_bytes(&mut bytes);
+        let mut encoded = STANDARD.encode(&bytes).into_bytes();
+
+        let mut expected_bytes = Vec::new();
+        let mut expected_errors = Vec::new();
+        for quad in 0..quads {
+            if rng.gen_ratio(1, 10) {
+                let offset = quad * 4 + rng.gen_range(0..4);
+                encoded[offset] = b'*';
+                expected_errors.push(DecodeError::InvalidByte(offset, b'*'));
+            } else {
+                expected_bytes.extend_from_slice(&bytes[quad * 3..quad * 3 + 3]);
+            }
+        }
+
+        let mut wrapped_reader = io::Cursor::new(&encoded);
+        let mut short_reader = RandomShortRead {
+            delegate: &mut wrapped_reader,
+            rng: &mut rng,
+        };
+        let capacity = short_reader.rng.gen_range(1..2 * BUF_SIZE);
+        let mut decoder = DecoderReader::with_capacity(capacity, &mut short_reader, &STANDARD)
+            .recover_from_errors();
+
+        let mut decoded = Vec::new();
+        let mut buf = [0; 100];
+        loop {
+            let read_len = read_rng.gen_range(1..buf.len());
+            let read = decoder.read(&mut buf[..read_len]).unwrap();
+            if read == 0 {
+                break;
+            }
+            decoded.extend_from_slice(&buf[..read]);
+        }
+
+        assert_eq!(expected_bytes, decoded);
+        assert_eq!(&expected_errors[..], decoder.recovered_errors());
+    }
+}
+
+#[test]
+fn recover_from_errors_at_end_of_input() {
+    for input in ["YWJjZ", "YWJjZGW=", "YWJjZA"].iter() {
+        let mut decoder = DecoderReader::new(input.as_bytes(), &STANDARD).recover_from_errors();
+        let mut decoded = Vec::new();
+        let _ = decoder.read_to_end(&mut decoded).unwrap();
+
+        assert_eq!(b"abc", &decoded[..]);
+        assert_eq!(
+            vec![STANDARD.decode(input).unwrap_err()],
+            decoder.take_recovered_errors()
+        );
+        assert!(decoder.recovered_errors().is_empty());
+    }
+}
+
+#[test]
+fn recover_from_errors_after_padding() {
+    // the first record is decoded on its own, so the padding ends it and the next starts after it
+    let mut decoder =
+        DecoderReader::with_capacity(4, &b"YQ==YWJj"[..], &STANDARD).recover_from_errors();
+    let mut decoded = Vec::new();
+    let _ = decoder.read_to_end(&mut decoded).unwrap();
+
+    assert_eq!(b"aabc", &decoded[..]);
+    assert_eq!(
+        &[DecodeError::InvalidByte(2, PAD_BYTE)],
+        decoder.recovered_errors()
+    );
+}
+
 fn consume_with_short_reads_and_validate<R: io::Read>(
     rng: &mut rand::rngs::ThreadRng,
     expected_bytes: &[u8],
@@ -485,3 +739,57 @@ impl<R: io::Read> io::Read for ShortRead<R> {
         self.delegate.read(&mut buf[..len])
     }
 }
+
+// Counts reads from the delegate, and the largest buffer offered to it
+struct CountingRead<R: io::Read> {
+    delegate: R,
+    reads: usize,
+    max_read_len: usize,
+}
+
+impl<R: io::Read> io::Read for CountingRead<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.reads += 1;
+        self.max_read_len = cmp::max(self.max_read_len, buf.len());
+        self.delegate.read(buf)
+    }
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn traces_chunks_and_errors() {
+    use crate::tests::EventRecorder;
+
+    let encoded = STANDARD.encode([0xAB; 3000]);
+    let events = EventRecorder::record(|| {
+        let mut decoder = DecoderReader::new(encoded.as_bytes(), &STANDARD);
+        let mut decoded = [0; 3000];
+        while decoder.read(&mut decoded).unwrap() > 0 {}
+    });
+    assert!(
+        events.contains(&"decoded chunk input_len=1024 decoded_len=768".to_string()),
+        "{:?}",
+        events
+    );
+    assert!(
+        events.contains(&"end of base64 input_len=4000".to_string()),
+        "{:?}",
+        events
+    );
+
+    let events = EventRecorder::record(|| {
+        let mut decoded = [0; 2];
+        let mut decoder = DecoderReader::new(&b"aGVsbG8*"[..], &STANDARD);
+        assert_eq!(2, decoder.read(&mut decoded).unwrap());
+        assert!(decoder.read_to_end(&mut Vec::new()).is_err());
+    });
+    assert!(
+        events.contains(&"holding decoded bytes for a later read held_len=1".to_string()),
+        "{:?}",
+        events
+    );
+    assert_eq!(
+        Some(&"invalid base64 error=Invalid symbol 42, offset 7.".to_string()),
+        events.last()
+    );
+}
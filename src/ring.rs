@@ -0,0 +1,440 @@
+//! Decoding into a ring buffer, with [`Engine::decode_into_ring`].
+//!
+//! [`RingSlice`] is a view of a fixed region of memory that's used circularly, like the buffer of a
+//! DMA channel or a UART: decoded output goes at the write position, wrapping around to the start
+//! of the region at the end, and whatever consumes the output frees up space as it goes. Nothing
+//! is allocated, so this works without `alloc`.
+//!
+//! # Examples
+//!
+//! ```
+//! use base64::{engine::general_purpose::STANDARD, ring::RingSlice, Engine as _};
+//!
+//! let mut buf = [0_u8; 8];
+//! let mut ring = RingSlice::new(&mut buf);
+//!
+//! assert_eq!(6, STANDARD.decode_into_ring("aGVsbG8h", &mut ring).unwrap());
+//! // the consumer has read "hello"
+//! ring.release(5);
+//! // " world" wraps around the end
+//! assert_eq!(6, STANDARD.decode_into_ring("IHdvcmxk", &mut ring).unwrap());
+//! assert_eq!(4, ring.head());
+//! assert_eq!(b"orldo! w", &buf);
+//! ```
+
+use crate::{
+    engine::{Config, DecodeEstimate},
+    DecodeError, DecodeSliceError, Engine,
+};
+use core::cmp;
+
+/// A ring buffer that decoded output is written to, wrapping around at the end.
+///
+/// Only the producer side is tracked: the position the next byte goes at, and how many bytes are
+/// free from there on, wrapping around. Call [`release`](RingSlice::release) as the consumer frees
+/// up space.
+#[derive(Debug)]
+pub struct RingSlice<'a> {
+    buf: &'a mut [u8],
+    head: usize,
+    available: usize,
+}
+
+impl<'a> RingSlice<'a> {
+    /// An empty ring buffer over `buf`, written from the start.
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        let available = buf.len();
+        Self {
+            buf,
+            head: 0,
+            available,
+        }
+    }
+
+    /// A ring buffer over `buf` with the next byte going at `head`, and `available` bytes free
+    /// from there, as when resuming from the positions in a DMA controller's registers.
+    ///
+    /// # Panics
+    ///
+    /// If `head` isn't in `buf`, unless `buf` is empty and `head` is 0, or if `available` is more
+    /// than the length of `buf`.
+    pub fn with_head(buf: &'a mut [u8], head: usize, available: usize) -> Self {
+        assert!(
+            head < buf.len() || head == 0,
+            "head {} is out of bounds",
+            head
+        );
+        assert!(
+            available <= buf.len(),
+            "{} bytes available in a buffer of {}",
+            available,
+            buf.len()
+        );
+
+        Self {
+            buf,
+            head,
+            available,
+        }
+    }
+
+    /// The offset in the buffer that the next byte is written at.
+    pub fn head(&self) -> usize {
+        self.head
+    }
+
+    /// How many bytes can be written before the ring is full.
+    pub fn available(&self) -> usize {
+        self.available
+    }
+
+    /// Mark `len` more bytes as consumed, so they can be written over.
+    ///
+    /// # Panics
+    ///
+    /// If that would make more bytes available than the buffer holds.
+    pub fn release(&mut self, len: usize) {
+        self.available = self
+            .available
+            .checked_add(len)
+            .filter(|&available| available <= self.buf.len())
+            .expect("released more bytes than were written");
+    }
+
+    /// Advance past `len` bytes that were just written.
+    fn advance(&mut self, len: usize) {
+        debug_assert!(len <= self.available);
+        self.available -= len;
+        if !self.buf.is_empty() {
+            self.head = (self.head + len) % self.buf.len();
+        }
+    }
+}
+
+/// Decode `input` with `engine` into `ring`, as for [`Engine::decode_into_ring`].
+pub(crate) fn decode_into_ring<E: Engine + ?Sized>(
+    engine: &E,
+    input: &[u8],
+    ring: &mut RingSlice<'_>,
+) -> Result<usize, DecodeSliceError> {
+    let too_small = || DecodeSliceError::OutputSliceTooSmall {
+        needed: engine
+            .internal_decoded_len_estimate(input.len())
+            .decoded_len_estimate(),
+    };
+    // errors from decoding a part of `input` are reported as for all of it
+    let map_err = |e, offset, symbols| match e {
+        DecodeSliceError::DecodeError(e) => offset_error(e, offset, symbols).into(),
+        DecodeSliceError::OutputSliceTooSmall { .. } => too_small(),
+    };
+    let head = ring.head;
+    let tail_len = cmp::min(ring.available, ring.buf.len() - head);
+    let wrapped_len = ring.available - tail_len;
+
+    // as many whole triples as fit before the end of the buffer
+    let tail_symbols = tail_len / 3 * 4;
+    let split = symbols_end(engine, input, tail_symbols);
+    let (mut len, done) = decode_head(engine, input, split, &mut ring.buf[head..head + tail_len])
+        .map_err(|e| map_err(e, 0, 0))?;
+    if done {
+        ring.advance(len);
+        return Ok(len);
+    }
+    debug_assert_eq!(tail_len / 3 * 3, len);
+
+    let mut rest = &input[split..];
+    let mut rest_offset = split;
+    let mut rest_symbols = tail_symbols;
+    let mut wrapped_start = 0;
+
+    // a triple that straddles the end of the buffer goes through the stack
+    if tail_len % 3 != 0 {
+        let quad_end = symbols_end(engine, rest, 4);
+        let mut triple = [0_u8; 3];
+        let (triple_len, done) = decode_head(engine, rest, quad_end, &mut triple)
+            .map_err(|e| map_err(e, rest_offset, rest_symbols))?;
+
+        let before_end = cmp::min(triple_len, tail_len % 3);
+        wrapped_start = triple_len - before_end;
+        if wrapped_start > wrapped_len {
+            return Err(too_small());
+        }
+        ring.buf[head + len..head + len + before_end].copy_from_slice(&triple[..before_end]);
+        ring.buf[..wrapped_start].copy_from_slice(&triple[before_end..triple_len]);
+        len += triple_len;
+
+        if done {
+            ring.advance(len);
+            return Ok(len);
+        }
+        rest = &rest[quad_end..];
+        rest_offset += quad_end;
+        rest_symbols += 4;
+    }
+
+    len += engine
+        .decode_slice(rest, &mut ring.buf[wrapped_start..wrapped_len])
+        .map_err(|e| map_err(e, rest_offset, rest_symbols))?;
+    ring.advance(len);
+    Ok(len)
+}
+
+/// Decode the start of `input`, up to `split`, into `output`, and return its decoded length and
+/// whether that was all of `input`.
+///
+/// The start of `input` is whole quads, which decode on their own, unless it has padding. Then
+/// all of `input` is decoded to find out what the engine makes of that padding: it may end the
+/// input, be invalid, or be skipped, in which case the input goes on after the start.
+fn decode_head<E: Engine + ?Sized>(
+    engine: &E,
+    input: &[u8],
+    split: usize,
+    output: &mut [u8],
+) -> Result<(usize, bool), DecodeSliceError> {
+    let (start, rest) = input.split_at(split);
+    let rest_has_symbols = rest.iter().any(|&b| is_symbol(engine, b));
+    if !rest_has_symbols || start.iter().any(|&b| is_padding(engine, b)) {
+        match engine.decode_slice(input, output) {
+            Err(DecodeSliceError::OutputSliceTooSmall { .. }) if rest_has_symbols => {}
+            result => return result.map(|len| (len, true)),
+        }
+    }
+
+    engine.decode_slice(start, output).map(|len| (len, false))
+}
+
+/// Returns the offset in `input` just past its first `n` symbols, or its length if it has fewer.
+fn symbols_end<E: Engine + ?Sized>(engine: &E, input: &[u8], n: usize) -> usize {
+    if n == 0 {
+        return 0;
+    }
+
+    input
+        .iter()
+        .enumerate()
+        .filter(|&(_, &b)| is_symbol(engine, b))
+        .nth(n - 1)
+        .map_or(input.len(), |(offset, _)| offset + 1)
+}
+
+/// Returns `true` if `byte` isn't padding or ignored by the engine, so it should be a symbol.
+fn is_symbol<E: Engine + ?Sized>(engine: &E, byte: u8) -> bool {
+    !engine.config().decode_ignores(byte) && !is_padding(engine, byte)
+}
+
+/// Returns `true` if `byte` is treated as padding by the engine.
+fn is_padding<E: Engine + ?Sized>(engine: &E, byte: u8) -> bool {
+    engine.config().padding().decode_byte() == byte
+}
+
+/// Shift the offsets in `error` from being within a part of the input that starts `offset` bytes
+/// and `symbols` symbols in, to being within the whole input.
+fn offset_error(error: DecodeError, offset: usize, symbols: usize) -> DecodeError {
+    match error {
+        DecodeError::InvalidByte(o, byte) => DecodeError::InvalidByte(offset + o, byte),
+        DecodeError::InvalidLength(len) => DecodeError::InvalidLength(symbols + len),
+        DecodeError::InvalidLastSymbol {
+            offset: o,
+            symbol,
+            symbol_value,
+        } => DecodeError::InvalidLastSymbol {
+            offset: offset + o,
+            symbol,
+            symbol_value,
+        },
+        DecodeError::PaddingNotAtEnd(o) => DecodeError::PaddingNotAtEnd(offset + o),
+        e => e,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alphabet,
+        engine::{
+            general_purpose::{PAD, STANDARD},
+            EmbeddedPaddingMode, GeneralPurpose,
+        },
+        tests::{random_alphabet, random_config},
+    };
+    use rand::{seq::SliceRandom, Rng};
+
+    /// An engine that may skip whitespace and may allow padding in the middle of the input.
+    fn random_ring_engine<R: Rng>(rng: &mut R) -> GeneralPurpose {
+        let mut config = random_config(rng).with_decode_embedded_padding(
+            *[
+                EmbeddedPaddingMode::Invalid,
+                EmbeddedPaddingMode::Strict,
+                EmbeddedPaddingMode::Terminate,
+                EmbeddedPaddingMode::Ignore,
+            ]
+            .choose(rng)
+            .unwrap(),
+        );
+        if rng.gen() {
+            config = config.with_decode_ignore_bytes(b" \n");
+        }
+
+        GeneralPurpose::new(random_alphabet(rng), config)
+    }
+
+    /// Random input for `engine`: usually an encoding, sometimes with whitespace, padding, or
+    /// invalid bytes added, or two encodings joined. Also returns how many of those changes there
+    /// are, each of which may be an error.
+    fn random_input<R: Rng>(rng: &mut R, engine: &GeneralPurpose) -> (Vec<u8>, usize) {
+        let mut data = vec![0; rng.gen_range(0..40)];
+        rng.fill_bytes(&mut data);
+        let mut input = engine.encode(&data).into_bytes();
+        let mut changes = 0;
+        if rng.gen() {
+            changes += 1;
+            input.extend_from_slice(
+                engine
+                    .encode(&data[..rng.gen_range(0..=data.len())])
+                    .as_bytes(),
+            );
+        }
+
+        for _ in 0..rng.gen_range(0..3) {
+            changes += 1;
+            let byte = *[b' ', b'\n', b'=', b'*', b'A'].choose(rng).unwrap();
+            let index = rng.gen_range(0..=input.len());
+            if rng.gen() || index == input.len() {
+                input.insert(index, byte);
+            } else {
+                input[index] = byte;
+            }
+        }
+
+        (input, changes)
+    }
+
+    #[test]
+    fn decode_into_ring_matches_decode_slice_with_the_same_space() {
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..100_000 {
+            let engine = random_ring_engine(&mut rng);
+            let (input, changes) = random_input(&mut rng, &engine);
+            let mut buf = vec![0_u8; rng.gen_range(0..50)];
+            let head = if buf.is_empty() {
+                0
+            } else {
+                rng.gen_range(0..buf.len())
+            };
+            let available = rng.gen_range(0..=buf.len());
+
+            let mut contiguous = vec![0_u8; available];
+            let expected = engine.decode_slice(&input, &mut contiguous);
+
+            let mut ring = RingSlice::with_head(&mut buf, head, available);
+            let result = engine.decode_into_ring(&input, &mut ring);
+            let (ring_head, ring_available) = (ring.head(), ring.available());
+            let context = format!(
+                "input {:?}, head {}, available {}",
+                String::from_utf8_lossy(&input),
+                head,
+                available
+            );
+            match (&expected, &result) {
+                // both are conservative, but the engine can count the symbols
+                (
+                    Err(DecodeSliceError::OutputSliceTooSmall { needed: expected }),
+                    Err(DecodeSliceError::OutputSliceTooSmall { needed }),
+                ) => assert!(needed >= expected, "{}", context),
+                // with several errors, the first part's may be found first
+                (Err(_), Err(_)) if changes > 1 => {}
+                // invalid input that doesn't fit may be reported as either
+                (
+                    Err(DecodeSliceError::OutputSliceTooSmall { .. }),
+                    Err(DecodeSliceError::DecodeError(e)),
+                )
+                | (
+                    Err(DecodeSliceError::DecodeError(e)),
+                    Err(DecodeSliceError::OutputSliceTooSmall { .. }),
+                ) => assert_eq!(Err(e.clone()), engine.decode(&input), "{}", context),
+                _ => assert_eq!(expected, result, "{}", context),
+            }
+
+            match result {
+                Ok(len) => {
+                    assert_eq!(available - len, ring_available);
+                    assert_eq!((head + len) % buf.len().max(1), ring_head);
+                    let written: Vec<u8> =
+                        buf.iter().cycle().skip(head).take(len).copied().collect();
+                    assert_eq!(&contiguous[..len], &written[..]);
+                }
+                Err(_) => {
+                    assert_eq!(available, ring_available);
+                    assert_eq!(head, ring_head);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn decode_into_ring_wraps_every_straddle() {
+        let data = b"hello world";
+        let encoded = STANDARD.encode(data);
+
+        for len in data.len()..data.len() + 3 {
+            for head in 0..len {
+                let mut buf = vec![0_u8; len];
+                let mut ring = RingSlice::with_head(&mut buf, head, len);
+                assert_eq!(
+                    Ok(data.len()),
+                    STANDARD.decode_into_ring(&encoded, &mut ring)
+                );
+
+                buf.rotate_left(head);
+                assert_eq!(&data[..], &buf[..data.len()]);
+            }
+        }
+    }
+
+    #[test]
+    fn decode_into_ring_reports_errors_in_the_whole_input() {
+        let mut buf = [0_u8; 9];
+        let mut ring = RingSlice::with_head(&mut buf, 7, 9);
+        // the wrap is after the first quad, so the error is in the second part
+        assert_eq!(
+            Err(DecodeError::InvalidByte(5, b'*').into()),
+            STANDARD.decode_into_ring("aGVsb*8gd29y", &mut ring)
+        );
+        // padding that would end the first part on its own
+        let engine = GeneralPurpose::new(
+            &alphabet::STANDARD,
+            PAD.with_decode_embedded_padding(EmbeddedPaddingMode::Strict),
+        );
+        assert_eq!(
+            Err(DecodeError::PaddingNotAtEnd(2).into()),
+            engine.decode_into_ring("aG==bG8g", &mut ring)
+        );
+        assert_eq!(7, ring.head());
+        assert_eq!(9, ring.available());
+    }
+
+    #[test]
+    fn release_frees_space() {
+        let mut buf = [0_u8; 4];
+        let mut ring = RingSlice::new(&mut buf);
+        assert_eq!(Ok(3), STANDARD.decode_into_ring("YWJj", &mut ring));
+        assert_eq!(
+            Err(DecodeSliceError::OutputSliceTooSmall { needed: 3 }),
+            STANDARD.decode_into_ring("ZGVm", &mut ring)
+        );
+
+        ring.release(3);
+        assert_eq!(Ok(3), STANDARD.decode_into_ring("ZGVm", &mut ring));
+        assert_eq!(2, ring.head());
+        assert_eq!(b"efcd", &buf);
+    }
+
+    #[test]
+    #[should_panic(expected = "released more bytes than were written")]
+    fn release_too_much_panics() {
+        let mut buf = [0_u8; 4];
+        RingSlice::with_head(&mut buf, 1, 3).release(2);
+    }
+}
@@ -0,0 +1,260 @@
+//! Destinations for encoded output, used by [`Engine::encode_into`](crate::Engine::encode_into).
+//!
+//! [`EncodeSink`] is implemented for `String`, `Vec<u8>`, and `&mut [u8]`, as well as
+//! `bytes::BytesMut` with the `bytes` feature and `heapless::{String, Vec}` with the `heapless`
+//! feature. Implement it for other types to encode directly into them.
+//!
+//! # Examples
+//!
+//! ```
+//! use base64::{engine::general_purpose::STANDARD, Engine as _};
+//!
+//! let mut buf = [0_u8; 8];
+//! let mut output = &mut buf[..];
+//! STANDARD.encode_into(b"abc", &mut output).unwrap();
+//! STANDARD.encode_into(b"def", &mut output).unwrap();
+//! assert_eq!(b"YWJjZGVm", &buf);
+//! ```
+
+use crate::EncodeSliceError;
+#[cfg(any(feature = "alloc", test))]
+use alloc::{string::String, vec::Vec};
+use core::mem;
+#[cfg(any(feature = "alloc", feature = "heapless", test))]
+use core::{convert::Infallible, str};
+
+/// A destination that encoded output can be appended to.
+pub trait EncodeSink {
+    /// The error returned when the output doesn't fit.
+    type Error;
+
+    /// Prepare for `len` more bytes of encoded output.
+    ///
+    /// This is called once before each input is encoded, so sinks with limited capacity should
+    /// return an error here rather than partway through writing the output.
+    fn reserve(&mut self, len: usize) -> Result<(), Self::Error> {
+        let _ = len;
+        Ok(())
+    }
+
+    /// Append a chunk of encoded output, which is always ASCII.
+    fn write_encoded(&mut self, encoded: &[u8]) -> Result<(), Self::Error>;
+
+    /// Optionally append `len` bytes to the sink and return them to be overwritten with the
+    /// encoded output, after a successful [`reserve`](EncodeSink::reserve) of `len`.
+    ///
+    /// This lets the whole input be encoded at once rather than through an intermediate buffer.
+    /// The default returns `None`, in which case [`write_encoded`](EncodeSink::write_encoded) is
+    /// used instead.
+    fn output_buffer(&mut self, len: usize) -> Option<&mut [u8]> {
+        let _ = len;
+        None
+    }
+}
+
+/// Writes to the start of the slice, and advances it past the written bytes.
+impl EncodeSink for &mut [u8] {
+    type Error = EncodeSliceError;
+
+    fn reserve(&mut self, len: usize) -> Result<(), Self::Error> {
+        if self.len() < len {
+            return Err(EncodeSliceError::OutputSliceTooSmall);
+        }
+
+        Ok(())
+    }
+
+    fn write_encoded(&mut self, encoded: &[u8]) -> Result<(), Self::Error> {
+        self.reserve(encoded.len())?;
+        let (output, rest) = mem::take(self).split_at_mut(encoded.len());
+        output.copy_from_slice(encoded);
+        *self = rest;
+
+        Ok(())
+    }
+
+    fn output_buffer(&mut self, len: usize) -> Option<&mut [u8]> {
+        if self.len() < len {
+            return None;
+        }
+
+        let (output, rest) = mem::take(self).split_at_mut(len);
+        *self = rest;
+        Some(output)
+    }
+}
+
+#[cfg(any(feature = "alloc", test))]
+impl EncodeSink for String {
+    type Error = Infallible;
+
+    fn reserve(&mut self, len: usize) -> Result<(), Self::Error> {
+        String::reserve(self, len);
+        Ok(())
+    }
+
+    fn write_encoded(&mut self, encoded: &[u8]) -> Result<(), Self::Error> {
+        self.push_str(str::from_utf8(encoded).expect("base64 is ASCII"));
+        Ok(())
+    }
+}
+
+#[cfg(any(feature = "alloc", test))]
+impl EncodeSink for Vec<u8> {
+    type Error = Infallible;
+
+    fn reserve(&mut self, len: usize) -> Result<(), Self::Error> {
+        Vec::reserve(self, len);
+        Ok(())
+    }
+
+    fn write_encoded(&mut self, encoded: &[u8]) -> Result<(), Self::Error> {
+        self.extend_from_slice(encoded);
+        Ok(())
+    }
+
+    fn output_buffer(&mut self, len: usize) -> Option<&mut [u8]> {
+        let start = self.len();
+        self.resize(start + len, 0);
+        Some(&mut self[start..])
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl EncodeSink for bytes::BytesMut {
+    type Error = Infallible;
+
+    fn reserve(&mut self, len: usize) -> Result<(), Self::Error> {
+        bytes::BytesMut::reserve(self, len);
+        Ok(())
+    }
+
+    fn write_encoded(&mut self, encoded: &[u8]) -> Result<(), Self::Error> {
+        self.extend_from_slice(encoded);
+        Ok(())
+    }
+
+    fn output_buffer(&mut self, len: usize) -> Option<&mut [u8]> {
+        let start = self.len();
+        self.resize(start + len, 0);
+        Some(&mut self[start..])
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> EncodeSink for heapless::String<N> {
+    type Error = EncodeSliceError;
+
+    fn reserve(&mut self, len: usize) -> Result<(), Self::Error> {
+        if self.capacity() - self.len() < len {
+            return Err(EncodeSliceError::OutputSliceTooSmall);
+        }
+
+        Ok(())
+    }
+
+    fn write_encoded(&mut self, encoded: &[u8]) -> Result<(), Self::Error> {
+        self.push_str(str::from_utf8(encoded).expect("base64 is ASCII"))
+            .map_err(|_| EncodeSliceError::OutputSliceTooSmall)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> EncodeSink for heapless::Vec<u8, N> {
+    type Error = EncodeSliceError;
+
+    fn reserve(&mut self, len: usize) -> Result<(), Self::Error> {
+        if self.capacity() - self.len() < len {
+            return Err(EncodeSliceError::OutputSliceTooSmall);
+        }
+
+        Ok(())
+    }
+
+    fn write_encoded(&mut self, encoded: &[u8]) -> Result<(), Self::Error> {
+        self.extend_from_slice(encoded)
+            .map_err(|_| EncodeSliceError::OutputSliceTooSmall)
+    }
+
+    fn output_buffer(&mut self, len: usize) -> Option<&mut [u8]> {
+        let start = self.len();
+        self.resize(start + len, 0).ok()?;
+        Some(&mut self[start..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{general_purpose::STANDARD, Engine as _};
+
+    /// A sink that only implements the required method, to exercise the chunked path
+    struct Chunks(Vec<Vec<u8>>);
+
+    impl EncodeSink for Chunks {
+        type Error = Infallible;
+
+        fn write_encoded(&mut self, encoded: &[u8]) -> Result<(), Self::Error> {
+            self.0.push(encoded.to_vec());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn sinks_append() {
+        let input = [7_u8; 2000];
+        let expected = STANDARD.encode(input);
+
+        let mut s = String::from("x");
+        assert_eq!(expected.len(), STANDARD.encode_into(input, &mut s).unwrap());
+        assert_eq!(format!("x{}", expected), s);
+
+        let mut v = b"x".to_vec();
+        assert_eq!(expected.len(), STANDARD.encode_into(input, &mut v).unwrap());
+        assert_eq!(format!("x{}", expected).as_bytes(), &v[..]);
+
+        let mut chunks = Chunks(Vec::new());
+        assert_eq!(
+            expected.len(),
+            STANDARD.encode_into(input, &mut chunks).unwrap()
+        );
+        assert!(chunks.0.len() > 1);
+        assert_eq!(expected.as_bytes(), &chunks.0.concat()[..]);
+    }
+
+    #[test]
+    fn slice_sink_too_small_writes_nothing() {
+        let mut buf = [0_u8; 7];
+        let mut output = &mut buf[..];
+        assert_eq!(
+            Err(EncodeSliceError::OutputSliceTooSmall),
+            STANDARD.encode_into(b"abcdef", &mut output)
+        );
+        assert_eq!(7, output.len());
+        assert_eq!([0_u8; 7], buf);
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn bytes_mut_sink() {
+        let mut b = bytes::BytesMut::new();
+        assert_eq!(4, STANDARD.encode_into(b"abc", &mut b).unwrap());
+        assert_eq!(b"YWJj", &b[..]);
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn heapless_sinks() {
+        let mut v = heapless::Vec::<u8, 6>::new();
+        assert_eq!(4, STANDARD.encode_into(b"abc", &mut v).unwrap());
+        assert_eq!(
+            Err(EncodeSliceError::OutputSliceTooSmall),
+            STANDARD.encode_into(b"abc", &mut v)
+        );
+        assert_eq!(b"YWJj", &v[..]);
+
+        let mut s = heapless::String::<6>::new();
+        assert_eq!(4, STANDARD.encode_into(b"abc", &mut s).unwrap());
+        assert_eq!("YWJj", s.as_str());
+    }
+}
@@ -0,0 +1,108 @@
+use crate::Engine;
+
+/// A kind of file recognized by its magic number with [`sniff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum MediaHint {
+    /// A PNG image
+    Png,
+    /// A JPEG image
+    Jpeg,
+    /// A PDF document
+    Pdf,
+    /// A gzip stream
+    Gzip,
+    /// A ZIP archive, which includes formats built on it like JAR, DOCX, or EPUB
+    Zip,
+}
+
+impl MediaHint {
+    /// The MIME type of this kind of file.
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Png => "image/png",
+            Self::Jpeg => "image/jpeg",
+            Self::Pdf => "application/pdf",
+            Self::Gzip => "application/gzip",
+            Self::Zip => "application/zip",
+        }
+    }
+}
+
+/// Magic numbers, as the bytes each kind of file starts with.
+const MAGIC_NUMBERS: &[(&[u8], MediaHint)] = &[
+    (b"\x89PNG\r\n\x1A\n", MediaHint::Png),
+    (b"\xFF\xD8\xFF", MediaHint::Jpeg),
+    (b"%PDF-", MediaHint::Pdf),
+    (b"\x1F\x8B", MediaHint::Gzip),
+    (b"PK\x03\x04", MediaHint::Zip),
+    // an empty archive
+    (b"PK\x05\x06", MediaHint::Zip),
+];
+
+/// The longest magic number, which is how many bytes have to be decoded to match any of them.
+const MAGIC_NUMBER_MAX_LEN: usize = 8;
+
+/// Guess what kind of file `encoded` is from the magic number at the start of its decoded bytes.
+///
+/// Only the first few bytes are decoded, with [`Engine::decode_prefix`], so this is cheap enough
+/// to reject an upload of the wrong kind before decoding all of it. `None` is returned if the
+/// magic number isn't one of [`MediaHint`], or if the start of `encoded` doesn't decode. The rest
+/// of `encoded` isn't checked, so it may still fail to decode.
+///
+/// # Examples
+///
+/// ```
+/// use base64::{engine::general_purpose::STANDARD, Engine as _, MediaHint};
+///
+/// let pdf = STANDARD.encode(b"%PDF-1.7\n...");
+/// assert_eq!(Some(MediaHint::Pdf), base64::sniff(&STANDARD, &pdf));
+/// assert_eq!("application/pdf", MediaHint::Pdf.mime_type());
+///
+/// assert_eq!(None, base64::sniff(&STANDARD, STANDARD.encode(b"hello world")));
+/// ```
+pub fn sniff<E: Engine + ?Sized, T: AsRef<[u8]>>(engine: &E, encoded: T) -> Option<MediaHint> {
+    let (prefix, _) = engine.decode_prefix(encoded, MAGIC_NUMBER_MAX_LEN).ok()?;
+
+    MAGIC_NUMBERS
+        .iter()
+        .find(|(magic, _)| prefix.starts_with(magic))
+        .map(|&(_, hint)| hint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+
+    #[test]
+    fn sniff_recognizes_magic_numbers() {
+        for &(magic, hint) in MAGIC_NUMBERS {
+            assert!(magic.len() <= MAGIC_NUMBER_MAX_LEN);
+
+            let mut file = magic.to_vec();
+            file.extend_from_slice(b" and the rest of the file");
+            assert_eq!(Some(hint), sniff(&STANDARD, STANDARD.encode(&file)));
+            assert_eq!(
+                Some(hint),
+                sniff(&URL_SAFE_NO_PAD, URL_SAFE_NO_PAD.encode(&file))
+            );
+            // just the magic number is enough
+            assert_eq!(Some(hint), sniff(&STANDARD, STANDARD.encode(magic)));
+            // but not part of it
+            assert_eq!(
+                None,
+                sniff(&STANDARD, STANDARD.encode(&magic[..magic.len() - 1]))
+            );
+        }
+    }
+
+    #[test]
+    fn sniff_returns_none_for_other_or_invalid_input() {
+        assert_eq!(None, sniff(&STANDARD, ""));
+        assert_eq!(None, sniff(&STANDARD, STANDARD.encode(b"PK\x01\x02")));
+        assert_eq!(None, sniff(&STANDARD, "*VBERi0x"));
+        // the end isn't decoded
+        assert_eq!(Some(MediaHint::Pdf), sniff(&STANDARD, "JVBERi0xLjcK*"));
+    }
+}
@@ -0,0 +1,396 @@
+//! Engine settings as data, for declaring base64 settings in config files.
+//!
+//! An [`EngineSpec`] names an alphabet, or lists its symbols, along with a
+//! [`GeneralPurposeConfig`], and [builds](EngineSpec::build) the corresponding engine. With the
+//! `serde` feature, specs can be deserialized from any format serde supports. The config settings
+//! are flattened alongside the alphabet, and any that are missing take their default values, so a
+//! TOML table like this is a valid spec:
+//!
+//! ```toml
+//! alphabet = "url-safe"
+//! encode_padding = false
+//! decode_padding_mode = "indifferent"
+//! encode_line_wrap = 76
+//! ```
+//!
+//! Specs can also be written compactly as a string, e.g. for a command line flag. See [`parse`].
+//!
+//! # Examples
+//!
+//! ```
+//! use base64::{engine::{general_purpose::NO_PAD, Engine as _}, spec::{self, EngineSpec}};
+//!
+//! let spec = EngineSpec::Named {
+//!     alphabet: "url-safe".to_string(),
+//!     config: NO_PAD,
+//! };
+//! assert_eq!("-_8", spec.build().unwrap().encode([0xfb, 0xff]));
+//!
+//! let engine = spec::parse("url-safe,no-pad").unwrap();
+//! assert_eq!("-_8", engine.encode([0xfb, 0xff]));
+//! ```
+
+use crate::{
+    alphabet::{self, Alphabet, ParseAlphabetError},
+    engine::{
+        general_purpose::{ConfigError, PAD},
+        DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig, LineEnding,
+    },
+};
+use alloc::string::{String, ToString};
+use core::{fmt, str::FromStr};
+#[cfg(any(feature = "std", test))]
+use std::error;
+
+/// The alphabet and config of a [`GeneralPurpose`] engine.
+///
+/// With the `serde` feature, the variant is inferred from whether there's an `alphabet` or a
+/// `symbols` field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum EngineSpec {
+    /// A named alphabet.
+    Named {
+        /// The name of the alphabet, as accepted by [`alphabet::by_name`].
+        alphabet: String,
+        /// The engine config
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        config: GeneralPurposeConfig,
+    },
+    /// A custom alphabet.
+    Custom {
+        /// The 64 symbols of the alphabet, as accepted by [`Alphabet::new`].
+        symbols: String,
+        /// The engine config
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        config: GeneralPurposeConfig,
+    },
+}
+
+impl EngineSpec {
+    /// Build the engine described by this spec.
+    ///
+    /// This never panics: a config that can't be used with the alphabet, like one whose padding
+    /// byte is one of its symbols, results in [`SpecError::InvalidConfig`].
+    pub fn build(&self) -> Result<GeneralPurpose, SpecError> {
+        match self {
+            Self::Named { alphabet, config } => {
+                let alphabet = alphabet::by_name(alphabet)
+                    .ok_or_else(|| SpecError::UnknownAlphabet(alphabet.clone()))?;
+                GeneralPurpose::try_new(alphabet, *config).map_err(SpecError::InvalidConfig)
+            }
+            Self::Custom { symbols, config } => {
+                let alphabet = Alphabet::new(symbols).map_err(SpecError::InvalidAlphabet)?;
+                GeneralPurpose::try_new(&alphabet, *config).map_err(SpecError::InvalidConfig)
+            }
+        }
+    }
+
+    /// The engine config.
+    pub fn config(&self) -> &GeneralPurposeConfig {
+        match self {
+            Self::Named { config, .. } | Self::Custom { config, .. } => config,
+        }
+    }
+}
+
+/// Parse a spec string and build the engine it describes.
+///
+/// A spec is an alphabet name, as in [`EngineSpec::Named`], followed by any number of
+/// comma-separated options, applied in order on top of [`PAD`]:
+///
+/// - `pad`, `no-pad`, `pad-indifferent`, `no-pad-indifferent`: the padding behavior of the
+///   [`general_purpose`](crate::engine::general_purpose) config of the same name
+/// - `trailing-bits`: see [`GeneralPurposeConfig::with_decode_allow_trailing_bits`]
+/// - `wrap=<n>`: see [`GeneralPurposeConfig::with_encode_line_wrap`]
+/// - `trailing-newline`: see [`GeneralPurposeConfig::with_encode_trailing_newline`]
+/// - `line-ending=lf`, `line-ending=crlf`: see [`GeneralPurposeConfig::with_encode_line_ending`]
+/// - `max-len=<n>`: see [`GeneralPurposeConfig::with_max_encoded_len`]
+///
+/// Whitespace around names and options is ignored.
+///
+/// [`Engine`](crate::Engine) can't be used as a trait object, but every spec describes a
+/// [`GeneralPurpose`] engine, so that is returned directly.
+///
+/// # Examples
+///
+/// ```
+/// use base64::{engine::Engine as _, spec};
+///
+/// let engine = spec::parse("url-safe, no-pad, wrap=4").unwrap();
+/// assert_eq!("-_v7\n-_v7\n-w", engine.encode([0xfb; 7]));
+///
+/// assert!(spec::parse("url-safe,wrap=often").is_err());
+/// ```
+pub fn parse(spec: &str) -> Result<GeneralPurpose, SpecError> {
+    spec.parse::<EngineSpec>()?.build()
+}
+
+impl FromStr for EngineSpec {
+    type Err = SpecError;
+
+    /// Parse a spec string as described in [`parse`], without checking the alphabet name.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(',').map(str::trim);
+        // split always yields at least one item
+        let alphabet = parts.next().unwrap_or_default().to_string();
+        let config = parts.try_fold(PAD, |config, option| {
+            let mut key_value = option.splitn(2, '=').map(str::trim);
+            let key = key_value.next().unwrap_or_default();
+            let value = key_value.next();
+            let invalid = || SpecError::InvalidOption(option.to_string());
+
+            Ok(match (key, value) {
+                ("pad", None) => config
+                    .with_encode_padding(true)
+                    .with_decode_padding_mode(DecodePaddingMode::RequireCanonical),
+                ("no-pad", None) => config
+                    .with_encode_padding(false)
+                    .with_decode_padding_mode(DecodePaddingMode::RequireNone),
+                ("pad-indifferent", None) => config
+                    .with_encode_padding(true)
+                    .with_decode_padding_mode(DecodePaddingMode::Indifferent),
+                ("no-pad-indifferent", None) => config
+                    .with_encode_padding(false)
+                    .with_decode_padding_mode(DecodePaddingMode::Indifferent),
+                ("trailing-bits", None) => config.with_decode_allow_trailing_bits(true),
+                ("wrap", Some(n)) => {
+                    config.with_encode_line_wrap(n.parse().map_err(|_| invalid())?)
+                }
+                ("trailing-newline", None) => config.with_encode_trailing_newline(true),
+                ("line-ending", Some("lf")) => config.with_encode_line_ending(LineEnding::LF),
+                ("line-ending", Some("crlf")) => config.with_encode_line_ending(LineEnding::CRLF),
+                ("max-len", Some(n)) => {
+                    config.with_max_encoded_len(n.parse().map_err(|_| invalid())?)
+                }
+                _ => return Err(invalid()),
+            })
+        })?;
+
+        Ok(Self::Named { alphabet, config })
+    }
+}
+
+/// Errors that can occur while parsing a spec or building an engine from an [`EngineSpec`].
+#[derive(Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SpecError {
+    /// There's no alphabet with the given name.
+    UnknownAlphabet(String),
+    /// The custom alphabet's symbols are invalid.
+    InvalidAlphabet(ParseAlphabetError),
+    /// An option in a spec string is unknown or has an invalid value.
+    InvalidOption(String),
+    /// The config can't be used with the alphabet.
+    InvalidConfig(ConfigError),
+}
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownAlphabet(name) => write!(f, "Unknown alphabet: {:?}", name),
+            Self::InvalidAlphabet(e) => write!(f, "Invalid alphabet: {}", e),
+            Self::InvalidOption(option) => write!(f, "Invalid option: {:?}", option),
+            Self::InvalidConfig(e) => write!(f, "Invalid config: {}", e),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", test))]
+impl error::Error for SpecError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::UnknownAlphabet(_) | Self::InvalidOption(_) => None,
+            Self::InvalidAlphabet(e) => Some(e),
+            Self::InvalidConfig(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{
+        general_purpose::{NO_PAD, NO_PAD_INDIFFERENT},
+        Engine as _, Padding,
+    };
+
+    #[test]
+    fn build_named_alphabets() {
+        for &name in &["standard", "URL_SAFE", "Url-Safe", "bin_hex"] {
+            let spec = EngineSpec::Named {
+                alphabet: name.into(),
+                config: PAD,
+            };
+            assert!(spec.build().is_ok(), "{}", name);
+        }
+
+        let spec = EngineSpec::Named {
+            alphabet: "url-safe".into(),
+            config: PAD.with_encode_line_wrap(4),
+        };
+        assert_eq!("-_v7\n-_v7\n-w==", spec.build().unwrap().encode([0xfb; 7]));
+
+        assert_eq!(
+            SpecError::UnknownAlphabet("url safe".into()),
+            EngineSpec::Named {
+                alphabet: "url safe".into(),
+                config: PAD,
+            }
+            .build()
+            .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn build_custom_alphabet() {
+        let spec = EngineSpec::Custom {
+            symbols: "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz+/".into(),
+            config: PAD.with_decode_padding_mode(DecodePaddingMode::Indifferent),
+        };
+        assert_eq!("OM9Z", spec.build().unwrap().encode(b"abc"));
+        assert_eq!(
+            &PAD.with_decode_padding_mode(DecodePaddingMode::Indifferent),
+            spec.config()
+        );
+
+        assert_eq!(
+            SpecError::InvalidAlphabet(ParseAlphabetError::InvalidLength { len: 3 }),
+            EngineSpec::Custom {
+                symbols: "abc".into(),
+                config: PAD,
+            }
+            .build()
+            .unwrap_err()
+        );
+        assert_eq!(
+            SpecError::InvalidConfig(ConfigError::PaddingInAlphabet(b'.')),
+            EngineSpec::Custom {
+                symbols: "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz-.".into(),
+                config: PAD.with_padding(Padding::Padded(b'.')),
+            }
+            .build()
+            .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn parse_options() {
+        assert_eq!(
+            Ok(EngineSpec::Named {
+                alphabet: "url-safe".into(),
+                config: PAD
+                    .with_encode_padding(false)
+                    .with_decode_padding_mode(DecodePaddingMode::RequireNone)
+                    .with_encode_line_wrap(76)
+                    .with_encode_trailing_newline(true)
+                    .with_encode_line_ending(LineEnding::CRLF),
+            }),
+            "url-safe,no-pad,wrap=76,trailing-newline,line-ending=crlf".parse()
+        );
+        assert_eq!(
+            Ok(EngineSpec::Named {
+                alphabet: "STANDARD".into(),
+                config: PAD
+                    .with_decode_padding_mode(DecodePaddingMode::Indifferent)
+                    .with_decode_allow_trailing_bits(true)
+                    .with_max_encoded_len(100),
+            }),
+            " STANDARD , pad-indifferent, trailing-bits, max-len = 100 ".parse()
+        );
+        assert_eq!(&NO_PAD, parse("standard,no-pad").unwrap().config());
+        assert_eq!(
+            &NO_PAD_INDIFFERENT,
+            parse("url_safe,no-pad-indifferent").unwrap().config()
+        );
+        // later options override earlier ones
+        assert_eq!(&PAD, parse("standard,no-pad,pad").unwrap().config());
+        assert_eq!(
+            "-_8",
+            parse("url-safe,no-pad").unwrap().encode([0xfb, 0xff])
+        );
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(
+            SpecError::UnknownAlphabet("".into()),
+            parse("").unwrap_err()
+        );
+        assert_eq!(
+            SpecError::UnknownAlphabet("hex".into()),
+            parse("hex,pad").unwrap_err()
+        );
+        for &(spec, option) in &[
+            ("standard,", ""),
+            ("standard,wrap", "wrap"),
+            ("standard,wrap=-1", "wrap=-1"),
+            ("standard,pad=yes", "pad=yes"),
+            ("standard,max-len=", "max-len="),
+            ("standard,no_pad", "no_pad"),
+        ] {
+            assert_eq!(
+                SpecError::InvalidOption(option.into()),
+                parse(spec).unwrap_err(),
+                "{}",
+                spec
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_with_defaults() {
+        let spec: EngineSpec = serde_json::from_str(
+            r#"{"alphabet": "url-safe", "encode_padding": false, "encode_line_wrap": 76}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            EngineSpec::Named {
+                alphabet: "url-safe".into(),
+                config: PAD.with_encode_padding(false).with_encode_line_wrap(76),
+            },
+            spec
+        );
+
+        let spec: EngineSpec = serde_json::from_str(
+            r#"{"symbols": "./ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                "decode_padding_mode": "require_none"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            &PAD.with_decode_padding_mode(DecodePaddingMode::RequireNone),
+            spec.config()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_round_trip() {
+        let spec = EngineSpec::Named {
+            alphabet: "standard".into(),
+            config: PAD.with_max_encoded_len(1024).with_encode_line_wrap(64),
+        };
+        let json = serde_json::to_string(&spec).unwrap();
+        assert_eq!(spec, serde_json::from_str::<EngineSpec>(&json).unwrap());
+
+        // no limit is left out rather than written as `usize::MAX`
+        let json = serde_json::to_value(PAD).unwrap();
+        assert!(json.get("decode_max_encoded_len").is_none());
+        assert!(json.get("encode_padding").is_some());
+        assert!(json.get("decode_ignore_bytes").is_none());
+        assert!(json.get("decode_embedded_padding").is_none());
+        let json = serde_json::to_value(
+            PAD.with_decode_embedded_padding(crate::engine::EmbeddedPaddingMode::Terminate),
+        )
+        .unwrap();
+        assert_eq!("terminate", json["decode_embedded_padding"]);
+
+        // ignored bytes are written as a list of bytes
+        let config = PAD.with_decode_ignore_bytes(b":.");
+        let json = serde_json::to_value(config).unwrap();
+        assert_eq!(serde_json::json!([46, 58]), json["decode_ignore_bytes"]);
+        assert_eq!(config, serde_json::from_value(json).unwrap());
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{DecodeError, Engine};
+use alloc::string::String;
+
+/// Split `encoded` into two encodings with `engine`: one of the first `n` decoded bytes, and one of
+/// the rest.
+///
+/// The split rarely falls on a quad boundary of `encoded`, so the payload is decoded and both halves
+/// are encoded again, each with its own padding as configured for `engine`. This is useful to serve
+/// a byte range of a blob stored as base64 without handing out the whole blob.
+///
+/// # Errors
+///
+/// Any error from decoding `encoded` with `engine`, or
+/// [`DecodeError::UnexpectedDecodedLength`] with `n` as `expected` if `encoded` decodes to fewer
+/// than `n` bytes.
+///
+/// # Examples
+///
+/// ```
+/// use base64::{engine::general_purpose::STANDARD, Engine as _};
+///
+/// let encoded = STANDARD.encode(b"hello world");
+///
+/// assert_eq!(
+///     Ok((STANDARD.encode(b"hello"), STANDARD.encode(b" world"))),
+///     base64::split_at_decoded(&STANDARD, &encoded, 5)
+/// );
+/// assert_eq!(
+///     Err(base64::DecodeError::UnexpectedDecodedLength {
+///         expected: 12,
+///         actual: Some(11),
+///     }),
+///     base64::split_at_decoded(&STANDARD, &encoded, 12)
+/// );
+/// ```
+pub fn split_at_decoded<E: Engine + ?Sized, T: AsRef<[u8]>>(
+    engine: &E,
+    encoded: T,
+    n: usize,
+) -> Result<(String, String), DecodeError> {
+    let decoded = engine.decode(encoded)?;
+    if n > decoded.len() {
+        return Err(DecodeError::UnexpectedDecodedLength {
+            expected: n,
+            actual: Some(decoded.len()),
+        });
+    }
+
+    let (head, tail) = decoded.split_at(n);
+    Ok((engine.encode(head), engine.encode(tail)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+    use rand::{Rng, RngCore};
+
+    #[test]
+    fn split_at_decoded_halves_decode_to_the_split_payload() {
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..1_000 {
+            let mut payload = vec![0; rng.gen_range(0..100)];
+            rng.fill_bytes(&mut payload);
+            let n = rng.gen_range(0..=payload.len());
+            let engine = if rng.gen() {
+                &STANDARD
+            } else {
+                &URL_SAFE_NO_PAD
+            };
+
+            let (head, tail) = split_at_decoded(engine, engine.encode(&payload), n).unwrap();
+            assert_eq!(&payload[..n], &engine.decode(head).unwrap()[..]);
+            assert_eq!(&payload[n..], &engine.decode(tail).unwrap()[..]);
+        }
+    }
+
+    #[test]
+    fn split_at_decoded_reports_invalid_input_and_short_payloads() {
+        assert_eq!(
+            Ok((String::new(), "YWJj".to_string())),
+            split_at_decoded(&STANDARD, "YWJj", 0)
+        );
+        assert_eq!(
+            Ok(("YWJj".to_string(), String::new())),
+            split_at_decoded(&STANDARD, "YWJj", 3)
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidByte(1, b'*')),
+            split_at_decoded(&STANDARD, "Y*Jj", 1)
+        );
+        assert_eq!(
+            Err(DecodeError::UnexpectedDecodedLength {
+                expected: 1,
+                actual: Some(0),
+            }),
+            split_at_decoded(&STANDARD, "", 1)
+        );
+    }
+}
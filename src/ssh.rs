@@ -0,0 +1,235 @@
+//! Encoding and decoding SSH public key blobs.
+//!
+//! OpenSSH writes public keys as standard base64 with padding in two layouts: on a single line
+//! between the key type and an optional comment, as in `authorized_keys` and `.pub` files, and in
+//! lines of 70 characters between `---- BEGIN SSH2 PUBLIC KEY ----` markers, as in the
+//! [RFC 4716](https://www.rfc-editor.org/rfc/rfc4716) files written by `ssh-keygen -e`.
+//! [`decode_blob`] finds the base64 in either one and leaves out everything else.
+//!
+//! # Examples
+//!
+//! ```
+//! use base64::ssh::{self, Layout};
+//!
+//! let line = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIAABAgMEBQYHCAkKCwwNDg8QERITFBUWFxgZGhscHR4f alice@example.com";
+//! let blob = ssh::decode_blob(line).unwrap();
+//! assert_eq!(b"\0\0\0\x0bssh-ed25519", &blob[..15]);
+//! assert_eq!(51, blob.len());
+//!
+//! let wrapped = ssh::encode_blob(&blob, Layout::Wrapped);
+//! assert_eq!(blob, ssh::decode_blob(wrapped).unwrap());
+//! ```
+
+use crate::{
+    alphabet,
+    engine::{
+        general_purpose::{PAD, STANDARD},
+        GeneralPurpose,
+    },
+    DecodeError, Engine,
+};
+use alloc::{string::String, vec::Vec};
+
+/// The length of the lines of base64 in [`Layout::Wrapped`].
+pub const WRAPPED_LINE_LEN: usize = 70;
+
+/// The start of the base64 of every key blob: the big-endian length of the key type name, whose
+/// first 3 bytes are 0 as names are short.
+const BLOB_START: &str = "AAAA";
+
+const WRAPPED: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    PAD.with_encode_line_wrap(WRAPPED_LINE_LEN),
+);
+
+/// How the base64 of a key blob is laid out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// On a single line, as in `authorized_keys`, `known_hosts`, and `.pub` files.
+    SingleLine,
+    /// In lines of [`WRAPPED_LINE_LEN`] separated by `\n`, as in the body of an RFC 4716 file.
+    Wrapped,
+}
+
+/// Encode a key blob as standard base64 in the given layout.
+///
+/// Neither layout has a line ending after the last line, so the key type and comment can follow
+/// on the same line in [`Layout::SingleLine`].
+pub fn encode_blob<T: AsRef<[u8]>>(blob: T, layout: Layout) -> String {
+    match layout {
+        Layout::SingleLine => STANDARD.encode(blob),
+        Layout::Wrapped => WRAPPED.encode(blob),
+    }
+}
+
+/// Find the base64 of a key blob in `text` and decode it.
+///
+/// `text` can be the bare base64 in either [`Layout`], a line from an `authorized_keys` file, or a
+/// whole RFC 4716 file:
+///
+/// - Blank lines, `#` comment lines, and the `----` begin and end markers are skipped.
+/// - Between the `---- BEGIN` and `---- END` markers, RFC 4716 headers like `Comment: "..."`,
+///   including their continuation lines, are skipped too. Elsewhere, lines with a `:` are kept, as
+///   `authorized_keys` options and comments can have one.
+/// - If a single line is left and it has several whitespace-separated fields, as in
+///   `[options] ssh-ed25519 AAAA... [comment]`, the blob is the first field starting with `AAAA`,
+///   which every blob does. The key type, options, and comment are left out.
+/// - Otherwise, the lines are joined and decoded.
+///
+/// # Errors
+///
+/// [`DecodeError::InvalidLength`] with a length of 0 if no base64 is found, otherwise any error
+/// from decoding the base64 that was found, with offsets relative to its start once the lines are
+/// joined.
+pub fn decode_blob<T: AsRef<str>>(text: T) -> Result<Vec<u8>, DecodeError> {
+    let mut lines = Vec::new();
+    let mut in_rfc4716 = false;
+    let mut in_header = false;
+    for line in text.as_ref().lines().map(str::trim) {
+        // a header ends on the first line that doesn't end in a backslash
+        let continued_header = in_header;
+        in_header = false;
+        if line.starts_with("---- BEGIN") {
+            in_rfc4716 = true;
+        } else if line.starts_with("---- END") {
+            in_rfc4716 = false;
+        } else if in_rfc4716 && (continued_header || is_header(line)) {
+            in_header = line.ends_with('\\');
+        } else if !(line.is_empty() || line.starts_with('#') || line.starts_with("----")) {
+            lines.push(line);
+        }
+    }
+
+    let b64 = match lines[..] {
+        [] => return Err(DecodeError::InvalidLength(0)),
+        [line] => line
+            .split_whitespace()
+            .find(|field| field.starts_with(BLOB_START))
+            .unwrap_or(line),
+        _ => return STANDARD.decode(lines.concat()),
+    };
+
+    STANDARD.decode(b64)
+}
+
+/// Returns `true` if `line` starts an RFC 4716 header: `Tag: value`, where the tag is 1 to 64
+/// printable ASCII characters other than `:` and space.
+fn is_header(line: &str) -> bool {
+    match line.find(':') {
+        Some(colon) => {
+            let tag = &line[..colon];
+            (1..=64).contains(&tag.len())
+                && tag.bytes().all(|b| b.is_ascii_graphic())
+                && line[colon + 1..].starts_with(' ')
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, RngCore};
+
+    /// A random blob shaped like an OpenSSH key blob: a length-prefixed key type, then key data.
+    fn random_blob<R: RngCore>(rng: &mut R) -> Vec<u8> {
+        let key_type = b"ssh-ed25519";
+        let mut blob = (key_type.len() as u32).to_be_bytes().to_vec();
+        blob.extend_from_slice(key_type);
+        let mut key = vec![0; rng.gen_range(0..600)];
+        rng.fill_bytes(&mut key);
+        blob.extend_from_slice(&(key.len() as u32).to_be_bytes());
+        blob.extend_from_slice(&key);
+        blob
+    }
+
+    #[test]
+    fn encode_blob_layouts() {
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..100 {
+            let blob = random_blob(&mut rng);
+
+            let single = encode_blob(&blob, Layout::SingleLine);
+            assert!(single.starts_with(BLOB_START));
+            assert!(!single.contains('\n'));
+            assert_eq!(blob, decode_blob(&single).unwrap());
+
+            let wrapped = encode_blob(&blob, Layout::Wrapped);
+            assert!(wrapped.lines().all(|line| line.len() <= WRAPPED_LINE_LEN));
+            assert!(!wrapped.ends_with('\n'));
+            assert_eq!(single, wrapped.replace('\n', ""));
+            assert_eq!(blob, decode_blob(&wrapped).unwrap());
+        }
+    }
+
+    #[test]
+    fn decode_blob_from_authorized_keys_line() {
+        let mut rng = rand::thread_rng();
+        let blob = random_blob(&mut rng);
+        let b64 = encode_blob(&blob, Layout::SingleLine);
+
+        for line in &[
+            format!("ssh-ed25519 {}", b64),
+            format!("ssh-ed25519 {} alice@example.com\n", b64),
+            format!("ssh-ed25519 {} a comment with spaces", b64),
+            format!(
+                "from=\"10.0.0.1\",no-pty ssh-ed25519 {} alice@example.com",
+                b64
+            ),
+            format!("# alice's key\n\nssh-ed25519 {}\n", b64),
+            format!(
+                "from=\"2001:db8::/32\",permitopen=\"host:22\" ssh-ed25519 {} alice@example.com",
+                b64
+            ),
+            format!("ssh-ed25519 {} backup key: laptop", b64),
+            format!("# key: alice\nssh-ed25519 {} alice@host:22", b64),
+        ] {
+            assert_eq!(blob, decode_blob(line).unwrap(), "{}", line);
+        }
+    }
+
+    #[test]
+    fn decode_blob_from_rfc4716_file() {
+        let mut rng = rand::thread_rng();
+        let blob = random_blob(&mut rng);
+        let file = format!(
+            "---- BEGIN SSH2 PUBLIC KEY ----\r\n\
+             Comment: \"alice's key, which has a comment long enough to be \\\r\n\
+             continued on the next line\"\r\n\
+             x-private: AAAA\r\n\
+             {}\r\n\
+             ---- END SSH2 PUBLIC KEY ----\r\n",
+            encode_blob(&blob, Layout::Wrapped).replace('\n', "\r\n")
+        );
+
+        assert_eq!(blob, decode_blob(file).unwrap());
+    }
+
+    #[test]
+    fn decode_blob_without_base64_is_an_error() {
+        for text in &[
+            "",
+            "# just a comment",
+            "---- BEGIN SSH2 PUBLIC KEY ----\nComment: \"nothing else\"\n---- END SSH2 PUBLIC KEY ----\n",
+        ] {
+            assert_eq!(Err(DecodeError::InvalidLength(0)), decode_blob(text), "{}", text);
+        }
+    }
+
+    #[test]
+    fn decode_blob_errors_are_relative_to_the_base64() {
+        assert_eq!(
+            Err(DecodeError::InvalidByte(4, b'*')),
+            decode_blob("ssh-rsa AAAA*AAA comment")
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidByte(3, b'-')),
+            decode_blob("ssh-rsa BBBB comment")
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidByte(6, b'*')),
+            decode_blob("AAAA\nAA*A\n")
+        );
+    }
+}
@@ -0,0 +1,278 @@
+//! Helpers for generating test data, with the `testing` feature.
+//!
+//! Hand-written base64 test inputs are easy to get subtly wrong, e.g. with the wrong amount of
+//! padding or nonzero trailing bits, which makes them fail to decode for reasons unrelated to
+//! what's being tested. These helpers produce encodings that the engine will always accept.
+//!
+//! Crates with their own [`Engine`] implementation can also check it against the invariants that
+//! the engines in this crate are tested for with [`check_engine`].
+//!
+//! # Examples
+//!
+//! ```
+//! use base64::{engine::general_purpose::URL_SAFE_NO_PAD, testing, Engine as _};
+//! use rand::SeedableRng;
+//!
+//! let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+//! let encoded = testing::random_encoded(&URL_SAFE_NO_PAD, 100, &mut rng);
+//! assert_eq!(100, URL_SAFE_NO_PAD.decode(&encoded).unwrap().len());
+//! ```
+
+use crate::engine::{DecodeEstimate as _, Engine};
+use alloc::{string::String, vec, vec::Vec};
+use rand::{Rng as _, RngCore};
+
+/// A byte that isn't written to output buffers, to check that nothing is written past the output.
+const SENTINEL: u8 = 0xA5;
+
+/// Returns the canonical encoding, as produced by `engine`, of `decoded_len` random bytes.
+///
+/// The result decodes with `engine` back to `decoded_len` bytes, and encodes back to itself.
+pub fn random_encoded<E, R>(engine: &E, decoded_len: usize, rng: &mut R) -> String
+where
+    E: Engine + ?Sized,
+    R: RngCore + ?Sized,
+{
+    let mut decoded = vec![0_u8; decoded_len];
+    rng.fill_bytes(&mut decoded);
+    engine.encode(decoded)
+}
+
+/// Check that `engine` upholds the invariants the rest of this crate relies on, with random input
+/// from `rng`.
+///
+/// This is for crates implementing [`Engine`] themselves, to run in their tests. It checks that:
+///
+/// - encoded output is as long as [`Engine::encoded_len`] says, decodes back to the input, and
+///   contains no bytes that [`Engine::find_invalid`] reports
+/// - the decoded length estimate is never too small
+/// - `encode_slice()` and `decode_slice()` write exactly the output into buffers of the exact
+///   length, write nothing past it, and return errors rather than panicking for buffers that are
+///   too short
+/// - decoding random bytes never panics, and fails whenever [`Engine::find_invalid`] reports an
+///   invalid byte
+///
+/// # Panics
+///
+/// Panics with a description of the first invariant that doesn't hold, and the input it failed
+/// for.
+///
+/// # Examples
+///
+/// ```
+/// use base64::{engine::general_purpose::STANDARD, testing};
+/// use rand::SeedableRng;
+///
+/// let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+/// testing::check_engine(&STANDARD, &mut rng);
+/// ```
+pub fn check_engine<E, R>(engine: &E, rng: &mut R)
+where
+    E: Engine + ?Sized,
+    R: RngCore + ?Sized,
+{
+    let mut input = Vec::new();
+    for _ in 0..1_000 {
+        let input_len = if rng.gen_ratio(1, 10) {
+            rng.gen_range(0..3_000)
+        } else {
+            rng.gen_range(0..30)
+        };
+        input.resize(input_len, 0);
+        rng.fill_bytes(&mut input);
+
+        check_roundtrip(engine, &input);
+        check_decode_never_panics(engine, &input, rng);
+    }
+}
+
+/// Check encoding and decoding `input`.
+fn check_roundtrip<E: Engine + ?Sized>(engine: &E, input: &[u8]) {
+    let encoded = engine.encode(input);
+    assert_eq!(
+        Some(encoded.len()),
+        engine.encoded_len(input.len()),
+        "encoded_len() is wrong for {:?}, encoded as {:?}",
+        input,
+        encoded
+    );
+    assert_eq!(
+        None,
+        engine.find_invalid(&encoded),
+        "find_invalid() reports a byte in {:?}, the encoding of {:?}",
+        encoded,
+        input
+    );
+    assert_eq!(
+        Ok(input),
+        engine.decode(&encoded).as_deref(),
+        "{:?} doesn't decode back to the input it's the encoding of",
+        encoded
+    );
+    let estimate = engine
+        .internal_decoded_len_estimate(encoded.len())
+        .decoded_len_estimate();
+    assert!(
+        estimate >= input.len(),
+        "decoded length estimate {} is less than the {} bytes {:?} decodes to",
+        estimate,
+        input.len(),
+        encoded
+    );
+
+    let mut encode_buf = vec![SENTINEL; encoded.len() + 1];
+    assert_eq!(
+        Ok(encoded.len()),
+        engine.encode_slice(input, &mut encode_buf[..encoded.len()]),
+        "encode_slice() fails with an exact length buffer for {:?}",
+        input
+    );
+    assert_eq!(encoded.as_bytes(), &encode_buf[..encoded.len()]);
+    assert_eq!(
+        SENTINEL,
+        encode_buf[encoded.len()],
+        "encode_slice() writes past the output"
+    );
+    if !encoded.is_empty() {
+        assert!(
+            engine
+                .encode_slice(input, &mut encode_buf[..encoded.len() - 1])
+                .is_err(),
+            "encode_slice() doesn't fail with a buffer too short for the encoding of {:?}",
+            input
+        );
+    }
+
+    let mut decode_buf = vec![SENTINEL; input.len() + 1];
+    assert_eq!(
+        Ok(input.len()),
+        engine.decode_slice(&encoded, &mut decode_buf[..input.len()]),
+        "decode_slice() fails with an exact length buffer for {:?}",
+        encoded
+    );
+    assert_eq!(input, &decode_buf[..input.len()]);
+    assert_eq!(
+        SENTINEL,
+        decode_buf[input.len()],
+        "decode_slice() writes past the output"
+    );
+    if !input.is_empty() {
+        assert!(
+            engine
+                .decode_slice(&encoded, &mut decode_buf[..input.len() - 1])
+                .is_err(),
+            "decode_slice() doesn't fail with a buffer too short for the decoding of {:?}",
+            encoded
+        );
+    }
+}
+
+/// Check decoding the encoding of `input` with random bytes changed.
+fn check_decode_never_panics<E, R>(engine: &E, input: &[u8], rng: &mut R)
+where
+    E: Engine + ?Sized,
+    R: RngCore + ?Sized,
+{
+    let mut corrupted = engine.encode(input).into_bytes();
+    if corrupted.is_empty() {
+        corrupted.push(rng.gen());
+    }
+    for _ in 0..rng.gen_range(1..4) {
+        let offset = rng.gen_range(0..corrupted.len());
+        corrupted[offset] = rng.gen();
+    }
+
+    let decoded = engine.decode(&corrupted);
+    if let Some(offset) = engine.find_invalid(&corrupted) {
+        assert!(
+            decoded.is_err(),
+            "{:?} decodes, though find_invalid() reports the byte at {}",
+            corrupted,
+            offset
+        );
+    }
+
+    let mut short_buf = vec![0; rng.gen_range(0..=input.len())];
+    // only that it returns
+    let _ = engine.decode_slice(&corrupted, &mut short_buf);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::{
+            general_purpose::{GeneralPurpose, STANDARD},
+            DecodeMetadata,
+        },
+        tests::random_engine,
+        DecodeSliceError,
+    };
+    use rand::SeedableRng;
+
+    #[test]
+    fn random_encoded_is_canonical() {
+        let mut rng = rand::rngs::SmallRng::from_entropy();
+        for _ in 0..1_000 {
+            let engine: GeneralPurpose = random_engine(&mut rng);
+            for decoded_len in 0..20 {
+                let encoded = random_encoded(&engine, decoded_len, &mut rng);
+                assert_eq!(Some(encoded.len()), engine.encoded_len(decoded_len));
+
+                let decoded = engine.decode(&encoded).unwrap();
+                assert_eq!(decoded_len, decoded.len());
+                assert_eq!(encoded, engine.encode(decoded));
+            }
+        }
+
+        assert_eq!("", random_encoded(&STANDARD, 0, &mut rng));
+    }
+
+    #[test]
+    fn check_engine_accepts_engines_in_this_crate() {
+        let mut rng = rand::rngs::SmallRng::from_entropy();
+        for _ in 0..10 {
+            check_engine(&random_engine(&mut rng), &mut rng);
+        }
+    }
+
+    /// Flips the bits of the first decoded byte
+    struct CorruptsDecoded(GeneralPurpose);
+
+    impl Engine for CorruptsDecoded {
+        type Config = <GeneralPurpose as Engine>::Config;
+        type DecodeEstimate = <GeneralPurpose as Engine>::DecodeEstimate;
+
+        fn internal_encode(&self, input: &[u8], output: &mut [u8]) -> usize {
+            self.0.internal_encode(input, output)
+        }
+
+        fn internal_decoded_len_estimate(&self, input_len: usize) -> Self::DecodeEstimate {
+            self.0.internal_decoded_len_estimate(input_len)
+        }
+
+        fn internal_decode(
+            &self,
+            input: &[u8],
+            output: &mut [u8],
+            estimate: Self::DecodeEstimate,
+        ) -> Result<DecodeMetadata, DecodeSliceError> {
+            let metadata = self.0.internal_decode(input, output, estimate)?;
+            if metadata.decoded_len > 0 {
+                output[0] = !output[0];
+            }
+            Ok(metadata)
+        }
+
+        fn config(&self) -> &Self::Config {
+            self.0.config()
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't decode back to the input")]
+    fn check_engine_rejects_wrong_decoding() {
+        let mut rng = rand::rngs::SmallRng::from_entropy();
+        check_engine(&CorruptsDecoded(STANDARD), &mut rng);
+    }
+}
@@ -12,7 +12,7 @@ use crate::{
     encode::encoded_len,
     engine::{
         general_purpose::{GeneralPurpose, GeneralPurposeConfig},
-        Config, DecodePaddingMode, Engine,
+        Config, DecodePaddingMode, Engine, LineEnding,
     },
 };
 
@@ -97,6 +97,16 @@ impl distributions::Distribution<DecodePaddingMode> for distributions::Standard
     }
 }
 
+impl distributions::Distribution<LineEnding> for distributions::Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> LineEnding {
+        if rng.gen() {
+            LineEnding::LF
+        } else {
+            LineEnding::CRLF
+        }
+    }
+}
+
 pub fn random_alphabet<R: Rng>(rng: &mut R) -> &'static alphabet::Alphabet {
     ALPHABETS.choose(rng).unwrap()
 }
@@ -115,3 +125,63 @@ const ALPHABETS: &[alphabet::Alphabet] = &[
     alphabet::IMAP_MUTF7,
     alphabet::BIN_HEX,
 ];
+
+/// Records every event as its message followed by its other fields, for tests of the `tracing`
+/// feature.
+#[cfg(feature = "tracing")]
+#[derive(Default)]
+pub struct EventRecorder {
+    events: std::sync::Mutex<Vec<String>>,
+    spans: std::sync::atomic::AtomicU64,
+}
+
+#[cfg(feature = "tracing")]
+impl EventRecorder {
+    /// Run `f` with `self` as the subscriber, and return the events it recorded.
+    pub fn record<F: FnOnce()>(f: F) -> Vec<String> {
+        let recorder = std::sync::Arc::new(Self::default());
+        tracing::subscriber::with_default(recorder.clone(), f);
+        let events = recorder.events.lock().unwrap();
+        events.clone()
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl tracing::Subscriber for EventRecorder {
+    fn enabled(&self, _metadata: &tracing::Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+        let id = self
+            .spans
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        tracing::span::Id::from_u64(id + 1)
+    }
+
+    fn record(&self, _span: &tracing::span::Id, _values: &tracing::span::Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &tracing::span::Id, _follows: &tracing::span::Id) {}
+
+    fn event(&self, event: &tracing::Event<'_>) {
+        struct Fields(String);
+
+        impl tracing::field::Visit for Fields {
+            fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+                if field.name() == "message" {
+                    self.0.insert_str(0, &format!("{:?}", value));
+                } else {
+                    self.0.push_str(&format!(" {}={:?}", field.name(), value));
+                }
+            }
+        }
+
+        let mut fields = Fields(String::new());
+        event.record(&mut fields);
+        self.events.lock().unwrap().push(fields.0);
+    }
+
+    fn enter(&self, _span: &tracing::span::Id) {}
+
+    fn exit(&self, _span: &tracing::span::Id) {}
+}
@@ -0,0 +1,227 @@
+//! Encoding and decoding into thread-local buffers to avoid allocating on every call.
+//!
+//! [`encode_tls`] and [`decode_tls`] are as convenient as [`Engine::encode`] and
+//! [`Engine::decode`], but write into a buffer cached per thread rather than a newly allocated
+//! one. The returned guard derefs to the output, and hands the buffer back for reuse when dropped.
+//!
+//! The cached buffers keep the capacity of the largest output that's been produced. To keep a
+//! one-off large input from pinning memory, use [`set_max_cached_capacity`] or [`clear`].
+//!
+//! # Examples
+//!
+//! ```
+//! use base64::{engine::general_purpose::STANDARD, tls};
+//!
+//! for _ in 0..3 {
+//!     let encoded = tls::encode_tls(&STANDARD, b"hot path");
+//!     assert_eq!("aG90IHBhdGg=", &*encoded);
+//! }
+//! ```
+
+use crate::{DecodeError, Engine};
+use std::{
+    cell::{Cell, RefCell},
+    fmt, mem, ops,
+};
+
+thread_local! {
+    static ENCODE_BUF: RefCell<String> = RefCell::new(String::new());
+    static DECODE_BUF: RefCell<Vec<u8>> = RefCell::new(Vec::new());
+    static MAX_CACHED_CAPACITY: Cell<usize> = Cell::new(usize::MAX);
+}
+
+/// Encode `input` into this thread's cached `String`.
+///
+/// If the cached buffer is in use by another guard on this thread, a new one is allocated.
+pub fn encode_tls<E: Engine + ?Sized, T: AsRef<[u8]>>(engine: &E, input: T) -> EncodedStr {
+    let mut buf = ENCODE_BUF.with(|b| mem::take(&mut *b.borrow_mut()));
+    engine.encode_string(input, &mut buf);
+    EncodedStr { buf }
+}
+
+/// Decode `input` into this thread's cached `Vec`.
+///
+/// If the cached buffer is in use by another guard on this thread, a new one is allocated.
+pub fn decode_tls<E: Engine + ?Sized, T: AsRef<[u8]>>(
+    engine: &E,
+    input: T,
+) -> Result<DecodedBytes, DecodeError> {
+    let mut bytes = DecodedBytes {
+        buf: DECODE_BUF.with(|b| mem::take(&mut *b.borrow_mut())),
+    };
+    engine.decode_vec(input, &mut bytes.buf)?;
+    Ok(bytes)
+}
+
+/// Set the largest capacity, in bytes, that this thread's buffers keep for reuse.
+///
+/// Buffers that have grown larger than this are freed rather than cached when their guard is
+/// dropped. The default is no limit.
+pub fn set_max_cached_capacity(max: usize) {
+    MAX_CACHED_CAPACITY.with(|m| m.set(max));
+    ENCODE_BUF.with(|b| {
+        if b.borrow().capacity() > max {
+            *b.borrow_mut() = String::new();
+        }
+    });
+    DECODE_BUF.with(|b| {
+        if b.borrow().capacity() > max {
+            *b.borrow_mut() = Vec::new();
+        }
+    });
+}
+
+/// Free this thread's cached buffers.
+pub fn clear() {
+    ENCODE_BUF.with(|b| *b.borrow_mut() = String::new());
+    DECODE_BUF.with(|b| *b.borrow_mut() = Vec::new());
+}
+
+/// Returns `true` if a buffer of `capacity` should be kept for reuse
+fn should_cache(capacity: usize) -> bool {
+    MAX_CACHED_CAPACITY
+        .try_with(|m| capacity <= m.get())
+        .unwrap_or(false)
+}
+
+/// Encoded output in a thread-local buffer, returned by [`encode_tls`].
+///
+/// The buffer is returned to the thread's cache when this is dropped.
+pub struct EncodedStr {
+    buf: String,
+}
+
+impl ops::Deref for EncodedStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.buf
+    }
+}
+
+impl fmt::Debug for EncodedStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.buf, f)
+    }
+}
+
+impl fmt::Display for EncodedStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.buf, f)
+    }
+}
+
+impl Drop for EncodedStr {
+    fn drop(&mut self) {
+        if should_cache(self.buf.capacity()) {
+            let mut buf = mem::take(&mut self.buf);
+            buf.clear();
+            // the thread may be exiting, in which case there's nothing to return the buffer to
+            let _ = ENCODE_BUF.try_with(|b| {
+                if let Ok(mut cached) = b.try_borrow_mut() {
+                    // keep the larger buffer if another guard returned one in the meantime
+                    if cached.capacity() < buf.capacity() {
+                        *cached = buf;
+                    }
+                }
+            });
+        }
+    }
+}
+
+/// Decoded output in a thread-local buffer, returned by [`decode_tls`].
+///
+/// The buffer is returned to the thread's cache when this is dropped.
+pub struct DecodedBytes {
+    buf: Vec<u8>,
+}
+
+impl ops::Deref for DecodedBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.buf
+    }
+}
+
+impl fmt::Debug for DecodedBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.buf, f)
+    }
+}
+
+impl Drop for DecodedBytes {
+    fn drop(&mut self) {
+        if should_cache(self.buf.capacity()) {
+            let mut buf = mem::take(&mut self.buf);
+            buf.clear();
+            // the thread may be exiting, in which case there's nothing to return the buffer to
+            let _ = DECODE_BUF.try_with(|b| {
+                if let Ok(mut cached) = b.try_borrow_mut() {
+                    // keep the larger buffer if another guard returned one in the meantime
+                    if cached.capacity() < buf.capacity() {
+                        *cached = buf;
+                    }
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::general_purpose::STANDARD;
+
+    #[test]
+    fn buffers_are_reused() {
+        clear();
+        let ptr = {
+            let encoded = encode_tls(&STANDARD, [0_u8; 300]);
+            assert_eq!(STANDARD.encode([0_u8; 300]), &*encoded);
+            encoded.as_ptr()
+        };
+        let encoded = encode_tls(&STANDARD, b"abc");
+        assert_eq!("YWJj", &*encoded);
+        assert_eq!(ptr, encoded.as_ptr());
+        drop(encoded);
+
+        let ptr = decode_tls(&STANDARD, "YWJjZGVm").unwrap().as_ptr();
+        let decoded = decode_tls(&STANDARD, "YWJj").unwrap();
+        assert_eq!(b"abc", &*decoded);
+        assert_eq!(ptr, decoded.as_ptr());
+    }
+
+    #[test]
+    fn nested_guards_get_separate_buffers() {
+        let first = encode_tls(&STANDARD, b"abc");
+        let second = encode_tls(&STANDARD, b"def");
+        assert_eq!("YWJj", &*first);
+        assert_eq!("ZGVm", &*second);
+    }
+
+    #[test]
+    fn decode_error_is_returned() {
+        assert_eq!(
+            DecodeError::InvalidByte(0, b'!'),
+            decode_tls(&STANDARD, "!!!!").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn max_cached_capacity_is_respected() {
+        set_max_cached_capacity(100);
+        drop(encode_tls(&STANDARD, [0_u8; 300]));
+        ENCODE_BUF.with(|b| assert!(b.borrow().capacity() <= 100));
+        drop(decode_tls(&STANDARD, "A".repeat(400)).unwrap());
+        DECODE_BUF.with(|b| assert!(b.borrow().capacity() <= 100));
+
+        set_max_cached_capacity(usize::MAX);
+        drop(encode_tls(&STANDARD, [0_u8; 300]));
+        ENCODE_BUF.with(|b| assert!(b.borrow().capacity() >= 400));
+
+        set_max_cached_capacity(100);
+        ENCODE_BUF.with(|b| assert_eq!(0, b.borrow().capacity()));
+        set_max_cached_capacity(usize::MAX);
+    }
+}
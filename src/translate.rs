@@ -0,0 +1,110 @@
+use crate::{alphabet::Alphabet, DecodeError, PAD_BYTE};
+use alloc::{string::String, vec::Vec};
+
+/// Convert base64 in one alphabet to the same base64 in another, like standard to URL-safe,
+/// without decoding and re-encoding it.
+///
+/// Each symbol of `from` is replaced by the symbol with the same value in `to`, so this is a
+/// single table lookup per byte. Padding is copied as is. Only the bytes are checked, not whether
+/// they are valid base64 as a whole: trailing bits, padding, and the length are left for whoever
+/// decodes the output to check.
+///
+/// # Errors
+///
+/// [`DecodeError::InvalidByte`] for the first byte that is neither a symbol of `from` nor `=`.
+///
+/// # Examples
+///
+/// ```
+/// use base64::alphabet;
+///
+/// assert_eq!(
+///     Ok("-_8gaGk=".to_string()),
+///     base64::translate("+/8gaGk=", &alphabet::STANDARD, &alphabet::URL_SAFE)
+/// );
+/// assert_eq!(
+///     Err(base64::DecodeError::InvalidByte(0, b'-')),
+///     base64::translate("-_8gaGk=", &alphabet::STANDARD, &alphabet::URL_SAFE)
+/// );
+/// ```
+pub fn translate<T: AsRef<[u8]>>(
+    input: T,
+    from: &Alphabet,
+    to: &Alphabet,
+) -> Result<String, DecodeError> {
+    // 0 is never a symbol, as symbols are printable, so it marks bytes that aren't in `from`
+    let mut table = [0_u8; 256];
+    for (&from_symbol, &to_symbol) in from.symbols.iter().zip(to.symbols.iter()) {
+        table[usize::from(from_symbol)] = to_symbol;
+    }
+    table[usize::from(PAD_BYTE)] = PAD_BYTE;
+
+    let input = input.as_ref();
+    let mut output = Vec::with_capacity(input.len());
+    for (offset, &byte) in input.iter().enumerate() {
+        match table[usize::from(byte)] {
+            0 => return Err(DecodeError::InvalidByte(offset, byte)),
+            translated => output.push(translated),
+        }
+    }
+
+    // every byte is a printable ASCII symbol or padding
+    Ok(String::from_utf8(output).expect("Invalid UTF8"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alphabet,
+        engine::{general_purpose, Engine as _, GeneralPurpose},
+    };
+    use rand::{Rng, RngCore};
+
+    #[test]
+    fn translate_matches_decode_and_encode() {
+        let mut rng = rand::thread_rng();
+        let alphabets = [
+            &alphabet::STANDARD,
+            &alphabet::URL_SAFE,
+            &alphabet::CRYPT,
+            &alphabet::BCRYPT,
+            &alphabet::IMAP_MUTF7,
+            &alphabet::BIN_HEX,
+        ];
+
+        for _ in 0..1_000 {
+            let mut input = vec![0; rng.gen_range(0..100)];
+            rng.fill_bytes(&mut input);
+            let from = alphabets[rng.gen_range(0..alphabets.len())];
+            let to = alphabets[rng.gen_range(0..alphabets.len())];
+            let from_engine = GeneralPurpose::new(from, general_purpose::PAD);
+            let to_engine = GeneralPurpose::new(to, general_purpose::PAD);
+
+            assert_eq!(
+                Ok(to_engine.encode(&input)),
+                translate(from_engine.encode(&input), from, to)
+            );
+        }
+    }
+
+    #[test]
+    fn translate_rejects_bytes_outside_the_alphabet() {
+        assert_eq!(
+            Err(DecodeError::InvalidByte(4, b'\n')),
+            translate("aGVs\nbG8=", &alphabet::STANDARD, &alphabet::URL_SAFE)
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidByte(2, b'.')),
+            translate("ab./", &alphabet::STANDARD, &alphabet::CRYPT)
+        );
+        assert_eq!(
+            Ok("ab-_".to_string()),
+            translate("ab+/", &alphabet::STANDARD, &alphabet::URL_SAFE)
+        );
+        assert_eq!(
+            Ok(String::new()),
+            translate("", &alphabet::STANDARD, &alphabet::URL_SAFE)
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! Helpers for base64 in URL query parameters and path segments.
+//!
+//! The URL-safe alphabet only uses characters that are unreserved in URLs, so without padding its
+//! output can be put in any URL component as is. Other software doesn't always see it that way and
+//! percent-encodes some of those characters anyway, or the padding of a value that was encoded with
+//! it, so decoding undoes any percent-encoding first.
+//!
+//! # Examples
+//!
+//! ```
+//! let token = base64::url::encode_component(b"\xfb\xff hello");
+//! assert_eq!("-_8gaGVsbG8", token);
+//!
+//! let url = format!("https://example.com/callback?state={}", token);
+//! assert!(url.ends_with("state=-_8gaGVsbG8"));
+//!
+//! // after a trip through a proxy that escapes more than it has to
+//! assert_eq!(
+//!     &b"\xfb\xff hello"[..],
+//!     &base64::url::decode_component("%2D_8gaGVsbG8%3D").unwrap()[..]
+//! );
+//! ```
+
+use crate::{
+    engine::general_purpose::{URL_SAFE_NO_PAD, URL_SAFE_NO_PAD_INDIFFERENT},
+    DecodeError, Engine,
+};
+use alloc::{string::String, vec::Vec};
+
+/// Encode `input` as URL-safe base64 without padding.
+///
+/// Every character of the output is unreserved in URLs (`A-Z`, `a-z`, `0-9`, `-` and `_`), so it
+/// never needs to be percent-encoded in a query parameter, path segment, or fragment.
+pub fn encode_component<T: AsRef<[u8]>>(input: T) -> String {
+    URL_SAFE_NO_PAD.encode(input)
+}
+
+/// Decode URL-safe base64, with or without padding, that may contain percent-encoded characters.
+///
+/// Percent-encoded bytes like `%3D` for `=` or `%2d` for `-` are decoded first. Only the URL-safe
+/// alphabet is accepted, also when percent-encoded, so `+` and `/` (or `%2B` and `%2F`) are
+/// invalid.
+///
+/// Offsets in any returned error are offsets in `input`; for a percent-encoded byte, the offset of
+/// its `%`. A `%` that isn't followed by two hex digits is reported as an invalid byte.
+pub fn decode_component<T: AsRef<[u8]>>(input: T) -> Result<Vec<u8>, DecodeError> {
+    let input = input.as_ref();
+    if !input.contains(&b'%') {
+        return URL_SAFE_NO_PAD_INDIFFERENT.decode(input);
+    }
+
+    // the unescaped bytes, and the offset in `input` that each came from
+    let mut unescaped = Vec::with_capacity(input.len());
+    let mut offsets = Vec::with_capacity(input.len());
+    let mut i = 0;
+    while i < input.len() {
+        offsets.push(i);
+        let byte = if input[i] == b'%' {
+            let escaped = input
+                .get(i + 1..i + 3)
+                .and_then(|hex| Some(hex_value(hex[0])? << 4 | hex_value(hex[1])?))
+                .ok_or(DecodeError::InvalidByte(i, b'%'))?;
+            i += 3;
+            escaped
+        } else {
+            i += 1;
+            input[i - 1]
+        };
+        unescaped.push(byte);
+    }
+
+    URL_SAFE_NO_PAD_INDIFFERENT
+        .decode(&unescaped)
+        .map_err(|e| match e {
+            DecodeError::InvalidByte(offset, byte) => {
+                DecodeError::InvalidByte(offsets[offset], byte)
+            }
+            DecodeError::InvalidLastSymbol {
+                offset,
+                symbol,
+                symbol_value,
+            } => DecodeError::InvalidLastSymbol {
+                offset: offsets[offset],
+                symbol,
+                symbol_value,
+            },
+            DecodeError::PaddingNotAtEnd(offset) => DecodeError::PaddingNotAtEnd(offsets[offset]),
+            e => e,
+        })
+}
+
+/// The value of the hex digit `digit`, in either case.
+fn hex_value(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, RngCore};
+
+    #[test]
+    fn encode_component_needs_no_escaping() {
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..1_000 {
+            let mut input = vec![0; rng.gen_range(0..100)];
+            rng.fill_bytes(&mut input);
+
+            let encoded = encode_component(&input);
+            assert!(encoded
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_'));
+            assert_eq!(input, decode_component(&encoded).unwrap());
+        }
+    }
+
+    #[test]
+    fn decode_component_unescapes_any_byte() {
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..1_000 {
+            let mut input = vec![0; rng.gen_range(0..100)];
+            rng.fill_bytes(&mut input);
+
+            // escape a random selection of the characters, padding included, in either case
+            let mut escaped = String::new();
+            for b in crate::engine::general_purpose::URL_SAFE
+                .encode(&input)
+                .bytes()
+            {
+                match rng.gen_range(0..3) {
+                    0 => escaped.push(char::from(b)),
+                    1 => escaped.push_str(&format!("%{:02X}", b)),
+                    _ => escaped.push_str(&format!("%{:02x}", b)),
+                }
+            }
+            assert_eq!(input, decode_component(&escaped).unwrap());
+        }
+    }
+
+    #[test]
+    fn decode_component_error_offsets_are_in_the_input() {
+        assert_eq!(
+            Err(DecodeError::InvalidByte(7, b'+')),
+            decode_component("%2D%5F8%2BaGVsbG8")
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidByte(4, b'/')),
+            decode_component("aGVs/G8")
+        );
+        assert_eq!(
+            Err(DecodeError::InvalidLastSymbol {
+                offset: 3,
+                symbol: b'b',
+                symbol_value: 27,
+            }),
+            decode_component("%5A%62")
+        );
+    }
+
+    #[test]
+    fn decode_component_rejects_incomplete_escapes() {
+        for input in &["aGVs%", "aGVs%3", "aGVs%3x", "%%3D"] {
+            let offset = input.find('%').unwrap();
+            assert_eq!(
+                Err(DecodeError::InvalidByte(offset, b'%')),
+                decode_component(input)
+            );
+        }
+    }
+}
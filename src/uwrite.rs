@@ -0,0 +1,101 @@
+//! Encoding to a [`ufmt::uWrite`], with the `ufmt` feature.
+//!
+//! `ufmt` is an alternative to `core::fmt` that generates much less code, for embedded targets.
+//!
+//! # Examples
+//!
+//! ```
+//! use base64::{engine::general_purpose::STANDARD, uwrite};
+//!
+//! struct Log(String);
+//!
+//! impl ufmt::uWrite for Log {
+//!     type Error = core::convert::Infallible;
+//!
+//!     fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+//!         self.0.push_str(s);
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let mut log = Log(String::from("frame: "));
+//! uwrite::encode_to_uwrite(&STANDARD, b"\x01\x02\x03", &mut log).unwrap();
+//! assert_eq!("frame: AQID", log.0);
+//! ```
+
+use crate::{chunked_encoder::ChunkedEncoder, engine::Engine, sink::EncodeSink};
+use core::str;
+use ufmt::uWrite;
+
+/// Encode `input` as base64, writing the output to `writer` in chunks without allocating.
+pub fn encode_to_uwrite<E, T, W>(engine: &E, input: T, writer: &mut W) -> Result<(), W::Error>
+where
+    E: Engine + ?Sized,
+    T: AsRef<[u8]>,
+    W: uWrite + ?Sized,
+{
+    ChunkedEncoder::new(engine).encode(input.as_ref(), &mut UWriteSink { writer })
+}
+
+struct UWriteSink<'w, W: ?Sized> {
+    writer: &'w mut W,
+}
+
+impl<'w, W: uWrite + ?Sized> EncodeSink for UWriteSink<'w, W> {
+    type Error = W::Error;
+
+    fn write_encoded(&mut self, encoded: &[u8]) -> Result<(), Self::Error> {
+        self.writer
+            .write_str(str::from_utf8(encoded).expect("base64 is ASCII"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+
+    /// Records each chunk written, and fails once `limit` chunks have been written
+    struct Chunks {
+        chunks: Vec<String>,
+        limit: usize,
+    }
+
+    impl uWrite for Chunks {
+        type Error = ();
+
+        fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+            if self.chunks.len() == self.limit {
+                return Err(());
+            }
+            self.chunks.push(s.to_owned());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn encode_to_uwrite_matches_encode() {
+        let input = [0xA5_u8; 2000];
+        for engine in &[STANDARD, URL_SAFE_NO_PAD] {
+            let mut writer = Chunks {
+                chunks: Vec::new(),
+                limit: usize::MAX,
+            };
+            encode_to_uwrite(engine, input, &mut writer).unwrap();
+            assert!(writer.chunks.len() > 1);
+            assert_eq!(engine.encode(input), writer.chunks.concat());
+        }
+    }
+
+    #[test]
+    fn encode_to_uwrite_propagates_error() {
+        let mut writer = Chunks {
+            chunks: Vec::new(),
+            limit: 1,
+        };
+        assert_eq!(
+            Err(()),
+            encode_to_uwrite(&STANDARD, [0_u8; 2000], &mut writer)
+        );
+    }
+}
@@ -0,0 +1,513 @@
+//! Validating base64 that is edited in place.
+//!
+//! [`Incremental`] keeps a copy of some text, like the contents of an editor buffer, split into
+//! chunks of at most a few KiB. Each chunk caches how many symbols it has and where its first
+//! padding and first invalid byte are, with bytes the engine ignores, like line endings in wrapped
+//! text, counted as neither. An edit only rebuilds the chunks it touches, and finding the first
+//! error only looks at the cached counts and decodes the text from the last whole quad of symbols,
+//! so validating as you type stays fast even when a large blob was pasted in.
+//!
+//! # Examples
+//!
+//! ```
+//! use base64::{engine::general_purpose::STANDARD, validate::Incremental, DecodeError};
+//!
+//! let mut text = Incremental::new(&STANDARD);
+//! text.insert(0, "aGVsbG8gd29ybGQ=");
+//! assert_eq!(None, text.error());
+//!
+//! text.insert(5, "*");
+//! assert_eq!(Some(DecodeError::InvalidByte(5, b'*')), text.error());
+//! assert_eq!(Some(5), text.first_invalid());
+//!
+//! text.delete(5..6);
+//! text.delete(15..16);
+//! assert_eq!(Some(DecodeError::InvalidPadding), text.error());
+//! assert_eq!(Some(15), text.first_invalid());
+//! ```
+
+use crate::{
+    engine::{decodes_as_symbol, Config, Engine},
+    DecodeError,
+};
+use alloc::vec::Vec;
+use core::{cmp, ops::Range};
+
+/// The length that edits split the text into chunks of.
+const CHUNK_LEN: usize = 4096;
+
+/// How a byte is treated when decoding.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Class {
+    Symbol,
+    Padding,
+    /// Skipped by the engine, e.g. line endings of wrapped text
+    Ignored,
+    Invalid,
+}
+
+/// A piece of the text, with what validating it needs to know cached.
+#[derive(Debug)]
+struct Chunk {
+    bytes: Vec<u8>,
+    /// The number of symbols in `bytes`
+    symbols: usize,
+    /// The offset in `bytes` of the first padding byte
+    first_padding: Option<usize>,
+    /// The offset in `bytes` of the first invalid byte
+    first_invalid: Option<usize>,
+}
+
+impl Chunk {
+    fn new(bytes: &[u8], classes: &[Class; 256]) -> Self {
+        let class = |b: &u8| classes[usize::from(*b)];
+        Self {
+            bytes: bytes.to_vec(),
+            symbols: bytes.iter().filter(|b| class(b) == Class::Symbol).count(),
+            first_padding: bytes.iter().position(|b| class(b) == Class::Padding),
+            first_invalid: bytes.iter().position(|b| class(b) == Class::Invalid),
+        }
+    }
+}
+
+/// Base64 text that is validated as it is edited.
+///
+/// The error reported is the one decoding the whole text with the engine would return, for engines
+/// that decode chunk by chunk like [`GeneralPurpose`](crate::engine::GeneralPurpose). A limit on
+/// the input length set with
+/// [`with_max_encoded_len`](crate::engine::GeneralPurposeConfig::with_max_encoded_len) isn't
+/// checked.
+pub struct Incremental<'e, E: Engine> {
+    engine: &'e E,
+    chunks: Vec<Chunk>,
+    len: usize,
+    /// How the engine treats each byte value
+    classes: [Class; 256],
+}
+
+impl<'e, E: Engine> Incremental<'e, E> {
+    /// Create empty text to be validated with `engine`.
+    pub fn new(engine: &'e E) -> Self {
+        let config = engine.config();
+        let padding_byte = config.padding().decode_byte();
+        let mut classes = [Class::Invalid; 256];
+        for (byte, class) in classes.iter_mut().enumerate() {
+            let byte = byte as u8;
+            // a quad of skipped bytes decodes too, as nothing, so check for those first
+            *class = if config.decode_ignores(byte) {
+                Class::Ignored
+            } else if decodes_as_symbol(engine, byte) {
+                Class::Symbol
+            } else if byte == padding_byte {
+                Class::Padding
+            } else {
+                Class::Invalid
+            };
+        }
+
+        Self {
+            engine,
+            chunks: Vec::new(),
+            len: 0,
+            classes,
+        }
+    }
+
+    /// Insert `bytes` at `offset`.
+    ///
+    /// # Panics
+    ///
+    /// If `offset` is greater than the length of the text.
+    pub fn insert<T: AsRef<[u8]>>(&mut self, offset: usize, bytes: T) {
+        self.replace(offset..offset, bytes);
+    }
+
+    /// Delete the bytes in `range`.
+    ///
+    /// # Panics
+    ///
+    /// If `range` is out of bounds of the text.
+    pub fn delete(&mut self, range: Range<usize>) {
+        self.replace(range, []);
+    }
+
+    /// Replace the bytes in `range` with `bytes`.
+    ///
+    /// Only the chunks of the text that `range` touches are rebuilt.
+    ///
+    /// # Panics
+    ///
+    /// If `range` is out of bounds of the text.
+    pub fn replace<T: AsRef<[u8]>>(&mut self, range: Range<usize>, bytes: T) {
+        let bytes = bytes.as_ref();
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "range {:?} out of bounds of text of length {}",
+            range,
+            self.len
+        );
+
+        let (first, first_start) = self.locate(range.start);
+        let (last, last_start) = self.locate(range.end);
+
+        let mut rebuilt = Vec::with_capacity(bytes.len() + CHUNK_LEN);
+        if let Some(chunk) = self.chunks.get(first) {
+            rebuilt.extend_from_slice(&chunk.bytes[..range.start - first_start]);
+        }
+        rebuilt.extend_from_slice(bytes);
+        let mut end = last;
+        if let Some(chunk) = self.chunks.get(last) {
+            rebuilt.extend_from_slice(&chunk.bytes[range.end - last_start..]);
+            end += 1;
+        }
+        // merge what's left of a small chunk into the next, so edits don't fragment the text
+        if rebuilt.len() < CHUNK_LEN / 2 {
+            if let Some(chunk) = self.chunks.get(end) {
+                rebuilt.extend_from_slice(&chunk.bytes);
+                end += 1;
+            }
+        }
+
+        let classes = &self.classes;
+        let _ = self.chunks.splice(
+            first..end,
+            rebuilt
+                .chunks(CHUNK_LEN)
+                .map(|bytes| Chunk::new(bytes, classes)),
+        );
+        self.len = self.len - (range.end - range.start) + bytes.len();
+    }
+
+    /// The index of the chunk that `offset` is in or at the end of, and the offset it starts at.
+    ///
+    /// That's the number of chunks, and the length of the text, for empty text.
+    fn locate(&self, offset: usize) -> (usize, usize) {
+        let mut chunk_start = 0;
+        for (index, chunk) in self.chunks.iter().enumerate() {
+            if offset <= chunk_start + chunk.bytes.len() {
+                return (index, chunk_start);
+            }
+            chunk_start += chunk.bytes.len();
+        }
+
+        (self.chunks.len(), chunk_start)
+    }
+
+    /// The byte at `offset`, which must be in bounds.
+    fn byte(&self, offset: usize) -> u8 {
+        let (index, chunk_start) = self.locate(offset + 1);
+        self.chunks[index].bytes[offset - chunk_start]
+    }
+
+    /// The offset in the text just after its `n`th symbol, or 0 if `n` is 0.
+    fn after_symbol(&self, n: usize) -> usize {
+        let mut remaining = n;
+        let mut chunk_start = 0;
+        for chunk in &self.chunks {
+            if remaining == 0 {
+                break;
+            }
+            if remaining <= chunk.symbols {
+                let classes = &self.classes;
+                let (offset, _) = chunk
+                    .bytes
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &b)| classes[usize::from(b)] == Class::Symbol)
+                    .nth(remaining - 1)
+                    .expect("chunk has the symbols it counted");
+                return chunk_start + offset + 1;
+            }
+            remaining -= chunk.symbols;
+            chunk_start += chunk.bytes.len();
+        }
+
+        chunk_start
+    }
+
+    /// The offset of the first padding byte in the text.
+    fn first_padding(&self) -> Option<usize> {
+        let mut chunk_start = 0;
+        for chunk in &self.chunks {
+            if let Some(offset) = chunk.first_padding {
+                return Some(chunk_start + offset);
+            }
+            chunk_start += chunk.bytes.len();
+        }
+
+        None
+    }
+
+    /// The current text.
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut text = Vec::with_capacity(self.len);
+        for chunk in &self.chunks {
+            text.extend_from_slice(&chunk.bytes);
+        }
+        text
+    }
+
+    /// The length of the current text.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the text is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The first error in the current text, if any.
+    ///
+    /// The text is valid exactly when decoding it with the engine succeeds, but when it's invalid
+    /// in more than one place this is the error earliest in the text, which isn't always the one
+    /// decoding reports: that checks for a stray byte at the very end first.
+    pub fn error(&self) -> Option<DecodeError> {
+        let start = match self.tail_start() {
+            Ok(start) => start,
+            Err(offset) => return Some(DecodeError::InvalidByte(offset, self.byte(offset))),
+        };
+
+        // the whole quads of symbols before the tail decode fine on their own, and don't change
+        // how the rest decodes
+        let mut tail = Vec::with_capacity(self.len - start);
+        let (index, chunk_start) = self.locate(start);
+        for (i, chunk) in self.chunks.iter().enumerate().skip(index) {
+            let from = if i == index { start - chunk_start } else { 0 };
+            tail.extend_from_slice(&chunk.bytes[from..]);
+        }
+
+        Some(match self.engine.decode(&tail).err()? {
+            DecodeError::InvalidByte(offset, byte) => {
+                DecodeError::InvalidByte(start + offset, byte)
+            }
+            DecodeError::InvalidLength(len) => DecodeError::InvalidLength(start + len),
+            DecodeError::InvalidLastSymbol {
+                offset,
+                symbol,
+                symbol_value,
+            } => DecodeError::InvalidLastSymbol {
+                offset: start + offset,
+                symbol,
+                symbol_value,
+            },
+            DecodeError::PaddingNotAtEnd(offset) => DecodeError::PaddingNotAtEnd(start + offset),
+            e => e,
+        })
+    }
+
+    /// The offset from which the text has to be decoded to find an error, or the offset of an
+    /// invalid byte if there is one.
+    ///
+    /// That's just after the last whole quad of symbols before any padding, so for valid text it's
+    /// at most a quad of symbols and whatever is ignored around it from the end.
+    fn tail_start(&self) -> Result<usize, usize> {
+        let mut chunk_start = 0;
+        let mut symbols = 0;
+        let mut first_padding = None;
+        let mut symbols_before_padding = None;
+        for chunk in &self.chunks {
+            if first_padding.is_none() {
+                if let Some(offset) = chunk.first_padding {
+                    first_padding = Some(chunk_start + offset);
+                    let classes = &self.classes;
+                    symbols_before_padding = Some(
+                        symbols
+                            + chunk.bytes[..offset]
+                                .iter()
+                                .filter(|&&b| classes[usize::from(b)] == Class::Symbol)
+                                .count(),
+                    );
+                }
+            }
+            // a byte that is neither a symbol, padding, nor skipped is invalid wherever it is, and
+            // padding before it isn't at the end, so it's invalid too
+            if let Some(offset) = chunk.first_invalid {
+                let invalid = chunk_start + offset;
+                return Err(first_padding.map_or(invalid, |padding| cmp::min(padding, invalid)));
+            }
+            symbols += chunk.symbols;
+            chunk_start += chunk.bytes.len();
+        }
+
+        let whole_quads = symbols_before_padding.unwrap_or(symbols) / 4 * 4;
+        Ok(self.after_symbol(whole_quads))
+    }
+
+    /// The offset of the first invalid byte of the current text, if any.
+    ///
+    /// For invalid padding, that is the first padding byte, and for text that ends too soon, the
+    /// length of the text.
+    pub fn first_invalid(&self) -> Option<usize> {
+        Some(match self.error()? {
+            DecodeError::InvalidByte(offset, _) | DecodeError::InvalidLastSymbol { offset, .. } => {
+                offset
+            }
+            DecodeError::InvalidPadding => self.first_padding().unwrap_or(self.len),
+            _ => self.len,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alphabet,
+        engine::{
+            general_purpose::{
+                GeneralPurpose, NO_PAD, PAD, STANDARD, STANDARD_NO_PAD, URL_SAFE_NO_PAD_INDIFFERENT,
+            },
+            DecodePaddingMode,
+        },
+    };
+    use rand::{seq::SliceRandom, Rng, SeedableRng};
+
+    /// Check that random edits of base64 keep the text valid exactly when it decodes, and report
+    /// the same error as inserting the whole text at once.
+    fn check_random_edits<E: Engine>(engine: &E) {
+        let mut rng = rand::rngs::SmallRng::from_entropy();
+        let noise = b"AZaz09+/-_= \n*";
+
+        for _ in 0..200 {
+            let mut text = Incremental::new(engine);
+            let data: Vec<u8> = (0..rng.gen_range(0..50)).map(|_| rng.gen()).collect();
+            text.insert(0, engine.encode(&data));
+
+            for _ in 0..20 {
+                let len = text.len();
+                let start = rng.gen_range(0..=len);
+                match rng.gen_range(0..3) {
+                    0 => {
+                        let bytes: Vec<u8> = (0..rng.gen_range(1..4))
+                            .map(|_| *noise.choose(&mut rng).unwrap())
+                            .collect();
+                        text.insert(start, bytes);
+                    }
+                    1 => text.delete(start..rng.gen_range(start..=len)),
+                    _ => text.replace(start..rng.gen_range(start..=len), "QUJD"),
+                }
+
+                let mut from_scratch = Incremental::new(engine);
+                from_scratch.insert(0, text.to_vec());
+                assert_eq!(from_scratch.error(), text.error());
+
+                let decoded = engine.decode(text.to_vec());
+                assert_eq!(
+                    decoded.is_ok(),
+                    text.error().is_none(),
+                    "text {:?}",
+                    String::from_utf8_lossy(&text.to_vec())
+                );
+                assert_eq!(text.error().is_none(), text.first_invalid().is_none());
+                if let Err(DecodeError::InvalidByte(offset, _)) = decoded {
+                    assert!(
+                        text.first_invalid().unwrap() <= offset,
+                        "text {:?}",
+                        String::from_utf8_lossy(&text.to_vec())
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn random_edits_agree_with_decoding() {
+        check_random_edits(&STANDARD);
+        check_random_edits(&STANDARD_NO_PAD);
+        check_random_edits(&URL_SAFE_NO_PAD_INDIFFERENT);
+        check_random_edits(&GeneralPurpose::new(
+            &alphabet::STANDARD,
+            NO_PAD.with_decode_padding_mode(DecodePaddingMode::RequireNone),
+        ));
+        check_random_edits(&GeneralPurpose::new(
+            &alphabet::STANDARD,
+            PAD.with_decode_ignore_bytes(b" \n"),
+        ));
+    }
+
+    #[test]
+    fn first_invalid_points_at_the_problem() {
+        let mut text = Incremental::new(&STANDARD);
+        assert_eq!(None, text.first_invalid());
+
+        text.insert(0, "aGVsbG8");
+        assert_eq!(Some(DecodeError::InvalidPadding), text.error());
+        assert_eq!(Some(7), text.first_invalid());
+
+        text.insert(7, "=");
+        assert_eq!(None, text.first_invalid());
+
+        text.insert(4, "=");
+        assert_eq!(Some(DecodeError::InvalidByte(4, b'=')), text.error());
+        assert_eq!(Some(4), text.first_invalid());
+
+        text.replace(4..5, "b");
+        assert_eq!(Some(DecodeError::InvalidByte(8, b'=')), text.error());
+        assert_eq!(Some(8), text.first_invalid());
+
+        text.delete(8..9);
+        assert_eq!(None, text.error());
+
+        // decoding reports the trailing newline first
+        text.replace(5..8, "*G8\n");
+        assert_eq!(
+            Err(DecodeError::InvalidByte(8, b'\n')),
+            STANDARD.decode(text.to_vec())
+        );
+        assert_eq!(Some(DecodeError::InvalidByte(5, b'*')), text.error());
+        assert_eq!(b"aGVsb*G8\n", &text.to_vec()[..]);
+    }
+
+    #[test]
+    fn edits_to_wrapped_text_stay_local() {
+        let engine = GeneralPurpose::new(&alphabet::STANDARD, PAD.with_encode_line_wrap(76));
+        let mut rng = rand::rngs::SmallRng::from_entropy();
+        let data: Vec<u8> = (0..100_000).map(|_| rng.gen()).collect();
+        let mut text = Incremental::new(&engine);
+        text.insert(0, engine.encode(&data));
+        assert_eq!(None, text.error());
+        // only the last quad is decoded, not everything from the first line ending
+        assert!(text.len() - text.tail_start().unwrap() <= 8);
+
+        for _ in 0..200 {
+            let chunks: Vec<*const u8> = text.chunks.iter().map(|c| c.bytes.as_ptr()).collect();
+            let start = rng.gen_range(0..text.len() - 4);
+            let replacement = if rng.gen() { "*" } else { "QUJD" };
+            text.replace(start..start + 4, replacement);
+
+            // at most the edited chunk and the one after it are rebuilt
+            let (edited, _) = text.locate(start);
+            let unchanged = text
+                .chunks
+                .iter()
+                .enumerate()
+                .filter(|&(i, c)| i < edited && chunks[i] == c.bytes.as_ptr())
+                .count();
+            assert_eq!(edited, unchanged);
+            assert!(text.chunks.len() + 1 >= chunks.len());
+
+            let decoded = engine.decode(text.to_vec());
+            assert_eq!(decoded.is_ok(), text.error().is_none());
+            let mut from_scratch = Incremental::new(&engine);
+            from_scratch.insert(0, text.to_vec());
+            assert_eq!(from_scratch.error(), text.error());
+            if let Err(DecodeError::InvalidByte(offset, _)) = decoded {
+                assert!(text.first_invalid().unwrap() <= offset);
+            }
+
+            text.delete(start..start + replacement.len());
+            text.insert(start, &engine.encode(&data)[start..start + 4]);
+            assert_eq!(None, text.error());
+        }
+        assert_eq!(engine.encode(&data).as_bytes(), &text.to_vec()[..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn delete_out_of_bounds_panics() {
+        let mut text = Incremental::new(&STANDARD);
+        text.insert(0, "aGVs");
+        text.delete(2..5);
+    }
+}
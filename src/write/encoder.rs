@@ -1,4 +1,7 @@
-use crate::engine::Engine;
+use crate::{
+    encode::{add_padding, column_after, wrap_lines},
+    engine::{Config, Engine, Padding},
+};
 use std::{
     cmp, fmt, io,
     io::{ErrorKind, Result},
@@ -7,9 +10,27 @@ use std::{
 pub(crate) const BUF_SIZE: usize = 1024;
 /// The most bytes whose encoding will fit in `BUF_SIZE`
 const MAX_INPUT_LEN: usize = BUF_SIZE / 4 * 3;
-// 3 bytes of input = 4 bytes of base64, always (because we don't allow line wrapping)
+// 3 bytes of input = 4 bytes of base64, not counting line endings
 const MIN_ENCODE_CHUNK_SIZE: usize = 3;
 
+/// The most bytes whose encoding will fit in `BUF_SIZE` once broken into lines of `line_len` ending
+/// in `line_ending_len` bytes, wherever the first line starts.
+fn max_input_len(line_len: usize, line_ending_len: usize) -> usize {
+    if line_len == 0 {
+        return MAX_INPUT_LEN;
+    }
+
+    let max_encoded_len = if line_len >= BUF_SIZE {
+        // at most one line ending
+        BUF_SIZE - line_ending_len
+    } else {
+        // `n` bytes on lines of `line_len` need at most `n / line_len + 1` line endings
+        (BUF_SIZE - line_ending_len) * line_len / (line_len + line_ending_len)
+    };
+
+    max_encoded_len / 4 * 3
+}
+
 /// A `Write` implementation that base64 encodes data before delegating to the wrapped writer.
 ///
 /// Because base64 has special handling for the end of the input data (padding, etc), there's a
@@ -76,8 +97,18 @@ pub struct EncoderWriter<'e, E: Engine, W: io::Write> {
     output: [u8; BUF_SIZE],
     /// How much of `output` is occupied with encoded data that couldn't be written last time
     output_occupied_len: usize,
+    /// How many bytes have been written on the current line, when wrapping lines
+    line_column: usize,
+    /// Whether output has been encoded that still needs a line ending after it when finishing, if
+    /// the engine writes a trailing newline
+    trailing_newline_pending: bool,
+    /// The most input to encode into `output` at once, leaving room for line endings
+    max_input_len: usize,
     /// panic safety: don't write again in destructor if writer panicked while we were writing to it
     panicked: bool,
+    /// Entered for each call, so events are attributed to this writer
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
 }
 
 impl<'e, E: Engine, W: io::Write> fmt::Debug for EncoderWriter<'e, E, W> {
@@ -103,7 +134,15 @@ impl<'e, E: Engine, W: io::Write> EncoderWriter<'e, E, W> {
             extra_input_occupied_len: 0,
             output: [0u8; BUF_SIZE],
             output_occupied_len: 0,
+            line_column: 0,
+            trailing_newline_pending: false,
+            max_input_len: max_input_len(
+                engine.config().encode_line_wrap(),
+                engine.config().encode_line_ending().as_bytes().len(),
+            ),
             panicked: false,
+            #[cfg(feature = "tracing")]
+            span: tracing::debug_span!("base64::EncoderWriter"),
         }
     }
 
@@ -131,6 +170,11 @@ impl<'e, E: Engine, W: io::Write> EncoderWriter<'e, E, W> {
             "Encoder has already had finish() called"
         );
 
+        #[cfg(feature = "tracing")]
+        let _entered = self.span.clone().entered();
+        #[cfg(feature = "tracing")]
+        tracing::trace!(extra_input_len = self.extra_input_occupied_len, "finishing");
+
         self.write_final_leftovers()?;
 
         let writer = self.delegate.take().expect("Writer must be present");
@@ -149,23 +193,46 @@ impl<'e, E: Engine, W: io::Write> EncoderWriter<'e, E, W> {
         self.write_all_encoded_output()?;
 
         if self.extra_input_occupied_len > 0 {
-            let encoded_len = self
-                .engine
-                .encode_slice(
-                    &self.extra_input[..self.extra_input_occupied_len],
-                    &mut self.output[..],
-                )
-                .expect("buffer is large enough");
-
-            self.output_occupied_len = encoded_len;
-
-            self.write_all_encoded_output()?;
+            let config = self.engine.config();
+            let mut encoded_len = self.engine.internal_encode(
+                &self.extra_input[..self.extra_input_occupied_len],
+                &mut self.output[..],
+            );
+            if let Padding::Padded(padding_byte) = config.padding() {
+                encoded_len +=
+                    add_padding(encoded_len, &mut self.output[encoded_len..], padding_byte);
+            }
 
-            // write succeeded, do not write the encoding of extra again if finish() is retried
+            let line_len = config.encode_line_wrap();
+            self.output_occupied_len = wrap_lines(
+                &mut self.output,
+                encoded_len,
+                line_len,
+                self.line_column,
+                config.encode_line_ending().as_bytes(),
+            );
+            self.line_column = column_after(encoded_len, line_len, self.line_column);
+            #[cfg(feature = "tracing")]
+            tracing::trace!(
+                input_len = self.extra_input_occupied_len,
+                encoded_len = self.output_occupied_len,
+                "encoded chunk"
+            );
+
+            // the encoding of extra is buffered now, so don't encode it again if finish() is retried
             self.extra_input_occupied_len = 0;
+            self.trailing_newline_pending = config.encode_trailing_newline();
         }
 
-        Ok(())
+        if self.trailing_newline_pending {
+            let line_ending = self.engine.config().encode_line_ending().as_bytes();
+            let end = self.output_occupied_len + line_ending.len();
+            self.output[self.output_occupied_len..end].copy_from_slice(line_ending);
+            self.output_occupied_len = end;
+            self.trailing_newline_pending = false;
+        }
+
+        self.write_all_encoded_output()
     }
 
     /// Write as much of the encoded output to the delegate writer as it will accept, and store the
@@ -185,11 +252,21 @@ impl<'e, E: Engine, W: io::Write> EncoderWriter<'e, E, W> {
             .write(&self.output[..current_output_len]);
         self.panicked = false;
 
+        #[cfg(feature = "tracing")]
+        if let Err(error) = &res {
+            tracing::debug!(%error, "delegate write failed");
+        }
+
         res.map(|consumed| {
             debug_assert!(consumed <= current_output_len);
 
             if consumed < current_output_len {
                 self.output_occupied_len = current_output_len.checked_sub(consumed).unwrap();
+                #[cfg(feature = "tracing")]
+                tracing::trace!(
+                    pending_len = self.output_occupied_len,
+                    "delegate accepted part of the output"
+                );
                 // If we're blocking on I/O, the minor inefficiency of copying bytes to the
                 // start of the buffer is the least of our concerns...
                 // TODO Rotate moves more than we need to; copy_within now stable.
@@ -273,6 +350,9 @@ impl<'e, E: Engine, W: io::Write> io::Write for EncoderWriter<'e, E, W> {
             return Ok(0);
         }
 
+        #[cfg(feature = "tracing")]
+        let _entered = self.span.clone().entered();
+
         // The contract of `Write::write` places some constraints on this implementation:
         // - a call to `write()` represents at most one call to a wrapped `Write`, so we can't
         // iterate over the input and encode multiple chunks.
@@ -298,7 +378,7 @@ impl<'e, E: Engine, W: io::Write> io::Write for EncoderWriter<'e, E, W> {
 
         let mut encoded_size = 0;
         // always a multiple of MIN_ENCODE_CHUNK_SIZE
-        let mut max_input_len = MAX_INPUT_LEN;
+        let mut max_input_len = self.max_input_len;
 
         // process leftover un-encoded input from last write
         if self.extra_input_occupied_len > 0 {
@@ -329,7 +409,7 @@ impl<'e, E: Engine, W: io::Write> io::Write for EncoderWriter<'e, E, W> {
                 // don't clobber where we just encoded to
                 encoded_size = 4;
                 // and don't read more than can be encoded
-                max_input_len = MAX_INPUT_LEN - MIN_ENCODE_CHUNK_SIZE;
+                max_input_len = self.max_input_len - MIN_ENCODE_CHUNK_SIZE;
 
             // fall through to normal encoding
             } else {
@@ -340,12 +420,22 @@ impl<'e, E: Engine, W: io::Write> io::Write for EncoderWriter<'e, E, W> {
 
                 self.extra_input[self.extra_input_occupied_len] = input[0];
                 self.extra_input_occupied_len += 1;
+                #[cfg(feature = "tracing")]
+                tracing::trace!(
+                    extra_input_len = self.extra_input_occupied_len,
+                    "holding an incomplete chunk of input"
+                );
                 return Ok(1);
             };
         } else if input.len() < MIN_ENCODE_CHUNK_SIZE {
             // `extra` is empty, and `input` fits inside it
             self.extra_input[0..input.len()].copy_from_slice(input);
             self.extra_input_occupied_len = input.len();
+            #[cfg(feature = "tracing")]
+            tracing::trace!(
+                extra_input_len = self.extra_input_occupied_len,
+                "holding an incomplete chunk of input"
+            );
             return Ok(input.len());
         };
 
@@ -353,9 +443,9 @@ impl<'e, E: Engine, W: io::Write> io::Write for EncoderWriter<'e, E, W> {
         debug_assert!(encoded_size == 0 || encoded_size == 4);
         debug_assert!(
             // didn't encode extra input
-            MAX_INPUT_LEN == max_input_len
+            self.max_input_len == max_input_len
                 // encoded one triple
-                || MAX_INPUT_LEN == max_input_len + MIN_ENCODE_CHUNK_SIZE
+                || self.max_input_len == max_input_len + MIN_ENCODE_CHUNK_SIZE
         );
 
         // encode complete triples only
@@ -369,14 +459,34 @@ impl<'e, E: Engine, W: io::Write> io::Write for EncoderWriter<'e, E, W> {
             &mut self.output[encoded_size..],
         );
 
-        // not updating `self.output_occupied_len` here because if the below write fails, it should
-        // "never take place" -- the buffer contents we encoded are ignored and perhaps retried
-        // later, if the consumer chooses.
+        let line_len = self.engine.config().encode_line_wrap();
+        let output_len = wrap_lines(
+            &mut self.output,
+            encoded_size,
+            line_len,
+            self.line_column,
+            self.engine.config().encode_line_ending().as_bytes(),
+        );
+
+        // not updating `self.output_occupied_len` or `self.line_column` here because if the below
+        // write fails, it should "never take place" -- the buffer contents we encoded are ignored
+        // and perhaps retried later, if the consumer chooses.
 
-        self.write_to_delegate(encoded_size)
+        self.write_to_delegate(output_len)
             // no matter whether we wrote the full encoded buffer or not, we consumed the same
             // input
-            .map(|()| extra_input_read_len + input_chunks_to_encode_len)
+            .map(|()| {
+                self.line_column = column_after(encoded_size, line_len, self.line_column);
+                self.trailing_newline_pending = self.engine.config().encode_trailing_newline();
+                // the leftover input from last time was encoded too, if `extra` was filled up
+                #[cfg(feature = "tracing")]
+                tracing::trace!(
+                    input_len = orig_extra_len + extra_input_read_len + input_chunks_to_encode_len,
+                    encoded_len = output_len,
+                    "encoded chunk"
+                );
+                extra_input_read_len + input_chunks_to_encode_len
+            })
             .map_err(|e| {
                 // in case we filled and encoded `extra`, reset extra_len
                 self.extra_input_occupied_len = orig_extra_len;
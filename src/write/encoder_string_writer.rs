@@ -44,6 +44,18 @@ use std::io;
 /// assert_eq!("base64: YXNkZg==", &buf);
 /// ```
 ///
+/// Since it's an `io::Write`, it can be passed to anything that writes bytes, like `io::copy`:
+///
+/// ```
+/// use std::io;
+/// use base64::engine::general_purpose;
+///
+/// let mut enc = base64::write::EncoderStringWriter::new(&general_purpose::STANDARD);
+/// io::copy(&mut &b"asdf"[..], &mut enc).unwrap();
+///
+/// assert_eq!("YXNkZg==", enc.into_inner());
+/// ```
+///
 /// # Performance
 ///
 /// Because it has to validate that the base64 is UTF-8, it is about 80% as fast as writing plain
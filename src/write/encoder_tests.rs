@@ -7,7 +7,7 @@ use crate::{
     alphabet::{STANDARD, URL_SAFE},
     engine::{
         general_purpose::{GeneralPurpose, NO_PAD, PAD},
-        Engine,
+        Engine, Padding,
     },
     tests::random_engine,
 };
@@ -148,6 +148,31 @@ fn finish_writes_extra_byte() {
     );
 }
 
+#[test]
+fn finish_writes_custom_padding() {
+    let engine = GeneralPurpose::new(&URL_SAFE, PAD.with_padding(Padding::Padded(b'.')));
+    let mut enc = EncoderWriter::new(Vec::new(), &engine);
+    enc.write_all(b"abcd").unwrap();
+
+    assert_eq!(b"YWJjZA..", &enc.finish().unwrap()[..]);
+}
+
+#[test]
+fn finish_retried_after_error_writes_extra_once() {
+    let writer = FailingWriter {
+        written: Vec::new(),
+        fail_next: true,
+    };
+    let mut enc = EncoderWriter::new(writer, &URL_SAFE_ENGINE);
+    // buffered until finish() since it's less than a chunk
+    assert_eq!(1, enc.write(b"g").unwrap());
+
+    assert_eq!(io::ErrorKind::Other, enc.finish().unwrap_err().kind());
+    let writer = enc.finish().unwrap();
+
+    assert_eq!(URL_SAFE_ENGINE.encode("g").as_bytes(), &writer.written[..]);
+}
+
 #[test]
 fn write_partial_chunk_encodes_partial_chunk() {
     let mut c = Cursor::new(Vec::new());
@@ -431,6 +456,58 @@ fn writes_that_only_write_part_of_input_and_sometimes_interrupt_produce_correct_
     }
 }
 
+#[test]
+fn wrapped_lines_with_partial_and_interrupted_writes_match_normal_encode() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..1_000 {
+        let orig_len: usize = rng.gen_range(0..5_000);
+        let orig_data: Vec<u8> = (0..orig_len).map(|_| rng.gen()).collect();
+        let line_len = match rng.gen_range(0..4) {
+            0 => rng.gen_range(1..5),
+            1 => rng.gen_range(1_000..1_100),
+            _ => rng.gen_range(1..100),
+        };
+        let engine = GeneralPurpose::new(
+            &STANDARD,
+            PAD.with_encode_padding(rng.gen())
+                .with_encode_line_wrap(line_len)
+                .with_encode_trailing_newline(rng.gen())
+                .with_encode_line_ending(rng.gen()),
+        );
+
+        let mut stream_encoded = Vec::new();
+        {
+            let mut partial_rng = rand::thread_rng();
+            let mut partial_writer = PartialInterruptingWriter {
+                w: &mut stream_encoded,
+                rng: &mut partial_rng,
+                full_input_fraction: 0.1,
+                no_interrupt_fraction: 0.5,
+            };
+
+            let mut stream_encoder = EncoderWriter::new(&mut partial_writer, &engine);
+            let mut bytes_consumed = 0;
+            while bytes_consumed < orig_len {
+                let input_len: usize = cmp::min(rng.gen_range(0..2_000), orig_len - bytes_consumed);
+                match stream_encoder.write(&orig_data[bytes_consumed..bytes_consumed + input_len]) {
+                    Ok(len) => bytes_consumed += len,
+                    Err(e) => assert_eq!(io::ErrorKind::Interrupted, e.kind()),
+                }
+            }
+
+            let _ = stream_encoder.finish().unwrap();
+        }
+
+        assert_eq!(
+            engine.encode(&orig_data),
+            str::from_utf8(&stream_encoded).unwrap(),
+            "line_len={}",
+            line_len
+        );
+    }
+}
+
 /// Retry writes until all the data is written or an error that isn't Interrupted is returned.
 fn retry_interrupted_write_all<W: Write>(w: &mut W, buf: &[u8]) -> io::Result<()> {
     let mut bytes_consumed = 0;
@@ -522,6 +599,28 @@ impl<'a, W: Write, R: Rng> Write for InterruptingWriter<'a, W, R> {
     }
 }
 
+/// A `Write` implementation that fails once if `fail_next` is set.
+#[derive(Debug)]
+struct FailingWriter {
+    written: Vec<u8>,
+    fail_next: bool,
+}
+
+impl Write for FailingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.fail_next {
+            self.fail_next = false;
+            return Err(io::Error::new(io::ErrorKind::Other, "failed"));
+        }
+
+        self.written.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 /// A `Write` implementation that sometimes will only write part of its input.
 struct PartialInterruptingWriter<'a, W: 'a + Write, R: 'a + Rng> {
     w: &'a mut W,
@@ -538,8 +637,8 @@ impl<'a, W: Write, R: Rng> Write for PartialInterruptingWriter<'a, W, R> {
             return Err(io::Error::new(io::ErrorKind::Interrupted, "interrupted"));
         }
 
-        if self.rng.gen_range(0.0..1.0) <= self.full_input_fraction || buf.is_empty() {
-            // pass through the buf untouched
+        if self.rng.gen_range(0.0..1.0) <= self.full_input_fraction || buf.len() <= 1 {
+            // pass through the buf untouched, also when it has no nonempty shorter prefix to write
             self.w.write(buf)
         } else {
             // only use a prefix of it
@@ -552,3 +651,26 @@ impl<'a, W: Write, R: Rng> Write for PartialInterruptingWriter<'a, W, R> {
         self.w.flush()
     }
 }
+
+#[cfg(feature = "tracing")]
+#[test]
+fn traces_chunks_and_carry_over() {
+    use crate::tests::EventRecorder;
+
+    let events = EventRecorder::record(|| {
+        let mut writer = EncoderWriter::new(Vec::new(), &crate::engine::general_purpose::STANDARD);
+        writer.write_all(b"he").unwrap();
+        writer.write_all(b"llo").unwrap();
+        assert_eq!(b"aGVsbG8=", &writer.finish().unwrap()[..]);
+    });
+    assert_eq!(
+        vec![
+            "holding an incomplete chunk of input extra_input_len=2",
+            "encoded chunk input_len=3 encoded_len=4",
+            "holding an incomplete chunk of input extra_input_len=2",
+            "finishing extra_input_len=2",
+            "encoded chunk input_len=2 encoded_len=4",
+        ],
+        events
+    );
+}
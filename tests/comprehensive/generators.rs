@@ -4,18 +4,82 @@
 //! of the rust-base64 library. The generators create various types of inputs including
 //! byte sequences, base64 strings, invalid inputs, and engine configurations.
 
-use crate::comprehensive::test_config::{AlphabetType, PaddingMode, TestConfig};
+use crate::comprehensive::test_config::{AlphabetType, ExpectedBehavior, ExpectedError, PaddingMode, TestConfig, TestInput, Wrapping};
+use base64::alphabet::{self, Alphabet};
+use base64::engine::general_purpose::{GeneralPurpose, PAD};
+use base64::engine::{Config, LineEnding, Padding};
+use base64::Engine;
 use bolero_generator::{gen, ValueGenerator};
 
+/// Distributions that byte sequences can be drawn from
+///
+/// Uniformly random bytes rarely hit the paths in optimized engines that depend on the byte values,
+/// so the structured distributions make those cases common.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ByteDistribution {
+    /// Uniformly random bytes
+    Random,
+    /// All bytes are 0x00
+    Zeros,
+    /// All bytes are 0xFF
+    Ones,
+    /// Printable ASCII bytes only
+    Ascii,
+    /// A short random pattern of 1 to 8 bytes, repeated
+    Repeating,
+}
+
+impl ByteDistribution {
+    /// Distributions with their weights when chosen by the driver
+    const WEIGHTED: &'static [(ByteDistribution, usize)] = &[
+        (ByteDistribution::Random, 4),
+        (ByteDistribution::Zeros, 1),
+        (ByteDistribution::Ones, 1),
+        (ByteDistribution::Ascii, 1),
+        (ByteDistribution::Repeating, 1),
+    ];
+
+    /// Choose a distribution according to the weights in `WEIGHTED`
+    fn choose<D>(driver: &mut D) -> Option<Self>
+    where
+        D: bolero_generator::driver::Driver,
+    {
+        let total: usize = Self::WEIGHTED.iter().map(|(_, weight)| weight).sum();
+        let mut choice = gen::<usize>().generate(driver)? % total;
+        for (distribution, weight) in Self::WEIGHTED {
+            if choice < *weight {
+                return Some(*distribution);
+            }
+            choice -= weight;
+        }
+        unreachable!("choice is less than the total weight")
+    }
+}
+
 /// Generator for arbitrary byte sequences
+///
+/// By default the distribution of each sequence is chosen by the driver, weighted towards uniformly
+/// random bytes; use `with_distribution` to always use one distribution.
 #[derive(Debug)]
 pub struct ByteSequenceGenerator {
     max_size: usize,
+    distribution: Option<ByteDistribution>,
 }
 
 impl ByteSequenceGenerator {
     pub fn new(max_size: usize) -> Self {
-        Self { max_size }
+        Self {
+            max_size,
+            distribution: None,
+        }
+    }
+
+    /// Always generate sequences from `distribution`
+    pub fn with_distribution(self, distribution: ByteDistribution) -> Self {
+        Self {
+            distribution: Some(distribution),
+            ..self
+        }
     }
 }
 
@@ -26,19 +90,51 @@ impl ValueGenerator for ByteSequenceGenerator {
     where
         D: bolero_generator::driver::Driver,
     {
+        let distribution = match self.distribution {
+            Some(distribution) => distribution,
+            None => ByteDistribution::choose(driver)?,
+        };
         let size = gen::<usize>().generate(driver)? % (self.max_size + 1);
-        let mut bytes = Vec::with_capacity(size);
-        for _ in 0..size {
-            bytes.push(gen::<u8>().generate(driver)?);
-        }
+        
+        let bytes = match distribution {
+            ByteDistribution::Random => {
+                let mut bytes = Vec::with_capacity(size);
+                for _ in 0..size {
+                    bytes.push(gen::<u8>().generate(driver)?);
+                }
+                bytes
+            }
+            ByteDistribution::Zeros => vec![0x00; size],
+            ByteDistribution::Ones => vec![0xFF; size],
+            ByteDistribution::Ascii => {
+                let mut bytes = Vec::with_capacity(size);
+                for _ in 0..size {
+                    bytes.push(b' ' + gen::<u8>().generate(driver)? % 95);
+                }
+                bytes
+            }
+            ByteDistribution::Repeating => {
+                let pattern_len = 1 + gen::<usize>().generate(driver)? % 8;
+                let mut pattern = Vec::with_capacity(pattern_len);
+                for _ in 0..pattern_len {
+                    pattern.push(gen::<u8>().generate(driver)?);
+                }
+                pattern.iter().cycle().take(size).cloned().collect()
+            }
+        };
         Some(bytes)
     }
 }
 
 /// Generator for valid base64 strings
+///
+/// Strings are made by encoding random bytes with the target alphabet, so they always decode. The
+/// padding is then adjusted according to the padding mode: kept for the canonical modes, removed
+/// for the no-padding modes, and either kept or removed for `Indifferent`.
 #[derive(Debug)]
 pub struct Base64StringGenerator {
     alphabet_type: AlphabetType,
+    padding_mode: PaddingMode,
     max_size: usize,
 }
 
@@ -46,17 +142,31 @@ impl Base64StringGenerator {
     pub fn new(alphabet_type: AlphabetType, max_size: usize) -> Self {
         Self {
             alphabet_type,
+            padding_mode: PaddingMode::Canonical,
             max_size,
         }
     }
 
-    fn get_alphabet_chars(&self) -> &[u8] {
-        match &self.alphabet_type {
-            AlphabetType::Standard => b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/",
-            AlphabetType::UrlSafe => b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
-            AlphabetType::Custom(chars) => chars,
+    /// Adjust the padding of generated strings to suit `padding_mode`
+    pub fn with_padding_mode(self, padding_mode: PaddingMode) -> Self {
+        Self {
+            padding_mode,
+            ..self
         }
     }
+
+    fn create_engine(&self) -> GeneralPurpose {
+        let alphabet = match &self.alphabet_type {
+            AlphabetType::Standard => alphabet::STANDARD,
+            AlphabetType::UrlSafe => alphabet::URL_SAFE,
+            AlphabetType::Custom(chars) => {
+                let alphabet_str = std::str::from_utf8(chars).expect("Custom alphabet should be valid UTF-8");
+                Alphabet::new(alphabet_str).expect("Custom alphabet should be valid")
+            }
+        };
+        
+        GeneralPurpose::new(&alphabet, PAD)
+    }
 }
 
 impl ValueGenerator for Base64StringGenerator {
@@ -66,32 +176,17 @@ impl ValueGenerator for Base64StringGenerator {
     where
         D: bolero_generator::driver::Driver,
     {
-        let alphabet = self.get_alphabet_chars();
-        let size = gen::<usize>().generate(driver)? % (self.max_size + 1);
-        
-        // Generate a valid base64 string by creating groups of 4 characters
-        let mut result = String::new();
-        let groups = size / 4;
+        // every 3 bytes encode to 4 characters, so this keeps the padded output within max_size
+        let bytes = ByteSequenceGenerator::new(self.max_size / 4 * 3).generate(driver)?;
+        let mut result = self.create_engine().encode(&bytes);
         
-        for _ in 0..groups {
-            for _ in 0..4 {
-                let char_index = gen::<usize>().generate(driver)? % alphabet.len();
-                result.push(alphabet[char_index] as char);
-            }
-        }
-        
-        // Handle remaining characters (should be 0, 1, 2, or 3)
-        let remaining = size % 4;
-        for _ in 0..remaining {
-            let char_index = gen::<usize>().generate(driver)? % alphabet.len();
-            result.push(alphabet[char_index] as char);
-        }
-        
-        // Add padding if needed for standard base64
-        match remaining {
-            2 => result.push_str("=="),
-            3 => result.push('='),
-            _ => {}
+        let strip_padding = match self.padding_mode {
+            PaddingMode::Canonical | PaddingMode::RequireCanonical => false,
+            PaddingMode::None | PaddingMode::RequireNone => true,
+            PaddingMode::Indifferent => gen::<bool>().generate(driver)?,
+        };
+        if strip_padding {
+            result.truncate(result.trim_end_matches('=').len());
         }
         
         Some(result)
@@ -121,7 +216,7 @@ impl ValueGenerator for InvalidInputGenerator {
         let mut result = String::new();
         
         // Generate string with potentially invalid characters
-        let invalid_chars = b"!@#$%^&*()[]{}|\\:;\"'<>?,./~`";
+        let invalid_chars: &[u8] = b"!@#$%^&*()[]{}|\\:;\"'<>?,./~`";
         let valid_chars = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
         
         for _ in 0..size {
@@ -139,6 +234,203 @@ impl ValueGenerator for InvalidInputGenerator {
     }
 }
 
+/// Generator for inputs with one known defect, paired with the error it should cause
+///
+/// Each input is the encoding of random bytes by an engine for a generated configuration, with one
+/// defect injected: a symbol replaced by a byte outside the alphabet, a symbol replaced by padding,
+/// a truncation to one symbol past a whole quad, trailing bits set in the last symbol, or, for the
+/// configurations that require a padding style, the opposite style of padding.
+#[derive(Debug)]
+pub struct DefectiveInputGenerator {
+    max_size: usize,
+}
+
+impl DefectiveInputGenerator {
+    pub fn new(max_size: usize) -> Self {
+        Self { max_size }
+    }
+}
+
+impl ValueGenerator for DefectiveInputGenerator {
+    type Output = TestInput;
+
+    fn generate<D>(&self, driver: &mut D) -> Option<Self::Output>
+    where
+        D: bolero_generator::driver::Driver,
+    {
+        let config = ConfigurationGenerator.generate(driver)?;
+        let engine = config.create_engine();
+        let symbols = config.alphabet.symbols();
+        
+        // A partial last block, so there is always padding to get wrong and trailing bits to set,
+        // and at least 2 symbols
+        let mut bytes = ByteSequenceGenerator::new(self.max_size).generate(driver)?;
+        if bytes.len() % 3 == 0 {
+            bytes.push(gen::<u8>().generate(driver)?);
+        }
+        
+        // Configs that don't write padding still check for the default `=` when decoding
+        let padding_byte = match engine.config().padding() {
+            Padding::Padded(padding_byte) => padding_byte,
+            Padding::Unpadded => b'=',
+        };
+        let mut input = engine.encode(&bytes).into_bytes();
+        let body_len = input.iter().position(|&b| b == padding_byte).unwrap_or(input.len());
+        
+        let requires_padding_style = matches!(config.padding_mode, PaddingMode::RequireCanonical | PaddingMode::RequireNone);
+        let kinds = if requires_padding_style { 5 } else { 4 };
+        let expected = match gen::<u8>().generate(driver)? % kinds {
+            0 => {
+                let position = gen::<usize>().generate(driver)? % body_len;
+                let mut byte = gen::<u8>().generate(driver)?;
+                while symbols.contains(&byte) || byte == padding_byte {
+                    byte = byte.wrapping_add(1);
+                }
+                input[position] = byte;
+                ExpectedError::InvalidByte { position, byte }
+            }
+            1 => {
+                // Followed by at least one symbol, so it can't be taken as trailing padding
+                let position = gen::<usize>().generate(driver)? % (body_len - 1);
+                input[position] = padding_byte;
+                ExpectedError::InvalidByte { position, byte: padding_byte }
+            }
+            2 => {
+                let length = gen::<usize>().generate(driver)? % (body_len / 4 + 1) * 4 + 1;
+                input.truncate(length);
+                ExpectedError::InvalidLength { length }
+            }
+            3 => {
+                // Canonical encodings leave the unused low bits of the last symbol clear
+                let position = body_len - 1;
+                let value = symbols.iter().position(|&b| b == input[position]).expect("Encoded symbol should be in the alphabet");
+                input[position] = symbols[value | 1];
+                ExpectedError::InvalidLastSymbol { position }
+            }
+            _ => {
+                if input.len() > body_len {
+                    input.truncate(body_len);
+                } else {
+                    input.resize(body_len + (4 - body_len % 4) % 4, padding_byte);
+                }
+                ExpectedError::InvalidPadding
+            }
+        };
+        
+        Some(TestInput {
+            raw_input: input,
+            config,
+            expected_behavior: ExpectedBehavior::Error(expected),
+        })
+    }
+}
+
+/// How `MutatedCanonicalGenerator` changed a canonical encoding
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MutationKind {
+    /// A letter symbol with its case flipped
+    FlipCase,
+    /// A symbol replaced by a different symbol of the alphabet
+    SwapSymbol,
+    /// A padding byte replaced by a symbol or, if there is no padding, the last symbol replaced by
+    /// padding
+    ChangePadding,
+}
+
+/// A canonical encoding with exactly one byte changed
+#[derive(Debug, Clone)]
+pub struct MutatedCanonical {
+    pub config: TestConfig,
+    /// The bytes that were encoded
+    pub payload: Vec<u8>,
+    /// The canonical encoding of `payload`
+    pub canonical: Vec<u8>,
+    /// `canonical` with the byte at `position` replaced
+    pub mutated: Vec<u8>,
+    pub position: usize,
+    pub kind: MutationKind,
+}
+
+/// Generator for near-canonical inputs that differ from a canonical encoding in exactly one byte
+///
+/// Each input is the encoding of nonempty random bytes by an engine for a generated configuration,
+/// with one byte changed in a way a corrupted transfer might: a letter's case flipped, a symbol
+/// swapped for another, or padding changed. Unlike `DefectiveInputGenerator`, the outcome isn't
+/// predicted, since many of these are still valid base64.
+#[derive(Debug)]
+pub struct MutatedCanonicalGenerator {
+    max_size: usize,
+}
+
+impl MutatedCanonicalGenerator {
+    pub fn new(max_size: usize) -> Self {
+        Self { max_size }
+    }
+}
+
+impl ValueGenerator for MutatedCanonicalGenerator {
+    type Output = MutatedCanonical;
+
+    fn generate<D>(&self, driver: &mut D) -> Option<Self::Output>
+    where
+        D: bolero_generator::driver::Driver,
+    {
+        let config = ConfigurationGenerator.generate(driver)?;
+        let engine = config.create_engine();
+        let symbols = config.alphabet.symbols();
+        
+        // At least one symbol to change
+        let mut payload = ByteSequenceGenerator::new(self.max_size).generate(driver)?;
+        if payload.is_empty() {
+            payload.push(gen::<u8>().generate(driver)?);
+        }
+        
+        // Configs that don't write padding still check for the default `=` when decoding
+        let padding_byte = match engine.config().padding() {
+            Padding::Padded(padding_byte) => padding_byte,
+            Padding::Unpadded => b'=',
+        };
+        let canonical = engine.encode(&payload).into_bytes();
+        let body_len = canonical.iter().position(|&b| b == padding_byte).unwrap_or(canonical.len());
+        let value_at = |position: usize| symbols.iter().position(|&b| b == canonical[position]).expect("Encoded symbol should be in the alphabet");
+        
+        let start = gen::<usize>().generate(driver)? % body_len;
+        // The first letter from `start` on, wrapping around, if there are any
+        let letter = (0..body_len).map(|i| (start + i) % body_len).find(|&i| canonical[i].is_ascii_alphabetic());
+        let (position, replacement, kind) = match (gen::<u8>().generate(driver)? % 3, letter) {
+            (0, Some(position)) => {
+                let byte = canonical[position];
+                let flipped = if byte.is_ascii_lowercase() { byte.to_ascii_uppercase() } else { byte.to_ascii_lowercase() };
+                (position, flipped, MutationKind::FlipCase)
+            }
+            (2, _) => {
+                let replacement = if body_len < canonical.len() {
+                    (body_len + gen::<usize>().generate(driver)? % (canonical.len() - body_len), symbols[gen::<usize>().generate(driver)? % 64])
+                } else {
+                    (body_len - 1, padding_byte)
+                };
+                (replacement.0, replacement.1, MutationKind::ChangePadding)
+            }
+            _ => {
+                let offset = 1 + gen::<usize>().generate(driver)? % 63;
+                (start, symbols[(value_at(start) + offset) % 64], MutationKind::SwapSymbol)
+            }
+        };
+        
+        let mut mutated = canonical.clone();
+        mutated[position] = replacement;
+        
+        Some(MutatedCanonical {
+            config,
+            payload,
+            canonical,
+            mutated,
+            position,
+            kind,
+        })
+    }
+}
+
 /// Generator for test configurations
 #[derive(Debug)]
 pub struct ConfigurationGenerator;
@@ -165,17 +457,65 @@ impl ValueGenerator for ConfigurationGenerator {
             _ => PaddingMode::Canonical,
         };
         
+        // Usually the standard `=`, otherwise a printable byte that isn't a symbol of either alphabet
+        let padding_byte = if gen::<bool>().generate(driver)? {
+            b'='
+        } else {
+            let padding_bytes = b"!\"#$%&'()*,.:;<>?@[\\]^`{|}~";
+            padding_bytes[gen::<usize>().generate(driver)? % padding_bytes.len()]
+        };
+        
         Some(TestConfig {
             alphabet,
             padding_mode,
+            padding_byte,
+            wrapping: Wrapping::NONE,
             engine_type: crate::comprehensive::test_config::EngineType::GeneralPurpose,
             test_iterations: 1000,
             max_input_size: 1024,
+            seed: None,
+        })
+    }
+}
+
+/// Generator for test configurations that wrap encoded output
+///
+/// `ConfigurationGenerator` leaves output on a single line, since most properties look at encoded
+/// output symbol by symbol. This adds wrapping on top: no wrapping at all, short lines that break
+/// every few symbols, the MIME and PEM line lengths, or any other length, each with either line
+/// ending and with or without one at the end.
+#[derive(Debug)]
+pub struct WrappedConfigurationGenerator;
+
+impl ValueGenerator for WrappedConfigurationGenerator {
+    type Output = TestConfig;
+
+    fn generate<D>(&self, driver: &mut D) -> Option<Self::Output>
+    where
+        D: bolero_generator::driver::Driver,
+    {
+        let config = ConfigurationGenerator.generate(driver)?;
+        
+        let line_len = match gen::<u8>().generate(driver)? % 4 {
+            0 => 0,
+            1 => 1 + gen::<usize>().generate(driver)? % 8,
+            2 => if gen::<bool>().generate(driver)? { 76 } else { 64 },
+            _ => 1 + gen::<usize>().generate(driver)? % 1000,
+        };
+        let line_ending = if gen::<bool>().generate(driver)? { LineEnding::CRLF } else { LineEnding::LF };
+        let trailing_newline = gen::<bool>().generate(driver)?;
+        
+        Some(TestConfig {
+            wrapping: Wrapping { line_len, line_ending, trailing_newline },
+            ..config
         })
     }
 }
 
 /// Generator for custom alphabets
+///
+/// Each alphabet is 64 distinct symbols drawn from every printable ASCII character except `=`, in a
+/// random order, so it is always accepted by `Alphabet::new`.
 #[derive(Debug)]
 pub struct CustomAlphabetGenerator;
 
@@ -186,17 +526,75 @@ impl ValueGenerator for CustomAlphabetGenerator {
     where
         D: bolero_generator::driver::Driver,
     {
-        // Start with the standard alphabet as a base
-        let base_chars = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
-        let mut chars = *base_chars;
-        
-        // Shuffle the characters to create a custom alphabet
-        // We'll use a simple Fisher-Yates shuffle
-        for i in (1..64).rev() {
-            let j = gen::<usize>().generate(driver)? % (i + 1);
-            chars.swap(i, j);
+        // Every printable ASCII character other than the padding character
+        let mut pool: Vec<u8> = (b' '..=b'~').filter(|&b| b != b'=').collect();
+        
+        // Choose the symbols with a partial Fisher-Yates shuffle of the pool
+        let mut chars = [0u8; 64];
+        for (i, symbol) in chars.iter_mut().enumerate() {
+            let j = i + gen::<usize>().generate(driver)? % (pool.len() - i);
+            pool.swap(i, j);
+            *symbol = pool[i];
         }
         
         Some(chars)
     }
-}
\ No newline at end of file
+}
+
+/// An operation on a streaming encoder or decoder, for model-based tests
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamOperation {
+    /// Write a chunk of bytes to the encoder
+    Write(Vec<u8>),
+    /// Flush the encoder
+    Flush,
+    /// Finish the encoder, after which it accepts no more operations
+    Finish,
+    /// Read into a buffer of this many bytes from the decoder
+    Read(usize),
+}
+
+/// Generator for sequences of streaming operations
+///
+/// Writes are made of chunks of up to `max_chunk` bytes, and reads are into buffers of up to
+/// `max_chunk` bytes, so that both carry partial blocks across operations.
+#[derive(Debug)]
+pub struct StreamOperationGenerator {
+    max_operations: usize,
+    max_chunk: usize,
+}
+
+impl StreamOperationGenerator {
+    pub fn new(max_operations: usize, max_chunk: usize) -> Self {
+        Self {
+            max_operations,
+            max_chunk,
+        }
+    }
+}
+
+impl ValueGenerator for StreamOperationGenerator {
+    type Output = Vec<StreamOperation>;
+
+    fn generate<D>(&self, driver: &mut D) -> Option<Self::Output>
+    where
+        D: bolero_generator::driver::Driver,
+    {
+        let count = gen::<usize>().generate(driver)? % (self.max_operations + 1);
+        let chunk_gen = ByteSequenceGenerator::new(self.max_chunk);
+        
+        let mut operations = Vec::with_capacity(count);
+        for _ in 0..count {
+            // Weighted towards writes and reads, which move data through the stream
+            let operation = match gen::<u8>().generate(driver)? % 8 {
+                0..=3 => StreamOperation::Write(chunk_gen.generate(driver)?),
+                4 => StreamOperation::Flush,
+                5 => StreamOperation::Finish,
+                _ => StreamOperation::Read(gen::<usize>().generate(driver)? % (self.max_chunk + 1)),
+            };
+            operations.push(operation);
+        }
+        
+        Some(operations)
+    }
+}
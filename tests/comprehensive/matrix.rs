@@ -0,0 +1,195 @@
+//! Engine Consistency Matrix
+//!
+//! Runs every engine type with every alphabet and padding mode over a fixed corpus. Engine types
+//! with the same alphabet and padding mode must agree exactly, on encoded output and on decode
+//! results down to error offsets. Between alphabets and padding modes, the matrix records which
+//! configurations can decode each other's output, as a markdown table for the docs or a CI artifact.
+
+use crate::comprehensive::regressions::{load_corpus, CORPUS_DIR};
+use crate::comprehensive::test_config::{AlphabetType, EngineType, PaddingMode, TestConfig};
+use base64::engine::adapters::PrefixSuffix;
+use base64::engine::adaptive::Adaptive;
+use base64::engine::GeneralPurpose;
+use base64::{DecodeError, Engine};
+use std::path::Path;
+
+/// The input length from which the `Adaptive` engine type uses its large input engine, low enough
+/// that the corpus exercises both sides
+const ADAPTIVE_THRESHOLD: usize = 8;
+
+/// The engine a `TestConfig` describes, including its engine type
+///
+/// `Engine` has generic methods, so it can't be a trait object; this dispatches to the concrete
+/// engine of each type instead.
+#[derive(Debug, Clone)]
+pub enum MatrixEngine {
+    GeneralPurpose(GeneralPurpose),
+    Adaptive(Box<Adaptive<GeneralPurpose, GeneralPurpose>>),
+    PrefixSuffix(PrefixSuffix<GeneralPurpose>),
+}
+
+impl MatrixEngine {
+    /// The engine of `config.engine_type`, wrapping the engine from `create_engine`
+    pub fn new(config: &TestConfig) -> Self {
+        let engine = config.create_engine();
+        match config.engine_type {
+            EngineType::GeneralPurpose => MatrixEngine::GeneralPurpose(engine),
+            EngineType::Adaptive => MatrixEngine::Adaptive(Box::new(Adaptive::new(engine.clone(), engine).with_threshold(ADAPTIVE_THRESHOLD))),
+            EngineType::PrefixSuffix => MatrixEngine::PrefixSuffix(PrefixSuffix::new(engine, "", "")),
+        }
+    }
+
+    pub fn encode(&self, input: &[u8]) -> String {
+        match self {
+            MatrixEngine::GeneralPurpose(engine) => engine.encode(input),
+            MatrixEngine::Adaptive(engine) => engine.encode(input),
+            MatrixEngine::PrefixSuffix(engine) => engine.encode(input),
+        }
+    }
+
+    pub fn decode(&self, input: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        match self {
+            MatrixEngine::GeneralPurpose(engine) => engine.decode(input),
+            MatrixEngine::Adaptive(engine) => engine.decode(input),
+            MatrixEngine::PrefixSuffix(engine) => engine.decode(input),
+        }
+    }
+}
+
+/// Every alphabet and padding mode the matrix runs each engine type with
+pub fn matrix_configs() -> Vec<TestConfig> {
+    let mut configs = Vec::new();
+    for alphabet in &[AlphabetType::Standard, AlphabetType::UrlSafe] {
+        for padding_mode in &[
+            PaddingMode::Canonical,
+            PaddingMode::None,
+            PaddingMode::Indifferent,
+            PaddingMode::RequireCanonical,
+            PaddingMode::RequireNone,
+        ] {
+            configs.push(TestConfig::builder().alphabet(alphabet.clone()).padding_mode(padding_mode.clone()).build());
+        }
+    }
+    configs
+}
+
+/// The fixed data the matrix encodes: every length up to a few chunks, of bytes that map to every
+/// symbol including `+/` and `-_`, and the regression corpus
+pub fn matrix_corpus() -> Vec<Vec<u8>> {
+    let mut corpus: Vec<Vec<u8>> = (0..=48)
+        .map(|len| (0..len).map(|i: usize| (i as u8).wrapping_mul(167) ^ 0xFB).collect())
+        .collect();
+    corpus.push((0..=255).collect());
+    corpus.extend(load_corpus(Path::new(CORPUS_DIR)).expect("Regression corpus should load").into_iter().map(|case| case.raw_input));
+    corpus
+}
+
+/// The outcome of running the matrix
+#[derive(Debug)]
+pub struct MatrixReport {
+    /// The alphabet and padding mode of each row and column of `compatibility`
+    pub configs: Vec<TestConfig>,
+    /// `compatibility[a][b]` is how many corpus entries encoded with `configs[a]` decode back to
+    /// the original with `configs[b]`
+    pub compatibility: Vec<Vec<usize>>,
+    /// Number of corpus entries each cell of `compatibility` is out of
+    pub corpus_len: usize,
+    /// Every input that engine types with the same configuration handled differently
+    pub disagreements: Vec<String>,
+}
+
+impl MatrixReport {
+    /// Whether everything encoded with `configs[encoded_with]` decodes with `configs[decoded_with]`
+    pub fn is_compatible(&self, encoded_with: usize, decoded_with: usize) -> bool {
+        self.compatibility[encoded_with][decoded_with] == self.corpus_len
+    }
+
+    /// The compatibility table in markdown, rows encoding and columns decoding
+    pub fn to_markdown(&self) -> String {
+        let labels: Vec<String> = self.configs.iter().map(|c| format!("{:?} {:?}", c.alphabet, c.padding_mode)).collect();
+        let engine_types: Vec<String> = EngineType::ALL.iter().map(|t| format!("{:?}", t)).collect();
+
+        let mut table = String::from("# Engine Compatibility\n\n");
+        table.push_str(&format!(
+            "Engine types {} agree on {} corpus entries with every configuration: {}.\n\n",
+            engine_types.join(", "),
+            self.corpus_len,
+            if self.disagreements.is_empty() { "yes".to_string() } else { format!("no, {} disagreements", self.disagreements.len()) }
+        ));
+        table.push_str("Rows encode and columns decode; ✓ means every entry round trips, otherwise how many do.\n\n");
+
+        table.push_str("| encoded with \\ decoded with |");
+        for label in &labels {
+            table.push_str(&format!(" {} |", label));
+        }
+        table.push_str("\n|---|");
+        table.push_str(&"---|".repeat(labels.len()));
+        table.push('\n');
+
+        for (a, label) in labels.iter().enumerate() {
+            table.push_str(&format!("| {} |", label));
+            for b in 0..labels.len() {
+                if self.is_compatible(a, b) {
+                    table.push_str(" ✓ |");
+                } else {
+                    table.push_str(&format!(" {}/{} |", self.compatibility[a][b], self.corpus_len));
+                }
+            }
+            table.push('\n');
+        }
+
+        table
+    }
+}
+
+/// Run every engine type with every configuration over the corpus
+pub fn run_matrix() -> MatrixReport {
+    // every alphabet and padding mode, with the default engine type
+    let configs = matrix_configs();
+    let corpus = matrix_corpus();
+
+    // Every configuration's encodings are decoded by every other, and the raw corpus is decoded as
+    // base64 too, so engine types are compared on invalid input as well
+    let mut decode_inputs: Vec<Vec<u8>> = corpus.clone();
+    for config in &configs {
+        let engine = config.create_engine();
+        decode_inputs.extend(corpus.iter().map(|data| engine.encode(data).into_bytes()));
+    }
+
+    let mut disagreements = Vec::new();
+    for config in &configs {
+        let reference = MatrixEngine::new(config);
+        for engine_type in EngineType::ALL.iter().skip(1) {
+            let engine = MatrixEngine::new(&TestConfig { engine_type: engine_type.clone(), ..config.clone() });
+
+            for data in &corpus {
+                let (expected, actual) = (reference.encode(data), engine.encode(data));
+                if expected != actual {
+                    disagreements.push(format!("{:?} with {:?} encoded {:?} as {:?}, not {:?}", engine_type, config, data, actual, expected));
+                }
+            }
+            for input in &decode_inputs {
+                let (expected, actual) = (reference.decode(input), engine.decode(input));
+                if expected != actual {
+                    disagreements.push(format!("{:?} with {:?} decoded {:?} as {:?}, not {:?}", engine_type, config, String::from_utf8_lossy(input), actual, expected));
+                }
+            }
+        }
+    }
+
+    let compatibility = configs
+        .iter()
+        .map(|encoded_with| {
+            let encoder = encoded_with.create_engine();
+            configs
+                .iter()
+                .map(|decoded_with| {
+                    let decoder = decoded_with.create_engine();
+                    corpus.iter().filter(|data| decoder.decode(encoder.encode(data)).as_ref() == Ok(*data)).count()
+                })
+                .collect()
+        })
+        .collect();
+
+    MatrixReport { configs, compatibility, corpus_len: corpus.len(), disagreements }
+}
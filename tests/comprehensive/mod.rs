@@ -9,12 +9,9 @@
 //! much more comprehensive coverage than traditional example-based unit tests.
 
 pub mod generators;
+pub mod matrix;
 pub mod properties;
+pub mod regressions;
 pub mod test_config;
 pub mod test_runner;
 
-// Re-export key types for convenience
-pub use generators::*;
-pub use properties::*;
-pub use test_config::*;
-pub use test_runner::*;
\ No newline at end of file
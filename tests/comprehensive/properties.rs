@@ -4,22 +4,55 @@
 //! of the rust-base64 library. Each property represents a universal characteristic
 //! that should hold true across all valid executions.
 
-// This module will be populated with property test implementations in subsequent tasks
-// For now, we provide the module structure and placeholder for future implementations
+use crate::comprehensive::test_config::PropertyTestResult;
+use crate::comprehensive::test_runner::PropertyTestRunner;
+
+/// A property, checked by the runner it's given
+pub type Property = fn(&PropertyTestRunner) -> PropertyTestResult;
+
+/// Every property, in the order they're defined
+pub const ALL: &[Property] = &[
+    roundtrip::test_encode_decode_roundtrip,
+    roundtrip::test_decode_encode_roundtrip,
+    roundtrip::test_cross_engine_consistency,
+    roundtrip::test_custom_alphabet_roundtrip,
+    roundtrip::test_padding_mode_roundtrip,
+    alphabet::test_character_set_compliance,
+    alphabet::test_invalid_character_detection,
+    alphabet::test_cross_alphabet_confusion,
+    alphabet::test_alphabet_acceptance,
+    alphabet::test_alphabet_rejection,
+    alphabet::test_alphabet_translation,
+    alphabet::test_custom_alphabet_table_confusion,
+    padding::test_custom_padding_encoding,
+    padding::test_custom_padding_decoding,
+    padding::test_padding_collision_rejection,
+    length::test_buffer_size_sufficiency,
+    error::test_decode_panic_freedom,
+    error::test_decode_entry_point_consistency,
+    error::test_decode_error_taxonomy,
+    error::test_single_byte_corruption_detection,
+    error::test_entry_point_panic_freedom,
+    error::test_engine_construction_panic_freedom,
+    error::test_buffer_overflow_error_reporting,
+    streaming::test_streaming_encode_consistency,
+    streaming::test_streaming_state_management,
+];
 
-/// Core roundtrip property tests
 pub mod roundtrip {
     //! Property tests for encode-decode roundtrip correctness
     
     use base64::Engine;
     use crate::comprehensive::generators::{Base64StringGenerator, ByteSequenceGenerator, ConfigurationGenerator, CustomAlphabetGenerator};
-    use crate::comprehensive::test_config::{AlphabetType, TestConfig};
+    use crate::comprehensive::test_config::{AlphabetType, PropertyTestResult, TestConfig};
+    use crate::comprehensive::test_runner::PropertyTestRunner;
     
     /// Property 1: Encode-Decode Roundtrip
     /// **Validates: Requirements 1.1**
     /// For any byte sequence, encoding then decoding should produce the original byte sequence
-    pub fn test_encode_decode_roundtrip() {
-        bolero::check!()
+    pub fn test_encode_decode_roundtrip(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("encode_decode_roundtrip")
             .with_generator(ByteSequenceGenerator::new(1000))
             .for_each(|input_bytes: &Vec<u8>| {
                 let config = TestConfig::default();
@@ -38,47 +71,44 @@ pub mod roundtrip {
                      Encoded: {}\n\
                      Decoded: {:?}", 
                     input_bytes, encoded, decoded);
-            });
+            })
     }
     
     /// Property 2: Decode-Encode Roundtrip
     /// **Validates: Requirements 1.2**
     /// For any valid base64 string, decoding then encoding should produce an equivalent base64 string
-    pub fn test_decode_encode_roundtrip() {
-        bolero::check!()
+    pub fn test_decode_encode_roundtrip(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("decode_encode_roundtrip")
             .with_generator(Base64StringGenerator::new(AlphabetType::Standard, 1000))
             .for_each(|base64_string: &String| {
                 let config = TestConfig::default();
                 let engine = config.create_engine();
                 
-                // Try to decode the generated base64 string
-                if let Ok(decoded_bytes) = engine.decode(base64_string) {
-                    // If decode succeeds, encode the result
-                    let re_encoded = engine.encode(&decoded_bytes);
-                    
-                    // The re-encoded string should decode to the same bytes
-                    let final_decoded = engine.decode(&re_encoded).expect("Re-encoded string should be valid");
-                    
-                    // The final decoded bytes should match the original decoded bytes
-                    assert_eq!(decoded_bytes, final_decoded, 
-                        "Decode-encode roundtrip failed: original decoded bytes don't match final decoded bytes");
-                    
-                    // Additionally, both base64 strings should represent the same data
-                    // (they might differ in padding but should decode to the same bytes)
-                    let original_decoded = engine.decode(base64_string).expect("Original string should decode");
-                    assert_eq!(original_decoded, final_decoded,
-                        "Original and re-encoded strings decode to different data");
-                }
-                // If decode fails, that's fine - we only test the roundtrip property for valid inputs
-            });
+                // The generator only produces valid strings, so every input exercises the roundtrip
+                let decoded_bytes = engine.decode(base64_string).expect("Generated string should decode");
+                
+                // Encode the result
+                let re_encoded = engine.encode(&decoded_bytes);
+                
+                // The generated strings are canonical, so encoding reproduces them exactly
+                assert_eq!(base64_string, &re_encoded,
+                    "Decode-encode roundtrip failed: re-encoded string doesn't match the original");
+                
+                // The re-encoded string should decode to the same bytes
+                let final_decoded = engine.decode(&re_encoded).expect("Re-encoded string should be valid");
+                assert_eq!(decoded_bytes, final_decoded,
+                    "Decode-encode roundtrip failed: original decoded bytes don't match final decoded bytes");
+            })
     }
 
     /// Property 3: Cross-Engine Consistency
     /// **Validates: Requirements 1.3, 7.1**
     /// For any byte sequence and any two engine configurations with the same alphabet and padding settings,
     /// both engines should produce identical encoded output
-    pub fn test_cross_engine_consistency() {
-        bolero::check!()
+    pub fn test_cross_engine_consistency(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("cross_engine_consistency")
             .with_generator((ByteSequenceGenerator::new(1000), ConfigurationGenerator))
             .for_each(|(input_bytes, config): &(Vec<u8>, TestConfig)| {
                 // Create two separate engine instances with the same configuration
@@ -114,23 +144,27 @@ pub mod roundtrip {
                     "Cross-decode failed: Engine1 couldn't decode Engine2's output correctly");
                 assert_eq!(cross_decoded2, *input_bytes,
                     "Cross-decode failed: Engine2 couldn't decode Engine1's output correctly");
-            });
+            })
     }
 
     /// Property 4: Custom Alphabet Roundtrip
     /// **Validates: Requirements 1.4**
     /// For any byte sequence and any valid custom alphabet, encoding with that alphabet then decoding should produce the original byte sequence
-    pub fn test_custom_alphabet_roundtrip() {
-        bolero::check!()
+    pub fn test_custom_alphabet_roundtrip(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("custom_alphabet_roundtrip")
             .with_generator((ByteSequenceGenerator::new(1000), CustomAlphabetGenerator))
             .for_each(|(input_bytes, custom_chars): &(Vec<u8>, [u8; 64])| {
                 // Create a test configuration with the custom alphabet
                 let config = TestConfig {
                     alphabet: AlphabetType::Custom(*custom_chars),
                     padding_mode: crate::comprehensive::test_config::PaddingMode::Canonical,
+                    padding_byte: b'=',
+                    wrapping: crate::comprehensive::test_config::Wrapping::NONE,
                     engine_type: crate::comprehensive::test_config::EngineType::GeneralPurpose,
                     test_iterations: 1000,
                     max_input_size: 1024,
+                    seed: None,
                 };
                 
                 // Create the engine with the custom alphabet
@@ -164,16 +198,17 @@ pub mod roundtrip {
                         std::str::from_utf8(custom_chars).unwrap_or("<invalid UTF-8>"),
                         encoded);
                 }
-            });
+            })
     }
 
     /// Property 5: Padding Mode Roundtrip
     /// **Validates: Requirements 1.5**
     /// For any byte sequence and any padding mode configuration, encoding then decoding should preserve the original data regardless of padding mode
-    pub fn test_padding_mode_roundtrip() {
+    pub fn test_padding_mode_roundtrip(runner: &PropertyTestRunner) -> PropertyTestResult {
         use crate::comprehensive::test_config::PaddingMode;
         
-        bolero::check!()
+        runner
+            .check("padding_mode_roundtrip")
             .with_generator(ByteSequenceGenerator::new(1000))
             .for_each(|input_bytes: &Vec<u8>| {
                 // Test all padding modes
@@ -190,9 +225,12 @@ pub mod roundtrip {
                     let config = TestConfig {
                         alphabet: AlphabetType::Standard,
                         padding_mode: padding_mode.clone(),
+                        padding_byte: b'=',
+                        wrapping: crate::comprehensive::test_config::Wrapping::NONE,
                         engine_type: crate::comprehensive::test_config::EngineType::GeneralPurpose,
                         test_iterations: 1000,
                         max_input_size: 1024,
+                        seed: None,
                     };
                     
                     // Create the engine with the specified padding mode
@@ -202,7 +240,7 @@ pub mod roundtrip {
                     let encoded = engine.encode(input_bytes);
                     
                     // Decode the encoded string - this should always work for our own encoded output
-                    let decoded = engine.decode(&encoded).expect(&format!(
+                    let decoded = engine.decode(&encoded).unwrap_or_else(|_| panic!(
                         "Encoded string should be decodable with the same engine\n\
                          Padding mode: {:?}\n\
                          Original input: {:?}\n\
@@ -249,9 +287,12 @@ pub mod roundtrip {
                 let canonical_config = TestConfig {
                     alphabet: AlphabetType::Standard,
                     padding_mode: PaddingMode::Canonical,
+                    padding_byte: b'=',
+                    wrapping: crate::comprehensive::test_config::Wrapping::NONE,
                     engine_type: crate::comprehensive::test_config::EngineType::GeneralPurpose,
                     test_iterations: 1000,
                     max_input_size: 1024,
+                    seed: None,
                 };
                 let canonical_engine = canonical_config.create_engine();
                 let canonical_encoded = canonical_engine.encode(input_bytes);
@@ -259,9 +300,12 @@ pub mod roundtrip {
                 let no_padding_config = TestConfig {
                     alphabet: AlphabetType::Standard,
                     padding_mode: PaddingMode::None,
+                    padding_byte: b'=',
+                    wrapping: crate::comprehensive::test_config::Wrapping::NONE,
                     engine_type: crate::comprehensive::test_config::EngineType::GeneralPurpose,
                     test_iterations: 1000,
                     max_input_size: 1024,
+                    seed: None,
                 };
                 let no_padding_engine = no_padding_config.create_engine();
                 let no_padding_encoded = no_padding_engine.encode(input_bytes);
@@ -269,9 +313,12 @@ pub mod roundtrip {
                 let indifferent_config = TestConfig {
                     alphabet: AlphabetType::Standard,
                     padding_mode: PaddingMode::Indifferent,
+                    padding_byte: b'=',
+                    wrapping: crate::comprehensive::test_config::Wrapping::NONE,
                     engine_type: crate::comprehensive::test_config::EngineType::GeneralPurpose,
                     test_iterations: 1000,
                     max_input_size: 1024,
+                    seed: None,
                 };
                 let indifferent_engine = indifferent_config.create_engine();
                 
@@ -285,23 +332,26 @@ pub mod roundtrip {
                     "Indifferent mode failed to decode canonical string correctly");
                 assert_eq!(decoded_no_padding, *input_bytes,
                     "Indifferent mode failed to decode no-padding string correctly");
-            });
+            })
     }
 }
 
-/// Alphabet compliance property tests  
 pub mod alphabet {
     //! Property tests for alphabet compliance and validation
     
-    use base64::Engine;
-    use crate::comprehensive::generators::{ByteSequenceGenerator, ConfigurationGenerator, InvalidInputGenerator};
-    use crate::comprehensive::test_config::{AlphabetType, TestConfig};
+    use base64::{alphabet::{Alphabet, ParseAlphabetError}, engine::GeneralPurpose, DecodeError, Engine};
+    use bolero_generator::gen;
+    use std::convert::TryFrom;
+    use crate::comprehensive::generators::{ByteSequenceGenerator, ConfigurationGenerator, CustomAlphabetGenerator, InvalidInputGenerator};
+    use crate::comprehensive::test_config::{AlphabetType, PropertyTestResult, TestConfig};
+    use crate::comprehensive::test_runner::PropertyTestRunner;
     
     /// Property 6: Character Set Compliance
     /// **Validates: Requirements 2.1, 2.2, 2.3, 2.4**
     /// For any byte sequence and any alphabet configuration, all characters in the encoded output should belong to the specified alphabet's character set
-    pub fn test_character_set_compliance() {
-        bolero::check!()
+    pub fn test_character_set_compliance(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("character_set_compliance")
             .with_generator((ByteSequenceGenerator::new(1000), ConfigurationGenerator))
             .for_each(|(input_bytes, config): &(Vec<u8>, TestConfig)| {
                 let engine = config.create_engine();
@@ -326,9 +376,9 @@ pub mod alphabet {
                 };
                 
                 // Verify that every character in the encoded output belongs to the expected character set
-                // (padding characters '=' are always allowed regardless of alphabet)
+                // (the configured padding character is always allowed regardless of alphabet)
                 for (pos, byte) in encoded.bytes().enumerate() {
-                    let is_valid = expected_chars.contains(&byte) || byte == b'=';
+                    let is_valid = expected_chars.contains(&byte) || byte == config.padding_byte;
                     
                     assert!(is_valid, 
                         "Character set compliance failed: encoded output contains invalid character\n\
@@ -361,7 +411,7 @@ pub mod alphabet {
                 
                 // Verify that the encoded string can be decoded back successfully
                 // (this ensures we haven't broken the encoding with our alphabet)
-                let decoded = engine.decode(&encoded).expect(&format!(
+                let decoded = engine.decode(&encoded).unwrap_or_else(|_| panic!(
                     "Encoded string should be decodable with the same engine\n\
                      Alphabet type: {:?}\n\
                      Original input: {:?}\n\
@@ -375,14 +425,15 @@ pub mod alphabet {
                      Encoded: {}\n\
                      Decoded: {:?}", 
                     config.alphabet, input_bytes, encoded, decoded);
-            });
+            })
     }
 
     /// Property 7: Invalid Character Detection
     /// **Validates: Requirements 2.5**
     /// For any string containing characters not in the specified alphabet, decoding should return a DecodeError with InvalidByte information
-    pub fn test_invalid_character_detection() {
-        bolero::check!()
+    pub fn test_invalid_character_detection(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("invalid_character_detection")
             .with_generator((InvalidInputGenerator::new(100), ConfigurationGenerator))
             .for_each(|(invalid_input, config): &(String, TestConfig)| {
                 let engine = config.create_engine();
@@ -393,22 +444,8 @@ pub mod alphabet {
                 let mut first_invalid_byte = 0u8;
                 
                 for (pos, byte) in invalid_input.bytes().enumerate() {
-                    let is_valid = match &config.alphabet {
-                        AlphabetType::Standard => {
-                            // STANDARD alphabet: A-Z, a-z, 0-9, +, / and padding =
-                            let standard_chars = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/=";
-                            standard_chars.contains(&byte)
-                        },
-                        AlphabetType::UrlSafe => {
-                            // URL_SAFE alphabet: A-Z, a-z, 0-9, -, _ and padding =
-                            let url_safe_chars = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_=";
-                            url_safe_chars.contains(&byte)
-                        },
-                        AlphabetType::Custom(chars) => {
-                            // For custom alphabets, check the 64 characters plus padding
-                            chars.contains(&byte) || byte == b'='
-                        },
-                    };
+                    // The alphabet's symbols plus the configured padding character
+                    let is_valid = config.alphabet.symbols().contains(&byte) || byte == config.padding_byte;
                     
                     if !is_valid {
                         has_invalid_char = true;
@@ -436,22 +473,23 @@ pub mod alphabet {
                         first_invalid_pos,
                         decode_result);
                     
-                    // Check that the error is related to invalid bytes
-                    // Note: The exact error type depends on the base64 library implementation
-                    // We verify that it's an error, which is the key requirement
-                    let error = decode_result.unwrap_err();
-                    
-                    // The error should indicate an invalid character/byte issue
-                    // Different base64 implementations may have different error types,
-                    // but the key requirement is that invalid characters are detected
-                    let error_string = format!("{:?}", error);
-                    
-                    // Log the error for debugging but don't assert on specific error types
-                    // since the base64 library may change its error representation
-                    if !error_string.to_lowercase().contains("invalid") {
-                        // If the error doesn't seem to be about invalid characters,
-                        // it might be a different kind of error (like length), which is also acceptable
-                        // as long as the decode failed
+                    // The error is the first invalid character, or an earlier padding character
+                    // out of place, which is also reported as an invalid byte. A lone invalid byte
+                    // after the last whole quad, like a trailing newline, is reported first.
+                    let trailing_byte_pos = if invalid_input.len() % 4 == 1 { invalid_input.len() - 1 } else { first_invalid_pos };
+                    match decode_result.unwrap_err() {
+                        DecodeError::InvalidByte(pos, byte) => {
+                            assert!((pos <= first_invalid_pos || pos == trailing_byte_pos) && invalid_input.as_bytes()[pos] == byte,
+                                "Invalid byte error doesn't point at an invalid character\n\
+                                 Input: {}\n\
+                                 Error position: {}\n\
+                                 First invalid character position: {}",
+                                invalid_input, pos, first_invalid_pos);
+                        }
+                        error => panic!(
+                            "Input with invalid characters was rejected with {:?} rather than an invalid byte\n\
+                             Input: {}",
+                            error, invalid_input),
                     }
                 } else {
                     // If the input contains only valid characters, decoding might succeed or fail
@@ -466,19 +504,7 @@ pub mod alphabet {
                         
                         // The re-encoded string should only contain valid characters
                         for byte in re_encoded.bytes() {
-                            let is_valid = match &config.alphabet {
-                                AlphabetType::Standard => {
-                                    let standard_chars = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/=";
-                                    standard_chars.contains(&byte)
-                                },
-                                AlphabetType::UrlSafe => {
-                                    let url_safe_chars = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_=";
-                                    url_safe_chars.contains(&byte)
-                                },
-                                AlphabetType::Custom(chars) => {
-                                    chars.contains(&byte) || byte == b'='
-                                },
-                            };
+                            let is_valid = config.alphabet.symbols().contains(&byte) || byte == config.padding_byte;
                             
                             assert!(is_valid, 
                                 "Re-encoded string contains invalid character: '{}' (0x{:02x})\n\
@@ -490,53 +516,902 @@ pub mod alphabet {
                         }
                     }
                 }
-            });
+            })
+    }
+
+    /// Property 34: Cross-Alphabet Confusion
+    /// **Validates: Requirements 2.6**
+    /// For any byte sequence, output encoded with URL_SAFE that contains `-` or `_` should be rejected by a STANDARD engine and vice versa for `+` and `/`, and output without those characters should decode identically with both
+    pub fn test_cross_alphabet_confusion(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("cross_alphabet_confusion")
+            .with_generator((ByteSequenceGenerator::new(1000), ConfigurationGenerator))
+            .for_each(|(input_bytes, config): &(Vec<u8>, TestConfig)| {
+                let standard = TestConfig { alphabet: AlphabetType::Standard, ..config.clone() }.create_engine();
+                let url_safe = TestConfig { alphabet: AlphabetType::UrlSafe, ..config.clone() }.create_engine();
+                
+                // Each pair is (encoding engine, decoding engine, symbols only the encoding alphabet uses)
+                let pairs: [(&GeneralPurpose, &GeneralPurpose, &[u8]); 2] = [
+                    (&url_safe, &standard, b"-_"),
+                    (&standard, &url_safe, b"+/"),
+                ];
+                
+                for (encoder, decoder, distinct_symbols) in &pairs {
+                    let encoded = encoder.encode(input_bytes);
+                    let decoded = decoder.decode(&encoded);
+                    
+                    match encoded.bytes().position(|b| distinct_symbols.contains(&b)) {
+                        Some(pos) => {
+                            // The first symbol from the other alphabet is reported
+                            assert_eq!(Err(DecodeError::InvalidByte(pos, encoded.as_bytes()[pos])), decoded,
+                                "Cross-alphabet confusion: symbol from the wrong alphabet was not rejected\n\
+                                 Input bytes: {:?}\n\
+                                 Encoded: {}", 
+                                input_bytes, encoded);
+                        },
+                        None => {
+                            // The alphabets agree on every other symbol, so the encodings are identical
+                            assert_eq!(Ok(input_bytes), decoded.as_ref(),
+                                "Cross-alphabet confusion: shared symbols decoded differently\n\
+                                 Encoded: {}", 
+                                encoded);
+                            assert_eq!(encoded, decoder.encode(input_bytes),
+                                "Cross-alphabet confusion: alphabets encoded shared symbols differently\n\
+                                 Input bytes: {:?}", 
+                                input_bytes);
+                        },
+                    }
+                }
+            })
+    }
+    
+    /// Property 40: Alphabet Acceptance
+    /// **Validates: Requirements 2.7**
+    /// For any 64 distinct printable ASCII characters other than `=`, `Alphabet::new` should accept them, and the alphabet should return them unchanged from `as_str()`
+    pub fn test_alphabet_acceptance(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("alphabet_acceptance")
+            .with_generator(CustomAlphabetGenerator)
+            .for_each(|custom_chars: &[u8; 64]| {
+                let symbols = std::str::from_utf8(custom_chars).expect("Custom alphabet should be valid UTF-8");
+                
+                let alphabet = Alphabet::new(symbols).unwrap_or_else(|e| panic!(
+                    "Valid alphabet was rejected with {:?}\n\
+                     Alphabet: {:?}",
+                    e, symbols));
+                assert_eq!(symbols, alphabet.as_str(),
+                    "Alphabet did not roundtrip through as_str()");
+                
+                // The roundtripped symbols make an identical alphabet
+                assert_eq!(Ok(alphabet.clone()), Alphabet::new(alphabet.as_str()));
+                assert_eq!(Ok(alphabet), Alphabet::try_from(symbols));
+            })
+    }
+    
+    /// Property 41: Alphabet Rejection
+    /// **Validates: Requirements 2.8**
+    /// For any valid alphabet with one symbol replaced by a duplicate of another symbol, by `=`, or by a control character, `Alphabet::new` should reject it with the error naming that byte
+    pub fn test_alphabet_rejection(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("alphabet_rejection")
+            .with_generator((CustomAlphabetGenerator, gen::<usize>(), gen::<usize>(), gen::<u8>()))
+            .for_each(|(custom_chars, position, other, kind): &([u8; 64], usize, usize, u8)| {
+                let position = position % 64;
+                let mut chars = *custom_chars;
+                
+                let expected = match kind % 3 {
+                    0 => {
+                        // Any other symbol, which now appears twice
+                        let other = (position + 1 + other % 63) % 64;
+                        chars[position] = chars[other];
+                        ParseAlphabetError::DuplicatedByte {
+                            byte: chars[other],
+                            index: position.max(other),
+                            first_index: position.min(other),
+                        }
+                    }
+                    1 => {
+                        chars[position] = b'=';
+                        ParseAlphabetError::ReservedByte { byte: b'=', index: position }
+                    }
+                    _ => {
+                        // Control characters are 0x00 to 0x1F and 0x7F (DEL)
+                        let control = match (other % 33) as u8 {
+                            32 => 0x7F,
+                            control => control,
+                        };
+                        chars[position] = control;
+                        ParseAlphabetError::UnprintableByte { byte: control, index: position }
+                    }
+                };
+                
+                let symbols = std::str::from_utf8(&chars).expect("ASCII should be valid UTF-8");
+                assert_eq!(Err(expected), Alphabet::new(symbols),
+                    "Invalid alphabet was not rejected with the expected error\n\
+                     Alphabet: {:?}\n\
+                     Replaced position: {}",
+                    symbols, position);
+            })
+    }
+    
+    /// Property 43: Alphabet Translation
+    /// **Validates: Requirements 2.9**
+    /// For any byte sequence, any configuration, and any custom alphabet, translating the configuration's encoding to the custom alphabet should produce the custom alphabet's encoding, and translating it back should restore the original
+    pub fn test_alphabet_translation(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("alphabet_translation")
+            .with_generator((ByteSequenceGenerator::new(1000), ConfigurationGenerator, CustomAlphabetGenerator))
+            .for_each(|(input_bytes, config, custom_chars): &(Vec<u8>, TestConfig, [u8; 64])| {
+                // Only `=` is copied through as padding
+                let from = TestConfig { padding_byte: b'=', ..config.clone() };
+                let to = TestConfig { alphabet: AlphabetType::Custom(*custom_chars), ..from.clone() };
+                let from_alphabet = from.create_alphabet();
+                let to_alphabet = to.create_alphabet();
+                
+                let encoded = from.create_engine().encode(input_bytes);
+                let translated = base64::translate(&encoded, from_alphabet, to_alphabet)
+                    .expect("Encoded output should only contain symbols and padding");
+                assert_eq!(to.create_engine().encode(input_bytes), translated,
+                    "Alphabet translation failed: translated output doesn't match encoding with the target alphabet\n\
+                     Input bytes: {:?}\n\
+                     Encoded: {}\n\
+                     Target alphabet: {:?}",
+                    input_bytes, encoded, to_alphabet.as_str());
+                
+                assert_eq!(Ok(encoded), base64::translate(&translated, to_alphabet, from_alphabet),
+                    "Alphabet translation failed: translating back didn't restore the original encoding");
+            })
+    }
+    
+    /// Property 46: Custom Alphabet Table Confusion
+    /// **Validates: Requirements 2.10**
+    /// For any byte sequence and any two custom alphabets that differ in a single symbol, output encoded with one that contains its differing symbol should be rejected by the other at the first occurrence of that symbol, and output without it should decode identically with both
+    pub fn test_custom_alphabet_table_confusion(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("custom_alphabet_table_confusion")
+            .with_generator((ByteSequenceGenerator::new(1000), ConfigurationGenerator, CustomAlphabetGenerator, gen::<Option<usize>>(), gen::<usize>()))
+            .for_each(|(input_bytes, config, custom_chars, replaced, replacement): &(Vec<u8>, TestConfig, [u8; 64], Option<usize>, usize)| {
+                // Only `=` can't be in a custom alphabet
+                let config = TestConfig { padding_byte: b'=', ..config.clone() };
+                let ours = TestConfig { alphabet: AlphabetType::Custom(*custom_chars), ..config.clone() };
+                let ours_engine = ours.create_engine();
+                let encoded = ours_engine.encode(input_bytes);
+                
+                // Usually replace a symbol the encoding uses, so it has to be rejected
+                let position = match (replaced, encoded.as_bytes()) {
+                    (Some(index), encoded_bytes) if !encoded_bytes.is_empty() => {
+                        let symbol = encoded_bytes[index % encoded_bytes.len()];
+                        custom_chars.iter().position(|&b| b == symbol).unwrap_or(0)
+                    }
+                    (index, _) => index.unwrap_or(0) % 64,
+                };
+                let unused: Vec<u8> = (b' '..=b'~').filter(|b| *b != b'=' && !custom_chars.contains(b)).collect();
+                let mut their_chars = *custom_chars;
+                their_chars[position] = unused[replacement % unused.len()];
+                let theirs_engine = TestConfig { alphabet: AlphabetType::Custom(their_chars), ..config.clone() }.create_engine();
+                
+                let (ours_symbol, theirs_symbol) = (custom_chars[position], their_chars[position]);
+                let their_encoding = theirs_engine.encode(input_bytes);
+                assert_eq!(encoded.replace(ours_symbol as char, &(theirs_symbol as char).to_string()), their_encoding,
+                    "Alphabets differing only in {:?} and {:?} encoded other symbols differently",
+                    ours_symbol as char, theirs_symbol as char);
+                
+                for (encoding, symbol, engine) in &[(&encoded, ours_symbol, &theirs_engine), (&their_encoding, theirs_symbol, &ours_engine)] {
+                    let expected = match encoding.bytes().position(|b| b == *symbol) {
+                        Some(offset) => Err(DecodeError::InvalidByte(offset, *symbol)),
+                        None => Ok(input_bytes.clone()),
+                    };
+                    assert_eq!(expected, engine.decode(encoding),
+                        "Alphabet table confusion: {:?} wasn't handled as expected by the alphabet without {:?}\n\
+                         Alphabets: {:?} and {:?}",
+                        encoding, *symbol as char,
+                        std::str::from_utf8(custom_chars).unwrap_or("<invalid UTF-8>"),
+                        std::str::from_utf8(&their_chars).unwrap_or("<invalid UTF-8>"));
+                }
+            })
     }
 }
 
-/// Padding behavior property tests
 pub mod padding {
     //! Property tests for padding correctness and configuration
     
-    // Property test implementations will be added in task 6
+    use base64::{alphabet::Alphabet, engine::{general_purpose::PAD, Config, GeneralPurpose, Padding}, DecodeError, Engine};
+    use bolero_generator::gen;
+    use std::panic;
+    use crate::comprehensive::generators::{ByteSequenceGenerator, ConfigurationGenerator, CustomAlphabetGenerator};
+    use crate::comprehensive::test_config::{PaddingMode, PropertyTestResult, TestConfig};
+    use crate::comprehensive::test_runner::PropertyTestRunner;
+    
+    /// Property 35: Custom Padding Character Encoding
+    /// **Validates: Requirements 3.6**
+    /// For any byte sequence and any padding character outside the alphabet, encoding with padding should end the output with the correct number of that character, and encoding without padding should never emit it
+    pub fn test_custom_padding_encoding(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("custom_padding_encoding")
+            .with_generator((ByteSequenceGenerator::new(1000), ConfigurationGenerator))
+            .for_each(|(input_bytes, config): &(Vec<u8>, TestConfig)| {
+                let engine = config.create_engine();
+                let encoded = engine.encode(input_bytes);
+                
+                let expected_padding = match config.padding_mode {
+                    PaddingMode::Canonical | PaddingMode::Indifferent | PaddingMode::RequireCanonical => (3 - input_bytes.len() % 3) % 3,
+                    PaddingMode::None | PaddingMode::RequireNone => 0,
+                };
+                let unpadded_len = encoded.trim_end_matches(config.padding_byte as char).len();
+                
+                assert_eq!(expected_padding, encoded.len() - unpadded_len,
+                    "Incorrect padding for mode {:?} with padding character '{}'\n\
+                     Input length: {}, Encoded: {}", 
+                    config.padding_mode, config.padding_byte as char, input_bytes.len(), encoded);
+                assert!(!encoded[..unpadded_len].contains(config.padding_byte as char),
+                    "Padding character '{}' appeared before the end of the output\n\
+                     Encoded: {}", 
+                    config.padding_byte as char, encoded);
+                if config.padding_byte != b'=' {
+                    assert!(!encoded.contains('='),
+                        "Default padding was used instead of '{}'\n\
+                         Encoded: {}", 
+                        config.padding_byte as char, encoded);
+                }
+            })
+    }
+    
+    /// Property 36: Custom Padding Character Decoding
+    /// **Validates: Requirements 3.7**
+    /// For any byte sequence and any padding character outside the alphabet, decoding should require, reject, or accept that character according to the padding mode, and never treat `=` as padding in its place (configs that don't write padding recognize `=` as padding)
+    pub fn test_custom_padding_decoding(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("custom_padding_decoding")
+            .with_generator((ByteSequenceGenerator::new(1000), ConfigurationGenerator))
+            .for_each(|(input_bytes, config): &(Vec<u8>, TestConfig)| {
+                let engine = config.create_engine();
+                
+                // Configs that don't write padding still check for the default `=` when decoding
+                let padding_byte = match engine.config().padding() {
+                    Padding::Padded(padding_byte) => padding_byte,
+                    Padding::Unpadded => b'=',
+                };
+                
+                let unpadded = TestConfig { padding_mode: PaddingMode::None, ..config.clone() }.create_engine().encode(input_bytes);
+                let padding_len = (4 - unpadded.len() % 4) % 4;
+                let padded = format!("{}{}", unpadded, (padding_byte as char).to_string().repeat(padding_len));
+                let unpadded = &unpadded[..];
+                let needs_padding = padding_len > 0;
+                
+                match config.padding_mode {
+                    PaddingMode::RequireCanonical => {
+                        assert_eq!(Ok(input_bytes), engine.decode(&padded).as_ref(),
+                            "RequireCanonical rejected canonical padding '{}': {}", padding_byte as char, padded);
+                        if needs_padding {
+                            assert_eq!(Err(DecodeError::InvalidPadding), engine.decode(unpadded),
+                                "RequireCanonical accepted missing padding: {}", unpadded);
+                        }
+                    },
+                    PaddingMode::RequireNone => {
+                        assert_eq!(Ok(input_bytes), engine.decode(unpadded).as_ref(),
+                            "RequireNone rejected unpadded input: {}", unpadded);
+                        if needs_padding {
+                            assert_eq!(Err(DecodeError::InvalidPadding), engine.decode(&padded),
+                                "RequireNone accepted padding '{}': {}", padding_byte as char, padded);
+                        }
+                    },
+                    PaddingMode::Canonical | PaddingMode::None | PaddingMode::Indifferent => {
+                        assert_eq!(Ok(input_bytes), engine.decode(&padded).as_ref(),
+                            "Indifferent decoding rejected padding '{}': {}", padding_byte as char, padded);
+                        assert_eq!(Ok(input_bytes), engine.decode(unpadded).as_ref(),
+                            "Indifferent decoding rejected unpadded input: {}", unpadded);
+                    },
+                }
+                
+                // `=` is just another invalid byte when a different padding character is configured
+                if needs_padding && padding_byte != b'=' {
+                    let default_padded = padded.replace(padding_byte as char, "=");
+                    assert_eq!(Err(DecodeError::InvalidByte(unpadded.len(), b'=')), engine.decode(&default_padded),
+                        "'=' was accepted as padding instead of '{}': {}", padding_byte as char, default_padded);
+                }
+            })
+    }
+    
+    /// Property 37: Padding Collision Rejection
+    /// **Validates: Requirements 3.8**
+    /// For any alphabet and any padding character, creating an engine should be rejected exactly when the padding character is one of the alphabet's symbols or isn't printable ASCII
+    pub fn test_padding_collision_rejection(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("padding_collision_rejection")
+            .with_generator((CustomAlphabetGenerator, gen::<u8>()))
+            .for_each(|(custom_chars, padding_byte): &([u8; 64], u8)| {
+                let alphabet = Alphabet::new(std::str::from_utf8(custom_chars).expect("Custom alphabet should be valid UTF-8"))
+                    .expect("Custom alphabet should be valid");
+                let config = PAD.with_padding(Padding::Padded(*padding_byte));
+                
+                let should_reject = custom_chars.contains(padding_byte) || !padding_byte.is_ascii_graphic();
+                
+                // Engine creation panics on invalid configs
+                let result = panic::catch_unwind(|| GeneralPurpose::new(&alphabet, config));
+                
+                assert_eq!(should_reject, result.is_err(),
+                    "Padding character 0x{:02x} with alphabet {} was {}", 
+                    padding_byte,
+                    std::str::from_utf8(custom_chars).unwrap_or("<invalid UTF-8>"),
+                    if result.is_err() { "rejected" } else { "accepted" });
+            })
+    }
 }
 
-/// Length calculation property tests
 pub mod length {
     //! Property tests for length calculation accuracy
     
-    // Property test implementations will be added in task 7
+    use base64::{EncodeSliceError, Engine};
+    use bolero_generator::gen;
+    use crate::comprehensive::generators::{ByteSequenceGenerator, WrappedConfigurationGenerator};
+    use crate::comprehensive::test_config::{PropertyTestResult, TestConfig};
+    use crate::comprehensive::test_runner::PropertyTestRunner;
+    
+    /// Property 15: Buffer Size Sufficiency
+    /// **Validates: Requirements 4.5**
+    /// For any byte sequence and any configuration, including line wrapping, the encoded length an engine reports should be at least the length of its output, and encoding into a buffer of exactly that length should succeed without writing past the output
+    pub fn test_buffer_size_sufficiency(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("buffer_size_sufficiency")
+            .with_generator((ByteSequenceGenerator::new(1000), WrappedConfigurationGenerator, gen::<u8>()))
+            .for_each(|(input_bytes, config, sentinel): &(Vec<u8>, TestConfig, u8)| {
+                let engine = config.create_engine();
+                let encoded = engine.encode(input_bytes);
+                let estimate = engine.encoded_len(input_bytes.len())
+                    .expect("Small inputs should have an encoded length");
+                
+                assert!(encoded.len() <= estimate,
+                    "Encoded output is longer than the estimate\n\
+                     Wrapping: {:?}, Input length: {}, Estimate: {}, Encoded: {:?}",
+                    config.wrapping, input_bytes.len(), estimate, encoded);
+                
+                // A buffer of the estimated length is enough, and nothing past the output is touched
+                let mut buf = vec![*sentinel; estimate + 8];
+                let written = engine.encode_slice(input_bytes, &mut buf[..estimate])
+                    .unwrap_or_else(|e| panic!(
+                        "Encoding into a buffer of the estimated length failed with {:?}\n\
+                         Wrapping: {:?}, Input length: {}, Estimate: {}",
+                        e, config.wrapping, input_bytes.len(), estimate));
+                assert_eq!(encoded.as_bytes(), &buf[..written],
+                    "encode_slice output differs from encode with wrapping {:?}", config.wrapping);
+                assert!(buf[written..].iter().all(|b| b == sentinel),
+                    "encode_slice wrote past its output with wrapping {:?}", config.wrapping);
+                
+                // One byte less than the output isn't enough
+                if !encoded.is_empty() {
+                    assert_eq!(Err(EncodeSliceError::OutputSliceTooSmall), engine.encode_slice(input_bytes, &mut buf[..encoded.len() - 1]),
+                        "encode_slice accepted a buffer too small for its output with wrapping {:?}", config.wrapping);
+                }
+            })
+    }
 }
 
-/// Error detection property tests
 pub mod error {
     //! Property tests for error detection and reporting
     
-    // Property test implementations will be added in task 9
+    use base64::{alphabet::Alphabet, block::BlockCodec, display::Base64Display, engine::{general_purpose::PAD, GeneralPurpose, LineEnding, Padding}, read::DecoderReader, spec::{EngineSpec, SpecError}, write::{EncoderStringWriter, EncoderWriter}, DecodeSliceError, EncodeSliceError, Engine};
+    use bolero_generator::gen;
+    use std::io::{Read, Write};
+    use std::panic::{self, AssertUnwindSafe};
+    use crate::comprehensive::generators::{Base64StringGenerator, ByteSequenceGenerator, ConfigurationGenerator, CustomAlphabetGenerator, DefectiveInputGenerator, MutatedCanonical, MutatedCanonicalGenerator};
+    use crate::comprehensive::test_config::{AlphabetType, ExpectedBehavior, PropertyTestResult, TestConfig, TestInput};
+    use crate::comprehensive::test_runner::PropertyTestRunner;
+    
+    /// Property 33: Decode Panic Freedom
+    /// **Validates: Requirements 9.2, 10.5**
+    /// For any input and any output buffer size, decoding should return either the decoded bytes or an error, never panic
+    pub fn test_decode_panic_freedom(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("decode_panic_freedom")
+            .with_generator((
+                ByteSequenceGenerator::new(100),
+                Base64StringGenerator::new(AlphabetType::Standard, 100),
+                ConfigurationGenerator,
+                gen::<usize>(),
+            ))
+            .for_each(|(garbage, base64_string, config, splice_at): &(Vec<u8>, String, TestConfig, usize)| {
+                let engine = config.create_engine();
+                
+                // Mostly valid input with arbitrary bytes spliced in somewhere reaches the most code
+                let mut input = base64_string.clone().into_bytes();
+                let splice_at = splice_at % (input.len() + 1);
+                let _ = input.splice(splice_at..splice_at, garbage.iter().cloned());
+                
+                for candidate in &[&input[..], base64_string.as_bytes(), &garbage[..]] {
+                    let decoded = engine.decode(candidate);
+                    
+                    let mut vec = Vec::new();
+                    assert_eq!(decoded, engine.decode_vec(candidate, &mut vec).map(|_| vec.clone()));
+                    
+                    // every buffer size up to and past the decoded length
+                    for len in 0..=candidate.len() / 4 * 3 + 3 {
+                        let mut buf = vec![0; len];
+                        match engine.decode_slice(candidate, &mut buf) {
+                            Ok(n) => assert_eq!(decoded.as_ref().map(|d| &d[..]), Ok(&buf[..n])),
+                            Err(DecodeSliceError::DecodeError(e)) => assert_eq!(Err(e), decoded),
+                            Err(e) => assert!(e.output_len_needed().unwrap() > len),
+                        }
+                    }
+                    
+                    let mut read = Vec::new();
+                    let read_result = DecoderReader::new(*candidate, &engine).read_to_end(&mut read);
+                    assert_eq!(decoded.is_ok(), read_result.is_ok(),
+                        "DecoderReader disagreed with decode for input {:?}", candidate);
+                }
+            })
+    }
+    
+    /// Property 38: Decode Entry Point Consistency
+    /// **Validates: Requirements 5.6**
+    /// For any input and any configuration, `decode`, `decode_vec` (appending to existing content), and `decode_slice` (with a buffer of exactly the decoded length) should produce identical bytes, or the same error
+    pub fn test_decode_entry_point_consistency(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("decode_entry_point_consistency")
+            .with_generator((ByteSequenceGenerator::new(100), ConfigurationGenerator, gen::<Option<(usize, u8)>>()))
+            .for_each(|(input_bytes, config, corruption): &(Vec<u8>, TestConfig, Option<(usize, u8)>)| {
+                let engine = config.create_engine();
+                
+                // Valid input, optionally with one byte replaced to exercise the error paths
+                let mut input = engine.encode(input_bytes).into_bytes();
+                if let Some((pos, byte)) = corruption {
+                    if !input.is_empty() {
+                        let pos = pos % input.len();
+                        input[pos] = *byte;
+                    }
+                }
+                
+                let decoded = engine.decode(&input);
+                
+                let prefix = b"existing content".to_vec();
+                let mut vec = prefix.clone();
+                let vec_result = engine.decode_vec(&input, &mut vec);
+                assert_eq!(&prefix[..], &vec[..prefix.len()],
+                    "decode_vec modified existing content for input {:?}", input);
+                
+                match &decoded {
+                    Ok(decoded_bytes) => {
+                        assert_eq!(Ok(()), vec_result,
+                            "decode_vec failed where decode succeeded for input {:?}", input);
+                        assert_eq!(&decoded_bytes[..], &vec[prefix.len()..],
+                            "decode_vec bytes differ from decode for input {:?}", input);
+                        
+                        let mut exact = vec![0; decoded_bytes.len()];
+                        assert_eq!(Ok(decoded_bytes.len()), engine.decode_slice(&input, &mut exact),
+                            "decode_slice failed with an exactly sized buffer for input {:?}", input);
+                        assert_eq!(decoded_bytes, &exact,
+                            "decode_slice bytes differ from decode for input {:?}", input);
+                    },
+                    Err(e) => {
+                        assert_eq!(Err(e.clone()), vec_result,
+                            "decode_vec error differs from decode for input {:?}", input);
+                        
+                        // a buffer big enough for any decoding of the input, so the only error is the input's
+                        let mut buf = vec![0; input.len() / 4 * 3 + 3];
+                        assert_eq!(Err(DecodeSliceError::DecodeError(e.clone())), engine.decode_slice(&input, &mut buf),
+                            "decode_slice error differs from decode for input {:?}", input);
+                    },
+                }
+            })
+    }
+    
+    /// Property 42: Decode Error Taxonomy
+    /// **Validates: Requirements 5.1, 5.2, 5.3, 5.4**
+    /// For any valid encoding with one injected defect, decoding should fail with the error variant, and offset, that the defect predicts
+    pub fn test_decode_error_taxonomy(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("decode_error_taxonomy")
+            .with_generator(DefectiveInputGenerator::new(100))
+            .for_each(|test_input: &TestInput| {
+                let engine = test_input.config.create_engine();
+                let expected = match &test_input.expected_behavior {
+                    ExpectedBehavior::Error(expected) => expected,
+                    ExpectedBehavior::Success => unreachable!("Defective inputs should always expect an error"),
+                };
+                
+                let result = engine.decode(&test_input.raw_input);
+                assert!(matches!(&result, Err(error) if expected.matches(error)),
+                    "Decoding a defective input didn't fail as predicted\n\
+                     Input: {:?}\n\
+                     Config: {:?}\n\
+                     Expected: {:?}\n\
+                     Result: {:?}",
+                    String::from_utf8_lossy(&test_input.raw_input), test_input.config, expected, result);
+            })
+    }
+    
+    /// Property 45: Single-Byte Corruption Detection
+    /// **Validates: Requirements 5.7**
+    /// For any canonical encoding with exactly one byte changed, by flipping a letter's case, swapping a symbol, or changing padding, decoding should either fail or produce a different payload, never silently return the original
+    pub fn test_single_byte_corruption_detection(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("single_byte_corruption_detection")
+            .with_generator(MutatedCanonicalGenerator::new(100))
+            .for_each(|input: &MutatedCanonical| {
+                let engine = input.config.create_engine();
+                assert_eq!(Ok(&input.payload), engine.decode(&input.canonical).as_ref(),
+                    "The canonical encoding didn't decode to its payload");
+                assert_eq!(1, input.canonical.iter().zip(&input.mutated).filter(|(a, b)| a != b).count(),
+                    "The mutation didn't change exactly one byte");
+                
+                let result = engine.decode(&input.mutated);
+                assert_ne!(Ok(&input.payload), result.as_ref(),
+                    "A corrupted encoding was silently accepted\n\
+                     Canonical: {:?}\n\
+                     Mutated: {:?}\n\
+                     {:?} at position {}\n\
+                     Config: {:?}",
+                    String::from_utf8_lossy(&input.canonical), String::from_utf8_lossy(&input.mutated), input.kind, input.position, input.config);
+            })
+    }
+    
+    /// Property 39: Entry Point Panic Freedom
+    /// **Validates: Requirements 9.2, 10.5**
+    /// For any input bytes, configuration, and buffer size, every public encode and decode entry point should return normally, with output or an error, and never panic
+    pub fn test_entry_point_panic_freedom(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("entry_point_panic_freedom")
+            .with_generator((
+                ByteSequenceGenerator::new(100),
+                ConfigurationGenerator,
+                gen::<usize>(),
+                gen::<usize>(),
+            ))
+            .for_each(|(input, config, buf_len, split): &(Vec<u8>, TestConfig, usize, usize)| {
+                let engine = config.create_engine();
+                let buf_len = buf_len % (input.len() * 2 + 8);
+                let (head, tail) = input.split_at(split % (input.len() + 1));
+                
+                // Everything is exercised with the arbitrary bytes as plaintext and as encoded input
+                let encoded = engine.encode(input);
+                for candidate in &[&input[..], encoded.as_bytes()] {
+                    let candidate = *candidate;
+                    
+                    assert_no_panic("encode", candidate, || { let _ = engine.encode(candidate); });
+                    assert_no_panic("encode_string", candidate, || engine.encode_string(candidate, &mut String::new()));
+                    assert_no_panic("encode_slice", candidate, || { let _ = engine.encode_slice(candidate, &mut vec![0; buf_len]); });
+                    assert_no_panic("encode_into", candidate, || { let _ = engine.encode_into(candidate, &mut &mut vec![0; buf_len][..]); });
+                    assert_no_panic("encode_chunks", candidate, || { let _ = engine.encode_chunks(candidate, buf_len + 1).iter().count(); });
+                    assert_no_panic("encode_scattered", candidate, || { let _ = engine.encode_scattered(&[head, tail, candidate]); });
+                    assert_no_panic("encode_scattered_slice", candidate, || { let _ = engine.encode_scattered_slice(&[head, tail, candidate], &mut vec![0; buf_len]); });
+                    assert_no_panic("encoded_len", candidate, || { let _ = engine.encoded_len(candidate.len()); });
+                    assert_no_panic("Base64Display", candidate, || { let _ = Base64Display::new(candidate, &engine).to_string(); });
+                    assert_no_panic("EncoderWriter", candidate, || {
+                        let mut writer = EncoderWriter::new(Vec::new(), &engine);
+                        let _ = writer.write_all(head).and_then(|_| writer.write_all(candidate));
+                        let _ = writer.finish();
+                    });
+                    assert_no_panic("EncoderStringWriter", candidate, || {
+                        let mut writer = EncoderStringWriter::new(&engine);
+                        let _ = writer.write_all(candidate);
+                        let _ = writer.into_inner();
+                    });
+                    
+                    assert_no_panic("decode", candidate, || { let _ = engine.decode(candidate); });
+                    assert_no_panic("decode_vec", candidate, || { let _ = engine.decode_vec(candidate, &mut head.to_vec()); });
+                    assert_no_panic("decode_slice", candidate, || { let _ = engine.decode_slice(candidate, &mut vec![0; buf_len]); });
+                    assert_no_panic("decode_cow", candidate, || { let _ = engine.decode_cow(candidate); });
+                    #[cfg(feature = "const-generics")]
+                    assert_no_panic("decode_array", candidate, || { let _ = engine.decode_array::<_, 16>(candidate); });
+                    assert_no_panic("decode_report_all", candidate, || { let _ = engine.decode_report_all(candidate); });
+                    assert_no_panic("decode_with_stats", candidate, || { let _ = engine.decode_with_stats(candidate); });
+                    assert_no_panic("decode_write", candidate, || { let _ = engine.decode_write(candidate, &mut Vec::new()); });
+                    assert_no_panic("decode_chunked", candidate, || { let _ = engine.decode_chunked(candidate, |_| {}); });
+                    assert_no_panic("DecoderReader", candidate, || { let _ = DecoderReader::new(candidate, &engine).read_to_end(&mut Vec::new()); });
+                }
+            })
+    }
+    
+    /// Run `f`, which exercises the entry point `name` with `input`, and fail if it panics
+    fn assert_no_panic<F: FnOnce()>(name: &str, input: &[u8], f: F) {
+        assert!(panic::catch_unwind(AssertUnwindSafe(f)).is_ok(),
+            "{} panicked for input {:?}", name, input);
+    }
+    
+    /// Property 44: Engine Construction Panic Freedom
+    /// **Validates: Requirements 3.9, 10.5**
+    /// For any symbols, padding byte, and settings, including a line wrap length of 0, the fallible alphabet, engine, and spec constructors should return normally, rejecting the engine exactly when the padding byte is one of the alphabet's symbols or isn't printable ASCII
+    pub fn test_engine_construction_panic_freedom(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("engine_construction_panic_freedom")
+            .with_generator((
+                CustomAlphabetGenerator,
+                ByteSequenceGenerator::new(80),
+                gen::<u8>(),
+                gen::<u8>(),
+                gen::<usize>(),
+                gen::<usize>(),
+                ByteSequenceGenerator::new(8),
+            ))
+            .for_each(|(custom_chars, arbitrary_symbols, flags, padding_byte, line_wrap, max_len, ignore_bytes): &([u8; 64], Vec<u8>, u8, u8, usize, usize, Vec<u8>)| {
+                // Arbitrary bytes are almost never a valid alphabet, so use a valid one half the time
+                let symbols = if flags & 1 == 0 { &custom_chars[..] } else { &arbitrary_symbols[..] };
+                let symbols = String::from_utf8_lossy(symbols).into_owned();
+                let line_ending = if flags & 2 == 0 { LineEnding::LF } else { LineEnding::CRLF };
+                let config = PAD
+                    .with_padding(Padding::Padded(*padding_byte))
+                    .with_encode_padding(flags & 4 == 0)
+                    .with_encode_trailing_newline(flags & 8 == 0)
+                    .with_encode_line_ending(line_ending)
+                    // small lengths, 0 among them, are the interesting ones
+                    .with_encode_line_wrap(if flags & 16 == 0 { line_wrap % 4 } else { *line_wrap })
+                    .with_max_encoded_len(*max_len)
+                    .with_decode_ignore_bytes(ignore_bytes);
+                
+                let alphabet = catch_panic("Alphabet::new", &symbols, || Alphabet::new(&symbols));
+                let spec = EngineSpec::Custom { symbols: symbols.clone(), config };
+                let built = catch_panic("EngineSpec::build", &symbols, || spec.build());
+                let alphabet = match alphabet {
+                    Ok(alphabet) => alphabet,
+                    Err(e) => {
+                        assert_eq!(Some(SpecError::InvalidAlphabet(e)), built.err(),
+                            "EngineSpec::build didn't report the invalid alphabet");
+                        return;
+                    }
+                };
+                
+                let should_reject = !padding_byte.is_ascii_graphic() || symbols.as_bytes().contains(padding_byte);
+                let engine = catch_panic("GeneralPurpose::try_new", &symbols, || GeneralPurpose::try_new(&alphabet, config));
+                let from_codec = catch_panic("GeneralPurpose::try_from_block_codec", &symbols,
+                    || GeneralPurpose::try_from_block_codec(&BlockCodec::new(&alphabet), config));
+                
+                assert_eq!(should_reject, engine.is_err(),
+                    "Padding byte 0x{:02x} with alphabet {} was {}",
+                    padding_byte, symbols, if engine.is_err() { "rejected" } else { "accepted" });
+                assert_eq!(engine.as_ref().err(), from_codec.as_ref().err(),
+                    "try_from_block_codec disagreed with try_new");
+                assert_eq!(engine.as_ref().err().cloned().map(SpecError::InvalidConfig).as_ref(), built.as_ref().err(),
+                    "EngineSpec::build disagreed with try_new");
+                
+                if let Ok(engine) = engine {
+                    assert_eq!(&config, engine.config());
+                    assert_no_panic("encode", symbols.as_bytes(), || { let _ = engine.encode(symbols.as_bytes()); });
+                }
+            })
+    }
+    
+    /// Run `f`, which calls the constructor `name` with `symbols`, and fail if it panics
+    fn catch_panic<T, F: FnOnce() -> T>(name: &str, symbols: &str, f: F) -> T {
+        panic::catch_unwind(AssertUnwindSafe(f))
+            .unwrap_or_else(|_| panic!("{} panicked for symbols {:?}", name, symbols))
+    }
+    
+    /// Property 20: Buffer Overflow Error Reporting
+    /// **Validates: Requirements 5.5**
+    /// For any input and any output buffer size, encoding into a slice should either fill the buffer or report that it's too small, never panic
+    pub fn test_buffer_overflow_error_reporting(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("buffer_overflow_error_reporting")
+            .with_generator((ByteSequenceGenerator::new(100), ConfigurationGenerator))
+            .for_each(|(input, config): &(Vec<u8>, TestConfig)| {
+                let engine = config.create_engine();
+                let encoded = engine.encode(input);
+                
+                for len in 0..=encoded.len() + 2 {
+                    let mut buf = vec![0; len];
+                    match engine.encode_slice(input, &mut buf) {
+                        Ok(n) => assert_eq!(encoded.as_bytes(), &buf[..n]),
+                        Err(EncodeSliceError::OutputSliceTooSmall) => assert!(len < encoded.len()),
+                        Err(e) => panic!("Unexpected error {:?}", e),
+                    }
+                }
+            })
+    }
 }
 
-/// Streaming operation property tests
 pub mod streaming {
     //! Property tests for streaming operation consistency
     
-    // Property test implementations will be added in task 10
+    use base64::{display::Base64Display, read::DecoderReader, write::{EncoderStringWriter, EncoderWriter}, Engine};
+    use bolero_generator::gen;
+    use std::cell::RefCell;
+    use std::io::{self, Read, Write};
+    use std::rc::Rc;
+    use crate::comprehensive::generators::{ByteSequenceGenerator, ConfigurationGenerator, StreamOperation, StreamOperationGenerator};
+    use crate::comprehensive::test_config::{PropertyTestResult, TestConfig};
+    use crate::comprehensive::test_runner::PropertyTestRunner;
+    
+    /// Property 22: Streaming Encode Consistency
+    /// **Validates: Requirements 6.2**
+    /// For any byte sequence and any configuration, every output-producing API (EncoderWriter, EncoderStringWriter, Base64Display, and the engine's encode variants) should produce output identical to batch encode
+    pub fn test_streaming_encode_consistency(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("streaming_encode_consistency")
+            .with_generator((ByteSequenceGenerator::new(1000), ConfigurationGenerator, gen::<usize>()))
+            .for_each(|(input_bytes, config, chunk_len): &(Vec<u8>, TestConfig, usize)| {
+                let engine = config.create_engine();
+                let expected = engine.encode(input_bytes);
+                
+                // Write in chunks of an arbitrary size, so the writers have to carry partial blocks
+                let chunk_len = chunk_len % 100 + 1;
+                
+                let mut writer = EncoderWriter::new(Vec::new(), &engine);
+                for chunk in input_bytes.chunks(chunk_len) {
+                    writer.write_all(chunk).expect("Writing to a Vec should not fail");
+                }
+                let written = writer.finish().expect("Finishing a Vec writer should not fail");
+                assert_eq!(expected.as_bytes(), &written[..],
+                    "EncoderWriter output differs from encode\n\
+                     Input bytes: {:?}\n\
+                     Chunk length: {}", 
+                    input_bytes, chunk_len);
+                
+                let mut string_writer = EncoderStringWriter::new(&engine);
+                for chunk in input_bytes.chunks(chunk_len) {
+                    string_writer.write_all(chunk).expect("Writing to a String should not fail");
+                }
+                assert_eq!(expected, string_writer.into_inner(),
+                    "EncoderStringWriter output differs from encode\n\
+                     Input bytes: {:?}\n\
+                     Chunk length: {}", 
+                    input_bytes, chunk_len);
+                
+                assert_eq!(expected, Base64Display::new(input_bytes, &engine).to_string(),
+                    "Base64Display output differs from encode\n\
+                     Input bytes: {:?}", 
+                    input_bytes);
+                
+                // encode_string appends to existing content
+                let mut string = String::from("prefix");
+                engine.encode_string(input_bytes, &mut string);
+                assert_eq!(format!("prefix{}", expected), string,
+                    "encode_string output differs from encode\n\
+                     Input bytes: {:?}", 
+                    input_bytes);
+                
+                let mut slice = vec![0; expected.len()];
+                assert_eq!(Ok(expected.len()), engine.encode_slice(input_bytes, &mut slice));
+                assert_eq!(expected.as_bytes(), &slice[..],
+                    "encode_slice output differs from encode\n\
+                     Input bytes: {:?}", 
+                    input_bytes);
+                
+                let mut sink = Vec::new();
+                assert_eq!(Ok(expected.len()), engine.encode_into(input_bytes, &mut sink));
+                assert_eq!(expected.as_bytes(), &sink[..],
+                    "encode_into output differs from encode\n\
+                     Input bytes: {:?}", 
+                    input_bytes);
+                
+                let parts: Vec<&[u8]> = input_bytes.chunks(chunk_len).collect();
+                assert_eq!(expected, engine.encode_scattered(&parts),
+                    "encode_scattered output differs from encode\n\
+                     Input bytes: {:?}\n\
+                     Chunk length: {}", 
+                    input_bytes, chunk_len);
+            })
+    }
+    
+    /// Property 24: Streaming State Management
+    /// **Validates: Requirements 6.4**
+    /// For any sequence of writes, flushes, and a finish on an EncoderWriter, and any sequence of reads from a DecoderReader, each operation should agree with a simple in-memory model of the stream
+    pub fn test_streaming_state_management(runner: &PropertyTestRunner) -> PropertyTestResult {
+        runner
+            .check("streaming_state_management")
+            .with_generator((
+                StreamOperationGenerator::new(20, 50),
+                ConfigurationGenerator,
+                gen::<usize>(),
+            ))
+            .for_each(|(operations, config, max_io): &(Vec<StreamOperation>, TestConfig, usize)| {
+                let engine = config.create_engine();
+                
+                // The delegates accept or return at most this many bytes per call, so partial
+                // writes and reads are carried in the streams' internal state
+                let max_io = max_io % 16 + 1;
+                
+                // Model of the encoder: every byte it has accepted, and whether it is finished.
+                // Once flushed, the delegate holds the encoding of every complete block written.
+                let output = Rc::new(RefCell::new(Vec::new()));
+                let mut writer = EncoderWriter::new(SharedWriter { output: output.clone(), max_write: max_io }, &engine);
+                let mut written = Vec::new();
+                let mut finished = false;
+                for operation in operations {
+                    match operation {
+                        StreamOperation::Write(chunk) if !finished => {
+                            let accepted = writer.write(chunk).expect("Writing to a Vec should not fail");
+                            assert!(accepted <= chunk.len(),
+                                "EncoderWriter accepted {} bytes of a {} byte write", accepted, chunk.len());
+                            written.extend_from_slice(&chunk[..accepted]);
+                        }
+                        StreamOperation::Flush if !finished => {
+                            writer.flush().expect("Flushing a Vec should not fail");
+                            let complete_blocks = written.len() / 3 * 3;
+                            assert_eq!(engine.encode(&written[..complete_blocks]).as_bytes(), &output.borrow()[..],
+                                "Flushed output differs from the model\n\
+                                 Operations: {:?}\n\
+                                 Written so far: {:?}",
+                                operations, written);
+                        }
+                        StreamOperation::Finish if !finished => {
+                            let _ = writer.finish().expect("Finishing a Vec writer should not fail");
+                            finished = true;
+                        }
+                        _ => {}
+                    }
+                }
+                if !finished {
+                    let _ = writer.finish().expect("Finishing a Vec writer should not fail");
+                }
+                let encoded = output.borrow().clone();
+                assert_eq!(engine.encode(&written).as_bytes(), &encoded[..],
+                    "Finished output differs from the model\n\
+                     Operations: {:?}",
+                    operations);
+                
+                // Model of the decoder: the decoded bytes and how many have been read
+                let mut reader = DecoderReader::new(LimitedReader { input: &encoded[..], max_read: max_io }, &engine);
+                let mut read = 0;
+                for operation in operations {
+                    if let StreamOperation::Read(len) = operation {
+                        let mut buf = vec![0; *len];
+                        let count = reader.read(&mut buf).expect("Decoding valid input should not fail");
+                        let remaining = written.len() - read;
+                        assert!(count <= remaining.min(*len),
+                            "DecoderReader returned {} bytes with {} remaining and a {} byte buffer", count, remaining, len);
+                        assert!(count > 0 || remaining == 0 || *len == 0,
+                            "DecoderReader returned no bytes with {} remaining", remaining);
+                        assert_eq!(&written[read..read + count], &buf[..count],
+                            "Read output differs from the model\n\
+                             Operations: {:?}\n\
+                             Read so far: {}",
+                            operations, read);
+                        read += count;
+                    }
+                }
+                let mut rest = Vec::new();
+                let _ = reader.read_to_end(&mut rest).expect("Decoding valid input should not fail");
+                assert_eq!(&written[read..], &rest[..],
+                    "Remaining decoded output differs from the model\n\
+                     Operations: {:?}",
+                    operations);
+            })
+    }
+    
+    /// A writer that appends to a shared buffer, which can be inspected while an encoder owns it,
+    /// accepting at most `max_write` bytes per call
+    struct SharedWriter {
+        output: Rc<RefCell<Vec<u8>>>,
+        max_write: usize,
+    }
+    
+    impl Write for SharedWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let len = buf.len().min(self.max_write);
+            self.output.borrow_mut().extend_from_slice(&buf[..len]);
+            Ok(len)
+        }
+        
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+    
+    /// A reader that returns at most `max_read` bytes per call
+    struct LimitedReader<'a> {
+        input: &'a [u8],
+        max_read: usize,
+    }
+    
+    impl Read for LimitedReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = buf.len().min(self.max_read);
+            self.input.read(&mut buf[..len])
+        }
+    }
 }
 
-/// Configuration property tests
 pub mod configuration {
     //! Property tests for engine configuration consistency
     
     // Property test implementations will be added in task 11
 }
 
-/// Memory safety property tests
 pub mod memory {
     //! Property tests for memory safety and performance characteristics
     
     // Property test implementations will be added in task 12
 }
 
-/// Edge case property tests
 pub mod edge_cases {
     //! Property tests for edge case handling
     
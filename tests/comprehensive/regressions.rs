@@ -0,0 +1,138 @@
+//! Regression Corpus of Captured Counterexamples
+//!
+//! Inputs that once made a property fail, whether found by the runner or by fuzzing, are kept as
+//! small text files under `tests/regressions/` and replayed through every property on each run, so
+//! a fixed bug stays fixed and the inputs that found it are reviewed like any other change.
+//!
+//! Each `.case` file holds the raw bytes a property's generator turned into the failing value, as
+//! hex, with `#` comment lines for notes such as the property that found it. Whitespace between hex
+//! digits is ignored.
+
+use crate::comprehensive::properties;
+use crate::comprehensive::test_config::{PropertyTestResult, TestConfig};
+use crate::comprehensive::test_runner::PropertyTestRunner;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Directory of the checked-in regression corpus
+pub const CORPUS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/regressions");
+
+/// File extension of regression cases; other files in the corpus directory are ignored
+pub const CASE_EXTENSION: &str = "case";
+
+/// A captured counterexample
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegressionCase {
+    /// File name without the extension
+    pub name: String,
+    /// The `#` comment lines, without the `#`
+    pub notes: Vec<String>,
+    pub raw_input: Vec<u8>,
+}
+
+impl RegressionCase {
+    /// Parse the contents of a case file
+    pub fn parse(name: &str, contents: &str) -> Result<Self, String> {
+        let mut notes = Vec::new();
+        let mut hex = Vec::new();
+        for line in contents.lines() {
+            match line.trim_start().strip_prefix('#') {
+                Some(note) => notes.push(note.trim().to_string()),
+                None => hex.extend(line.bytes().filter(|b| !b.is_ascii_whitespace())),
+            }
+        }
+
+        if hex.len() % 2 != 0 {
+            return Err(format!("{}: odd number of hex digits", name));
+        }
+        let raw_input = hex
+            .chunks(2)
+            .map(|pair| {
+                std::str::from_utf8(pair)
+                    .ok()
+                    .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                    .ok_or_else(|| format!("{}: invalid hex {:?}", name, String::from_utf8_lossy(pair)))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { name: name.to_string(), notes, raw_input })
+    }
+
+    /// The contents of this case's file, which `parse` reads back
+    pub fn to_file_contents(&self) -> String {
+        let mut contents = String::new();
+        for note in &self.notes {
+            contents.push_str(&format!("# {}\n", note));
+        }
+        // 32 bytes per line keeps diffs of larger inputs readable
+        for line in self.raw_input.chunks(32) {
+            for byte in line {
+                contents.push_str(&format!("{:02x}", byte));
+            }
+            contents.push('\n');
+        }
+        contents
+    }
+}
+
+/// Every case in `dir`, sorted by name
+pub fn load_corpus(dir: &Path) -> io::Result<Vec<RegressionCase>> {
+    let mut cases = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().map_or(true, |ext| ext != CASE_EXTENSION) {
+            continue;
+        }
+
+        let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+        let case = RegressionCase::parse(&name, &fs::read_to_string(&path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        cases.push(case);
+    }
+
+    cases.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(cases)
+}
+
+/// Save the counterexample of a failed run as a case in `dir`, returning its path
+///
+/// Cases are named after the property and a hash of the input, so capturing the same
+/// counterexample twice overwrites the first file rather than adding a duplicate. Returns `None`
+/// if the run has no counterexample.
+pub fn save_counterexample(dir: &Path, result: &PropertyTestResult) -> io::Result<Option<PathBuf>> {
+    let counterexample = match &result.counterexample {
+        Some(counterexample) => counterexample,
+        None => return Ok(None),
+    };
+
+    let case = RegressionCase {
+        name: format!("{}-{:016x}", result.property_name, fnv1a(&counterexample.raw_input)),
+        notes: vec![
+            format!("Found by {}", result.property_name),
+            format!("Config: {:?}", counterexample.config),
+        ],
+        raw_input: counterexample.raw_input.clone(),
+    };
+
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{}.{}", case.name, CASE_EXTENSION));
+    fs::write(&path, case.to_file_contents())?;
+    Ok(Some(path))
+}
+
+/// Replay `case` through every property, returning a description of each failure
+pub fn check_case(case: &RegressionCase) -> Vec<String> {
+    let runner = PropertyTestRunner::replaying(TestConfig::default(), vec![case.raw_input.clone()]);
+    properties::ALL
+        .iter()
+        .map(|property| property(&runner))
+        .filter(|result| !result.success)
+        .map(|result| format!("{}: {}: {}", case.name, result.property_name, result.failure.unwrap_or_default()))
+        .collect()
+}
+
+/// 64-bit FNV-1a, a stable hash for naming cases
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3))
+}
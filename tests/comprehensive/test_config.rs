@@ -4,7 +4,8 @@
 //! property-based testing suite.
 
 use base64::alphabet::{Alphabet, STANDARD, URL_SAFE};
-use base64::engine::{GeneralPurpose, GeneralPurposeConfig};
+use base64::engine::{GeneralPurpose, GeneralPurposeConfig, LineEnding, Padding};
+use base64::DecodeError;
 use std::time::Duration;
 
 /// Test configuration for property-based tests
@@ -12,9 +13,15 @@ use std::time::Duration;
 pub struct TestConfig {
     pub alphabet: AlphabetType,
     pub padding_mode: PaddingMode,
+    /// The byte used for padding, which must not be in the alphabet
+    pub padding_byte: u8,
+    pub wrapping: Wrapping,
     pub engine_type: EngineType,
     pub test_iterations: usize,
     pub max_input_size: usize,
+    /// Seed for the raw input of every iteration, to make a run reproducible; `None` leaves
+    /// bolero to pick inputs at random
+    pub seed: Option<u64>,
 }
 
 impl Default for TestConfig {
@@ -22,13 +29,146 @@ impl Default for TestConfig {
         Self {
             alphabet: AlphabetType::Standard,
             padding_mode: PaddingMode::Canonical,
+            padding_byte: b'=',
+            wrapping: Wrapping::NONE,
             engine_type: EngineType::GeneralPurpose,
             test_iterations: 1000,
             max_input_size: 1024 * 1024, // 1MB default
+            seed: None,
         }
     }
 }
 
+impl TestConfig {
+    /// Environment variable that selects a preset for `from_env`
+    pub const PRESET_VAR: &'static str = "BASE64_TEST_PRESET";
+    /// Environment variable that overrides the iteration count for `from_env`
+    pub const ITERATIONS_VAR: &'static str = "BASE64_TEST_ITERATIONS";
+    /// Environment variable that overrides the max input size for `from_env`
+    pub const MAX_INPUT_VAR: &'static str = "BASE64_TEST_MAX_INPUT";
+    /// Environment variable with a seed for `from_env`
+    pub const SEED_VAR: &'static str = "BASE64_TEST_SEED";
+    /// Environment variable that makes `PropertyTestRunner::from_env` print a report of every run
+    pub const REPORT_VAR: &'static str = "BASE64_TEST_REPORT";
+
+    /// Few iterations over small inputs, for quick local runs
+    pub fn fast() -> Self {
+        Self::builder().test_iterations(100).max_input_size(1024).build()
+    }
+
+    /// Enough iterations over medium inputs to catch most regressions in CI
+    pub fn ci() -> Self {
+        Self::builder().test_iterations(10_000).max_input_size(64 * 1024).build()
+    }
+
+    /// Many iterations over large inputs, for occasional thorough runs
+    pub fn exhaustive() -> Self {
+        Self::builder().test_iterations(100_000).max_input_size(1024 * 1024).build()
+    }
+
+    /// The configuration for this run, from the environment
+    ///
+    /// Starts from the preset named by `BASE64_TEST_PRESET` (`fast`, `ci`, or `exhaustive`), or the
+    /// default configuration if it isn't set. `BASE64_TEST_ITERATIONS` and `BASE64_TEST_MAX_INPUT`
+    /// then override the preset's settings, so runs can be dialed up without code changes.
+    ///
+    /// `BASE64_TEST_SEED` sets `seed`, so the run's inputs are the same every time. The environment
+    /// is only read, since tests calling this run in parallel.
+    pub fn from_env() -> Self {
+        let mut config = match env_var(Self::PRESET_VAR).as_deref() {
+            None => Self::default(),
+            Some("fast") => Self::fast(),
+            Some("ci") => Self::ci(),
+            Some("exhaustive") => Self::exhaustive(),
+            Some(other) => panic!("Unknown {} preset: {:?}, expected fast, ci, or exhaustive", Self::PRESET_VAR, other),
+        };
+        
+        if let Some(iterations) = parse_env_var(Self::ITERATIONS_VAR) {
+            config.test_iterations = iterations;
+        }
+        if let Some(max_input_size) = parse_env_var(Self::MAX_INPUT_VAR) {
+            config.max_input_size = max_input_size;
+        }
+        if let Some(seed) = parse_env_var(Self::SEED_VAR) {
+            config.seed = Some(seed);
+        }
+        
+        config
+    }
+
+    /// A builder starting from the default configuration
+    pub fn builder() -> TestConfigBuilder {
+        TestConfigBuilder { config: Self::default() }
+    }
+}
+
+/// The value of the environment variable `name`, if it's set and not empty
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+/// Parse the environment variable `name`, panicking if it's set to something invalid
+fn parse_env_var<T: std::str::FromStr>(name: &str) -> Option<T> {
+    env_var(name).map(|value| {
+        value.parse().unwrap_or_else(|_| panic!("Invalid value for {}: {:?}", name, value))
+    })
+}
+
+/// Builder for `TestConfig`, starting from the default configuration
+#[derive(Debug, Clone)]
+pub struct TestConfigBuilder {
+    config: TestConfig,
+}
+
+impl TestConfigBuilder {
+    pub fn alphabet(mut self, alphabet: AlphabetType) -> Self {
+        self.config.alphabet = alphabet;
+        self
+    }
+
+    pub fn padding_mode(mut self, padding_mode: PaddingMode) -> Self {
+        self.config.padding_mode = padding_mode;
+        self
+    }
+
+    pub fn padding_byte(mut self, padding_byte: u8) -> Self {
+        self.config.padding_byte = padding_byte;
+        self
+    }
+
+    pub fn wrapping(mut self, wrapping: Wrapping) -> Self {
+        self.config.wrapping = wrapping;
+        self
+    }
+
+    pub fn engine_type(mut self, engine_type: EngineType) -> Self {
+        self.config.engine_type = engine_type;
+        self
+    }
+
+    /// Number of inputs each property is checked with
+    pub fn test_iterations(mut self, test_iterations: usize) -> Self {
+        self.config.test_iterations = test_iterations;
+        self
+    }
+
+    /// Maximum number of bytes of generator input for each iteration
+    pub fn max_input_size(mut self, max_input_size: usize) -> Self {
+        self.config.max_input_size = max_input_size;
+        self
+    }
+
+    /// Seed for the raw input of every iteration, to make a run reproducible
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.config.seed = Some(seed);
+        self
+    }
+
+    pub fn build(self) -> TestConfig {
+        self.config
+    }
+}
+
 /// Alphabet types supported in testing
 #[derive(Debug, Clone)]
 pub enum AlphabetType {
@@ -37,6 +177,17 @@ pub enum AlphabetType {
     Custom([u8; 64]),
 }
 
+impl AlphabetType {
+    /// The 64 symbols of this alphabet
+    pub fn symbols(&self) -> &[u8] {
+        match self {
+            AlphabetType::Standard => b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/",
+            AlphabetType::UrlSafe => b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
+            AlphabetType::Custom(chars) => chars,
+        }
+    }
+}
+
 /// Padding modes for testing
 #[derive(Debug, Clone)]
 pub enum PaddingMode {
@@ -47,10 +198,37 @@ pub enum PaddingMode {
     RequireNone,
 }
 
+/// Line wrapping of encoded output for testing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Wrapping {
+    /// Length of each line, or 0 for a single line
+    pub line_len: usize,
+    pub line_ending: LineEnding,
+    /// Whether nonempty output ends with a line ending
+    pub trailing_newline: bool,
+}
+
+impl Wrapping {
+    /// A single line with no line ending, as most properties expect
+    pub const NONE: Wrapping = Wrapping { line_len: 0, line_ending: LineEnding::LF, trailing_newline: false };
+}
+
 /// Engine types for testing
+///
+/// The types other than `GeneralPurpose` wrap the `GeneralPurpose` engine from `create_engine` in
+/// a way that must not change its output; see `matrix::MatrixEngine`.
 #[derive(Debug, Clone)]
 pub enum EngineType {
     GeneralPurpose,
+    /// `Adaptive` with `GeneralPurpose` engines for both short and long input
+    Adaptive,
+    /// `PrefixSuffix` with an empty prefix and suffix
+    PrefixSuffix,
+}
+
+impl EngineType {
+    /// Every engine type, for running a property with each
+    pub const ALL: [EngineType; 3] = [EngineType::GeneralPurpose, EngineType::Adaptive, EngineType::PrefixSuffix];
 }
 
 /// Test result for property-based tests
@@ -60,8 +238,91 @@ pub struct PropertyTestResult {
     pub iterations_run: usize,
     pub success: bool,
     pub counterexample: Option<TestInput>,
+    /// Why the counterexample failed, usually the message it panicked with
+    pub failure: Option<String>,
     pub execution_time: Duration,
-    pub memory_usage: Option<usize>,
+    /// Latency of individual iterations, if the runner timed them
+    pub latency: Option<LatencyPercentiles>,
+    /// Inputs that took far longer than the median iteration, slowest first
+    pub slow_inputs: Vec<SlowInput>,
+}
+
+impl PropertyTestResult {
+    /// Human-readable summary of the run, with a section for slow inputs if there were any
+    pub fn report(&self) -> String {
+        let mut report = format!(
+            "{}: {} after {} iterations in {:?}\n",
+            self.property_name,
+            if self.success { "passed" } else { "FAILED" },
+            self.iterations_run,
+            self.execution_time
+        );
+        
+        if let Some(failure) = &self.failure {
+            report.push_str(&format!("  failure: {}\n", failure));
+        }
+        if let Some(counterexample) = &self.counterexample {
+            let hex: String = counterexample.raw_input.iter().map(|b| format!("{:02x}", b)).collect();
+            report.push_str(&format!("  counterexample: {}\n", hex));
+        }
+        
+        if let Some(latency) = &self.latency {
+            report.push_str(&format!(
+                "  latency: p50 {:?}, p99 {:?}, max {:?}\n",
+                latency.p50, latency.p99, latency.max
+            ));
+        }
+        
+        if !self.slow_inputs.is_empty() {
+            report.push_str("  slow inputs:\n");
+            for slow in &self.slow_inputs {
+                report.push_str(&format!(
+                    "    {:?} for {} bytes starting {:02x?}\n",
+                    slow.latency,
+                    slow.raw_input.len(),
+                    &slow.raw_input[..slow.raw_input.len().min(16)]
+                ));
+            }
+        }
+        
+        report
+    }
+}
+
+/// Percentiles of the time taken by individual iterations of a property
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyPercentiles {
+    pub p50: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+impl LatencyPercentiles {
+    /// Compute percentiles of `samples`, or `None` if there are none
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let max = *sorted.last()?;
+        
+        // nearest-rank percentile: the smallest sample that at least `percent`% of samples are at or below
+        let percentile = |percent: usize| {
+            let rank = (sorted.len() * percent + 99) / 100;
+            sorted[rank.max(1) - 1]
+        };
+        
+        Some(LatencyPercentiles {
+            p50: percentile(50),
+            p99: percentile(99),
+            max,
+        })
+    }
+}
+
+/// An input that took unusually long to check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlowInput {
+    pub raw_input: Vec<u8>,
+    pub latency: Duration,
 }
 
 /// Test input data structure
@@ -80,19 +341,56 @@ pub enum ExpectedBehavior {
 }
 
 /// Expected error types for validation
+///
+/// Variants are named after the `DecodeError` variants they match.
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, PartialEq)]
 pub enum ExpectedError {
     InvalidByte { position: usize, byte: u8 },
     InvalidLength { length: usize },
     InvalidLastSymbol { position: usize },
-    InvalidPadding { position: usize },
-    BufferTooSmall { required: usize, provided: usize },
+    InvalidPadding,
+}
+
+impl ExpectedError {
+    /// Whether `error` is the decode error this describes
+    pub fn matches(&self, error: &DecodeError) -> bool {
+        match (self, error) {
+            (ExpectedError::InvalidByte { position, byte }, DecodeError::InvalidByte(offset, actual)) => position == offset && byte == actual,
+            (ExpectedError::InvalidLength { length }, DecodeError::InvalidLength(actual)) => length == actual,
+            (ExpectedError::InvalidLastSymbol { position }, DecodeError::InvalidLastSymbol { offset, .. }) => position == offset,
+            (ExpectedError::InvalidPadding, DecodeError::InvalidPadding) => true,
+            _ => false,
+        }
+    }
 }
 
 impl TestConfig {
     /// Create a GeneralPurpose engine from this configuration
+    ///
+    /// This ignores `engine_type`; `matrix::MatrixEngine::new` wraps this engine to match it.
     pub fn create_engine(&self) -> GeneralPurpose {
-        let alphabet = match &self.alphabet {
+        let alphabet = self.create_alphabet();
+
+        // Set the padding byte first, since `with_encode_padding` keeps it
+        let padded = GeneralPurposeConfig::new().with_padding(Padding::Padded(self.padding_byte));
+        let config = match self.padding_mode {
+            PaddingMode::Canonical => padded.with_encode_padding(true).with_decode_padding_mode(base64::engine::DecodePaddingMode::Indifferent),
+            PaddingMode::None => padded.with_encode_padding(false).with_decode_padding_mode(base64::engine::DecodePaddingMode::Indifferent),
+            PaddingMode::Indifferent => padded.with_encode_padding(true).with_decode_padding_mode(base64::engine::DecodePaddingMode::Indifferent),
+            PaddingMode::RequireCanonical => padded.with_encode_padding(true).with_decode_padding_mode(base64::engine::DecodePaddingMode::RequireCanonical),
+            PaddingMode::RequireNone => padded.with_encode_padding(false).with_decode_padding_mode(base64::engine::DecodePaddingMode::RequireNone),
+        }
+        .with_encode_line_wrap(self.wrapping.line_len)
+        .with_encode_line_ending(self.wrapping.line_ending)
+        .with_encode_trailing_newline(self.wrapping.trailing_newline);
+
+        GeneralPurpose::new(alphabet, config)
+    }
+
+    /// The alphabet of this configuration
+    pub fn create_alphabet(&self) -> &'static Alphabet {
+        match &self.alphabet {
             AlphabetType::Standard => &STANDARD,
             AlphabetType::UrlSafe => &URL_SAFE,
             AlphabetType::Custom(chars) => {
@@ -102,16 +400,6 @@ impl TestConfig {
                 // This is not ideal for performance but necessary for testing
                 Box::leak(Box::new(Alphabet::new(alphabet_str).expect("Custom alphabet should be valid")))
             }
-        };
-
-        let config = match self.padding_mode {
-            PaddingMode::Canonical => GeneralPurposeConfig::new().with_encode_padding(true).with_decode_padding_mode(base64::engine::DecodePaddingMode::Indifferent),
-            PaddingMode::None => GeneralPurposeConfig::new().with_encode_padding(false).with_decode_padding_mode(base64::engine::DecodePaddingMode::Indifferent),
-            PaddingMode::Indifferent => GeneralPurposeConfig::new().with_encode_padding(true).with_decode_padding_mode(base64::engine::DecodePaddingMode::Indifferent),
-            PaddingMode::RequireCanonical => GeneralPurposeConfig::new().with_encode_padding(true).with_decode_padding_mode(base64::engine::DecodePaddingMode::RequireCanonical),
-            PaddingMode::RequireNone => GeneralPurposeConfig::new().with_encode_padding(false).with_decode_padding_mode(base64::engine::DecodePaddingMode::RequireNone),
-        };
-
-        GeneralPurpose::new(alphabet, config)
+        }
     }
 }
\ No newline at end of file
@@ -3,18 +3,74 @@
 //! This module provides the test runner and execution infrastructure for the
 //! comprehensive property-based testing suite.
 
-use crate::comprehensive::test_config::{PropertyTestResult, TestConfig};
+use crate::comprehensive::test_config::{
+    ExpectedBehavior, LatencyPercentiles, PropertyTestResult, SlowInput, TestConfig, TestInput,
+};
+use bolero_generator::driver::{ByteSliceDriver, Options};
+use bolero_generator::{gen, ValueGenerator};
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use std::any::Any;
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
+/// How many times slower than the median an iteration has to be to be reported as slow
+const SLOW_INPUT_FACTOR: u32 = 20;
+/// Iterations faster than this aren't reported as slow however they compare to the median, since
+/// timer resolution and scheduling noise dominate at that scale
+const SLOW_INPUT_MIN_LATENCY: Duration = Duration::from_micros(100);
+/// The most slow inputs kept for a report
+const MAX_SLOW_INPUTS: usize = 5;
+/// The most raw bytes each iteration's value is generated from, fewer if `max_input_size` is smaller
+///
+/// That's more than the largest generators read, so they rarely run out and read zeros instead.
+const RAW_INPUT_LEN: usize = 4096;
+
+/// How property test inputs are explored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExplorationMode {
+    /// Inputs are generated from random seeds, for the configured number of iterations
+    Random,
+    /// Inputs are chosen by a fuzzing engine such as libfuzzer using coverage feedback, when run
+    /// with `cargo bolero test`. The fuzzer decides how long to run, so `test_iterations` is ignored.
+    CoverageGuided,
+}
+
+impl ExplorationMode {
+    /// The mode the properties were compiled for: `cargo bolero` builds them with `cfg(fuzzing)`
+    pub fn current() -> Self {
+        if cfg!(fuzzing) {
+            ExplorationMode::CoverageGuided
+        } else {
+            ExplorationMode::Random
+        }
+    }
+}
+
 /// Property test runner that orchestrates execution of all property tests
 pub struct PropertyTestRunner {
     config: TestConfig,
+    /// Whether to print the report of every timed run
+    report: bool,
+    /// Raw inputs to check every property on instead of generating them
+    replay: Option<Vec<Vec<u8>>>,
 }
 
 impl PropertyTestRunner {
     /// Create a new property test runner with the given configuration
     pub fn new(config: TestConfig) -> Self {
-        Self { config }
+        Self { config, report: false, replay: None }
+    }
+
+    /// Create a runner that checks each property on exactly `raw_inputs`, rather than on inputs
+    /// bolero generates
+    ///
+    /// Each raw input is turned into a value by the property's generator, as in a generated run, so
+    /// the raw input of a counterexample reproduces it. This is how the regression corpus is replayed.
+    pub fn replaying(config: TestConfig, raw_inputs: Vec<Vec<u8>>) -> Self {
+        Self { config, report: false, replay: Some(raw_inputs) }
     }
 
     /// Run a single property test with timing and result tracking
@@ -31,39 +87,224 @@ impl PropertyTestRunner {
             iterations_run: self.config.test_iterations,
             success,
             counterexample: None, // Will be populated when we implement actual property tests
+            failure: None,
             execution_time,
-            memory_usage: None, // Will be implemented when we add memory tracking
+            latency: None,
+            slow_inputs: Vec::new(),
+        }
+    }
+
+    /// Run `check` on inputs from `generator`, timing each iteration
+    ///
+    /// Unlike `run_property_test`, the runner drives bolero itself, so the result has latency
+    /// percentiles over the iterations and the inputs that were pathologically slow. A failed
+    /// check doesn't stop the run; the first failing input is kept as the counterexample.
+    pub fn run_timed_property<G, F, R>(&self, property_name: &str, generator: G, check: F) -> PropertyTestResult
+    where
+        G: ValueGenerator,
+        G::Output: Debug,
+        F: Fn(&G::Output) -> R,
+        R: CheckOutcome,
+    {
+        self.check(property_name).with_generator(generator).for_each(check)
+    }
+
+    /// Start checking the property `property_name`, like `bolero::check!()`
+    ///
+    /// Each iteration's value is generated from raw bytes, the way a fuzzer drives a generator, so
+    /// the raw bytes of a counterexample or slow input are enough to reproduce it. The check fails
+    /// by returning `false` or by panicking.
+    pub fn check<'r>(&'r self, property_name: &str) -> TimedCheck<'r, ()> {
+        TimedCheck { runner: self, property_name: property_name.to_string(), generator: () }
+    }
+
+    /// Create a property test runner configured from the environment (see `TestConfig::from_env`)
+    ///
+    /// If `BASE64_TEST_REPORT` is set, the runner prints the report of every timed run, with its
+    /// latency percentiles and slow inputs.
+    pub fn from_env() -> Self {
+        Self {
+            config: TestConfig::from_env(),
+            report: std::env::var_os(TestConfig::REPORT_VAR).map_or(false, |value| !value.is_empty()),
+            replay: None,
         }
     }
 
+    /// How the properties run by this runner explore their inputs
+    pub fn exploration_mode(&self) -> ExplorationMode {
+        ExplorationMode::current()
+    }
+
     /// Get the current test configuration
     pub fn config(&self) -> &TestConfig {
         &self.config
     }
 }
 
-/// Assertion validator for property test results
-pub struct AssertionValidator;
+/// A property being checked by a `PropertyTestRunner`, built like `bolero::check!()`
+pub struct TimedCheck<'r, G> {
+    runner: &'r PropertyTestRunner,
+    property_name: String,
+    generator: G,
+}
+
+impl<'r> TimedCheck<'r, ()> {
+    /// Generate each iteration's value with `generator`
+    pub fn with_generator<G: ValueGenerator>(self, generator: G) -> TimedCheck<'r, G> {
+        TimedCheck { runner: self.runner, property_name: self.property_name, generator }
+    }
+}
 
-impl AssertionValidator {
-    /// Validate that a property assertion holds
-    pub fn validate_property<T, F>(input: T, property: F) -> bool
+impl<'r, G> TimedCheck<'r, G>
+where
+    G: ValueGenerator,
+    G::Output: Debug,
+{
+    /// Run `check` on each generated value, timing each iteration
+    pub fn for_each<F, R>(self, check: F) -> PropertyTestResult
     where
-        F: FnOnce(T) -> bool,
+        F: Fn(&G::Output) -> R,
+        R: CheckOutcome,
     {
-        property(input)
+        let config = &self.runner.config;
+        let generator = AssertUnwindSafe(&self.generator);
+        let raw_input_len = config.max_input_size.min(RAW_INPUT_LEN);
+        let samples = Mutex::new(Vec::with_capacity(config.test_iterations));
+        let slowest = Mutex::new(SlowestInputs::default());
+        let counterexample = Mutex::new(None);
+        // failures are caught here, so bolero doesn't stop the run at the first one
+        let check = AssertUnwindSafe(check);
+        let run_one = |raw_input: &[u8]| {
+            let mut driver = ByteSliceDriver::new(raw_input, &Options::default());
+            let value = match generator.generate(&mut driver) {
+                Some(value) => value,
+                None => return,
+            };
+            // the value only depends on the bytes the generator read, so only those are kept
+            let raw_input = &raw_input[..raw_input.len() - driver.as_slice().len()];
+
+            let iteration_start = Instant::now();
+            let outcome = panic::catch_unwind(AssertUnwindSafe(|| (check.0)(&value).passed()));
+            let latency = iteration_start.elapsed();
+
+            samples.lock().unwrap().push(latency);
+            slowest.lock().unwrap().record(raw_input, latency);
+            let failure = match outcome {
+                Ok(true) => return,
+                Ok(false) => format!("check returned false for {:?}", value),
+                Err(payload) => panic_message(&*payload),
+            };
+            // a fuzzer only saves the inputs it sees fail
+            if ExplorationMode::current() == ExplorationMode::CoverageGuided {
+                panic!("{}", failure);
+            }
+            counterexample.lock().unwrap().get_or_insert((raw_input.to_vec(), failure));
+        };
+
+        let start_time = Instant::now();
+        // `check!` can expand to an early `return`, so keep it out of this fn's body
+        let run = || {
+            bolero::check!()
+                .with_iterations(config.test_iterations)
+                .with_max_len(config.max_input_size)
+                .with_generator(gen::<Vec<u8>>().with().len(raw_input_len))
+                .for_each(|raw_input: &Vec<u8>| run_one(raw_input));
+        };
+        match (&self.runner.replay, config.seed) {
+            (Some(raw_inputs), _) => raw_inputs.iter().for_each(|raw_input| run_one(raw_input)),
+            // a fuzzer chooses its own inputs, seed or not
+            (None, Some(seed)) if ExplorationMode::current() == ExplorationMode::Random => {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let mut raw_input = vec![0; raw_input_len];
+                for _ in 0..config.test_iterations {
+                    rng.fill_bytes(&mut raw_input);
+                    run_one(&raw_input);
+                }
+            }
+            (None, _) => run(),
+        }
+        let execution_time = start_time.elapsed();
+
+        let samples = samples.into_inner().unwrap();
+        let latency = LatencyPercentiles::from_samples(&samples);
+        let slow_inputs = match latency {
+            Some(latency) => slowest.into_inner().unwrap().outliers(latency.p50),
+            None => Vec::new(),
+        };
+        let (counterexample, failure) = match counterexample.into_inner().unwrap() {
+            Some((raw_input, failure)) => (
+                Some(TestInput { raw_input, config: config.clone(), expected_behavior: ExpectedBehavior::Success }),
+                Some(failure),
+            ),
+            None => (None, None),
+        };
+
+        let result = PropertyTestResult {
+            property_name: self.property_name,
+            iterations_run: samples.len(),
+            success: counterexample.is_none(),
+            counterexample,
+            failure,
+            execution_time,
+            latency,
+            slow_inputs,
+        };
+        if self.runner.report {
+            println!("{}", result.report());
+        }
+        result
     }
+}
 
-    /// Validate that an operation produces the expected result
-    pub fn validate_result<T: PartialEq>(actual: T, expected: T) -> bool {
-        actual == expected
+/// What a property check returns: `()` for checks that only fail by panicking, or whether it passed
+pub trait CheckOutcome {
+    fn passed(self) -> bool;
+}
+
+impl CheckOutcome for () {
+    fn passed(self) -> bool {
+        true
+    }
+}
+
+impl CheckOutcome for bool {
+    fn passed(self) -> bool {
+        self
     }
+}
 
-    /// Validate that an operation produces an error of the expected type
-    pub fn validate_error<T, E>(result: Result<T, E>, expected_error: bool) -> bool {
-        match (result.is_err(), expected_error) {
-            (true, true) | (false, false) => true,
-            _ => false,
+/// The message a panic was started with, if it was a string
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<String>()
+        .cloned()
+        .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+        .unwrap_or_else(|| "panicked".to_string())
+}
+
+/// The slowest inputs seen so far, kept while a property runs since the median that decides
+/// which of them are outliers is only known at the end
+#[derive(Debug, Default)]
+pub struct SlowestInputs {
+    // slowest first
+    inputs: Vec<SlowInput>,
+}
+
+impl SlowestInputs {
+    /// Keep `input` if it's among the `MAX_SLOW_INPUTS` slowest so far
+    pub fn record(&mut self, input: &[u8], latency: Duration) {
+        if self.inputs.len() == MAX_SLOW_INPUTS && self.inputs.last().map_or(false, |s| s.latency >= latency) {
+            return;
         }
+        
+        let index = self.inputs.iter().take_while(|s| s.latency >= latency).count();
+        self.inputs.insert(index, SlowInput { raw_input: input.to_vec(), latency });
+        self.inputs.truncate(MAX_SLOW_INPUTS);
     }
-}
\ No newline at end of file
+    
+    /// The kept inputs that were pathologically slow compared to the median latency `p50`
+    pub fn outliers(self, p50: Duration) -> Vec<SlowInput> {
+        let threshold = (p50 * SLOW_INPUT_FACTOR).max(SLOW_INPUT_MIN_LATENCY);
+        self.inputs.into_iter().filter(|s| s.latency >= threshold).collect()
+    }
+}
@@ -9,11 +9,8 @@
 
 mod comprehensive;
 
-use comprehensive::*;
-
 #[cfg(test)]
 mod tests {
-    use super::*;
     use crate::comprehensive::test_config::TestConfig;
     use crate::comprehensive::test_runner::PropertyTestRunner;
 
@@ -38,6 +35,8 @@ mod tests {
     #[test]
     fn test_generators_smoke_test() {
         use crate::comprehensive::generators::*;
+        use crate::comprehensive::test_config::PaddingMode;
+        use base64::Engine;
         use bolero_generator::{ValueGenerator, driver::{ByteSliceDriver, Options}};
         
         // Test byte sequence generator
@@ -46,6 +45,21 @@ mod tests {
         let bytes = byte_gen.generate(&mut driver);
         assert!(bytes.is_some());
         
+        // Fixed distributions produce bytes of the expected shape
+        let input = [7, 200, 1, 2, 3, 4, 5, 6, 7, 8];
+        for distribution in &[ByteDistribution::Random, ByteDistribution::Zeros, ByteDistribution::Ones, ByteDistribution::Ascii, ByteDistribution::Repeating] {
+            let byte_gen = ByteSequenceGenerator::new(100).with_distribution(*distribution);
+            let mut driver = ByteSliceDriver::new(&input, &Options::default());
+            let bytes = byte_gen.generate(&mut driver).unwrap();
+            match distribution {
+                ByteDistribution::Zeros => assert!(bytes.iter().all(|&b| b == 0x00)),
+                ByteDistribution::Ones => assert!(bytes.iter().all(|&b| b == 0xFF)),
+                ByteDistribution::Ascii => assert!(bytes.iter().all(|&b| b == b' ' || b.is_ascii_graphic())),
+                ByteDistribution::Repeating => assert!((1..=8).any(|period| bytes.iter().zip(bytes.iter().skip(period)).all(|(a, b)| a == b))),
+                ByteDistribution::Random => {}
+            }
+        }
+        
         // Test base64 string generator
         let string_gen = Base64StringGenerator::new(
             crate::comprehensive::test_config::AlphabetType::Standard, 
@@ -55,11 +69,73 @@ mod tests {
         let string = string_gen.generate(&mut driver);
         assert!(string.is_some());
         
+        // Generated strings decode with an engine using the same padding mode
+        for padding_mode in &[PaddingMode::Canonical, PaddingMode::None, PaddingMode::Indifferent, PaddingMode::RequireCanonical, PaddingMode::RequireNone] {
+            let string_gen = Base64StringGenerator::new(
+                crate::comprehensive::test_config::AlphabetType::UrlSafe,
+                100
+            ).with_padding_mode(padding_mode.clone());
+            let config = TestConfig {
+                alphabet: crate::comprehensive::test_config::AlphabetType::UrlSafe,
+                padding_mode: padding_mode.clone(),
+                ..TestConfig::default()
+            };
+            let engine = config.create_engine();
+            for seed in 0..=255u8 {
+                let input = [seed, 3, seed, 141, 59, 26, 53, 58, 97, 93];
+                let mut driver = ByteSliceDriver::new(&input, &Options::default());
+                let string = string_gen.generate(&mut driver).unwrap();
+                assert!(engine.decode(&string).is_ok(), "{:?} failed to decode with {:?}", string, padding_mode);
+            }
+        }
+        
         // Test configuration generator
         let config_gen = ConfigurationGenerator;
         let mut driver = ByteSliceDriver::new(&[21, 22, 23, 24, 25, 26, 27, 28, 29, 30], &Options::default());
         let config = config_gen.generate(&mut driver);
         assert!(config.is_some());
+        
+        // Test stream operation generator
+        let operation_gen = StreamOperationGenerator::new(20, 50);
+        let mut driver = ByteSliceDriver::new(&[31, 32, 33, 34, 35, 36, 37, 38, 39, 40], &Options::default());
+        let operations = operation_gen.generate(&mut driver).unwrap();
+        assert!(operations.len() <= 20);
+    }
+
+    /// Test that the presets and builder produce the expected configurations
+    #[test]
+    fn test_config_presets_and_builder() {
+        use crate::comprehensive::test_config::{AlphabetType, EngineType, PaddingMode, Wrapping};
+        
+        let fast = TestConfig::fast();
+        let ci = TestConfig::ci();
+        let exhaustive = TestConfig::exhaustive();
+        assert!(fast.test_iterations < ci.test_iterations && ci.test_iterations < exhaustive.test_iterations);
+        assert!(fast.max_input_size < ci.max_input_size && ci.max_input_size < exhaustive.max_input_size);
+        
+        let config = TestConfig::builder()
+            .alphabet(AlphabetType::UrlSafe)
+            .padding_mode(PaddingMode::RequireNone)
+            .padding_byte(b'.')
+            .wrapping(Wrapping { line_len: 76, ..Wrapping::NONE })
+            .engine_type(EngineType::Adaptive)
+            .test_iterations(7)
+            .max_input_size(64)
+            .build();
+        assert!(matches!(config.alphabet, AlphabetType::UrlSafe));
+        assert_eq!(76, config.wrapping.line_len);
+        assert!(matches!(config.engine_type, EngineType::Adaptive));
+        assert!(matches!(config.padding_mode, PaddingMode::RequireNone));
+        assert_eq!(b'.', config.padding_byte);
+        assert_eq!(7, config.test_iterations);
+        assert_eq!(64, config.max_input_size);
+        
+        // unset settings keep their defaults
+        let default = TestConfig::default();
+        let built = TestConfig::builder().build();
+        assert_eq!(default.test_iterations, built.test_iterations);
+        assert_eq!(default.max_input_size, built.max_input_size);
+        assert_eq!(default.padding_byte, built.padding_byte);
     }
 
     /// Test that the test runner can execute basic property tests
@@ -79,6 +155,181 @@ mod tests {
         assert_eq!(result.iterations_run, 1000); // Default iteration count
     }
 
+    /// Test that a timed run reports ordered latency percentiles for every iteration
+    #[test]
+    fn test_runner_timed_property() {
+        use crate::comprehensive::generators::ByteSequenceGenerator;
+        use base64::Engine as _;
+        
+        let config = TestConfig { test_iterations: 200, ..TestConfig::default() };
+        let runner = PropertyTestRunner::new(config);
+        let engine = runner.config().create_engine();
+        
+        let result = runner.run_timed_property("timed_roundtrip", ByteSequenceGenerator::new(1000), |input| {
+            engine.decode(engine.encode(input)).map_or(false, |decoded| decoded == *input)
+        });
+        
+        assert!(result.success);
+        assert!(result.counterexample.is_none());
+        let latency = result.latency.expect("a run with iterations should have latency percentiles");
+        assert!(latency.p50 <= latency.p99);
+        assert!(latency.p99 <= latency.max);
+        for slow in &result.slow_inputs {
+            assert!(slow.latency <= latency.max);
+        }
+    }
+
+    /// Test that a failed check is reported with its input as the counterexample
+    #[test]
+    fn test_runner_timed_property_failure() {
+        use crate::comprehensive::generators::ByteSequenceGenerator;
+        
+        let runner = PropertyTestRunner::new(TestConfig::default());
+        
+        let result = runner.run_timed_property("always_fails", ByteSequenceGenerator::new(10), |_| false);
+        
+        assert!(!result.success);
+        assert!(result.counterexample.is_some());
+        assert!(result.report().contains("FAILED"));
+    }
+
+    /// Test percentile and slow input selection on synthetic latencies
+    #[test]
+    fn test_latency_percentiles_and_slow_inputs() {
+        use crate::comprehensive::test_config::LatencyPercentiles;
+        use crate::comprehensive::test_runner::SlowestInputs;
+        use std::time::Duration;
+        
+        assert!(LatencyPercentiles::from_samples(&[]).is_none());
+        
+        // 98 fast iterations, then two pathological ones
+        let mut samples: Vec<Duration> = (1..=98).map(Duration::from_micros).collect();
+        samples.push(Duration::from_millis(50));
+        samples.push(Duration::from_millis(80));
+        
+        let latency = LatencyPercentiles::from_samples(&samples).unwrap();
+        assert_eq!(Duration::from_micros(50), latency.p50);
+        assert_eq!(Duration::from_millis(50), latency.p99);
+        assert_eq!(Duration::from_millis(80), latency.max);
+        
+        let mut slowest = SlowestInputs::default();
+        for (i, &sample) in samples.iter().enumerate() {
+            slowest.record(&[i as u8], sample);
+        }
+        
+        // only the two pathological inputs clear 20x the median, slowest first
+        let slow_inputs = slowest.outliers(latency.p50);
+        let slow_bytes: Vec<Vec<u8>> = slow_inputs.iter().map(|s| s.raw_input.clone()).collect();
+        assert_eq!(vec![vec![99_u8], vec![98_u8]], slow_bytes);
+    }
+
+    /// Replay every captured counterexample in `tests/regressions/` through every property
+    #[test]
+    fn test_regression_corpus() {
+        use crate::comprehensive::regressions::{check_case, load_corpus, CORPUS_DIR};
+        use std::path::Path;
+        
+        let cases = load_corpus(Path::new(CORPUS_DIR)).expect("Regression corpus should load");
+        assert!(!cases.is_empty(), "No cases found in {}", CORPUS_DIR);
+        
+        let failures: Vec<String> = cases.iter().flat_map(check_case).collect();
+        assert!(failures.is_empty(), "{} regression failures:\n{}", failures.len(), failures.join("\n"));
+    }
+
+    /// Test that a saved counterexample loads back as the same input, and that saving it again doesn't duplicate it
+    #[test]
+    fn test_regression_case_capture() {
+        use crate::comprehensive::generators::ByteSequenceGenerator;
+        use crate::comprehensive::regressions::{load_corpus, save_counterexample, RegressionCase};
+        
+        let case = RegressionCase {
+            name: "hex".to_string(),
+            notes: vec!["a note".to_string()],
+            raw_input: (0..=255).collect(),
+        };
+        assert_eq!(Ok(case.clone()), RegressionCase::parse("hex", &case.to_file_contents()));
+        assert!(RegressionCase::parse("odd", "abc").is_err());
+        assert!(RegressionCase::parse("not_hex", "zz").is_err());
+        
+        let dir = std::env::temp_dir().join(format!("base64-regressions-{}", std::process::id()));
+        let runner = PropertyTestRunner::new(TestConfig::builder().test_iterations(10).build());
+        let passed = runner.run_timed_property("passes", ByteSequenceGenerator::new(10), |_| true);
+        assert_eq!(None, save_counterexample(&dir, &passed).unwrap());
+        
+        let failed = runner.run_timed_property("fails", ByteSequenceGenerator::new(10), |_| false);
+        let path = save_counterexample(&dir, &failed).unwrap().expect("A failed run should save its counterexample");
+        assert_eq!(path, save_counterexample(&dir, &failed).unwrap().unwrap());
+        
+        let cases = load_corpus(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(1, cases.len());
+        assert_eq!(failed.counterexample.unwrap().raw_input, cases[0].raw_input);
+        
+        // replaying the saved case reproduces the failure, on that input alone
+        let replay = PropertyTestRunner::replaying(TestConfig::default(), vec![cases[0].raw_input.clone()]);
+        let replayed = replay.run_timed_property("fails", ByteSequenceGenerator::new(10), |_| false);
+        assert_eq!(1, replayed.iterations_run);
+        assert_eq!(Some(&cases[0].raw_input), replayed.counterexample.as_ref().map(|c| &c.raw_input));
+    }
+
+    /// Check that every engine type agrees with every alphabet and padding mode, and write the compatibility table
+    #[test]
+    fn test_engine_consistency_matrix() {
+        use crate::comprehensive::matrix::run_matrix;
+        use crate::comprehensive::test_config::{AlphabetType, PaddingMode};
+        
+        let report = run_matrix();
+        
+        let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("engine-compatibility.md");
+        std::fs::write(&path, report.to_markdown()).expect("Compatibility table should be writable");
+        println!("Engine compatibility table written to {}", path.display());
+        
+        assert!(report.disagreements.is_empty(), "{} disagreements between engine types:\n{}",
+            report.disagreements.len(), report.disagreements.join("\n"));
+        
+        let index = |alphabet: AlphabetType, padding_mode: PaddingMode| {
+            let label = format!("{:?} {:?}", alphabet, padding_mode);
+            report.configs.iter().position(|c| format!("{:?} {:?}", c.alphabet, c.padding_mode) == label).unwrap()
+        };
+        for i in 0..report.configs.len() {
+            assert!(report.is_compatible(i, i), "{:?} can't decode its own output", report.configs[i]);
+        }
+        // padded output only decodes where padding is allowed, and the alphabets differ in two symbols
+        assert!(report.is_compatible(index(AlphabetType::Standard, PaddingMode::RequireCanonical), index(AlphabetType::Standard, PaddingMode::Indifferent)));
+        assert!(!report.is_compatible(index(AlphabetType::Standard, PaddingMode::RequireCanonical), index(AlphabetType::Standard, PaddingMode::RequireNone)));
+        assert!(!report.is_compatible(index(AlphabetType::Standard, PaddingMode::Canonical), index(AlphabetType::UrlSafe, PaddingMode::Canonical)));
+    }
+
+    /// Test that a seeded run checks the same inputs every time
+    #[test]
+    fn test_runner_seed() {
+        use crate::comprehensive::generators::ByteSequenceGenerator;
+        
+        let runner = PropertyTestRunner::new(TestConfig::builder().test_iterations(20).max_input_size(64).seed(7).build());
+        let run = || runner.run_timed_property("seeded", ByteSequenceGenerator::new(64), |bytes| bytes.len() < 32);
+        let (first, second) = (run(), run());
+        
+        assert_eq!(20, first.iterations_run);
+        assert!(first.counterexample.is_some(), "20 inputs up to 64 bytes long should include one of 32 bytes or more");
+        assert_eq!(first.counterexample.map(|c| c.raw_input), second.counterexample.map(|c| c.raw_input));
+    }
+
+    /// Test that a runner configured from the environment reports the environment's settings
+    #[test]
+    fn test_runner_from_env() {
+        let runner = PropertyTestRunner::from_env();
+        let expected = TestConfig::from_env();
+        
+        let result = runner.run_property_test("env_smoke_test", || true);
+        
+        assert!(result.success);
+        assert_eq!(result.iterations_run, expected.test_iterations);
+        assert_eq!(runner.config().max_input_size, expected.max_input_size);
+        
+        // `cargo test` explores randomly; only `cargo bolero test` is coverage-guided
+        assert_eq!(crate::comprehensive::test_runner::ExplorationMode::Random, runner.exploration_mode());
+    }
+
     /// Unit test for custom alphabet functionality
     #[test]
     fn test_custom_alphabet_basic() {
@@ -89,9 +340,12 @@ mod tests {
         let config = TestConfig {
             alphabet: crate::comprehensive::test_config::AlphabetType::Custom(*custom_chars),
             padding_mode: crate::comprehensive::test_config::PaddingMode::Canonical,
+            padding_byte: b'=',
+            wrapping: crate::comprehensive::test_config::Wrapping::NONE,
             engine_type: crate::comprehensive::test_config::EngineType::GeneralPurpose,
             test_iterations: 1000,
             max_input_size: 1024,
+            seed: None,
         };
         
         let engine = config.create_engine();
@@ -116,7 +370,8 @@ mod tests {
     #[test]
     #[cfg_attr(kani, kani::proof)]
     fn property_1_encode_decode_roundtrip() {
-        crate::comprehensive::properties::roundtrip::test_encode_decode_roundtrip();
+        let result = crate::comprehensive::properties::roundtrip::test_encode_decode_roundtrip(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
     }
 
     /// Property 2: Decode-Encode Roundtrip Test
@@ -125,7 +380,8 @@ mod tests {
     #[test]
     #[cfg_attr(kani, kani::proof)]
     fn property_2_decode_encode_roundtrip() {
-        crate::comprehensive::properties::roundtrip::test_decode_encode_roundtrip();
+        let result = crate::comprehensive::properties::roundtrip::test_decode_encode_roundtrip(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
     }
 
     /// Property 3: Cross-Engine Consistency Test
@@ -135,7 +391,8 @@ mod tests {
     #[test]
     #[cfg_attr(kani, kani::proof)]
     fn property_3_cross_engine_consistency() {
-        crate::comprehensive::properties::roundtrip::test_cross_engine_consistency();
+        let result = crate::comprehensive::properties::roundtrip::test_cross_engine_consistency(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
     }
 
     /// Property 4: Custom Alphabet Roundtrip Test
@@ -144,7 +401,8 @@ mod tests {
     #[test]
     #[cfg_attr(kani, kani::proof)]
     fn property_4_custom_alphabet_roundtrip() {
-        crate::comprehensive::properties::roundtrip::test_custom_alphabet_roundtrip();
+        let result = crate::comprehensive::properties::roundtrip::test_custom_alphabet_roundtrip(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
     }
 
     /// Property 5: Padding Mode Roundtrip Test
@@ -153,7 +411,8 @@ mod tests {
     #[test]
     #[cfg_attr(kani, kani::proof)]
     fn property_5_padding_mode_roundtrip() {
-        crate::comprehensive::properties::roundtrip::test_padding_mode_roundtrip();
+        let result = crate::comprehensive::properties::roundtrip::test_padding_mode_roundtrip(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
     }
 
     /// Property 6: Character Set Compliance Test
@@ -162,7 +421,8 @@ mod tests {
     #[test]
     #[cfg_attr(kani, kani::proof)]
     fn property_6_character_set_compliance() {
-        crate::comprehensive::properties::alphabet::test_character_set_compliance();
+        let result = crate::comprehensive::properties::alphabet::test_character_set_compliance(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
     }
 
     /// Property 7: Invalid Character Detection Test
@@ -171,7 +431,18 @@ mod tests {
     #[test]
     #[cfg_attr(kani, kani::proof)]
     fn property_7_invalid_character_detection() {
-        crate::comprehensive::properties::alphabet::test_invalid_character_detection();
+        let result = crate::comprehensive::properties::alphabet::test_invalid_character_detection(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
+    }
+
+    /// Property 34: Cross-Alphabet Confusion Test
+    /// **Validates: Requirements 2.6**
+    /// For any byte sequence, output encoded with URL_SAFE that contains `-` or `_` should be rejected by a STANDARD engine and vice versa for `+` and `/`, and output without those characters should decode identically with both
+    #[test]
+    #[cfg_attr(kani, kani::proof)]
+    fn property_34_cross_alphabet_confusion() {
+        let result = crate::comprehensive::properties::alphabet::test_cross_alphabet_confusion(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
     }
 
     /// Unit test for invalid character detection with specific examples
@@ -184,9 +455,12 @@ mod tests {
         let standard_config = TestConfig {
             alphabet: AlphabetType::Standard,
             padding_mode: PaddingMode::Canonical,
+            padding_byte: b'=',
+            wrapping: crate::comprehensive::test_config::Wrapping::NONE,
             engine_type: EngineType::GeneralPurpose,
             test_iterations: 1000,
             max_input_size: 1024,
+            seed: None,
         };
         let standard_engine = standard_config.create_engine();
         
@@ -214,9 +488,12 @@ mod tests {
         let url_safe_config = TestConfig {
             alphabet: AlphabetType::UrlSafe,
             padding_mode: PaddingMode::Canonical,
+            padding_byte: b'=',
+            wrapping: crate::comprehensive::test_config::Wrapping::NONE,
             engine_type: EngineType::GeneralPurpose,
             test_iterations: 1000,
             max_input_size: 1024,
+            seed: None,
         };
         let url_safe_engine = url_safe_config.create_engine();
         
@@ -279,9 +556,12 @@ mod tests {
         let custom_config = TestConfig {
             alphabet: AlphabetType::Custom(*custom_chars),
             padding_mode: PaddingMode::Canonical,
+            padding_byte: b'=',
+            wrapping: crate::comprehensive::test_config::Wrapping::NONE,
             engine_type: EngineType::GeneralPurpose,
             test_iterations: 1000,
             max_input_size: 1024,
+            seed: None,
         };
         let custom_engine = custom_config.create_engine();
         
@@ -299,6 +579,176 @@ mod tests {
                 "Custom alphabet should reject invalid character in: {}", invalid_input);
         }
     }
+
+    /// Property 40: Alphabet Acceptance Test
+    /// **Validates: Requirements 2.7**
+    /// For any 64 distinct printable ASCII characters other than `=`, `Alphabet::new` should accept them, and the alphabet should return them unchanged from `as_str()`
+    #[test]
+    #[cfg_attr(kani, kani::proof)]
+    fn property_40_alphabet_acceptance() {
+        let result = crate::comprehensive::properties::alphabet::test_alphabet_acceptance(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
+    }
+
+    /// Property 41: Alphabet Rejection Test
+    /// **Validates: Requirements 2.8**
+    /// For any valid alphabet with one symbol replaced by a duplicate of another symbol, by `=`, or by a control character, `Alphabet::new` should reject it with the error naming that byte
+    #[test]
+    #[cfg_attr(kani, kani::proof)]
+    fn property_41_alphabet_rejection() {
+        let result = crate::comprehensive::properties::alphabet::test_alphabet_rejection(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
+    }
+
+    /// Property 43: Alphabet Translation Test
+    /// **Validates: Requirements 2.9**
+    /// For any byte sequence, any configuration, and any custom alphabet, translating the configuration's encoding to the custom alphabet should produce the custom alphabet's encoding, and translating it back should restore the original
+    #[test]
+    #[cfg_attr(kani, kani::proof)]
+    fn property_43_alphabet_translation() {
+        let result = crate::comprehensive::properties::alphabet::test_alphabet_translation(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
+    }
+
+    /// Property 46: Custom Alphabet Table Confusion Test
+    /// **Validates: Requirements 2.10**
+    /// For any byte sequence and any two custom alphabets that differ in a single symbol, output encoded with one that contains its differing symbol should be rejected by the other at the first occurrence of that symbol, and output without it should decode identically with both
+    #[test]
+    #[cfg_attr(kani, kani::proof)]
+    fn property_46_custom_alphabet_table_confusion() {
+        let result = crate::comprehensive::properties::alphabet::test_custom_alphabet_table_confusion(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
+    }
+
+    /// Property 35: Custom Padding Character Encoding Test
+    /// **Validates: Requirements 3.6**
+    /// For any byte sequence and any padding character outside the alphabet, encoding with padding should end the output with the correct number of that character, and encoding without padding should never emit it
+    #[test]
+    #[cfg_attr(kani, kani::proof)]
+    fn property_35_custom_padding_encoding() {
+        let result = crate::comprehensive::properties::padding::test_custom_padding_encoding(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
+    }
+
+    /// Property 36: Custom Padding Character Decoding Test
+    /// **Validates: Requirements 3.7**
+    /// For any byte sequence and any padding character outside the alphabet, decoding should require, reject, or accept that character according to the padding mode, and never treat `=` as padding in its place (configs that don't write padding recognize `=` as padding)
+    #[test]
+    #[cfg_attr(kani, kani::proof)]
+    fn property_36_custom_padding_decoding() {
+        let result = crate::comprehensive::properties::padding::test_custom_padding_decoding(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
+    }
+
+    /// Property 37: Padding Collision Rejection Test
+    /// **Validates: Requirements 3.8**
+    /// For any alphabet and any padding character, creating an engine should be rejected exactly when the padding character is one of the alphabet's symbols or isn't printable ASCII
+    #[test]
+    #[cfg_attr(kani, kani::proof)]
+    fn property_37_padding_collision_rejection() {
+        let result = crate::comprehensive::properties::padding::test_padding_collision_rejection(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
+    }
+
+    /// Property 15: Buffer Size Sufficiency Test
+    /// **Validates: Requirements 4.5**
+    /// For any byte sequence and any configuration, including line wrapping, the encoded length an engine reports should be at least the length of its output, and encoding into a buffer of exactly that length should succeed without writing past the output
+    #[test]
+    #[cfg_attr(kani, kani::proof)]
+    fn property_15_buffer_size_sufficiency() {
+        let result = crate::comprehensive::properties::length::test_buffer_size_sufficiency(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
+    }
+
+    /// Property 22: Streaming Encode Consistency Test
+    /// **Validates: Requirements 6.2**
+    /// For any byte sequence and any configuration, every output-producing API (EncoderWriter, EncoderStringWriter, Base64Display, and the engine's encode variants) should produce output identical to batch encode
+    #[test]
+    #[cfg_attr(kani, kani::proof)]
+    fn property_22_streaming_encode_consistency() {
+        let result = crate::comprehensive::properties::streaming::test_streaming_encode_consistency(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
+    }
+
+    /// Property 24: Streaming State Management Test
+    /// **Validates: Requirements 6.4**
+    /// For any sequence of writes, flushes, and a finish on an EncoderWriter, and any sequence of reads from a DecoderReader, each operation should agree with a simple in-memory model of the stream
+    #[test]
+    #[cfg_attr(kani, kani::proof)]
+    fn property_24_streaming_state_management() {
+        let result = crate::comprehensive::properties::streaming::test_streaming_state_management(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
+    }
+
+    /// Property 33: Decode Panic Freedom
+    /// **Validates: Requirements 9.2, 10.5**
+    /// For any input and any output buffer size, decoding should return either the decoded bytes or an error, never panic
+    #[test]
+    #[cfg_attr(kani, kani::proof)]
+    fn property_33_decode_panic_freedom() {
+        let result = crate::comprehensive::properties::error::test_decode_panic_freedom(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
+    }
+
+    /// Property 38: Decode Entry Point Consistency Test
+    /// **Validates: Requirements 5.6**
+    /// For any input and any configuration, `decode`, `decode_vec` (appending to existing content), and `decode_slice` (with a buffer of exactly the decoded length) should produce identical bytes, or the same error
+    #[test]
+    #[cfg_attr(kani, kani::proof)]
+    fn property_38_decode_entry_point_consistency() {
+        let result = crate::comprehensive::properties::error::test_decode_entry_point_consistency(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
+    }
+
+    /// Property 42: Decode Error Taxonomy Test
+    /// **Validates: Requirements 5.1, 5.2, 5.3, 5.4**
+    /// For any valid encoding with one injected defect, decoding should fail with the error variant, and offset, that the defect predicts
+    #[test]
+    #[cfg_attr(kani, kani::proof)]
+    fn property_42_decode_error_taxonomy() {
+        let result = crate::comprehensive::properties::error::test_decode_error_taxonomy(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
+    }
+
+    /// Property 45: Single-Byte Corruption Detection Test
+    /// **Validates: Requirements 5.7**
+    /// For any canonical encoding with exactly one byte changed, by flipping a letter's case, swapping a symbol, or changing padding, decoding should either fail or produce a different payload, never silently return the original
+    #[test]
+    #[cfg_attr(kani, kani::proof)]
+    fn property_45_single_byte_corruption_detection() {
+        let result = crate::comprehensive::properties::error::test_single_byte_corruption_detection(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
+    }
+
+    /// Property 39: Entry Point Panic Freedom Test
+    /// **Validates: Requirements 9.2, 10.5**
+    /// For any input bytes, configuration, and buffer size, every public encode and decode entry point should return normally, with output or an error, and never panic
+    #[test]
+    #[cfg_attr(kani, kani::proof)]
+    fn property_39_entry_point_panic_freedom() {
+        let result = crate::comprehensive::properties::error::test_entry_point_panic_freedom(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
+    }
+
+    /// Property 44: Engine Construction Panic Freedom Test
+    /// **Validates: Requirements 3.9, 10.5**
+    /// For any symbols, padding byte, and settings, including a line wrap length of 0, the fallible alphabet, engine, and spec constructors should return normally, rejecting the engine exactly when the padding byte is one of the alphabet's symbols or isn't printable ASCII
+    #[test]
+    #[cfg_attr(kani, kani::proof)]
+    fn property_44_engine_construction_panic_freedom() {
+        let result = crate::comprehensive::properties::error::test_engine_construction_panic_freedom(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
+    }
+
+    /// Property 20: Buffer Overflow Error Reporting
+    /// **Validates: Requirements 5.5**
+    /// For any input and any output buffer size, encoding into a slice should either fill the buffer or report that it's too small, never panic
+    #[test]
+    #[cfg_attr(kani, kani::proof)]
+    fn property_20_buffer_overflow_error_reporting() {
+        let result = crate::comprehensive::properties::error::test_buffer_overflow_error_reporting(&PropertyTestRunner::from_env());
+        assert!(result.success, "{}", result.report());
+    }
 }
 
 // Property tests will be added in subsequent tasks